@@ -18,7 +18,12 @@ import * as anchor from "@coral-xyz/anchor";
 import { PreAuthorizedDebitV1 } from "../../target/types/pre_authorized_debit_v1";
 
 import "./setup";
-import { PreAuthTestVariant, derivePreAuthorization } from "./utils";
+import {
+  PreAuthTestVariant,
+  derivePreAuthorization,
+  derivePreAuthorizationCounter,
+  deriveOwnerPreAuthorization,
+} from "./utils";
 import {
   fundAccounts,
   initSmartDelegateIdempotent,
@@ -158,6 +163,8 @@ describe("pre-authorized-debit-v1#close-pre-authorization", () => {
                       expiryUnixTimestamp: new anchor.BN(
                         expirationUnixTimestamp,
                       ),
+                      maxAmountPerDebit: null,
+                      closeOnExhaustion: false,
                     },
                   }
                 : {
@@ -166,14 +173,41 @@ describe("pre-authorized-debit-v1#close-pre-authorization", () => {
                       recurringAmountAuthorized: new anchor.BN(10e6),
                       numCycles: null,
                       resetEveryCycle: false,
+                      maxAmountPerDebit: null,
+                      minAmountPerDebit: null,
+                      maxAccruedCycles: null,
+                      calendarSchedule: null,
+                      debitWindowSeconds: null,
+                      debitWindowOffsetSeconds: null,
+                      introSchedule: null,
                     },
                   };
 
+            // the token account has no pre-authorization yet: verify the count
+            // (such that the smart delegate is revoked once the last pre-authorization is closed)
+            await program.methods
+              .initPreAuthorizationCounter({ count: new anchor.BN(0) })
+              .accounts({
+                payer: provider.publicKey,
+                owner: owner.publicKey,
+                tokenAccount,
+                preAuthorizationCounter: derivePreAuthorizationCounter(
+                  tokenAccount,
+                  program.programId,
+                )[0],
+                systemProgram: SystemProgram.programId,
+              })
+              .signers([owner])
+              .rpc();
+
             await program.methods
               .initPreAuthorization({
                 variant: preAuthVariant,
                 debitAuthority: debitAuthority.publicKey,
                 activationUnixTimestamp: new anchor.BN(activationUnixTimestamp),
+                rotationRequiresOwnerSignature: false,
+                destinationConstraint: null,
+                boundedDelegation: false,
               })
               .accounts({
                 payer: provider.publicKey,
@@ -181,6 +215,10 @@ describe("pre-authorized-debit-v1#close-pre-authorization", () => {
                 smartDelegate: smartDelegatePublicKey,
                 tokenAccount,
                 preAuthorization,
+                preAuthorizationCounter: derivePreAuthorizationCounter(
+                  tokenAccount,
+                  program.programId,
+                )[0],
                 tokenProgram: tokenProgramId,
                 systemProgram: SystemProgram.programId,
               })
@@ -210,7 +248,13 @@ describe("pre-authorized-debit-v1#close-pre-authorization", () => {
                     receiver: owner.publicKey,
                     authority: closeAuthorityKeypair.publicKey,
                     tokenAccount,
+                    smartDelegate: smartDelegatePublicKey,
                     preAuthorization,
+                    preAuthorizationCounter: derivePreAuthorizationCounter(
+                      tokenAccount,
+                      program.programId,
+                    )[0],
+                    tokenProgram: tokenProgramId,
                   })
                   .signers([closeAuthorityKeypair])
                   .rpc();
@@ -236,6 +280,7 @@ describe("pre-authorized-debit-v1#close-pre-authorization", () => {
                 );
 
                 // verify token account is unchanged
+                // (except for the smart delegate being revoked when the owner closes the last pre-authorization)
                 const tokenAccountDataAfter = await getAccount(
                   provider.connection,
                   tokenAccount,
@@ -243,7 +288,13 @@ describe("pre-authorized-debit-v1#close-pre-authorization", () => {
                   tokenProgramId,
                 );
                 expect(tokenAccountDataAfter).to.deep.equal(
-                  tokenAccountDataBefore,
+                  closeAuthority === "owner"
+                    ? {
+                        ...tokenAccountDataBefore,
+                        delegate: null,
+                        delegatedAmount: BigInt(0),
+                      }
+                    : tokenAccountDataBefore,
                 );
               });
             });
@@ -264,7 +315,13 @@ describe("pre-authorized-debit-v1#close-pre-authorization", () => {
                 receiver: newReceiver.publicKey,
                 authority: owner.publicKey,
                 tokenAccount,
+                smartDelegate: smartDelegatePublicKey,
                 preAuthorization,
+                preAuthorizationCounter: derivePreAuthorizationCounter(
+                  tokenAccount,
+                  program.programId,
+                )[0],
+                tokenProgram: tokenProgramId,
               })
               .signers([owner])
               .rpc();
@@ -287,14 +344,221 @@ describe("pre-authorized-debit-v1#close-pre-authorization", () => {
             // should refund the receiver
             expect(receiverAccountInfoAfter.lamports).to.be.greaterThan(0);
 
-            // verify token account balance is unchanged
+            // verify token account balance is unchanged (and the smart delegate is revoked)
             const tokenAccountDataAfter = await getAccount(
               provider.connection,
               tokenAccount,
               undefined,
               tokenProgramId,
             );
+            expect(tokenAccountDataAfter).to.deep.equal({
+              ...tokenAccountDataBefore,
+              delegate: null,
+              delegatedAmount: BigInt(0),
+            });
+          });
+
+          ["owner", "debit authority"].forEach((closeAuthority: string) => {
+            it(`should fail to close the pre authorization as the ${closeAuthority} without the pre-authorization counter`, async () => {
+              const closeAuthorityKeypair =
+                closeAuthority === "owner" ? owner : debitAuthority;
+              await expect(
+                program.methods
+                  .closePreAuthorization()
+                  .accounts({
+                    receiver: owner.publicKey,
+                    authority: closeAuthorityKeypair.publicKey,
+                    tokenAccount,
+                    smartDelegate: smartDelegatePublicKey,
+                    preAuthorization,
+                    preAuthorizationCounter: null,
+                    tokenProgram: tokenProgramId,
+                  })
+                  .signers([closeAuthorityKeypair])
+                  .rpc(),
+              ).to.eventually.be.rejectedWith(
+                /Error Code: PreAuthorizationCounterRequired. Error Number: 6049/,
+              );
+
+              expect(
+                await provider.connection.getAccountInfo(preAuthorization),
+              ).to.not.equal(null);
+            });
+          });
+
+          it("should not revoke the smart delegate if the pre-authorization counter count is not verified", async () => {
+            // the counter is created (unverified) by the first pre-authorization of the new token account
+            const newTokenAccount = await createAccount(
+              provider.connection,
+              mintAuthority,
+              mint,
+              owner.publicKey,
+              Keypair.generate(),
+              undefined,
+              tokenProgramId,
+            );
+            const [newPreAuthorization] = derivePreAuthorization(
+              newTokenAccount,
+              debitAuthority.publicKey,
+              program.programId,
+            );
+            const [newPreAuthorizationCounter] = derivePreAuthorizationCounter(
+              newTokenAccount,
+              program.programId,
+            );
+            await program.methods
+              .initPreAuthorization({
+                variant: {
+                  oneTime: {
+                    amountAuthorized: new anchor.BN(100e6),
+                    expiryUnixTimestamp: new anchor.BN(expirationUnixTimestamp),
+                    maxAmountPerDebit: null,
+                    closeOnExhaustion: false,
+                  },
+                },
+                debitAuthority: debitAuthority.publicKey,
+                activationUnixTimestamp: new anchor.BN(activationUnixTimestamp),
+                rotationRequiresOwnerSignature: false,
+                destinationConstraint: null,
+                boundedDelegation: false,
+              })
+              .accounts({
+                payer: provider.publicKey,
+                owner: owner.publicKey,
+                smartDelegate: smartDelegatePublicKey,
+                tokenAccount: newTokenAccount,
+                preAuthorization: newPreAuthorization,
+                preAuthorizationCounter: newPreAuthorizationCounter,
+                tokenProgram: tokenProgramId,
+                systemProgram: SystemProgram.programId,
+              })
+              .signers([owner])
+              .rpc();
+
+            const counterData =
+              await program.account.preAuthorizationCounter.fetch(
+                newPreAuthorizationCounter,
+              );
+            expect(counterData.count.toString()).to.equal("1");
+            expect(counterData.countVerified).to.equal(false);
+
+            const tokenAccountDataBefore = await getAccount(
+              provider.connection,
+              newTokenAccount,
+              undefined,
+              tokenProgramId,
+            );
+            await program.methods
+              .closePreAuthorization()
+              .accounts({
+                receiver: owner.publicKey,
+                authority: owner.publicKey,
+                tokenAccount: newTokenAccount,
+                smartDelegate: smartDelegatePublicKey,
+                preAuthorization: newPreAuthorization,
+                preAuthorizationCounter: newPreAuthorizationCounter,
+                tokenProgram: tokenProgramId,
+              })
+              .signers([owner])
+              .rpc();
+
+            const tokenAccountDataAfter = await getAccount(
+              provider.connection,
+              newTokenAccount,
+              undefined,
+              tokenProgramId,
+            );
             expect(tokenAccountDataAfter).to.deep.equal(tokenAccountDataBefore);
+            expect(tokenAccountDataAfter.delegate?.toBase58()).to.equal(
+              smartDelegatePublicKey.toBase58(),
+            );
+          });
+
+          it("should fail to close the expired pre authorization without the pre-authorization counter", async () => {
+            const newTokenAccount = await createAccount(
+              provider.connection,
+              mintAuthority,
+              mint,
+              owner.publicKey,
+              Keypair.generate(),
+              undefined,
+              tokenProgramId,
+            );
+            const [newPreAuthorization] = derivePreAuthorization(
+              newTokenAccount,
+              debitAuthority.publicKey,
+              program.programId,
+            );
+            const [newPreAuthorizationCounter] = derivePreAuthorizationCounter(
+              newTokenAccount,
+              program.programId,
+            );
+            const currentUnixTimestamp = Math.floor(new Date().getTime() / 1e3);
+            await program.methods
+              .initPreAuthorization({
+                variant: {
+                  oneTime: {
+                    amountAuthorized: new anchor.BN(100e6),
+                    expiryUnixTimestamp: new anchor.BN(
+                      currentUnixTimestamp - 60,
+                    ),
+                    maxAmountPerDebit: null,
+                    closeOnExhaustion: false,
+                  },
+                },
+                debitAuthority: debitAuthority.publicKey,
+                activationUnixTimestamp: new anchor.BN(
+                  currentUnixTimestamp - 120,
+                ),
+                rotationRequiresOwnerSignature: false,
+                destinationConstraint: null,
+                boundedDelegation: false,
+              })
+              .accounts({
+                payer: provider.publicKey,
+                owner: owner.publicKey,
+                smartDelegate: smartDelegatePublicKey,
+                tokenAccount: newTokenAccount,
+                preAuthorization: newPreAuthorization,
+                preAuthorizationCounter: newPreAuthorizationCounter,
+                tokenProgram: tokenProgramId,
+                systemProgram: SystemProgram.programId,
+              })
+              .signers([owner])
+              .rpc();
+
+            await expect(
+              program.methods
+                .closeExpiredPreAuthorization()
+                .accounts({
+                  caller: provider.publicKey,
+                  receiver: owner.publicKey,
+                  bountyReceiver: null,
+                  tokenAccount: newTokenAccount,
+                  preAuthorization: newPreAuthorization,
+                  preAuthorizationCounter: null,
+                })
+                .rpc(),
+            ).to.eventually.be.rejectedWith(
+              /Error Code: PreAuthorizationCounterRequired. Error Number: 6049/,
+            );
+
+            await program.methods
+              .closeExpiredPreAuthorization()
+              .accounts({
+                caller: provider.publicKey,
+                receiver: owner.publicKey,
+                bountyReceiver: null,
+                tokenAccount: newTokenAccount,
+                preAuthorization: newPreAuthorization,
+                preAuthorizationCounter: newPreAuthorizationCounter,
+              })
+              .rpc();
+            const counterData =
+              await program.account.preAuthorizationCounter.fetch(
+                newPreAuthorizationCounter,
+              );
+            expect(counterData.count.toString()).to.equal("0");
           });
 
           it("should throw an error if a token account does not match the pre-authorization", async () => {
@@ -314,7 +578,14 @@ describe("pre-authorized-debit-v1#close-pre-authorization", () => {
                   receiver: owner.publicKey,
                   authority: owner.publicKey,
                   tokenAccount: newTokenAccount,
+                  smartDelegate: smartDelegatePublicKey,
                   preAuthorization,
+                  // the counter of the pre-authorization's token account (the new one has none)
+                  preAuthorizationCounter: derivePreAuthorizationCounter(
+                    tokenAccount,
+                    program.programId,
+                  )[0],
+                  tokenProgram: tokenProgramId,
                 })
                 .signers([owner])
                 .rpc(),
@@ -331,7 +602,13 @@ describe("pre-authorized-debit-v1#close-pre-authorization", () => {
                   receiver: owner.publicKey,
                   authority: mintAuthority.publicKey,
                   tokenAccount,
+                  smartDelegate: smartDelegatePublicKey,
                   preAuthorization,
+                  preAuthorizationCounter: derivePreAuthorizationCounter(
+                    tokenAccount,
+                    program.programId,
+                  )[0],
+                  tokenProgram: tokenProgramId,
                 })
                 .signers([mintAuthority])
                 .rpc(),
@@ -348,7 +625,13 @@ describe("pre-authorized-debit-v1#close-pre-authorization", () => {
                   receiver: mintAuthority.publicKey,
                   authority: debitAuthority.publicKey,
                   tokenAccount,
+                  smartDelegate: smartDelegatePublicKey,
                   preAuthorization,
+                  preAuthorizationCounter: derivePreAuthorizationCounter(
+                    tokenAccount,
+                    program.programId,
+                  )[0],
+                  tokenProgram: tokenProgramId,
                 })
                 .signers([debitAuthority])
                 .rpc(),
@@ -356,6 +639,255 @@ describe("pre-authorized-debit-v1#close-pre-authorization", () => {
               /AnchorError caused by account: receiver. Error Code: OnlyTokenAccountOwnerCanReceiveClosePreAuthFunds. Error Number: 6005. Error Message: Only token account owner can receive funds from closing pre-authorization account./,
             );
           });
+
+          it("should re-approve the smart delegate for the other pre-authorizations in bounded delegation mode", async () => {
+            const newTokenAccount = await createAccount(
+              provider.connection,
+              mintAuthority,
+              mint,
+              owner.publicKey,
+              Keypair.generate(),
+              undefined,
+              tokenProgramId,
+            );
+            const [newPreAuthorizationCounter] = derivePreAuthorizationCounter(
+              newTokenAccount,
+              program.programId,
+            );
+            await program.methods
+              .initPreAuthorizationCounter({ count: new anchor.BN(0) })
+              .accounts({
+                payer: provider.publicKey,
+                owner: owner.publicKey,
+                tokenAccount: newTokenAccount,
+                preAuthorizationCounter: newPreAuthorizationCounter,
+                systemProgram: SystemProgram.programId,
+              })
+              .signers([owner])
+              .rpc();
+            const debitAuthorities = [debitAuthority, Keypair.generate()];
+            const newPreAuthorizations = debitAuthorities.map(
+              (newDebitAuthority) =>
+                derivePreAuthorization(
+                  newTokenAccount,
+                  newDebitAuthority.publicKey,
+                  program.programId,
+                )[0],
+            );
+            for (const [i, newDebitAuthority] of debitAuthorities.entries()) {
+              await program.methods
+                .initPreAuthorization({
+                  variant: {
+                    oneTime: {
+                      amountAuthorized: new anchor.BN((i + 1) * 100e6),
+                      expiryUnixTimestamp: new anchor.BN(
+                        expirationUnixTimestamp,
+                      ),
+                      maxAmountPerDebit: null,
+                      closeOnExhaustion: false,
+                    },
+                  },
+                  debitAuthority: newDebitAuthority.publicKey,
+                  activationUnixTimestamp: new anchor.BN(
+                    activationUnixTimestamp,
+                  ),
+                  rotationRequiresOwnerSignature: false,
+                  destinationConstraint: null,
+                  boundedDelegation: true,
+                })
+                .accounts({
+                  payer: provider.publicKey,
+                  owner: owner.publicKey,
+                  smartDelegate: smartDelegatePublicKey,
+                  tokenAccount: newTokenAccount,
+                  preAuthorization: newPreAuthorizations[i],
+                  preAuthorizationCounter: newPreAuthorizationCounter,
+                  tokenProgram: tokenProgramId,
+                  systemProgram: SystemProgram.programId,
+                })
+                .signers([owner])
+                .rpc();
+            }
+
+            const closeFirstPreAuthorization = (
+              remainingAccounts: PublicKey[],
+            ): Promise<string> =>
+              program.methods
+                .closePreAuthorization()
+                .accounts({
+                  receiver: owner.publicKey,
+                  authority: owner.publicKey,
+                  tokenAccount: newTokenAccount,
+                  smartDelegate: smartDelegatePublicKey,
+                  preAuthorization: newPreAuthorizations[0],
+                  preAuthorizationCounter: newPreAuthorizationCounter,
+                  tokenProgram: tokenProgramId,
+                })
+                .remainingAccounts(
+                  remainingAccounts.map((pubkey) => ({
+                    pubkey,
+                    isSigner: false,
+                    isWritable: false,
+                  })),
+                )
+                .signers([owner])
+                .rpc();
+
+            await expect(
+              closeFirstPreAuthorization([]),
+            ).to.eventually.be.rejectedWith(
+              /Error Code: MissingPreAuthorizations. Error Number: 6056/,
+            );
+
+            await closeFirstPreAuthorization([newPreAuthorizations[1]]);
+
+            const tokenAccountData = await getAccount(
+              provider.connection,
+              newTokenAccount,
+              undefined,
+              tokenProgramId,
+            );
+            expect(tokenAccountData.delegate?.toBase58()).to.equal(
+              smartDelegatePublicKey.toBase58(),
+            );
+            expect(tokenAccountData.delegatedAmount.toString()).to.equal(
+              (200e6).toString(),
+            );
+          });
+        });
+      });
+
+      context("with an owner scoped pre-authorization", () => {
+        let preAuthorization: PublicKey, preAuthorizationCounter: PublicKey;
+
+        async function initOwnerPreAuthorization(
+          activationUnixTimestamp: number,
+          expiryUnixTimestamp: number,
+        ): Promise<void> {
+          await program.methods
+            .initOwnerPreAuthorization({
+              variant: {
+                oneTime: {
+                  amountAuthorized: new anchor.BN(100e6),
+                  expiryUnixTimestamp: new anchor.BN(expiryUnixTimestamp),
+                  maxAmountPerDebit: null,
+                  closeOnExhaustion: false,
+                },
+              },
+              debitAuthority: debitAuthority.publicKey,
+              activationUnixTimestamp: new anchor.BN(activationUnixTimestamp),
+              rotationRequiresOwnerSignature: false,
+              destinationConstraint: null,
+              boundedDelegation: false,
+            })
+            .accounts({
+              payer: provider.publicKey,
+              owner: owner.publicKey,
+              mint,
+              preAuthorization,
+              systemProgram: SystemProgram.programId,
+            })
+            .signers([owner])
+            .rpc();
+        }
+
+        beforeEach(async () => {
+          mint = await createMint(
+            provider.connection,
+            mintAuthority,
+            mintAuthority.publicKey,
+            null,
+            6,
+            Keypair.generate(),
+            undefined,
+            tokenProgramId,
+          );
+          tokenAccount = await createAccount(
+            provider.connection,
+            mintAuthority,
+            mint,
+            owner.publicKey,
+            Keypair.generate(),
+            undefined,
+            tokenProgramId,
+          );
+          [preAuthorization] = deriveOwnerPreAuthorization(
+            owner.publicKey,
+            mint,
+            debitAuthority.publicKey,
+            program.programId,
+          );
+          [preAuthorizationCounter] = derivePreAuthorizationCounter(
+            tokenAccount,
+            program.programId,
+          );
+          await program.methods
+            .initPreAuthorizationCounter({ count: new anchor.BN(0) })
+            .accounts({
+              payer: provider.publicKey,
+              owner: owner.publicKey,
+              tokenAccount,
+              preAuthorizationCounter,
+              systemProgram: SystemProgram.programId,
+            })
+            .signers([owner])
+            .rpc();
+        });
+
+        it("should close the owner scoped pre authorization as the owner", async () => {
+          await initOwnerPreAuthorization(
+            activationUnixTimestamp,
+            expirationUnixTimestamp,
+          );
+
+          await program.methods
+            .closeOwnerPreAuthorization()
+            .accounts({
+              receiver: owner.publicKey,
+              authority: owner.publicKey,
+              owner: owner.publicKey,
+              mint,
+              preAuthorization,
+            })
+            .signers([owner])
+            .rpc();
+
+          expect(
+            await program.account.preAuthorization.fetchNullable(
+              preAuthorization,
+            ),
+          ).to.equal(null);
+        });
+
+        it("should close the expired owner scoped pre authorization (without decrementing the pre-authorization counter)", async () => {
+          const currentUnixTimestamp = Math.floor(new Date().getTime() / 1e3);
+          await initOwnerPreAuthorization(
+            currentUnixTimestamp - 120,
+            currentUnixTimestamp - 60,
+          );
+
+          await program.methods
+            .closeExpiredPreAuthorization()
+            .accounts({
+              caller: provider.publicKey,
+              receiver: owner.publicKey,
+              bountyReceiver: null,
+              tokenAccount,
+              preAuthorization,
+              preAuthorizationCounter,
+            })
+            .rpc();
+
+          expect(
+            await program.account.preAuthorization.fetchNullable(
+              preAuthorization,
+            ),
+          ).to.equal(null);
+          const preAuthorizationCounterData =
+            await program.account.preAuthorizationCounter.fetch(
+              preAuthorizationCounter,
+            );
+          expect(preAuthorizationCounterData.count.toString()).to.equal("0");
         });
       });
     });
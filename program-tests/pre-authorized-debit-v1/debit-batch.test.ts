@@ -0,0 +1,253 @@
+import "./setup";
+import { PreAuthorizedDebitV1 } from "../../target/types/pre_authorized_debit_v1";
+import * as anchor from "@coral-xyz/anchor";
+import { assert, expect } from "chai";
+import {
+  AccountMeta,
+  Keypair,
+  PublicKey,
+  SystemProgram,
+} from "@solana/web3.js";
+import {
+  TOKEN_PROGRAM_ID,
+  createAccount,
+  createAssociatedTokenAccount,
+  createMint,
+  getAccount,
+  mintTo,
+} from "@solana/spl-token";
+import {
+  derivePreAuthorization,
+  derivePreAuthorizationCounter,
+  getCurrentUnixTimestamp,
+} from "./utils";
+import {
+  fundAccounts,
+  initSmartDelegateIdempotent,
+  waitForTxToConfirm,
+} from "@seabed-labs/pad-test-utils";
+
+describe("pre-authorized-debit-v1#debit-batch", () => {
+  const provider = anchor.getProvider() as anchor.AnchorProvider;
+  const program = anchor.workspace
+    .PreAuthorizedDebitV1 as anchor.Program<PreAuthorizedDebitV1>;
+  const eventParser = new anchor.EventParser(program.programId, program.coder);
+
+  let fundedKeypair: Keypair,
+    mintAuthorityKeypair: Keypair,
+    debitAuthorityKeypair: Keypair;
+
+  let mintPubkey: PublicKey,
+    smartDelegatePubkey: PublicKey,
+    destinationTokenAccountPubkey: PublicKey;
+
+  // the (token_account, pre_authorization) of each entry
+  let entries: [PublicKey, PublicKey][];
+
+  before(async () => {
+    smartDelegatePubkey = await initSmartDelegateIdempotent(program, provider);
+  });
+
+  async function setupEntry(): Promise<[PublicKey, PublicKey]> {
+    const ownerKeypair = Keypair.generate();
+    const tokenAccountPubkey = await createAccount(
+      provider.connection,
+      fundedKeypair,
+      mintPubkey,
+      ownerKeypair.publicKey,
+      Keypair.generate(),
+      undefined,
+      TOKEN_PROGRAM_ID,
+    );
+    await mintTo(
+      provider.connection,
+      fundedKeypair,
+      mintPubkey,
+      tokenAccountPubkey,
+      mintAuthorityKeypair,
+      1000e6,
+      undefined,
+      undefined,
+      TOKEN_PROGRAM_ID,
+    );
+    const [preAuthorizationPubkey] = derivePreAuthorization(
+      tokenAccountPubkey,
+      debitAuthorityKeypair.publicKey,
+      program.programId,
+    );
+    const activationUnixTimestamp = getCurrentUnixTimestamp() - 60;
+    await program.methods
+      .initPreAuthorization({
+        variant: {
+          oneTime: {
+            amountAuthorized: new anchor.BN(100e6),
+            expiryUnixTimestamp: new anchor.BN(
+              activationUnixTimestamp + 10 * 24 * 60 * 60,
+            ),
+            maxAmountPerDebit: null,
+            closeOnExhaustion: false,
+          },
+        },
+        debitAuthority: debitAuthorityKeypair.publicKey,
+        activationUnixTimestamp: new anchor.BN(activationUnixTimestamp),
+        rotationRequiresOwnerSignature: false,
+        destinationConstraint: null,
+        boundedDelegation: false,
+      })
+      .accounts({
+        payer: provider.publicKey,
+        owner: ownerKeypair.publicKey,
+        smartDelegate: smartDelegatePubkey,
+        tokenAccount: tokenAccountPubkey,
+        preAuthorization: preAuthorizationPubkey,
+        preAuthorizationCounter: derivePreAuthorizationCounter(
+          tokenAccountPubkey,
+          program.programId,
+        )[0],
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([ownerKeypair])
+      .rpc();
+    return [tokenAccountPubkey, preAuthorizationPubkey];
+  }
+
+  function remainingAccounts(
+    batchEntries: [PublicKey, PublicKey][],
+  ): AccountMeta[] {
+    return batchEntries.flatMap(
+      ([tokenAccountPubkey, preAuthorizationPubkey]) =>
+        [
+          tokenAccountPubkey,
+          preAuthorizationPubkey,
+          destinationTokenAccountPubkey,
+        ].map((pubkey) => ({ pubkey, isWritable: true, isSigner: false })),
+    );
+  }
+
+  async function debitBatch(
+    amounts: number[],
+    mode:
+      | { atomic: Record<string, never> }
+      | { skipFailed: Record<string, never> },
+    accounts: AccountMeta[],
+  ): Promise<string> {
+    return program.methods
+      .debitBatch({
+        amounts: amounts.map((amount) => new anchor.BN(amount)),
+        mode,
+      })
+      .accounts({
+        debitAuthority: debitAuthorityKeypair.publicKey,
+        mint: mintPubkey,
+        smartDelegate: smartDelegatePubkey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .remainingAccounts(accounts)
+      .signers([debitAuthorityKeypair])
+      .rpc();
+  }
+
+  beforeEach(async () => {
+    fundedKeypair = Keypair.generate();
+    mintAuthorityKeypair = Keypair.generate();
+    debitAuthorityKeypair = Keypair.generate();
+
+    await fundAccounts(provider, [fundedKeypair.publicKey], 10e9);
+
+    mintPubkey = await createMint(
+      provider.connection,
+      fundedKeypair,
+      mintAuthorityKeypair.publicKey,
+      null,
+      6,
+      undefined,
+      undefined,
+      TOKEN_PROGRAM_ID,
+    );
+    destinationTokenAccountPubkey = await createAssociatedTokenAccount(
+      provider.connection,
+      fundedKeypair,
+      mintPubkey,
+      Keypair.generate().publicKey,
+      undefined,
+      TOKEN_PROGRAM_ID,
+    );
+
+    entries = [await setupEntry(), await setupEntry()];
+  });
+
+  it("debits every entry of the batch", async () => {
+    const signature = await debitBatch(
+      [10e6, 20e6],
+      { atomic: {} },
+      remainingAccounts(entries),
+    );
+
+    const tx = await waitForTxToConfirm(signature, provider.connection);
+    assert(tx.meta?.logMessages, "tx.meta?.logMessages undefined");
+    const events = [...eventParser.parseLogs(tx.meta.logMessages)];
+    expect(events.map((event) => event.name)).to.deep.equal([
+      "DebitEvent",
+      "DebitEvent",
+      "DebitBatchProcessed",
+    ]);
+    expect(events[2].data.numEntries).to.equal(2);
+    expect(events[2].data.failedEntries).to.deep.equal([]);
+
+    const destinationTokenAccount = await getAccount(
+      provider.connection,
+      destinationTokenAccountPubkey,
+      undefined,
+      TOKEN_PROGRAM_ID,
+    );
+    expect(destinationTokenAccount.amount.toString()).to.equal(
+      (30e6).toString(),
+    );
+  });
+
+  it("skips and reports the failed entries in the SkipFailed mode", async () => {
+    const signature = await debitBatch(
+      [10e6, 101e6],
+      { skipFailed: {} },
+      remainingAccounts(entries),
+    );
+
+    const tx = await waitForTxToConfirm(signature, provider.connection);
+    assert(tx.meta?.logMessages, "tx.meta?.logMessages undefined");
+    const events = [...eventParser.parseLogs(tx.meta.logMessages)];
+    const debitBatchProcessedEvent = events[events.length - 1];
+    expect(debitBatchProcessedEvent.name).to.equal("DebitBatchProcessed");
+    // eslint-disable-next-line  @typescript-eslint/no-explicit-any
+    const failedEntries = debitBatchProcessedEvent.data.failedEntries as any[];
+    expect(failedEntries.length).to.equal(1);
+    expect(failedEntries[0].index).to.equal(1);
+    expect(failedEntries[0].preAuthorization.toString()).to.equal(
+      entries[1][1].toString(),
+    );
+    // CannotDebitMoreThanAvailable
+    expect(failedEntries[0].errorCode.toString()).to.equal("6001");
+  });
+
+  it("fails if a pre-authorization is in more than one entry", async () => {
+    await expect(
+      debitBatch(
+        [10e6, 20e6],
+        { atomic: {} },
+        remainingAccounts([entries[0], entries[0]]),
+      ),
+    ).to.eventually.be.rejectedWith(
+      /Error Code: InvalidDebitBatch. Error Number: 6057/,
+    );
+  });
+
+  it("fails if a remaining account is not writable", async () => {
+    const accounts = remainingAccounts(entries);
+    accounts[1].isWritable = false;
+    await expect(
+      debitBatch([10e6, 20e6], { atomic: {} }, accounts),
+    ).to.eventually.be.rejectedWith(
+      /Error Code: InvalidDebitBatch. Error Number: 6057/,
+    );
+  });
+});
@@ -4,6 +4,7 @@ import * as anchor from "@coral-xyz/anchor";
 import { assert, expect } from "chai";
 import { Keypair, PublicKey, SystemProgram } from "@solana/web3.js";
 import {
+  createAccount,
   createAssociatedTokenAccount,
   createMint,
   getAccount,
@@ -11,8 +12,11 @@ import {
 } from "@solana/spl-token";
 import {
   DebitEvent,
+  MEMO_PROGRAM_ID,
   U64_MAX,
+  deriveOwnerPreAuthorization,
   derivePreAuthorization,
+  derivePreAuthorizationCounter,
   getCurrentUnixTimestamp,
 } from "../utils";
 import {
@@ -69,10 +73,15 @@ export function testOneTimeDebit(
             oneTime: {
               amountAuthorized: new anchor.BN(100e6),
               expiryUnixTimestamp: new anchor.BN(expirationUnixTimestamp),
+              maxAmountPerDebit: null,
+              closeOnExhaustion: false,
             },
           },
           debitAuthority: debitAuthorityKeypair.publicKey,
           activationUnixTimestamp: new anchor.BN(activationUnixTimestamp),
+          rotationRequiresOwnerSignature: false,
+          destinationConstraint: null,
+          boundedDelegation: false,
         })
         .accounts({
           payer: provider.publicKey,
@@ -80,6 +89,10 @@ export function testOneTimeDebit(
           smartDelegate: smartDelegatePubkey,
           tokenAccount: tokenAccountPubkey,
           preAuthorization: preAuthorizationPubkey,
+          preAuthorizationCounter: derivePreAuthorizationCounter(
+            tokenAccountPubkey,
+            program.programId,
+          )[0],
           tokenProgram: tokenProgramId,
           systemProgram: SystemProgram.programId,
         })
@@ -183,7 +196,11 @@ export function testOneTimeDebit(
       ).to.equal("0");
 
       await program.methods
-        .debit({ amount: new anchor.BN(50e6) })
+        .debit({
+          amount: new anchor.BN(50e6),
+          amountMode: { exact: {} },
+          reference: null,
+        })
         .accounts({
           debitAuthority: debitAuthorityKeypair.publicKey,
           mint: mintPubkey,
@@ -227,8 +244,12 @@ export function testOneTimeDebit(
       expect(
         preAuthorizationAfter.variant.oneTime?.amountDebited.toString(),
       ).to.equal((50e6).toString());
+      expect(preAuthorizationAfter.numDebits.toString()).to.equal(
+        preAuthorizationBefore.numDebits.addn(1).toString(),
+      );
       expect({
         ...preAuthorizationBefore,
+        numDebits: null,
         variant: {
           oneTime: {
             ...preAuthorizationBefore.variant.oneTime,
@@ -237,6 +258,7 @@ export function testOneTimeDebit(
         },
       }).to.deep.equal({
         ...preAuthorizationAfter,
+        numDebits: null,
         variant: {
           oneTime: {
             ...preAuthorizationAfter.variant.oneTime,
@@ -288,7 +310,11 @@ export function testOneTimeDebit(
           ).to.equal((i * amount).toString());
 
           await program.methods
-            .debit({ amount: new anchor.BN(amount) })
+            .debit({
+              amount: new anchor.BN(amount),
+              amountMode: { exact: {} },
+              reference: null,
+            })
             .accounts({
               debitAuthority: debitAuthorityKeypair.publicKey,
               mint: mintPubkey,
@@ -336,6 +362,7 @@ export function testOneTimeDebit(
           ).to.equal(((i + 1) * amount).toString());
           expect({
             ...preAuthorizationBefore,
+            numDebits: null,
             variant: {
               oneTime: {
                 ...preAuthorizationBefore.variant.oneTime,
@@ -344,6 +371,7 @@ export function testOneTimeDebit(
             },
           }).to.deep.equal({
             ...preAuthorizationAfter,
+            numDebits: null,
             variant: {
               oneTime: {
                 ...preAuthorizationAfter.variant.oneTime,
@@ -373,7 +401,11 @@ export function testOneTimeDebit(
         await partialDebitNTimes(4, partialWithdrawAmount);
         await expect(
           program.methods
-            .debit({ amount: new anchor.BN(partialWithdrawAmount) })
+            .debit({
+              amount: new anchor.BN(partialWithdrawAmount),
+              amountMode: { exact: {} },
+              reference: null,
+            })
             .accounts({
               debitAuthority: debitAuthorityKeypair.publicKey,
               mint: mintPubkey,
@@ -395,7 +427,11 @@ export function testOneTimeDebit(
         await partialDebitNTimes(3, partialWithdrawAmount);
         await expect(
           program.methods
-            .debit({ amount: new anchor.BN(2 * partialWithdrawAmount) })
+            .debit({
+              amount: new anchor.BN(2 * partialWithdrawAmount),
+              amountMode: { exact: {} },
+              reference: null,
+            })
             .accounts({
               debitAuthority: debitAuthorityKeypair.publicKey,
               mint: mintPubkey,
@@ -414,14 +450,20 @@ export function testOneTimeDebit(
     });
 
     context("negative timestamps", () => {
-      it("fails if expiry is negative", async () => {
+      it("fails to create a pre_authorization if expiry is negative (before activation)", async () => {
         await program.methods
           .closePreAuthorization()
           .accounts({
             receiver: userKeypair.publicKey,
             authority: userKeypair.publicKey,
             tokenAccount: tokenAccountPubkey,
+            smartDelegate: smartDelegatePubkey,
             preAuthorization: preAuthorizationPubkey,
+            preAuthorizationCounter: derivePreAuthorizationCounter(
+              tokenAccountPubkey,
+              program.programId,
+            )[0],
+            tokenProgram: tokenProgramId,
           })
           .signers([userKeypair])
           .rpc();
@@ -435,27 +477,13 @@ export function testOneTimeDebit(
           1
         ); // negative(+10 days from now) (so thousands of year before epoch 0)
 
-        await setupOneTimePreAuthorization(
-          activationUnixTimestamp,
-          expirationUnixTimestamp,
-        );
-
         await expect(
-          program.methods
-            .debit({ amount: new anchor.BN(50e6) })
-            .accounts({
-              debitAuthority: debitAuthorityKeypair.publicKey,
-              mint: mintPubkey,
-              tokenAccount: tokenAccountPubkey,
-              destinationTokenAccount: destinationTokenAccountPubkey,
-              smartDelegate: smartDelegatePubkey,
-              preAuthorization: preAuthorizationPubkey,
-              tokenProgram: tokenProgramId,
-            })
-            .signers([debitAuthorityKeypair])
-            .rpc(),
+          setupOneTimePreAuthorization(
+            activationUnixTimestamp,
+            expirationUnixTimestamp,
+          ),
         ).to.eventually.be.rejectedWith(
-          /Error Code: PreAuthorizationNotActive. Error Number: 6000/,
+          /Error Code: ExpiryBeforeActivation. Error Number: 6019/,
         );
       });
       it("fails when activation_timestamp < 0 && 0 < expiry_timestamp < now && abs(activation_timestamp) > expiry_timestamp", async () => {
@@ -465,7 +493,13 @@ export function testOneTimeDebit(
             receiver: userKeypair.publicKey,
             authority: userKeypair.publicKey,
             tokenAccount: tokenAccountPubkey,
+            smartDelegate: smartDelegatePubkey,
             preAuthorization: preAuthorizationPubkey,
+            preAuthorizationCounter: derivePreAuthorizationCounter(
+              tokenAccountPubkey,
+              program.programId,
+            )[0],
+            tokenProgram: tokenProgramId,
           })
           .signers([userKeypair])
           .rpc();
@@ -485,7 +519,11 @@ export function testOneTimeDebit(
 
         await expect(
           program.methods
-            .debit({ amount: new anchor.BN(50e6) })
+            .debit({
+              amount: new anchor.BN(50e6),
+              amountMode: { exact: {} },
+              reference: null,
+            })
             .accounts({
               debitAuthority: debitAuthorityKeypair.publicKey,
               mint: mintPubkey,
@@ -508,7 +546,13 @@ export function testOneTimeDebit(
             receiver: userKeypair.publicKey,
             authority: userKeypair.publicKey,
             tokenAccount: tokenAccountPubkey,
+            smartDelegate: smartDelegatePubkey,
             preAuthorization: preAuthorizationPubkey,
+            preAuthorizationCounter: derivePreAuthorizationCounter(
+              tokenAccountPubkey,
+              program.programId,
+            )[0],
+            tokenProgram: tokenProgramId,
           })
           .signers([userKeypair])
           .rpc();
@@ -534,7 +578,11 @@ export function testOneTimeDebit(
         );
 
         await program.methods
-          .debit({ amount: new anchor.BN(50e6) })
+          .debit({
+            amount: new anchor.BN(50e6),
+            amountMode: { exact: {} },
+            reference: null,
+          })
           .accounts({
             debitAuthority: debitAuthorityKeypair.publicKey,
             mint: mintPubkey,
@@ -580,7 +628,11 @@ export function testOneTimeDebit(
 
       await expect(
         program.methods
-          .debit({ amount: new anchor.BN(50e6) })
+          .debit({
+            amount: new anchor.BN(50e6),
+            amountMode: { exact: {} },
+            reference: null,
+          })
           .accounts({
             debitAuthority: debitAuthorityKeypair.publicKey,
             mint: mintPubkey,
@@ -604,7 +656,13 @@ export function testOneTimeDebit(
           receiver: userKeypair.publicKey,
           authority: userKeypair.publicKey,
           tokenAccount: tokenAccountPubkey,
+          smartDelegate: smartDelegatePubkey,
           preAuthorization: preAuthorizationPubkey,
+          preAuthorizationCounter: derivePreAuthorizationCounter(
+            tokenAccountPubkey,
+            program.programId,
+          )[0],
+          tokenProgram: tokenProgramId,
         })
         .signers([userKeypair])
         .rpc();
@@ -622,7 +680,11 @@ export function testOneTimeDebit(
 
       await expect(
         program.methods
-          .debit({ amount: new anchor.BN(50e6) })
+          .debit({
+            amount: new anchor.BN(50e6),
+            amountMode: { exact: {} },
+            reference: null,
+          })
           .accounts({
             debitAuthority: debitAuthorityKeypair.publicKey,
             mint: mintPubkey,
@@ -646,7 +708,13 @@ export function testOneTimeDebit(
           receiver: userKeypair.publicKey,
           authority: userKeypair.publicKey,
           tokenAccount: tokenAccountPubkey,
+          smartDelegate: smartDelegatePubkey,
           preAuthorization: preAuthorizationPubkey,
+          preAuthorizationCounter: derivePreAuthorizationCounter(
+            tokenAccountPubkey,
+            program.programId,
+          )[0],
+          tokenProgram: tokenProgramId,
         })
         .signers([userKeypair])
         .rpc();
@@ -664,7 +732,11 @@ export function testOneTimeDebit(
 
       await expect(
         program.methods
-          .debit({ amount: new anchor.BN(50e6) })
+          .debit({
+            amount: new anchor.BN(50e6),
+            amountMode: { exact: {} },
+            reference: null,
+          })
           .accounts({
             debitAuthority: debitAuthorityKeypair.publicKey,
             mint: mintPubkey,
@@ -684,7 +756,11 @@ export function testOneTimeDebit(
     it("fails if attempting to debit more than pre_authorization initial authorization", async () => {
       await expect(
         program.methods
-          .debit({ amount: new anchor.BN(101e6) })
+          .debit({
+            amount: new anchor.BN(101e6),
+            amountMode: { exact: {} },
+            reference: null,
+          })
           .accounts({
             debitAuthority: debitAuthorityKeypair.publicKey,
             mint: mintPubkey,
@@ -703,7 +779,11 @@ export function testOneTimeDebit(
 
     it("fails if attempting to debit more than pre_authorization's remaining available amount", async () => {
       await program.methods
-        .debit({ amount: new anchor.BN(95e6) })
+        .debit({
+          amount: new anchor.BN(95e6),
+          amountMode: { exact: {} },
+          reference: null,
+        })
         .accounts({
           debitAuthority: debitAuthorityKeypair.publicKey,
           mint: mintPubkey,
@@ -718,7 +798,11 @@ export function testOneTimeDebit(
 
       await expect(
         program.methods
-          .debit({ amount: new anchor.BN(5e6 + 1) })
+          .debit({
+            amount: new anchor.BN(5e6 + 1),
+            amountMode: { exact: {} },
+            reference: null,
+          })
           .accounts({
             debitAuthority: debitAuthorityKeypair.publicKey,
             mint: mintPubkey,
@@ -735,7 +819,11 @@ export function testOneTimeDebit(
       );
 
       await program.methods
-        .debit({ amount: new anchor.BN(5e6) })
+        .debit({
+          amount: new anchor.BN(5e6),
+          amountMode: { exact: {} },
+          reference: null,
+        })
         .accounts({
           debitAuthority: debitAuthorityKeypair.publicKey,
           mint: mintPubkey,
@@ -788,10 +876,15 @@ export function testOneTimeDebit(
             oneTime: {
               amountAuthorized: new anchor.BN(100e6),
               expiryUnixTimestamp: new anchor.BN(expirationUnixTimestamp),
+              maxAmountPerDebit: null,
+              closeOnExhaustion: false,
             },
           },
           debitAuthority: debitAuthorityKeypair.publicKey,
           activationUnixTimestamp: new anchor.BN(activationUnixTimestamp),
+          rotationRequiresOwnerSignature: false,
+          destinationConstraint: null,
+          boundedDelegation: false,
         })
         .accounts({
           payer: provider.publicKey,
@@ -799,17 +892,23 @@ export function testOneTimeDebit(
           smartDelegate: smartDelegatePubkey,
           tokenAccount: newTokenAccountPubkey,
           preAuthorization: newPreAuthorizationPubkey,
+          preAuthorizationCounter: derivePreAuthorizationCounter(
+            newTokenAccountPubkey,
+            program.programId,
+          )[0],
           tokenProgram: tokenProgramId,
           systemProgram: SystemProgram.programId,
         })
         .signers([newUserKeypair])
         .rpc();
 
-      // we don't see the actual validation error because the PDA error is hit first
-      // it is redundant but we'll leave it in there
       await expect(
         program.methods
-          .debit({ amount: new anchor.BN(50e6) })
+          .debit({
+            amount: new anchor.BN(50e6),
+            amountMode: { exact: {} },
+            reference: null,
+          })
           .accounts({
             debitAuthority: debitAuthorityKeypair.publicKey,
             mint: mintPubkey,
@@ -822,18 +921,20 @@ export function testOneTimeDebit(
           .signers([debitAuthorityKeypair])
           .rpc(),
       ).to.eventually.be.rejectedWith(
-        /AnchorError caused by account: pre_authorization\. Error Code: ConstraintSeeds\. Error Number: 2006\. Error Message: A seeds constraint was violated\./,
+        /AnchorError caused by account: pre_authorization\. Error Code: PreAuthorizationTokenAccountMismatch\. Error Number: 6006\. Error Message: Pre-authorization and token account mismatch\./,
       );
     });
 
     it("fails if debit_authority doesn't match", async () => {
       const newDebitAuthorityKeypair = Keypair.generate();
 
-      // we don't see the actual validation error because the PDA error is hit first
-      // it is redundant but we'll leave it in there
       await expect(
         program.methods
-          .debit({ amount: new anchor.BN(50e6) })
+          .debit({
+            amount: new anchor.BN(50e6),
+            amountMode: { exact: {} },
+            reference: null,
+          })
           .accounts({
             debitAuthority: newDebitAuthorityKeypair.publicKey,
             mint: mintPubkey,
@@ -846,13 +947,17 @@ export function testOneTimeDebit(
           .signers([newDebitAuthorityKeypair])
           .rpc(),
       ).to.eventually.be.rejectedWith(
-        /AnchorError caused by account: pre_authorization\. Error Code: ConstraintSeeds\. Error Number: 2006\. Error Message: A seeds constraint was violated\./,
+        /AnchorError caused by account: pre_authorization\. Error Code: DebitUnauthorized\. Error Number: 6010\. Error Message: Only pre_authorization\.debit_authority is authorized to debit funds using pre-authorizations\./,
       );
     });
 
     it("fires the DebitEvent event", async () => {
       const signature = await program.methods
-        .debit({ amount: new anchor.BN(50e6) })
+        .debit({
+          amount: new anchor.BN(50e6),
+          amountMode: { exact: {} },
+          reference: null,
+        })
         .accounts({
           debitAuthority: debitAuthorityKeypair.publicKey,
           mint: mintPubkey,
@@ -873,7 +978,7 @@ export function testOneTimeDebit(
       expect(events.length).to.equal(1);
       expect(events[0].name).to.equal("DebitEvent");
       const [debitEvent] = events as [DebitEvent];
-      expect(Object.keys(debitEvent.data).length).to.equal(10);
+      expect(Object.keys(debitEvent.data).length).to.equal(13);
       expect(debitEvent.data.debitAuthority.toString()).to.equal(
         debitAuthorityKeypair.publicKey.toBase58(),
       );
@@ -899,6 +1004,11 @@ export function testOneTimeDebit(
       expect(debitEvent.data.destinationTokenAccount.toString()).to.equal(
         destinationTokenAccountPubkey.toBase58(),
       );
+      expect(debitEvent.data.requestedAmount.toString()).to.equal(
+        (50e6).toString(),
+      );
+      expect(debitEvent.data.reference).to.equal(null);
+      expect(debitEvent.data.debitReceipt).to.equal(null);
       expect(
         JSON.stringify(
           // eslint-disable-next-line @typescript-eslint/no-explicit-any
@@ -910,5 +1020,380 @@ export function testOneTimeDebit(
         }),
       );
     });
+
+    it("debits up to the amount available with the UpTo amount mode", async () => {
+      const signature = await program.methods
+        .debit({
+          amount: new anchor.BN(150e6),
+          amountMode: { upTo: {} },
+          reference: null,
+        })
+        .accounts({
+          debitAuthority: debitAuthorityKeypair.publicKey,
+          mint: mintPubkey,
+          tokenAccount: tokenAccountPubkey,
+          destinationTokenAccount: destinationTokenAccountPubkey,
+          smartDelegate: smartDelegatePubkey,
+          preAuthorization: preAuthorizationPubkey,
+          tokenProgram: tokenProgramId,
+        })
+        .signers([debitAuthorityKeypair])
+        .rpc();
+
+      const destinationTokenAccountAfter = await getAccount(
+        provider.connection,
+        destinationTokenAccountPubkey,
+        undefined,
+        tokenProgramId,
+      );
+      const preAuthorizationAfter =
+        await program.account.preAuthorization.fetch(preAuthorizationPubkey);
+
+      expect(destinationTokenAccountAfter.amount.toString()).to.equal(
+        (100e6).toString(),
+      );
+      expect(
+        preAuthorizationAfter.variant.oneTime?.amountDebited.toString(),
+      ).to.equal((100e6).toString());
+
+      const tx = await waitForTxToConfirm(signature, provider.connection);
+      assert(tx.meta?.logMessages, "tx.meta?.logMessages undefined");
+
+      const eventGenerator = eventParser.parseLogs(tx.meta.logMessages);
+      const [debitEvent] = [...eventGenerator] as [DebitEvent];
+      expect(debitEvent.name).to.equal("DebitEvent");
+      expect(debitEvent.data.requestedAmount.toString()).to.equal(
+        (150e6).toString(),
+      );
+      expect(
+        JSON.stringify(
+          // eslint-disable-next-line @typescript-eslint/no-explicit-any
+          (debitEvent.data.debitVariant as any).oneTime,
+        ),
+      ).to.deep.equal(
+        JSON.stringify({
+          debitAmount: new anchor.BN(100e6),
+        }),
+      );
+    });
+
+    it("fails an UpTo debit if nothing can be debited", async () => {
+      await program.methods
+        .debit({
+          amount: new anchor.BN(100e6),
+          amountMode: { exact: {} },
+          reference: null,
+        })
+        .accounts({
+          debitAuthority: debitAuthorityKeypair.publicKey,
+          mint: mintPubkey,
+          tokenAccount: tokenAccountPubkey,
+          destinationTokenAccount: destinationTokenAccountPubkey,
+          smartDelegate: smartDelegatePubkey,
+          preAuthorization: preAuthorizationPubkey,
+          tokenProgram: tokenProgramId,
+        })
+        .signers([debitAuthorityKeypair])
+        .rpc();
+
+      await expect(
+        program.methods
+          .debit({
+            amount: new anchor.BN(10e6),
+            amountMode: { upTo: {} },
+            reference: null,
+          })
+          .accounts({
+            debitAuthority: debitAuthorityKeypair.publicKey,
+            mint: mintPubkey,
+            tokenAccount: tokenAccountPubkey,
+            destinationTokenAccount: destinationTokenAccountPubkey,
+            smartDelegate: smartDelegatePubkey,
+            preAuthorization: preAuthorizationPubkey,
+            tokenProgram: tokenProgramId,
+          })
+          .signers([debitAuthorityKeypair])
+          .rpc(),
+      ).to.eventually.be.rejectedWith(
+        /Error Code: NothingToDebit. Error Number: 6060. Error Message: Nothing can be debited from the token account/,
+      );
+    });
+
+    it("records the reference in the DebitEvent and in a debit receipt", async () => {
+      const reference = Array.from(Buffer.alloc(32, 0xab));
+      const preAuthorizationBefore =
+        await program.account.preAuthorization.fetch(preAuthorizationPubkey);
+      const [debitReceiptPubkey] = PublicKey.findProgramAddressSync(
+        [
+          Buffer.from("debit-receipt"),
+          preAuthorizationPubkey.toBuffer(),
+          preAuthorizationBefore.numDebits.toArrayLike(Buffer, "le", 8),
+        ],
+        program.programId,
+      );
+
+      const signature = await program.methods
+        .debit({
+          amount: new anchor.BN(50e6),
+          amountMode: { exact: {} },
+          reference,
+        })
+        .accounts({
+          debitAuthority: debitAuthorityKeypair.publicKey,
+          mint: mintPubkey,
+          tokenAccount: tokenAccountPubkey,
+          destinationTokenAccount: destinationTokenAccountPubkey,
+          smartDelegate: smartDelegatePubkey,
+          preAuthorization: preAuthorizationPubkey,
+          tokenProgram: tokenProgramId,
+          memoProgram: MEMO_PROGRAM_ID,
+          receiptPayer: fundedKeypair.publicKey,
+          debitReceipt: debitReceiptPubkey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([debitAuthorityKeypair, fundedKeypair])
+        .rpc();
+
+      const tx = await waitForTxToConfirm(signature, provider.connection);
+      assert(tx.meta?.logMessages, "tx.meta?.logMessages undefined");
+
+      const eventGenerator = eventParser.parseLogs(tx.meta.logMessages);
+      const [debitEvent] = [...eventGenerator] as [DebitEvent];
+      expect(debitEvent.name).to.equal("DebitEvent");
+      expect(debitEvent.data.reference).to.deep.equal(reference);
+      expect(debitEvent.data.debitReceipt?.toString()).to.equal(
+        debitReceiptPubkey.toBase58(),
+      );
+
+      const debitReceipt =
+        await program.account.debitReceipt.fetch(debitReceiptPubkey);
+      expect(debitReceipt.preAuthorization.toString()).to.equal(
+        preAuthorizationPubkey.toBase58(),
+      );
+      expect(debitReceipt.debitIndex.toString()).to.equal(
+        preAuthorizationBefore.numDebits.toString(),
+      );
+      expect(debitReceipt.debitAuthority.toString()).to.equal(
+        debitAuthorityKeypair.publicKey.toBase58(),
+      );
+      expect(debitReceipt.payer.toString()).to.equal(
+        fundedKeypair.publicKey.toBase58(),
+      );
+      expect(debitReceipt.sourceTokenAccount.toString()).to.equal(
+        tokenAccountPubkey.toBase58(),
+      );
+      expect(debitReceipt.destinationTokenAccount.toString()).to.equal(
+        destinationTokenAccountPubkey.toBase58(),
+      );
+      expect(debitReceipt.amount.toString()).to.equal((50e6).toString());
+      expect(debitReceipt.reference).to.deep.equal(reference);
+    });
+
+    context("owner scoped pre-authorization", () => {
+      let ownerDebitAuthorityKeypair: Keypair,
+        ownerPreAuthorizationPubkey: PublicKey;
+
+      beforeEach(async () => {
+        ownerDebitAuthorityKeypair = Keypair.generate();
+        [ownerPreAuthorizationPubkey] = deriveOwnerPreAuthorization(
+          userKeypair.publicKey,
+          mintPubkey,
+          ownerDebitAuthorityKeypair.publicKey,
+          program.programId,
+        );
+        const activationUnixTimestamp = getCurrentUnixTimestamp() - 60;
+        await program.methods
+          .initOwnerPreAuthorization({
+            variant: {
+              oneTime: {
+                amountAuthorized: new anchor.BN(100e6),
+                expiryUnixTimestamp: new anchor.BN(
+                  activationUnixTimestamp + 10 * 24 * 60 * 60,
+                ),
+                maxAmountPerDebit: null,
+                closeOnExhaustion: false,
+              },
+            },
+            debitAuthority: ownerDebitAuthorityKeypair.publicKey,
+            activationUnixTimestamp: new anchor.BN(activationUnixTimestamp),
+            rotationRequiresOwnerSignature: false,
+            destinationConstraint: null,
+            boundedDelegation: false,
+          })
+          .accounts({
+            payer: provider.publicKey,
+            owner: userKeypair.publicKey,
+            mint: mintPubkey,
+            preAuthorization: ownerPreAuthorizationPubkey,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([userKeypair])
+          .rpc();
+      });
+
+      it("allows the debit_authority to debit funds with the pre-authorization counter", async () => {
+        const sourceTokenAccountBefore = await getAccount(
+          provider.connection,
+          tokenAccountPubkey,
+          undefined,
+          tokenProgramId,
+        );
+
+        await program.methods
+          .debit({
+            amount: new anchor.BN(10e6),
+            amountMode: { exact: {} },
+            reference: null,
+          })
+          .accounts({
+            debitAuthority: ownerDebitAuthorityKeypair.publicKey,
+            mint: mintPubkey,
+            tokenAccount: tokenAccountPubkey,
+            destinationTokenAccount: destinationTokenAccountPubkey,
+            smartDelegate: smartDelegatePubkey,
+            preAuthorization: ownerPreAuthorizationPubkey,
+            tokenProgram: tokenProgramId,
+            preAuthorizationCounter: derivePreAuthorizationCounter(
+              tokenAccountPubkey,
+              program.programId,
+            )[0],
+          })
+          .signers([ownerDebitAuthorityKeypair])
+          .rpc();
+
+        const sourceTokenAccountAfter = await getAccount(
+          provider.connection,
+          tokenAccountPubkey,
+          undefined,
+          tokenProgramId,
+        );
+        expect(
+          (
+            sourceTokenAccountBefore.amount - sourceTokenAccountAfter.amount
+          ).toString(),
+        ).to.equal((10e6).toString());
+      });
+
+      it("fails if the pre-authorization counter is not set", async () => {
+        await expect(
+          program.methods
+            .debit({
+              amount: new anchor.BN(10e6),
+              amountMode: { exact: {} },
+              reference: null,
+            })
+            .accounts({
+              debitAuthority: ownerDebitAuthorityKeypair.publicKey,
+              mint: mintPubkey,
+              tokenAccount: tokenAccountPubkey,
+              destinationTokenAccount: destinationTokenAccountPubkey,
+              smartDelegate: smartDelegatePubkey,
+              preAuthorization: ownerPreAuthorizationPubkey,
+              tokenProgram: tokenProgramId,
+            })
+            .signers([ownerDebitAuthorityKeypair])
+            .rpc(),
+        ).to.eventually.be.rejectedWith(
+          /Error Code: PreAuthorizationCounterRequired. Error Number: 6049. Error Message: Pre-authorization counter of the token account is required./,
+        );
+      });
+
+      it("fails if the token account is in bounded delegation mode", async () => {
+        const boundedTokenAccountPubkey = await createAccount(
+          provider.connection,
+          fundedKeypair,
+          mintPubkey,
+          userKeypair.publicKey,
+          Keypair.generate(),
+          undefined,
+          tokenProgramId,
+        );
+        await mintTo(
+          provider.connection,
+          fundedKeypair,
+          mintPubkey,
+          boundedTokenAccountPubkey,
+          mintAuthorityKeypair,
+          1000e6,
+          undefined,
+          undefined,
+          tokenProgramId,
+        );
+        const [boundedPreAuthorizationCounterPubkey] =
+          derivePreAuthorizationCounter(
+            boundedTokenAccountPubkey,
+            program.programId,
+          );
+        await program.methods
+          .initPreAuthorizationCounter({ count: new anchor.BN(0) })
+          .accounts({
+            payer: provider.publicKey,
+            owner: userKeypair.publicKey,
+            tokenAccount: boundedTokenAccountPubkey,
+            preAuthorizationCounter: boundedPreAuthorizationCounterPubkey,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([userKeypair])
+          .rpc();
+        const activationUnixTimestamp = getCurrentUnixTimestamp() - 60;
+        await program.methods
+          .initPreAuthorization({
+            variant: {
+              oneTime: {
+                amountAuthorized: new anchor.BN(100e6),
+                expiryUnixTimestamp: new anchor.BN(
+                  activationUnixTimestamp + 10 * 24 * 60 * 60,
+                ),
+                maxAmountPerDebit: null,
+                closeOnExhaustion: false,
+              },
+            },
+            debitAuthority: debitAuthorityKeypair.publicKey,
+            activationUnixTimestamp: new anchor.BN(activationUnixTimestamp),
+            rotationRequiresOwnerSignature: false,
+            destinationConstraint: null,
+            boundedDelegation: true,
+          })
+          .accounts({
+            payer: provider.publicKey,
+            owner: userKeypair.publicKey,
+            smartDelegate: smartDelegatePubkey,
+            tokenAccount: boundedTokenAccountPubkey,
+            preAuthorization: derivePreAuthorization(
+              boundedTokenAccountPubkey,
+              debitAuthorityKeypair.publicKey,
+              program.programId,
+            )[0],
+            preAuthorizationCounter: boundedPreAuthorizationCounterPubkey,
+            tokenProgram: tokenProgramId,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([userKeypair])
+          .rpc();
+
+        await expect(
+          program.methods
+            .debit({
+              amount: new anchor.BN(10e6),
+              amountMode: { exact: {} },
+              reference: null,
+            })
+            .accounts({
+              debitAuthority: ownerDebitAuthorityKeypair.publicKey,
+              mint: mintPubkey,
+              tokenAccount: boundedTokenAccountPubkey,
+              destinationTokenAccount: destinationTokenAccountPubkey,
+              smartDelegate: smartDelegatePubkey,
+              preAuthorization: ownerPreAuthorizationPubkey,
+              tokenProgram: tokenProgramId,
+              preAuthorizationCounter: boundedPreAuthorizationCounterPubkey,
+            })
+            .signers([ownerDebitAuthorityKeypair])
+            .rpc(),
+        ).to.eventually.be.rejectedWith(
+          /Error Code: OwnerScopedDebitInBoundedDelegation. Error Number: 6069. Error Message: Owner scoped pre-authorizations cannot debit a token account in bounded delegation mode./,
+        );
+      });
+    });
   });
 }
@@ -13,6 +13,7 @@ import {
   DebitEvent,
   U64_MAX,
   derivePreAuthorization,
+  derivePreAuthorizationCounter,
   getCurrentUnixTimestamp,
 } from "../utils";
 import {
@@ -75,10 +76,20 @@ export function testRecurringDebit(
               ),
               numCycles: numCycles ? new anchor.BN(numCycles.toString()) : null,
               resetEveryCycle,
+              maxAmountPerDebit: null,
+              minAmountPerDebit: null,
+              maxAccruedCycles: null,
+              calendarSchedule: null,
+              debitWindowSeconds: null,
+              debitWindowOffsetSeconds: null,
+              introSchedule: null,
             },
           },
           debitAuthority: debitAuthorityKeypair.publicKey,
           activationUnixTimestamp: new anchor.BN(activationUnixTimestamp),
+          rotationRequiresOwnerSignature: false,
+          destinationConstraint: null,
+          boundedDelegation: false,
         })
         .accounts({
           payer: provider.publicKey,
@@ -86,6 +97,10 @@ export function testRecurringDebit(
           smartDelegate: smartDelegatePubkey,
           tokenAccount: tokenAccountPubkey,
           preAuthorization: preAuthorizationPubkey,
+          preAuthorizationCounter: derivePreAuthorizationCounter(
+            tokenAccountPubkey,
+            program.programId,
+          )[0],
           tokenProgram: tokenProgramId,
           systemProgram: SystemProgram.programId,
         })
@@ -199,7 +214,11 @@ export function testRecurringDebit(
 
         for (let i = 1; i <= 2; i++) {
           await program.methods
-            .debit({ amount: new anchor.BN(33e6) })
+            .debit({
+              amount: new anchor.BN(33e6),
+              amountMode: { exact: {} },
+              reference: null,
+            })
             .accounts({
               debitAuthority: debitAuthorityKeypair.publicKey,
               mint: mintPubkey,
@@ -257,6 +276,7 @@ export function testRecurringDebit(
 
           expect({
             ...preAuthorizationBefore,
+            numDebits: null,
             variant: {
               recurring: {
                 ...preAuthorizationBefore.variant.recurring,
@@ -267,6 +287,7 @@ export function testRecurringDebit(
             },
           }).to.deep.equal({
             ...preAuthorizationAfter,
+            numDebits: null,
             variant: {
               recurring: {
                 ...preAuthorizationAfter.variant.recurring,
@@ -321,7 +342,11 @@ export function testRecurringDebit(
 
         for (let i = 1; i <= 2; i++) {
           await program.methods
-            .debit({ amount: new anchor.BN(10e6) })
+            .debit({
+              amount: new anchor.BN(10e6),
+              amountMode: { exact: {} },
+              reference: null,
+            })
             .accounts({
               debitAuthority: debitAuthorityKeypair.publicKey,
               mint: mintPubkey,
@@ -379,6 +404,7 @@ export function testRecurringDebit(
 
           expect({
             ...preAuthorizationBefore,
+            numDebits: null,
             variant: {
               recurring: {
                 ...preAuthorizationBefore.variant.recurring,
@@ -389,6 +415,7 @@ export function testRecurringDebit(
             },
           }).to.deep.equal({
             ...preAuthorizationAfter,
+            numDebits: null,
             variant: {
               recurring: {
                 ...preAuthorizationAfter.variant.recurring,
@@ -421,7 +448,11 @@ export function testRecurringDebit(
 
         await expect(
           program.methods
-            .debit({ amount: new anchor.BN(50e6) })
+            .debit({
+              amount: new anchor.BN(50e6),
+              amountMode: { exact: {} },
+              reference: null,
+            })
             .accounts({
               debitAuthority: debitAuthorityKeypair.publicKey,
               mint: mintPubkey,
@@ -457,7 +488,11 @@ export function testRecurringDebit(
         ).to.equal((33e6).toString());
 
         await program.methods
-          .debit({ amount: new anchor.BN(30e6) })
+          .debit({
+            amount: new anchor.BN(30e6),
+            amountMode: { exact: {} },
+            reference: null,
+          })
           .accounts({
             debitAuthority: debitAuthorityKeypair.publicKey,
             mint: mintPubkey,
@@ -472,7 +507,11 @@ export function testRecurringDebit(
 
         await expect(
           program.methods
-            .debit({ amount: new anchor.BN(4e6) })
+            .debit({
+              amount: new anchor.BN(4e6),
+              amountMode: { exact: {} },
+              reference: null,
+            })
             .accounts({
               debitAuthority: debitAuthorityKeypair.publicKey,
               mint: mintPubkey,
@@ -489,7 +528,11 @@ export function testRecurringDebit(
         );
 
         await program.methods
-          .debit({ amount: new anchor.BN(3e6) })
+          .debit({
+            amount: new anchor.BN(3e6),
+            amountMode: { exact: {} },
+            reference: null,
+          })
           .accounts({
             debitAuthority: debitAuthorityKeypair.publicKey,
             mint: mintPubkey,
@@ -505,7 +548,11 @@ export function testRecurringDebit(
 
       it("does not carry-forward un-debited amount from previous cycle", async () => {
         await program.methods
-          .debit({ amount: new anchor.BN(23e6) })
+          .debit({
+            amount: new anchor.BN(23e6),
+            amountMode: { exact: {} },
+            reference: null,
+          })
           .accounts({
             debitAuthority: debitAuthorityKeypair.publicKey,
             mint: mintPubkey,
@@ -528,7 +575,11 @@ export function testRecurringDebit(
 
         await expect(
           program.methods
-            .debit({ amount: new anchor.BN(34e6) })
+            .debit({
+              amount: new anchor.BN(34e6),
+              amountMode: { exact: {} },
+              reference: null,
+            })
             .accounts({
               debitAuthority: debitAuthorityKeypair.publicKey,
               mint: mintPubkey,
@@ -545,7 +596,11 @@ export function testRecurringDebit(
         );
 
         await program.methods
-          .debit({ amount: new anchor.BN(33e6) })
+          .debit({
+            amount: new anchor.BN(33e6),
+            amountMode: { exact: {} },
+            reference: null,
+          })
           .accounts({
             debitAuthority: debitAuthorityKeypair.publicKey,
             mint: mintPubkey,
@@ -585,7 +640,11 @@ export function testRecurringDebit(
         it("prevents a 3rd cycle debit", async () => {
           for (let i = 1; i <= 2; i++) {
             await program.methods
-              .debit({ amount: new anchor.BN(33e6) })
+              .debit({
+                amount: new anchor.BN(33e6),
+                amountMode: { exact: {} },
+                reference: null,
+              })
               .accounts({
                 debitAuthority: debitAuthorityKeypair.publicKey,
                 mint: mintPubkey,
@@ -620,7 +679,11 @@ export function testRecurringDebit(
 
           await expect(
             program.methods
-              .debit({ amount: new anchor.BN(33e6) })
+              .debit({
+                amount: new anchor.BN(33e6),
+                amountMode: { exact: {} },
+                reference: null,
+              })
               .accounts({
                 debitAuthority: debitAuthorityKeypair.publicKey,
                 mint: mintPubkey,
@@ -694,7 +757,11 @@ export function testRecurringDebit(
         await delay(3);
 
         await program.methods
-          .debit({ amount: new anchor.BN(66e6) })
+          .debit({
+            amount: new anchor.BN(66e6),
+            amountMode: { exact: {} },
+            reference: null,
+          })
           .accounts({
             debitAuthority: debitAuthorityKeypair.publicKey,
             mint: mintPubkey,
@@ -750,6 +817,7 @@ export function testRecurringDebit(
 
         expect({
           ...preAuthorizationBefore,
+          numDebits: null,
           variant: {
             recurring: {
               ...preAuthorizationBefore.variant.recurring,
@@ -760,6 +828,7 @@ export function testRecurringDebit(
           },
         }).to.deep.equal({
           ...preAuthorizationAfter,
+          numDebits: null,
           variant: {
             recurring: {
               ...preAuthorizationAfter.variant.recurring,
@@ -792,7 +861,11 @@ export function testRecurringDebit(
           await delay(4);
 
           await program.methods
-            .debit({ amount: new anchor.BN(66e6) })
+            .debit({
+              amount: new anchor.BN(66e6),
+              amountMode: { exact: {} },
+              reference: null,
+            })
             .accounts({
               debitAuthority: debitAuthorityKeypair.publicKey,
               mint: mintPubkey,
@@ -826,7 +899,11 @@ export function testRecurringDebit(
 
           await expect(
             program.methods
-              .debit({ amount: new anchor.BN(33e6) })
+              .debit({
+                amount: new anchor.BN(33e6),
+                amountMode: { exact: {} },
+                reference: null,
+              })
               .accounts({
                 debitAuthority: debitAuthorityKeypair.publicKey,
                 mint: mintPubkey,
@@ -878,7 +955,11 @@ export function testRecurringDebit(
 
         await expect(
           program.methods
-            .debit({ amount: new anchor.BN(50e6) })
+            .debit({
+              amount: new anchor.BN(50e6),
+              amountMode: { exact: {} },
+              reference: null,
+            })
             .accounts({
               debitAuthority: debitAuthorityKeypair.publicKey,
               mint: mintPubkey,
@@ -902,7 +983,13 @@ export function testRecurringDebit(
             receiver: userKeypair.publicKey,
             authority: userKeypair.publicKey,
             tokenAccount: tokenAccountPubkey,
+            smartDelegate: smartDelegatePubkey,
             preAuthorization: preAuthorizationPubkey,
+            preAuthorizationCounter: derivePreAuthorizationCounter(
+              tokenAccountPubkey,
+              program.programId,
+            )[0],
+            tokenProgram: tokenProgramId,
           })
           .signers([userKeypair])
           .rpc();
@@ -920,7 +1007,11 @@ export function testRecurringDebit(
 
         await expect(
           program.methods
-            .debit({ amount: new anchor.BN(50e6) })
+            .debit({
+              amount: new anchor.BN(50e6),
+              amountMode: { exact: {} },
+              reference: null,
+            })
             .accounts({
               debitAuthority: debitAuthorityKeypair.publicKey,
               mint: mintPubkey,
@@ -977,10 +1068,15 @@ export function testRecurringDebit(
               oneTime: {
                 amountAuthorized: new anchor.BN(100e6),
                 expiryUnixTimestamp: new anchor.BN(expirationUnixTimestamp),
+                maxAmountPerDebit: null,
+                closeOnExhaustion: false,
               },
             },
             debitAuthority: debitAuthorityKeypair.publicKey,
             activationUnixTimestamp: new anchor.BN(activationUnixTimestamp),
+            rotationRequiresOwnerSignature: false,
+            destinationConstraint: null,
+            boundedDelegation: false,
           })
           .accounts({
             payer: provider.publicKey,
@@ -988,17 +1084,23 @@ export function testRecurringDebit(
             smartDelegate: smartDelegatePubkey,
             tokenAccount: newTokenAccountPubkey,
             preAuthorization: newPreAuthorizationPubkey,
+            preAuthorizationCounter: derivePreAuthorizationCounter(
+              newTokenAccountPubkey,
+              program.programId,
+            )[0],
             tokenProgram: tokenProgramId,
             systemProgram: SystemProgram.programId,
           })
           .signers([newUserKeypair])
           .rpc();
 
-        // we don't see the actual validation error because the PDA error is hit first
-        // it is redundant but we'll leave it in there
         await expect(
           program.methods
-            .debit({ amount: new anchor.BN(50e6) })
+            .debit({
+              amount: new anchor.BN(50e6),
+              amountMode: { exact: {} },
+              reference: null,
+            })
             .accounts({
               debitAuthority: debitAuthorityKeypair.publicKey,
               mint: mintPubkey,
@@ -1011,18 +1113,20 @@ export function testRecurringDebit(
             .signers([debitAuthorityKeypair])
             .rpc(),
         ).to.eventually.be.rejectedWith(
-          /AnchorError caused by account: pre_authorization\. Error Code: ConstraintSeeds\. Error Number: 2006\. Error Message: A seeds constraint was violated\./,
+          /AnchorError caused by account: pre_authorization\. Error Code: PreAuthorizationTokenAccountMismatch\. Error Number: 6006\. Error Message: Pre-authorization and token account mismatch\./,
         );
       });
 
       it("fails if debit_authority doesn't match", async () => {
         const newDebitAuthorityKeypair = Keypair.generate();
 
-        // we don't see the actual validation error because the PDA error is hit first
-        // it is redundant but we'll leave it in there
         await expect(
           program.methods
-            .debit({ amount: new anchor.BN(50e6) })
+            .debit({
+              amount: new anchor.BN(50e6),
+              amountMode: { exact: {} },
+              reference: null,
+            })
             .accounts({
               debitAuthority: newDebitAuthorityKeypair.publicKey,
               mint: mintPubkey,
@@ -1035,13 +1139,17 @@ export function testRecurringDebit(
             .signers([newDebitAuthorityKeypair])
             .rpc(),
         ).to.eventually.be.rejectedWith(
-          /AnchorError caused by account: pre_authorization\. Error Code: ConstraintSeeds\. Error Number: 2006\. Error Message: A seeds constraint was violated\./,
+          /AnchorError caused by account: pre_authorization\. Error Code: DebitUnauthorized\. Error Number: 6010\. Error Message: Only pre_authorization\.debit_authority is authorized to debit funds using pre-authorizations\./,
         );
       });
 
       it("fires the DebitEvent event", async () => {
         const signature = await program.methods
-          .debit({ amount: new anchor.BN(50e6) })
+          .debit({
+            amount: new anchor.BN(50e6),
+            amountMode: { exact: {} },
+            reference: null,
+          })
           .accounts({
             debitAuthority: debitAuthorityKeypair.publicKey,
             mint: mintPubkey,
@@ -1062,7 +1170,7 @@ export function testRecurringDebit(
         expect(events.length).to.equal(1);
         expect(events[0].name).to.equal("DebitEvent");
         const [debitEvent] = events as [DebitEvent];
-        expect(Object.keys(debitEvent.data).length).to.equal(10);
+        expect(Object.keys(debitEvent.data).length).to.equal(13);
         expect(debitEvent.data.debitAuthority.toString()).to.equal(
           debitAuthorityKeypair.publicKey.toBase58(),
         );
@@ -1088,6 +1196,11 @@ export function testRecurringDebit(
         expect(debitEvent.data.destinationTokenAccount.toString()).to.equal(
           destinationTokenAccountPubkey.toBase58(),
         );
+        expect(debitEvent.data.requestedAmount.toString()).to.equal(
+          (50e6).toString(),
+        );
+        expect(debitEvent.data.reference).to.equal(null);
+        expect(debitEvent.data.debitReceipt).to.equal(null);
         expect(
           JSON.stringify(
             // eslint-disable-next-line @typescript-eslint/no-explicit-any
@@ -1119,7 +1232,11 @@ export function testRecurringDebit(
 
         await expect(
           program.methods
-            .debit({ amount: new anchor.BN(50e6) })
+            .debit({
+              amount: new anchor.BN(50e6),
+              amountMode: { exact: {} },
+              reference: null,
+            })
             .accounts({
               debitAuthority: debitAuthorityKeypair.publicKey,
               mint: mintPubkey,
@@ -1158,7 +1275,11 @@ export function testRecurringDebit(
         );
 
         await program.methods
-          .debit({ amount: new anchor.BN(33e6) })
+          .debit({
+            amount: new anchor.BN(33e6),
+            amountMode: { exact: {} },
+            reference: null,
+          })
           .accounts({
             debitAuthority: debitAuthorityKeypair.publicKey,
             mint: mintPubkey,
@@ -1227,6 +1348,8 @@ export function testRecurringDebit(
         await program.methods
           .debit({
             amount: new anchor.BN(accumulatedAvailableAmount.toString()),
+            amountMode: { exact: {} },
+            reference: null,
           })
           .accounts({
             debitAuthority: debitAuthorityKeypair.publicKey,
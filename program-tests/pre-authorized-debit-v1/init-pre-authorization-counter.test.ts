@@ -0,0 +1,158 @@
+import { expect } from "chai";
+import { Keypair, PublicKey, SystemProgram } from "@solana/web3.js";
+import { createMint, TOKEN_PROGRAM_ID, createAccount } from "@solana/spl-token";
+import * as anchor from "@coral-xyz/anchor";
+
+import { program, provider } from "./setup";
+import {
+  derivePreAuthorization,
+  derivePreAuthorizationCounter,
+  getCurrentUnixTimestamp,
+} from "./utils";
+import {
+  fundAccounts,
+  initSmartDelegateIdempotent,
+} from "@seabed-labs/pad-test-utils";
+
+describe("pre-authorized-debit-v1#init-pre-authorization-counter", () => {
+  let owner: Keypair, mintAuthority: Keypair, debitAuthority: Keypair;
+  let smartDelegatePublicKey: PublicKey,
+    tokenAccount: PublicKey,
+    preAuthorizationCounter: PublicKey;
+
+  before(async () => {
+    smartDelegatePublicKey = await initSmartDelegateIdempotent(
+      program,
+      provider,
+    );
+  });
+
+  beforeEach(async () => {
+    owner = Keypair.generate();
+    mintAuthority = Keypair.generate();
+    debitAuthority = Keypair.generate();
+    await fundAccounts(
+      provider,
+      [owner.publicKey, mintAuthority.publicKey],
+      1e9,
+    );
+    const mint = await createMint(
+      provider.connection,
+      mintAuthority,
+      mintAuthority.publicKey,
+      null,
+      6,
+      Keypair.generate(),
+      undefined,
+      TOKEN_PROGRAM_ID,
+    );
+    tokenAccount = await createAccount(
+      provider.connection,
+      mintAuthority,
+      mint,
+      owner.publicKey,
+      Keypair.generate(),
+      undefined,
+      TOKEN_PROGRAM_ID,
+    );
+    [preAuthorizationCounter] = derivePreAuthorizationCounter(
+      tokenAccount,
+      program.programId,
+    );
+  });
+
+  async function initPreAuthorizationCounter(count: number): Promise<string> {
+    return program.methods
+      .initPreAuthorizationCounter({ count: new anchor.BN(count) })
+      .accounts({
+        payer: provider.publicKey,
+        owner: owner.publicKey,
+        tokenAccount,
+        preAuthorizationCounter,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([owner])
+      .rpc();
+  }
+
+  async function initPreAuthorization(
+    boundedDelegation: boolean,
+  ): Promise<void> {
+    const activationUnixTimestamp = getCurrentUnixTimestamp() - 60;
+    await program.methods
+      .initPreAuthorization({
+        variant: {
+          oneTime: {
+            amountAuthorized: new anchor.BN(100e6),
+            expiryUnixTimestamp: new anchor.BN(
+              activationUnixTimestamp + 10 * 24 * 60 * 60,
+            ),
+            maxAmountPerDebit: null,
+            closeOnExhaustion: false,
+          },
+        },
+        debitAuthority: debitAuthority.publicKey,
+        activationUnixTimestamp: new anchor.BN(activationUnixTimestamp),
+        rotationRequiresOwnerSignature: false,
+        destinationConstraint: null,
+        boundedDelegation,
+      })
+      .accounts({
+        payer: provider.publicKey,
+        owner: owner.publicKey,
+        smartDelegate: smartDelegatePublicKey,
+        tokenAccount,
+        preAuthorization: derivePreAuthorization(
+          tokenAccount,
+          debitAuthority.publicKey,
+          program.programId,
+        )[0],
+        preAuthorizationCounter,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([owner])
+      .rpc();
+  }
+
+  it("should set the count of an unverified pre-authorization counter", async () => {
+    // the counter is created (unverified) by the first pre-authorization
+    await initPreAuthorization(false);
+    await initPreAuthorizationCounter(3);
+
+    const counterData = await program.account.preAuthorizationCounter.fetch(
+      preAuthorizationCounter,
+    );
+    expect(counterData.count.toString()).to.equal("3");
+    expect(counterData.countVerified).to.equal(true);
+  });
+
+  it("should fail to set the count of a verified pre-authorization counter", async () => {
+    await initPreAuthorizationCounter(0);
+    await initPreAuthorization(false);
+
+    await expect(initPreAuthorizationCounter(0)).to.eventually.be.rejectedWith(
+      /Error Code: PreAuthorizationCounterAlreadyVerified. Error Number: 6070/,
+    );
+
+    const counterData = await program.account.preAuthorizationCounter.fetch(
+      preAuthorizationCounter,
+    );
+    expect(counterData.count.toString()).to.equal("1");
+  });
+
+  it("should fail to set the count in bounded delegation mode", async () => {
+    await initPreAuthorizationCounter(0);
+    await initPreAuthorization(true);
+
+    await expect(initPreAuthorizationCounter(0)).to.eventually.be.rejectedWith(
+      /Error Code: PreAuthorizationCounterInBoundedDelegation. Error Number: 6071/,
+    );
+
+    const counterData = await program.account.preAuthorizationCounter.fetch(
+      preAuthorizationCounter,
+    );
+    expect(counterData.count.toString()).to.equal("1");
+    expect(counterData.boundedDelegation).to.equal(true);
+  });
+});
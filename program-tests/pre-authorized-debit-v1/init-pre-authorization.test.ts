@@ -13,6 +13,7 @@ import {
   PreAuthTestVariant,
   deriveInvalidPreAuthorization,
   derivePreAuthorization,
+  derivePreAuthorizationCounter,
   U64_MAX,
 } from "./utils";
 import {
@@ -148,6 +149,8 @@ describe("pre-authorized-debit-v1#init-pre-authorization", () => {
                       expiryUnixTimestamp: new anchor.BN(
                         expirationUnixTimestamp,
                       ),
+                      maxAmountPerDebit: null,
+                      closeOnExhaustion: false,
                     },
                   }
                 : {
@@ -156,6 +159,13 @@ describe("pre-authorized-debit-v1#init-pre-authorization", () => {
                       recurringAmountAuthorized: new anchor.BN(10e6),
                       numCycles: null,
                       resetEveryCycle: false,
+                      maxAmountPerDebit: null,
+                      minAmountPerDebit: null,
+                      maxAccruedCycles: null,
+                      calendarSchedule: null,
+                      debitWindowSeconds: null,
+                      debitWindowOffsetSeconds: null,
+                      introSchedule: null,
                     },
                   };
 
@@ -164,6 +174,9 @@ describe("pre-authorized-debit-v1#init-pre-authorization", () => {
                 variant: preAuthVariant,
                 debitAuthority: debitAuthority.publicKey,
                 activationUnixTimestamp: new anchor.BN(activationUnixTimestamp),
+                rotationRequiresOwnerSignature: false,
+                destinationConstraint: null,
+                boundedDelegation: false,
               })
               .accounts({
                 payer: payer.publicKey,
@@ -171,6 +184,10 @@ describe("pre-authorized-debit-v1#init-pre-authorization", () => {
                 smartDelegate: smartDelegatePublicKey,
                 tokenAccount: validTokenAccount,
                 preAuthorization,
+                preAuthorizationCounter: derivePreAuthorizationCounter(
+                  validTokenAccount,
+                  program.programId,
+                )[0],
                 tokenProgram: tokenProgramId,
                 systemProgram: SystemProgram.programId,
               })
@@ -282,6 +299,7 @@ describe("pre-authorized-debit-v1#init-pre-authorization", () => {
                 ...preAuthorizationAccount,
                 activationUnixTimestamp:
                   preAuthorizationAccount.activationUnixTimestamp.toString(),
+                numDebits: preAuthorizationAccount.numDebits.toString(),
                 variant: {
                   oneTime: {
                     ...preAuthorizationAccount.variant.oneTime,
@@ -299,11 +317,17 @@ describe("pre-authorized-debit-v1#init-pre-authorization", () => {
                 tokenAccount: validTokenAccount,
                 debitAuthority: debitAuthority.publicKey,
                 activationUnixTimestamp: activationUnixTimestamp.toString(),
+                rotationRequiresOwnerSignature: false,
+                scope: { tokenAccount: {} },
+                destinationConstraint: null,
+                numDebits: "0",
                 variant: {
                   oneTime: {
                     amountAuthorized: (100e6).toString(),
                     expiryUnixTimestamp: expirationUnixTimestamp.toString(),
                     amountDebited: "0",
+                    maxAmountPerDebit: null,
+                    closeOnExhaustion: false,
                   },
                 },
               });
@@ -312,6 +336,7 @@ describe("pre-authorized-debit-v1#init-pre-authorization", () => {
                 ...preAuthorizationAccount,
                 activationUnixTimestamp:
                   preAuthorizationAccount.activationUnixTimestamp.toString(),
+                numDebits: preAuthorizationAccount.numDebits.toString(),
                 variant: {
                   recurring: {
                     ...preAuthorizationAccount.variant.recurring,
@@ -325,6 +350,10 @@ describe("pre-authorized-debit-v1#init-pre-authorization", () => {
                       preAuthorizationAccount.variant.recurring?.amountDebitedTotal.toString(),
                     lastDebitedCycle:
                       preAuthorizationAccount.variant.recurring?.lastDebitedCycle.toString(),
+                    amendedAtCycle:
+                      preAuthorizationAccount.variant.recurring?.amendedAtCycle.toString(),
+                    amountAuthorizedBeforeAmendment:
+                      preAuthorizationAccount.variant.recurring?.amountAuthorizedBeforeAmendment.toString(),
                   },
                 },
               }).to.deep.equal({
@@ -333,6 +362,10 @@ describe("pre-authorized-debit-v1#init-pre-authorization", () => {
                 tokenAccount: validTokenAccount,
                 debitAuthority: debitAuthority.publicKey,
                 activationUnixTimestamp: activationUnixTimestamp.toString(),
+                rotationRequiresOwnerSignature: false,
+                scope: { tokenAccount: {} },
+                destinationConstraint: null,
+                numDebits: "0",
                 variant: {
                   recurring: {
                     repeatFrequencySeconds: "30",
@@ -342,6 +375,15 @@ describe("pre-authorized-debit-v1#init-pre-authorization", () => {
                     lastDebitedCycle: "1",
                     numCycles: null,
                     resetEveryCycle: false,
+                    amendedAtCycle: "1",
+                    amountAuthorizedBeforeAmendment: "0",
+                    maxAmountPerDebit: null,
+                    minAmountPerDebit: null,
+                    maxAccruedCycles: null,
+                    calendarSchedule: null,
+                    debitWindowSeconds: null,
+                    debitWindowOffsetSeconds: null,
+                    introSchedule: null,
                   },
                 },
               });
@@ -377,13 +419,18 @@ describe("pre-authorized-debit-v1#init-pre-authorization", () => {
           oneTime: {
             amountAuthorized: new anchor.BN(100e6),
             expiryUnixTimestamp: new anchor.BN(-1),
+            maxAmountPerDebit: null,
+            closeOnExhaustion: false,
           },
         };
         await program.methods
           .initPreAuthorization({
             variant: preAuthVariant,
             debitAuthority: debitAuthority.publicKey,
-            activationUnixTimestamp: new anchor.BN(-1),
+            activationUnixTimestamp: new anchor.BN(-2),
+            rotationRequiresOwnerSignature: false,
+            destinationConstraint: null,
+            boundedDelegation: false,
           })
           .accounts({
             payer: payer.publicKey,
@@ -391,6 +438,10 @@ describe("pre-authorized-debit-v1#init-pre-authorization", () => {
             smartDelegate: smartDelegatePublicKey,
             tokenAccount: validTokenAccount,
             preAuthorization,
+            preAuthorizationCounter: derivePreAuthorizationCounter(
+              validTokenAccount,
+              program.programId,
+            )[0],
             tokenProgram: tokenProgramId,
             systemProgram: SystemProgram.programId,
           })
@@ -403,7 +454,7 @@ describe("pre-authorized-debit-v1#init-pre-authorization", () => {
           preAuthAccount.variant.oneTime?.expiryUnixTimestamp.toString(),
         ).to.equal("-1");
         expect(preAuthAccount.activationUnixTimestamp.toString()).to.equal(
-          "-1",
+          "-2",
         );
       });
 
@@ -419,6 +470,13 @@ describe("pre-authorized-debit-v1#init-pre-authorization", () => {
             recurringAmountAuthorized: new anchor.BN(10e6),
             numCycles: null,
             resetEveryCycle: false,
+            maxAmountPerDebit: null,
+            minAmountPerDebit: null,
+            maxAccruedCycles: null,
+            calendarSchedule: null,
+            debitWindowSeconds: null,
+            debitWindowOffsetSeconds: null,
+            introSchedule: null,
           },
         };
         await expect(
@@ -427,6 +485,9 @@ describe("pre-authorized-debit-v1#init-pre-authorization", () => {
               variant: preAuthVariant,
               debitAuthority: debitAuthority.publicKey,
               activationUnixTimestamp: new anchor.BN(-1),
+              rotationRequiresOwnerSignature: false,
+              destinationConstraint: null,
+              boundedDelegation: false,
             })
             .accounts({
               payer: payer.publicKey,
@@ -434,6 +495,10 @@ describe("pre-authorized-debit-v1#init-pre-authorization", () => {
               smartDelegate: smartDelegatePublicKey,
               tokenAccount: validTokenAccount,
               preAuthorization,
+              preAuthorizationCounter: derivePreAuthorizationCounter(
+                validTokenAccount,
+                program.programId,
+              )[0],
               tokenProgram: tokenProgramId,
               systemProgram: SystemProgram.programId,
             })
@@ -454,6 +519,8 @@ describe("pre-authorized-debit-v1#init-pre-authorization", () => {
           oneTime: {
             amountAuthorized: new anchor.BN(100e6),
             expiryUnixTimestamp: new anchor.BN(expirationUnixTimestamp),
+            maxAmountPerDebit: null,
+            closeOnExhaustion: false,
           },
         };
         await expect(
@@ -462,6 +529,9 @@ describe("pre-authorized-debit-v1#init-pre-authorization", () => {
               variant: preAuthVariant,
               debitAuthority: debitAuthority.publicKey,
               activationUnixTimestamp: new anchor.BN(activationUnixTimestamp),
+              rotationRequiresOwnerSignature: false,
+              destinationConstraint: null,
+              boundedDelegation: false,
             })
             .accounts({
               payer: payer.publicKey,
@@ -469,6 +539,10 @@ describe("pre-authorized-debit-v1#init-pre-authorization", () => {
               smartDelegate: smartDelegatePublicKey,
               tokenAccount: validTokenAccount,
               preAuthorization,
+              preAuthorizationCounter: derivePreAuthorizationCounter(
+                validTokenAccount,
+                program.programId,
+              )[0],
               tokenProgram: tokenProgramId,
               systemProgram: SystemProgram.programId,
             })
@@ -487,6 +561,8 @@ describe("pre-authorized-debit-v1#init-pre-authorization", () => {
           oneTime: {
             amountAuthorized: new anchor.BN(100e6),
             expiryUnixTimestamp: new anchor.BN(expirationUnixTimestamp),
+            maxAmountPerDebit: null,
+            closeOnExhaustion: false,
           },
         };
         const newOwner = Keypair.generate();
@@ -496,6 +572,9 @@ describe("pre-authorized-debit-v1#init-pre-authorization", () => {
               variant: preAuthVariant,
               debitAuthority: debitAuthority.publicKey,
               activationUnixTimestamp: new anchor.BN(activationUnixTimestamp),
+              rotationRequiresOwnerSignature: false,
+              destinationConstraint: null,
+              boundedDelegation: false,
             })
             .accounts({
               payer: payer.publicKey,
@@ -503,6 +582,10 @@ describe("pre-authorized-debit-v1#init-pre-authorization", () => {
               smartDelegate: smartDelegatePublicKey,
               tokenAccount: validTokenAccount,
               preAuthorization,
+              preAuthorizationCounter: derivePreAuthorizationCounter(
+                validTokenAccount,
+                program.programId,
+              )[0],
               tokenProgram: tokenProgramId,
               systemProgram: SystemProgram.programId,
             })
@@ -523,6 +606,8 @@ describe("pre-authorized-debit-v1#init-pre-authorization", () => {
           oneTime: {
             amountAuthorized: new anchor.BN(100e6),
             expiryUnixTimestamp: new anchor.BN(expirationUnixTimestamp),
+            maxAmountPerDebit: null,
+            closeOnExhaustion: false,
           },
         };
         await expect(
@@ -531,6 +616,9 @@ describe("pre-authorized-debit-v1#init-pre-authorization", () => {
               variant: preAuthVariant,
               debitAuthority: debitAuthority.publicKey,
               activationUnixTimestamp: new anchor.BN(activationUnixTimestamp),
+              rotationRequiresOwnerSignature: false,
+              destinationConstraint: null,
+              boundedDelegation: false,
             })
             .accounts({
               payer: payer.publicKey,
@@ -538,6 +626,10 @@ describe("pre-authorized-debit-v1#init-pre-authorization", () => {
               smartDelegate: smartDelegatePublicKey,
               tokenAccount: validTokenAccount,
               preAuthorization,
+              preAuthorizationCounter: derivePreAuthorizationCounter(
+                validTokenAccount,
+                program.programId,
+              )[0],
               tokenProgram: tokenProgramId,
               systemProgram: SystemProgram.programId,
             })
@@ -558,6 +650,8 @@ describe("pre-authorized-debit-v1#init-pre-authorization", () => {
           oneTime: {
             amountAuthorized: new anchor.BN(100e6),
             expiryUnixTimestamp: new anchor.BN(expirationUnixTimestamp),
+            maxAmountPerDebit: null,
+            closeOnExhaustion: false,
           },
         };
         await expect(
@@ -566,6 +660,9 @@ describe("pre-authorized-debit-v1#init-pre-authorization", () => {
               variant: preAuthVariant,
               debitAuthority: debitAuthority.publicKey,
               activationUnixTimestamp: new anchor.BN(activationUnixTimestamp),
+              rotationRequiresOwnerSignature: false,
+              destinationConstraint: null,
+              boundedDelegation: false,
             })
             .accounts({
               payer: payer.publicKey,
@@ -573,6 +670,10 @@ describe("pre-authorized-debit-v1#init-pre-authorization", () => {
               smartDelegate: smartDelegatePublicKey,
               tokenAccount: validTokenAccount,
               preAuthorization,
+              preAuthorizationCounter: derivePreAuthorizationCounter(
+                validTokenAccount,
+                program.programId,
+              )[0],
               tokenProgram: tokenProgramId,
               systemProgram: tokenProgramId,
             })
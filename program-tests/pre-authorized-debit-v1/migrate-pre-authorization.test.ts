@@ -0,0 +1,144 @@
+import { expect } from "chai";
+import { Keypair, PublicKey, SystemProgram } from "@solana/web3.js";
+import { createMint, TOKEN_PROGRAM_ID, createAccount } from "@solana/spl-token";
+import * as anchor from "@coral-xyz/anchor";
+
+import { program, provider } from "./setup";
+import {
+  derivePreAuthorization,
+  derivePreAuthorizationCounter,
+  getCurrentUnixTimestamp,
+} from "./utils";
+import {
+  fundAccounts,
+  initSmartDelegateIdempotent,
+} from "@seabed-labs/pad-test-utils";
+
+describe("pre-authorized-debit-v1#migrate-pre-authorization", () => {
+  let owner: Keypair, mintAuthority: Keypair, debitAuthority: Keypair;
+  let smartDelegatePublicKey: PublicKey,
+    tokenAccount: PublicKey,
+    preAuthorization: PublicKey;
+
+  before(async () => {
+    smartDelegatePublicKey = await initSmartDelegateIdempotent(
+      program,
+      provider,
+    );
+  });
+
+  beforeEach(async () => {
+    owner = Keypair.generate();
+    mintAuthority = Keypair.generate();
+    debitAuthority = Keypair.generate();
+    await fundAccounts(
+      provider,
+      [owner.publicKey, mintAuthority.publicKey],
+      1e9,
+    );
+    const mint = await createMint(
+      provider.connection,
+      mintAuthority,
+      mintAuthority.publicKey,
+      null,
+      6,
+      Keypair.generate(),
+      undefined,
+      TOKEN_PROGRAM_ID,
+    );
+    tokenAccount = await createAccount(
+      provider.connection,
+      mintAuthority,
+      mint,
+      owner.publicKey,
+      Keypair.generate(),
+      undefined,
+      TOKEN_PROGRAM_ID,
+    );
+    [preAuthorization] = derivePreAuthorization(
+      tokenAccount,
+      debitAuthority.publicKey,
+      program.programId,
+    );
+  });
+
+  async function migratePreAuthorization(
+    preAuthorizationToMigrate: PublicKey,
+  ): Promise<string> {
+    return program.methods
+      .migratePreAuthorization()
+      .accounts({
+        payer: provider.publicKey,
+        preAuthorization: preAuthorizationToMigrate,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+  }
+
+  it("should fail to migrate a pre-authorization with the current layout", async () => {
+    const activationUnixTimestamp = getCurrentUnixTimestamp() - 60;
+    await program.methods
+      .initPreAuthorization({
+        variant: {
+          oneTime: {
+            amountAuthorized: new anchor.BN(100e6),
+            expiryUnixTimestamp: new anchor.BN(
+              activationUnixTimestamp + 10 * 24 * 60 * 60,
+            ),
+            maxAmountPerDebit: null,
+            closeOnExhaustion: false,
+          },
+        },
+        debitAuthority: debitAuthority.publicKey,
+        activationUnixTimestamp: new anchor.BN(activationUnixTimestamp),
+        rotationRequiresOwnerSignature: false,
+        destinationConstraint: null,
+        boundedDelegation: false,
+      })
+      .accounts({
+        payer: provider.publicKey,
+        owner: owner.publicKey,
+        smartDelegate: smartDelegatePublicKey,
+        tokenAccount,
+        preAuthorization,
+        preAuthorizationCounter: derivePreAuthorizationCounter(
+          tokenAccount,
+          program.programId,
+        )[0],
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([owner])
+      .rpc();
+    const accountInfoBefore =
+      await provider.connection.getAccountInfo(preAuthorization);
+
+    await expect(
+      migratePreAuthorization(preAuthorization),
+    ).to.eventually.be.rejectedWith(
+      /Error Code: PreAuthorizationNotV1. Error Number: 6072. Error Message: Pre-authorization is not a v1.0.0 pre-authorization./,
+    );
+
+    const accountInfoAfter =
+      await provider.connection.getAccountInfo(preAuthorization);
+    expect(accountInfoAfter?.data.equals(accountInfoBefore!.data)).to.equal(
+      true,
+    );
+  });
+
+  it("should fail to migrate an account of another type", async () => {
+    await expect(
+      migratePreAuthorization(smartDelegatePublicKey),
+    ).to.eventually.be.rejectedWith(
+      /Error Code: PreAuthorizationNotV1. Error Number: 6072/,
+    );
+  });
+
+  it("should fail to migrate an account not owned by the program", async () => {
+    await expect(
+      migratePreAuthorization(tokenAccount),
+    ).to.eventually.be.rejectedWith(
+      /AnchorError caused by account: pre_authorization. Error Code: ConstraintOwner. Error Number: 2004/,
+    );
+  });
+});
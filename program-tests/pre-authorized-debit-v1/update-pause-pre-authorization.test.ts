@@ -10,7 +10,12 @@ import {
 import * as anchor from "@coral-xyz/anchor";
 
 import { program, provider, eventParser } from "./setup";
-import { PreAuthTestVariant, derivePreAuthorization } from "./utils";
+import {
+  PreAuthTestVariant,
+  derivePreAuthorization,
+  derivePreAuthorizationCounter,
+  deriveOwnerPreAuthorization,
+} from "./utils";
 import {
   fundAccounts,
   initSmartDelegateIdempotent,
@@ -162,6 +167,8 @@ describe("pre-authorized-debit-v1#update-pause-pre-authorization", () => {
                       expiryUnixTimestamp: new anchor.BN(
                         expirationUnixTimestamp,
                       ),
+                      maxAmountPerDebit: null,
+                      closeOnExhaustion: false,
                     },
                   }
                 : {
@@ -170,6 +177,13 @@ describe("pre-authorized-debit-v1#update-pause-pre-authorization", () => {
                       recurringAmountAuthorized: new anchor.BN(10e6),
                       numCycles: null,
                       resetEveryCycle: false,
+                      maxAmountPerDebit: null,
+                      minAmountPerDebit: null,
+                      maxAccruedCycles: null,
+                      calendarSchedule: null,
+                      debitWindowSeconds: null,
+                      debitWindowOffsetSeconds: null,
+                      introSchedule: null,
                     },
                   };
 
@@ -178,6 +192,9 @@ describe("pre-authorized-debit-v1#update-pause-pre-authorization", () => {
                 variant: preAuthVariant,
                 debitAuthority: debitAuthority.publicKey,
                 activationUnixTimestamp: new anchor.BN(activationUnixTimestamp),
+                rotationRequiresOwnerSignature: false,
+                destinationConstraint: null,
+                boundedDelegation: false,
               })
               .accounts({
                 payer: provider.publicKey,
@@ -185,6 +202,10 @@ describe("pre-authorized-debit-v1#update-pause-pre-authorization", () => {
                 smartDelegate: smartDelegatePublicKey,
                 tokenAccount,
                 preAuthorization,
+                preAuthorizationCounter: derivePreAuthorizationCounter(
+                  tokenAccount,
+                  program.programId,
+                )[0],
                 tokenProgram: tokenProgramId,
                 systemProgram: SystemProgram.programId,
               })
@@ -327,7 +348,7 @@ describe("pre-authorized-debit-v1#update-pause-pre-authorization", () => {
                 .signers([owner])
                 .rpc(),
             ).to.eventually.be.rejectedWith(
-              /AnchorError caused by account: pre_authorization. Error Code: ConstraintSeeds. Error Number: 2006. Error Message: A seeds constraint was violated./,
+              /AnchorError caused by account: pre_authorization. Error Code: PreAuthorizationTokenAccountMismatch. Error Number: 6006. Error Message: Pre-authorization and token account mismatch./,
             );
           });
 
@@ -350,6 +371,165 @@ describe("pre-authorized-debit-v1#update-pause-pre-authorization", () => {
           });
         });
       });
+
+      context("with an owner scoped pre-authorization", () => {
+        let otherTokenAccount: PublicKey, preAuthorization: PublicKey;
+
+        beforeEach(async () => {
+          mint = await createMint(
+            provider.connection,
+            mintAuthority,
+            mintAuthority.publicKey,
+            null,
+            6,
+            Keypair.generate(),
+            undefined,
+            tokenProgramId,
+          );
+          tokenAccount = await createAccount(
+            provider.connection,
+            mintAuthority,
+            mint,
+            owner.publicKey,
+            Keypair.generate(),
+            undefined,
+            tokenProgramId,
+          );
+          otherTokenAccount = await createAccount(
+            provider.connection,
+            mintAuthority,
+            mint,
+            owner.publicKey,
+            Keypair.generate(),
+            undefined,
+            tokenProgramId,
+          );
+          [preAuthorization] = deriveOwnerPreAuthorization(
+            owner.publicKey,
+            mint,
+            debitAuthority.publicKey,
+            program.programId,
+          );
+          await program.methods
+            .initOwnerPreAuthorization({
+              variant: {
+                oneTime: {
+                  amountAuthorized: new anchor.BN(100e6),
+                  expiryUnixTimestamp: new anchor.BN(expirationUnixTimestamp),
+                  maxAmountPerDebit: null,
+                  closeOnExhaustion: false,
+                },
+              },
+              debitAuthority: debitAuthority.publicKey,
+              activationUnixTimestamp: new anchor.BN(activationUnixTimestamp),
+              rotationRequiresOwnerSignature: false,
+              destinationConstraint: null,
+              boundedDelegation: false,
+            })
+            .accounts({
+              payer: provider.publicKey,
+              owner: owner.publicKey,
+              mint,
+              preAuthorization,
+              systemProgram: SystemProgram.programId,
+            })
+            .signers([owner])
+            .rpc();
+        });
+
+        it("should pause and un-pause the pre authorization via any token account of the owner and mint", async () => {
+          // pause
+          await verifyPreAuthorizationAccount(
+            preAuthorization,
+            false,
+            true,
+            async () => {
+              const signature = await program.methods
+                .updatePausePreAuthorization({
+                  pause: true,
+                })
+                .accounts({
+                  owner: owner.publicKey,
+                  tokenAccount,
+                  preAuthorization,
+                })
+                .signers([owner])
+                .rpc();
+              await verifyUpdatePausePreAuthorizationEvent(
+                signature,
+                true,
+                owner.publicKey,
+                tokenAccount,
+                preAuthorization,
+              );
+            },
+          );
+
+          // unpause
+          await verifyPreAuthorizationAccount(
+            preAuthorization,
+            true,
+            false,
+            async () => {
+              const signature = await program.methods
+                .updatePausePreAuthorization({
+                  pause: false,
+                })
+                .accounts({
+                  owner: owner.publicKey,
+                  tokenAccount: otherTokenAccount,
+                  preAuthorization,
+                })
+                .signers([owner])
+                .rpc();
+              await verifyUpdatePausePreAuthorizationEvent(
+                signature,
+                false,
+                owner.publicKey,
+                otherTokenAccount,
+                preAuthorization,
+              );
+            },
+          );
+        });
+
+        it("should throw an error if the token account mint does not match the pre-authorization scope", async () => {
+          const otherMint = await createMint(
+            provider.connection,
+            mintAuthority,
+            mintAuthority.publicKey,
+            null,
+            6,
+            Keypair.generate(),
+            undefined,
+            tokenProgramId,
+          );
+          const otherMintTokenAccount = await createAccount(
+            provider.connection,
+            mintAuthority,
+            otherMint,
+            owner.publicKey,
+            Keypair.generate(),
+            undefined,
+            tokenProgramId,
+          );
+          await expect(
+            program.methods
+              .updatePausePreAuthorization({
+                pause: true,
+              })
+              .accounts({
+                owner: owner.publicKey,
+                tokenAccount: otherMintTokenAccount,
+                preAuthorization,
+              })
+              .signers([owner])
+              .rpc(),
+          ).to.eventually.be.rejectedWith(
+            /AnchorError caused by account: pre_authorization. Error Code: PreAuthorizationTokenAccountMismatch. Error Number: 6006. Error Message: Pre-authorization and token account mismatch./,
+          );
+        });
+      });
     });
   });
 });
@@ -0,0 +1,213 @@
+import { expect } from "chai";
+import { Keypair, PublicKey, SystemProgram } from "@solana/web3.js";
+import {
+  approve,
+  createMint,
+  TOKEN_PROGRAM_ID,
+  createAccount,
+  getAccount,
+} from "@solana/spl-token";
+import * as anchor from "@coral-xyz/anchor";
+
+import { program, provider } from "./setup";
+import {
+  derivePreAuthorization,
+  derivePreAuthorizationCounter,
+  getCurrentUnixTimestamp,
+} from "./utils";
+import {
+  fundAccounts,
+  initSmartDelegateIdempotent,
+} from "@seabed-labs/pad-test-utils";
+
+describe("pre-authorized-debit-v1#update-pre-authorization", () => {
+  let owner: Keypair, mintAuthority: Keypair, debitAuthority: Keypair;
+  let smartDelegatePublicKey: PublicKey,
+    tokenAccount: PublicKey,
+    preAuthorization: PublicKey,
+    preAuthorizationCounter: PublicKey;
+
+  const activationUnixTimestamp = getCurrentUnixTimestamp() - 60;
+  const expiryUnixTimestamp = activationUnixTimestamp + 10 * 24 * 60 * 60;
+
+  before(async () => {
+    smartDelegatePublicKey = await initSmartDelegateIdempotent(
+      program,
+      provider,
+    );
+  });
+
+  beforeEach(async () => {
+    owner = Keypair.generate();
+    mintAuthority = Keypair.generate();
+    debitAuthority = Keypair.generate();
+    await fundAccounts(
+      provider,
+      [owner.publicKey, mintAuthority.publicKey],
+      1e9,
+    );
+    const mint = await createMint(
+      provider.connection,
+      mintAuthority,
+      mintAuthority.publicKey,
+      null,
+      6,
+      Keypair.generate(),
+      undefined,
+      TOKEN_PROGRAM_ID,
+    );
+    tokenAccount = await createAccount(
+      provider.connection,
+      mintAuthority,
+      mint,
+      owner.publicKey,
+      Keypair.generate(),
+      undefined,
+      TOKEN_PROGRAM_ID,
+    );
+    [preAuthorization] = derivePreAuthorization(
+      tokenAccount,
+      debitAuthority.publicKey,
+      program.programId,
+    );
+    [preAuthorizationCounter] = derivePreAuthorizationCounter(
+      tokenAccount,
+      program.programId,
+    );
+
+    await program.methods
+      .initPreAuthorizationCounter({ count: new anchor.BN(0) })
+      .accounts({
+        payer: provider.publicKey,
+        owner: owner.publicKey,
+        tokenAccount,
+        preAuthorizationCounter,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([owner])
+      .rpc();
+    await program.methods
+      .initPreAuthorization({
+        variant: {
+          oneTime: {
+            amountAuthorized: new anchor.BN(100e6),
+            expiryUnixTimestamp: new anchor.BN(expiryUnixTimestamp),
+            maxAmountPerDebit: null,
+            closeOnExhaustion: false,
+          },
+        },
+        debitAuthority: debitAuthority.publicKey,
+        activationUnixTimestamp: new anchor.BN(activationUnixTimestamp),
+        rotationRequiresOwnerSignature: false,
+        destinationConstraint: null,
+        boundedDelegation: true,
+      })
+      .accounts({
+        payer: provider.publicKey,
+        owner: owner.publicKey,
+        smartDelegate: smartDelegatePublicKey,
+        tokenAccount,
+        preAuthorization,
+        preAuthorizationCounter,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([owner])
+      .rpc();
+  });
+
+  async function updatePreAuthorization(
+    amountAuthorized: number,
+    counter: PublicKey | null,
+  ): Promise<string> {
+    return program.methods
+      .updatePreAuthorization({
+        variant: {
+          oneTime: {
+            amountAuthorized: new anchor.BN(amountAuthorized),
+            expiryUnixTimestamp: new anchor.BN(expiryUnixTimestamp),
+          },
+        },
+      })
+      .accounts({
+        owner: owner.publicKey,
+        tokenAccount,
+        preAuthorization,
+        smartDelegate: smartDelegatePublicKey,
+        preAuthorizationCounter: counter,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([owner])
+      .rpc();
+  }
+
+  it("should re-approve the smart delegate for the new amount in bounded delegation mode", async () => {
+    await updatePreAuthorization(150e6, preAuthorizationCounter);
+
+    const preAuthorizationData =
+      await program.account.preAuthorization.fetch(preAuthorization);
+    expect(
+      preAuthorizationData.variant.oneTime?.amountAuthorized.toString(),
+    ).to.equal((150e6).toString());
+    const tokenAccountData = await getAccount(
+      provider.connection,
+      tokenAccount,
+      undefined,
+      TOKEN_PROGRAM_ID,
+    );
+    expect(tokenAccountData.delegate?.toBase58()).to.equal(
+      smartDelegatePublicKey.toBase58(),
+    );
+    expect(tokenAccountData.delegatedAmount.toString()).to.equal(
+      (150e6).toString(),
+    );
+  });
+
+  it("should not re-approve the smart delegate if another delegate is approved", async () => {
+    const otherDelegate = Keypair.generate().publicKey;
+    await approve(
+      provider.connection,
+      owner,
+      tokenAccount,
+      otherDelegate,
+      owner,
+      10e6,
+      undefined,
+      undefined,
+      TOKEN_PROGRAM_ID,
+    );
+
+    await updatePreAuthorization(150e6, preAuthorizationCounter);
+
+    const tokenAccountData = await getAccount(
+      provider.connection,
+      tokenAccount,
+      undefined,
+      TOKEN_PROGRAM_ID,
+    );
+    expect(tokenAccountData.delegate?.toBase58()).to.equal(
+      otherDelegate.toBase58(),
+    );
+    expect(tokenAccountData.delegatedAmount.toString()).to.equal(
+      (10e6).toString(),
+    );
+  });
+
+  it("should fail to update the pre-authorization without the pre-authorization counter", async () => {
+    await expect(
+      updatePreAuthorization(150e6, null),
+    ).to.eventually.be.rejectedWith(
+      /Error Code: PreAuthorizationCounterRequired. Error Number: 6049/,
+    );
+
+    const tokenAccountData = await getAccount(
+      provider.connection,
+      tokenAccount,
+      undefined,
+      TOKEN_PROGRAM_ID,
+    );
+    expect(tokenAccountData.delegatedAmount.toString()).to.equal(
+      (100e6).toString(),
+    );
+  });
+});
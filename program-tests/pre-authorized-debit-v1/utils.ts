@@ -25,6 +25,47 @@ export function derivePreAuthorization(
   return [pdaPubkey, pdaBump];
 }
 
+/**
+ * Derives the canonical public key for the pre authorization scoped to an owner and a mint
+ * @param owner
+ * @param mint
+ * @param debitAuthority
+ * @param programId
+ */
+export function deriveOwnerPreAuthorization(
+  owner: PublicKey,
+  mint: PublicKey,
+  debitAuthority: PublicKey,
+  programId: PublicKey,
+): [PublicKey, number] {
+  const [pdaPubkey, pdaBump] = PublicKey.findProgramAddressSync(
+    [
+      Buffer.from("pre-authorization"),
+      owner.toBuffer(),
+      mint.toBuffer(),
+      debitAuthority.toBuffer(),
+    ],
+    programId,
+  );
+  return [pdaPubkey, pdaBump];
+}
+
+/**
+ * Derives the canonical public key for the pre-authorization counter of a token account
+ * @param tokenAccount
+ * @param programId
+ */
+export function derivePreAuthorizationCounter(
+  tokenAccount: PublicKey,
+  programId: PublicKey,
+): [PublicKey, number] {
+  const [pdaPubkey, pdaBump] = PublicKey.findProgramAddressSync(
+    [Buffer.from("pre-authorization-counter"), tokenAccount.toBuffer()],
+    programId,
+  );
+  return [pdaPubkey, pdaBump];
+}
+
 /**
  * Derives the non-canonical public key for the pre-authorization
  * @param tokenAccount
@@ -133,9 +174,13 @@ export function getCurrentUnixTimestamp(): number {
 }
 
 export type DebitEvent = Event<
-  Program<PreAuthorizedDebitV1>["idl"]["events"]["2"]
+  Program<PreAuthorizedDebitV1>["idl"]["events"]["10"]
 >;
 
+export const MEMO_PROGRAM_ID = new PublicKey(
+  "MemoSq4gqABAXKb96qnH8TysNcWxMyWCqXgDLGmfcHr",
+);
+
 export const U64_MAX = (BigInt(2) ** BigInt(64) - BigInt(1)).toString();
 
 export enum PreAuthTestVariant {
@@ -0,0 +1,100 @@
+/*!
+  The `CalendarSchedule` of recurring pre-authorizations with calendar cycles (stored in the `pre_authorization`).
+  The calendar computations are in `math::calendar`, on the plain `math::calendar::CalendarSchedule`.
+*/
+
+use anchor_lang::prelude::*;
+
+use crate::{errors::CustomProgramError, math};
+
+pub use crate::math::calendar::SECONDS_PER_DAY;
+
+#[derive(InitSpace, AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CalendarInterval {
+    Monthly,
+    Quarterly,
+    Yearly,
+}
+
+impl CalendarInterval {
+    pub fn months(&self) -> i64 {
+        match self {
+            CalendarInterval::Monthly => 1,
+            CalendarInterval::Quarterly => 3,
+            CalendarInterval::Yearly => 12,
+        }
+    }
+
+    /**
+      Returns the number of days in the shortest cycle of this interval
+      (e.g. February of a non-leap year for `Monthly`).
+    */
+    pub fn min_days(&self) -> i64 {
+        match self {
+            CalendarInterval::Monthly => 28,
+            CalendarInterval::Quarterly => 89,
+            CalendarInterval::Yearly => 365,
+        }
+    }
+}
+
+#[derive(InitSpace, AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CalendarSchedule {
+    pub interval: CalendarInterval,
+    // 1 to 31, clamped to the last day of shorter months (e.g. 31 is Feb 28 / Feb 29 and Apr 30)
+    pub day_of_month: u8,
+}
+
+impl CalendarSchedule {
+    pub fn validate(&self) -> core::result::Result<(), CustomProgramError> {
+        Ok(math::calendar::CalendarSchedule::from(*self).validate()?)
+    }
+}
+
+impl From<CalendarSchedule> for math::calendar::CalendarSchedule {
+    fn from(calendar_schedule: CalendarSchedule) -> Self {
+        math::calendar::CalendarSchedule {
+            interval_months: calendar_schedule.interval.months() as u8,
+            day_of_month: calendar_schedule.day_of_month,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math::calendar::{compute_calendar_cycle_bounds, days_from_civil};
+    use test_case::test_case;
+
+    fn unix_timestamp(year: i64, month: u32, day: u32, seconds: i64) -> i64 {
+        days_from_civil(year, month, day).unwrap() * SECONDS_PER_DAY + seconds
+    }
+
+    #[test_case(CalendarInterval::Monthly)]
+    #[test_case(CalendarInterval::Quarterly)]
+    #[test_case(CalendarInterval::Yearly)]
+    fn min_days_is_shortest_cycle_from_1600_to_2400(interval: CalendarInterval) {
+        for day_of_month in [1, 15, 28, 29, 30, 31] {
+            let calendar_schedule = CalendarSchedule {
+                interval,
+                day_of_month,
+            }
+            .into();
+            let activation_unix_timestamp = unix_timestamp(1600, 1, 1, 0);
+            let num_cycles = 800 * 12 / interval.months() as u64;
+            let min_seconds = (1..=num_cycles)
+                .map(|cycle| {
+                    let (start, end) = compute_calendar_cycle_bounds(
+                        cycle,
+                        activation_unix_timestamp,
+                        &calendar_schedule,
+                    )
+                    .unwrap();
+                    end - start
+                })
+                .min()
+                .unwrap();
+            assert!(min_seconds >= interval.min_days() * SECONDS_PER_DAY);
+        }
+    }
+}
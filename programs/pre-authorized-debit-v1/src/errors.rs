@@ -1,6 +1,9 @@
 use anchor_lang::prelude::*;
 
+use crate::math::MathError;
+
 #[error_code]
+#[derive(PartialEq, Eq)]
 pub enum CustomProgramError {
     #[msg("Pre-Authorization not active")]
     PreAuthorizationNotActive, // 6000 or 0x1770
@@ -41,4 +44,214 @@ pub enum CustomProgramError {
 
     #[msg("Only token account owner can initialize a smart delegate")]
     InitSmartDelegateUnauthorized, // 6012 or 0x177C
+
+    #[msg("Arithmetic overflow")]
+    ArithmeticOverflow, // 6013 or 0x177D
+
+    #[msg("Arithmetic underflow")]
+    ArithmeticUnderflow, // 6014 or 0x177E
+
+    // This may happen if the unix timestamp of the validator is weird
+    #[msg("Current timestamp is before the pre-authorization activation timestamp")]
+    CurrentTimestampBeforeActivation, // 6015 or 0x177F
+
+    #[msg("Repeat frequency seconds must be greater than 0")]
+    InvalidRepeatFrequency, // 6016 or 0x1780
+
+    #[msg("Recurring cycles start at 1 (invalid state)")]
+    InvalidCycle, // 6017 or 0x1781
+
+    #[msg(
+        "Amount debited in the last cycle is greater than the total amount debited (invalid state)"
+    )]
+    InvalidAmountDebited, // 6018 or 0x1782
+
+    #[msg("Pre-authorization expiry timestamp must be after its activation timestamp")]
+    ExpiryBeforeActivation, // 6019 or 0x1783
+
+    #[msg("Number of cycles must be greater than 0 and the last cycle must end before i64::MAX")]
+    InvalidNumCycles, // 6020 or 0x1784
+
+    #[msg("Recurring amount authorized across all cycles overflows u64")]
+    TotalAmountAuthorizedOverflow, // 6021 or 0x1785
+
+    #[msg("Only token account owner can update a pre-authorization")]
+    UpdatePreAuthorizationUnauthorized, // 6022 or 0x1786
+
+    #[msg("Pre-authorization variant cannot be changed")]
+    PreAuthorizationVariantMismatch, // 6023 or 0x1787
+
+    #[msg("Amount authorized cannot be less than the amount already debited")]
+    AmountAuthorizedBelowAmountDebited, // 6024 or 0x1788
+
+    #[msg("Only pre_authorization.debit_authority can propose an amendment")]
+    ProposeAmendmentUnauthorized, // 6025 or 0x1789
+
+    #[msg("Only token account owner can accept an amendment")]
+    AcceptAmendmentUnauthorized, // 6026 or 0x178A
+
+    #[msg("Amendment can only be rejected by token_account.owner or debit_authority")]
+    RejectAmendmentUnauthorized, // 6027 or 0x178B
+
+    #[msg("Only the amendment payer can receive funds from closing the pending amendment account")]
+    OnlyAmendmentPayerCanReceiveFunds, // 6028 or 0x178C
+
+    #[msg("Amendment acceptance deadline must be in the future")]
+    InvalidAcceptanceDeadline, // 6029 or 0x178D
+
+    #[msg("Amendment acceptance deadline has passed")]
+    AmendmentExpired, // 6030 or 0x178E
+
+    #[msg("Amendment terms do not match the expected terms")]
+    AmendmentMismatch, // 6031 or 0x178F
+
+    #[msg("Only pre_authorization.debit_authority can rotate the debit authority")]
+    RotateDebitAuthorityUnauthorized, // 6032 or 0x1790
+
+    #[msg("Token account owner signature is required to rotate the debit authority")]
+    OwnerSignatureRequiredForRotation, // 6033 or 0x1791
+
+    #[msg("New debit authority must be different from the current debit authority")]
+    NewDebitAuthorityMatchesCurrent, // 6034 or 0x1792
+
+    #[msg("Pre-authorization has a pending amendment")]
+    PendingAmendmentExists, // 6035 or 0x1793
+
+    #[msg("Destination token account is not allowed by the pre-authorization")]
+    DestinationNotAllowed, // 6036 or 0x1794
+
+    #[msg("Debit amount is above the maximum amount per debit")]
+    DebitAmountAboveMaximum, // 6037 or 0x1795
+
+    #[msg("Debit amount is below the minimum amount per debit")]
+    DebitAmountBelowMinimum, // 6038 or 0x1796
+
+    #[msg("Invalid minimum or maximum amount per debit")]
+    InvalidAmountPerDebitBounds, // 6039 or 0x1797
+
+    #[msg("Max accrued cycles must be greater than 0")]
+    InvalidMaxAccruedCycles, // 6040 or 0x1798
+
+    #[msg("Day of month must be between 1 and 31")]
+    InvalidDayOfMonth, // 6041 or 0x1799
+
+    #[msg("Debit is outside of the debit window of the current cycle")]
+    OutsideDebitWindow, // 6042 or 0x179A
+
+    #[msg("Debit window must be non-empty and start within the shortest cycle")]
+    InvalidDebitWindow, // 6043 or 0x179B
+
+    #[msg("Intro schedule must have at least one intro cycle")]
+    InvalidIntroSchedule, // 6044 or 0x179C
+
+    #[msg("Schedule steps must start at cycle 1 and be strictly increasing (at most 12 steps)")]
+    InvalidScheduleSteps, // 6045 or 0x179D
+
+    #[msg("Rolling window must be a non-zero multiple of the number of buckets")]
+    InvalidRollingWindow, // 6046 or 0x179E
+
+    #[msg("Receiver is required to close an exhausted one-time pre-authorization")]
+    ReceiverRequiredForCloseOnExhaustion, // 6047 or 0x179F
+
+    #[msg("Pre-authorization can still be debited")]
+    PreAuthorizationNotExpired, // 6048 or 0x17A0
+
+    #[msg("Pre-authorization counter of the token account is required")]
+    PreAuthorizationCounterRequired, // 6049 or 0x17A1
+
+    #[msg("Delegated amount of the smart delegate is below the debit amount (exhausted or changed outside of the program)")]
+    DelegatedAmountExhausted, // 6050 or 0x17A2
+
+    #[msg("Bounded delegation mode must match the other pre-authorizations of the token account")]
+    DelegationModeMismatch, // 6051 or 0x17A3
+
+    #[msg("Bounded delegation requires a pre-authorization with a bounded amount authorized")]
+    UnboundedPreAuthorization, // 6052 or 0x17A4
+
+    #[msg("Bounded delegation is only supported for pre-authorizations scoped to a token account")]
+    BoundedDelegationNotSupported, // 6053 or 0x17A5
+
+    #[msg("Smart delegate is not the delegate of the token account (approve it with reapprove_smart_delegate)")]
+    SmartDelegateNotApproved, // 6054 or 0x17A6
+
+    #[msg("Only token account owner can reapprove the smart delegate")]
+    ReapproveSmartDelegateUnauthorized, // 6055 or 0x17A7
+
+    #[msg("All the open pre-authorizations of the token account must be provided once")]
+    MissingPreAuthorizations, // 6056 or 0x17A8
+
+    #[msg("Debit batch must have 1 to 65535 entries of 3 distinct writable remaining accounts, one per pre-authorization")]
+    InvalidDebitBatch, // 6057 or 0x17A9
+
+    #[msg("Token account mint does not match the mint")]
+    MintMismatch, // 6058 or 0x17AA
+
+    #[msg("Token account balance is below the debit amount")]
+    InsufficientTokenAccountBalance, // 6059 or 0x17AB
+
+    #[msg("Nothing can be debited from the token account")]
+    NothingToDebit, // 6060 or 0x17AC
+
+    #[msg("Memo program must be the SPL Memo program")]
+    InvalidMemoProgram, // 6061 or 0x17AD
+
+    #[msg("Reference is required to log a memo")]
+    ReferenceRequiredForMemo, // 6062 or 0x17AE
+
+    #[msg("Receipt payer is required to create a debit receipt")]
+    ReceiptPayerRequired, // 6063 or 0x17AF
+
+    #[msg("Debit receipt can only be closed by its payer or debit authority")]
+    CloseDebitReceiptUnauthorized, // 6064 or 0x17B0
+
+    #[msg("Only the debit receipt payer can receive the lamports of a closed debit receipt")]
+    OnlyDebitReceiptPayerCanReceiveFunds, // 6065 or 0x17B1
+
+    #[msg("Debit receipt retention period is not over")]
+    DebitReceiptRetentionPeriodNotOver, // 6066 or 0x17B2
+
+    #[msg("Pre-authorization counter count must be set by the token account owner first (init_pre_authorization_counter)")]
+    PreAuthorizationCounterNotVerified, // 6067 or 0x17B3
+
+    #[msg("Only token account owner can initialize the pre-authorization counter")]
+    InitPreAuthorizationCounterUnauthorized, // 6068 or 0x17B4
+
+    #[msg(
+        "Owner scoped pre-authorizations cannot debit a token account in bounded delegation mode"
+    )]
+    OwnerScopedDebitInBoundedDelegation, // 6069 or 0x17B5
+
+    #[msg("Pre-authorization counter count is already verified")]
+    PreAuthorizationCounterAlreadyVerified, // 6070 or 0x17B6
+
+    #[msg("Pre-authorization counter count cannot be set in bounded delegation mode")]
+    PreAuthorizationCounterInBoundedDelegation, // 6071 or 0x17B7
+
+    #[msg("Pre-authorization is not a v1.0.0 pre-authorization")]
+    PreAuthorizationNotV1, // 6072 or 0x17B8
+}
+
+impl From<MathError> for CustomProgramError {
+    fn from(err: MathError) -> Self {
+        match err {
+            MathError::ArithmeticOverflow => CustomProgramError::ArithmeticOverflow,
+            MathError::ArithmeticUnderflow => CustomProgramError::ArithmeticUnderflow,
+            MathError::CurrentTimestampBeforeActivation => {
+                CustomProgramError::CurrentTimestampBeforeActivation
+            }
+            MathError::InvalidAmountDebited => CustomProgramError::InvalidAmountDebited,
+            MathError::InvalidCycle => CustomProgramError::InvalidCycle,
+            MathError::InvalidDayOfMonth => CustomProgramError::InvalidDayOfMonth,
+            MathError::InvalidRepeatFrequency => CustomProgramError::InvalidRepeatFrequency,
+            MathError::LastDebitedCycleBeforeCurrentCycle => {
+                CustomProgramError::LastDebitedCycleBeforeCurrentCycle
+            }
+        }
+    }
+}
+
+impl From<MathError> for Error {
+    fn from(err: MathError) -> Self {
+        CustomProgramError::from(err).into()
+    }
 }
@@ -0,0 +1,168 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{TokenAccount, TokenInterface};
+
+use crate::{
+    errors::CustomProgramError,
+    instructions::{
+        amend_pre_authorization, load_pre_authorization_counter, reapprove_bounded_delegation,
+        UpdatePreAuthorizationVariant,
+    },
+    state::{
+        pending_amendment::PendingAmendment,
+        pre_authorization::{PreAuthorization, PreAuthorizationScope},
+        smart_delegate::SmartDelegate,
+    },
+};
+
+#[derive(Accounts)]
+pub struct AcceptAmendment<'info> {
+    pub owner: Signer<'info>,
+
+    /// CHECK: This is validated against pending_amendment.payer
+    #[account(
+        mut,
+        constraint = receiver.key.eq(&pending_amendment.payer)
+            @ CustomProgramError::OnlyAmendmentPayerCanReceiveFunds
+    )]
+    pub receiver: AccountInfo<'info>,
+
+    // The smart_delegate is re-approved in bounded delegation mode
+    #[account(
+        mut,
+        has_one = owner @ CustomProgramError::AcceptAmendmentUnauthorized
+    )]
+    pub token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"pre-authorization",
+            pre_authorization.scope_seeds()[0],
+            pre_authorization.scope_seeds()[1],
+            pre_authorization.debit_authority.as_ref(),
+        ],
+        bump = pre_authorization.bump,
+        constraint = pre_authorization.covers_token_account(
+            &token_account.key(),
+            &token_account.owner,
+            &token_account.mint,
+        ) @ CustomProgramError::PreAuthorizationTokenAccountMismatch,
+    )]
+    pub pre_authorization: Account<'info, PreAuthorization>,
+
+    #[account(
+        mut,
+        close = receiver,
+        seeds = [
+            b"pending-amendment",
+            pre_authorization.key().as_ref(),
+        ],
+        bump = pending_amendment.bump,
+    )]
+    pub pending_amendment: Account<'info, PendingAmendment>,
+
+    #[account(
+        seeds = [
+            b"smart-delegate",
+        ],
+        bump = smart_delegate.bump,
+    )]
+    pub smart_delegate: Account<'info, SmartDelegate>,
+
+    // Only required if the pre_authorization is scoped to the token_account
+    // (it can be uninitialized, see `load_pre_authorization_counter`)
+    /// CHECK: This is validated by the seeds and deserialized in the handler (if initialized)
+    #[account(
+        seeds = [
+            b"pre-authorization-counter",
+            token_account.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub pre_authorization_counter: Option<UncheckedAccount<'info>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct AcceptAmendmentParams {
+    // MUST equal the proposed terms, guarantees the owner accepts the terms they have seen
+    pub expected_new_values: UpdatePreAuthorizationVariant,
+}
+
+pub fn handle_accept_amendment(
+    ctx: Context<AcceptAmendment>,
+    params: AcceptAmendmentParams,
+) -> Result<()> {
+    let current_unix_timestamp = Clock::get()?.unix_timestamp;
+
+    require!(
+        current_unix_timestamp
+            <= ctx
+                .accounts
+                .pending_amendment
+                .acceptance_deadline_unix_timestamp,
+        CustomProgramError::AmendmentExpired
+    );
+    require!(
+        params.expected_new_values == ctx.accounts.pending_amendment.new_values,
+        CustomProgramError::AmendmentMismatch
+    );
+
+    let old_outstanding_amount = ctx
+        .accounts
+        .pre_authorization
+        .outstanding_amount_authorized();
+    let old_values = amend_pre_authorization(
+        &mut ctx.accounts.pre_authorization,
+        &params.expected_new_values,
+        current_unix_timestamp,
+    )?;
+    // owner scoped pre_authorizations are not counted (nor part of the bounded allowance)
+    let is_counted = ctx.accounts.pre_authorization.scope == PreAuthorizationScope::TokenAccount;
+    require!(
+        !is_counted || ctx.accounts.pre_authorization_counter.is_some(),
+        CustomProgramError::PreAuthorizationCounterRequired
+    );
+    let bounded_delegation = is_counted
+        && load_pre_authorization_counter(ctx.accounts.pre_authorization_counter.as_ref())?
+            .is_some_and(|pre_authorization_counter| pre_authorization_counter.bounded_delegation);
+    if bounded_delegation {
+        let new_outstanding_amount = ctx
+            .accounts
+            .pre_authorization
+            .outstanding_amount_authorized()
+            .ok_or(CustomProgramError::UnboundedPreAuthorization)?;
+        reapprove_bounded_delegation(
+            old_outstanding_amount.unwrap_or_default(),
+            new_outstanding_amount,
+            &ctx.accounts.token_program,
+            &ctx.accounts.token_account,
+            &ctx.accounts.smart_delegate,
+            ctx.accounts.owner.to_account_info(),
+        )?;
+    }
+
+    emit!(AmendmentAccepted {
+        owner: ctx.accounts.owner.key(),
+        debit_authority: ctx.accounts.pre_authorization.debit_authority,
+        token_account: ctx.accounts.token_account.key(),
+        pre_authorization: ctx.accounts.pre_authorization.key(),
+        pending_amendment: ctx.accounts.pending_amendment.key(),
+        old_values,
+        new_values: params.expected_new_values,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct AmendmentAccepted {
+    pub owner: Pubkey,
+    pub debit_authority: Pubkey,
+    pub token_account: Pubkey,
+    pub pre_authorization: Pubkey,
+    pub pending_amendment: Pubkey,
+    pub old_values: UpdatePreAuthorizationVariant,
+    pub new_values: UpdatePreAuthorizationVariant,
+}
@@ -0,0 +1,63 @@
+use anchor_lang::prelude::*;
+
+use crate::{errors::CustomProgramError, state::debit_receipt::DebitReceipt};
+
+#[derive(Accounts)]
+pub struct CloseDebitReceipt<'info> {
+    // The payer or the debit authority of the debit receipt
+    #[account(
+        constraint = (
+            authority.key.eq(&debit_receipt.payer) ||
+            authority.key.eq(&debit_receipt.debit_authority)
+        ) @ CustomProgramError::CloseDebitReceiptUnauthorized
+    )]
+    pub authority: Signer<'info>,
+
+    /// CHECK: This is validated against debit_receipt.payer
+    #[account(
+        mut,
+        constraint = receiver.key.eq(&debit_receipt.payer)
+            @ CustomProgramError::OnlyDebitReceiptPayerCanReceiveFunds
+    )]
+    pub receiver: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        close = receiver,
+        seeds = [
+            b"debit-receipt",
+            debit_receipt.pre_authorization.as_ref(),
+            debit_receipt.debit_index.to_le_bytes().as_ref(),
+        ],
+        bump = debit_receipt.bump,
+    )]
+    pub debit_receipt: Account<'info, DebitReceipt>,
+}
+
+pub fn handle_close_debit_receipt(ctx: Context<CloseDebitReceipt>) -> Result<()> {
+    require!(
+        ctx.accounts
+            .debit_receipt
+            .is_retention_period_over_at(Clock::get()?.unix_timestamp),
+        CustomProgramError::DebitReceiptRetentionPeriodNotOver
+    );
+
+    emit!(DebitReceiptClosed {
+        closing_authority: ctx.accounts.authority.key(),
+        receiver: ctx.accounts.receiver.key(),
+        pre_authorization: ctx.accounts.debit_receipt.pre_authorization,
+        debit_receipt: ctx.accounts.debit_receipt.key(),
+        debit_index: ctx.accounts.debit_receipt.debit_index,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct DebitReceiptClosed {
+    pub closing_authority: Pubkey,
+    pub receiver: Pubkey,
+    pub pre_authorization: Pubkey,
+    pub debit_receipt: Pubkey,
+    pub debit_index: u64,
+}
@@ -0,0 +1,131 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::TokenAccount;
+
+use crate::{
+    errors::CustomProgramError,
+    instructions::{load_pre_authorization_counter, PreAuthorizationClosedEventData},
+    state::pre_authorization::{PreAuthorization, PreAuthorizationScope},
+};
+
+// Paid (out of the pre_authorization rent) to the bounty_receiver for closing an expired pre_authorization
+pub const EXPIRED_PRE_AUTHORIZATION_CLOSE_BOUNTY_LAMPORTS: u64 = 5_000;
+
+#[derive(Accounts)]
+pub struct CloseExpiredPreAuthorization<'info> {
+    // Any signer can close an expired pre_authorization
+    pub caller: Signer<'info>,
+
+    // The rent of the pre_authorization (minus the bounty) is refunded to the token account owner
+    /// CHECK: This is validated against token_account.owner
+    #[account(
+        mut,
+        constraint = receiver.key.eq(&token_account.owner)
+            @ CustomProgramError::OnlyTokenAccountOwnerCanReceiveClosePreAuthFunds
+    )]
+    pub receiver: AccountInfo<'info>,
+
+    // Only set if the caller claims the bounty
+    /// CHECK: This can be any account
+    #[account(mut)]
+    pub bounty_receiver: Option<AccountInfo<'info>>,
+
+    pub token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        close = receiver,
+        seeds = [
+            b"pre-authorization",
+            pre_authorization.scope_seeds()[0],
+            pre_authorization.scope_seeds()[1],
+            pre_authorization.debit_authority.as_ref(),
+        ],
+        bump = pre_authorization.bump,
+        constraint = pre_authorization.covers_token_account(
+            &token_account.key(),
+            &token_account.owner,
+            &token_account.mint,
+        ) @ CustomProgramError::PreAuthorizationTokenAccountMismatch,
+    )]
+    pub pre_authorization: Account<'info, PreAuthorization>,
+
+    // Only required if the pre_authorization is scoped to the token_account
+    // (it can be uninitialized, see `load_pre_authorization_counter`)
+    /// CHECK: This is validated by the seeds and deserialized in the handler (if initialized)
+    #[account(
+        mut,
+        seeds = [
+            b"pre-authorization-counter",
+            token_account.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub pre_authorization_counter: Option<UncheckedAccount<'info>>,
+}
+
+pub fn handle_close_expired_pre_authorization(
+    ctx: Context<CloseExpiredPreAuthorization>,
+) -> Result<()> {
+    let current_unix_timestamp = Clock::get()?.unix_timestamp;
+    require!(
+        ctx.accounts
+            .pre_authorization
+            .is_expired_at(current_unix_timestamp),
+        CustomProgramError::PreAuthorizationNotExpired
+    );
+
+    // The owner signature is required to revoke the smart_delegate, so it is never revoked here
+    // (nor re-approved without the outstanding amount of the pre_authorization in bounded delegation mode,
+    // see `reapprove_smart_delegate`), owner scoped pre_authorizations are not counted
+    if ctx.accounts.pre_authorization.scope == PreAuthorizationScope::TokenAccount {
+        require!(
+            ctx.accounts.pre_authorization_counter.is_some(),
+            CustomProgramError::PreAuthorizationCounterRequired
+        );
+        if let Some(mut pre_authorization_counter) =
+            load_pre_authorization_counter(ctx.accounts.pre_authorization_counter.as_ref())?
+        {
+            pre_authorization_counter.decrement()?;
+            pre_authorization_counter.exit(&crate::ID)?;
+        }
+    }
+
+    let bounty_lamports = match &ctx.accounts.bounty_receiver {
+        Some(bounty_receiver) => {
+            let pre_authorization_info = ctx.accounts.pre_authorization.to_account_info();
+            let bounty_lamports = EXPIRED_PRE_AUTHORIZATION_CLOSE_BOUNTY_LAMPORTS
+                .min(pre_authorization_info.lamports());
+
+            **pre_authorization_info.try_borrow_mut_lamports()? -= bounty_lamports;
+            let mut bounty_receiver_lamports = bounty_receiver.try_borrow_mut_lamports()?;
+            **bounty_receiver_lamports = bounty_receiver_lamports
+                .checked_add(bounty_lamports)
+                .ok_or(CustomProgramError::ArithmeticOverflow)?;
+
+            bounty_lamports
+        }
+        None => 0,
+    };
+
+    emit!(ExpiredPreAuthorizationClosed {
+        data: PreAuthorizationClosedEventData {
+            debit_authority: ctx.accounts.pre_authorization.debit_authority,
+            closing_authority: ctx.accounts.caller.key(),
+            token_account_owner: ctx.accounts.token_account.owner,
+            receiver: ctx.accounts.receiver.key(),
+            token_account: ctx.accounts.token_account.key(),
+            pre_authorization: ctx.accounts.pre_authorization.key(),
+        },
+        bounty_receiver: ctx.accounts.bounty_receiver.as_ref().map(|info| info.key()),
+        bounty_lamports,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct ExpiredPreAuthorizationClosed {
+    pub data: PreAuthorizationClosedEventData,
+    pub bounty_receiver: Option<Pubkey>,
+    pub bounty_lamports: u64,
+}
@@ -0,0 +1,77 @@
+use anchor_lang::prelude::*;
+
+use crate::{errors::CustomProgramError, state::pre_authorization::PreAuthorization};
+
+#[derive(Accounts)]
+pub struct CloseOwnerPreAuthorization<'info> {
+    // Either the owner signs and sets any receiver they want
+    // or receiver MUST be the owner
+    /// CHECK: This can be any account
+    #[account(
+        mut,
+        constraint = (
+            authority.key.eq(owner.key) ||
+            receiver.key.eq(owner.key)
+        ) @ CustomProgramError::OnlyTokenAccountOwnerCanReceiveClosePreAuthFunds
+    )]
+    pub receiver: AccountInfo<'info>,
+
+    #[account(
+        constraint = (
+            authority.key.eq(owner.key) ||
+            authority.key.eq(&pre_authorization.debit_authority)
+        ) @ CustomProgramError::PreAuthorizationCloseUnauthorized
+    )]
+    pub authority: Signer<'info>,
+
+    /// CHECK: This is validated by the pre_authorization seeds
+    pub owner: AccountInfo<'info>,
+
+    /// CHECK: This is validated by the pre_authorization seeds
+    pub mint: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        close = receiver,
+        seeds = [
+            b"pre-authorization",
+            owner.key().as_ref(),
+            mint.key().as_ref(),
+            pre_authorization.debit_authority.as_ref(),
+        ],
+        bump = pre_authorization.bump,
+    )]
+    pub pre_authorization: Account<'info, PreAuthorization>,
+}
+
+pub fn handle_close_owner_pre_authorization(
+    ctx: Context<CloseOwnerPreAuthorization>,
+) -> Result<()> {
+    emit!(OwnerPreAuthorizationClosed {
+        data: OwnerPreAuthorizationClosedEventData {
+            debit_authority: ctx.accounts.pre_authorization.debit_authority,
+            closing_authority: ctx.accounts.authority.key(),
+            owner: ctx.accounts.owner.key(),
+            mint: ctx.accounts.mint.key(),
+            receiver: ctx.accounts.receiver.key(),
+            pre_authorization: ctx.accounts.pre_authorization.key(),
+        }
+    });
+
+    Ok(())
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct OwnerPreAuthorizationClosedEventData {
+    pub debit_authority: Pubkey,
+    pub closing_authority: Pubkey,
+    pub owner: Pubkey,
+    pub mint: Pubkey,
+    pub receiver: Pubkey,
+    pub pre_authorization: Pubkey,
+}
+
+#[event]
+pub struct OwnerPreAuthorizationClosed {
+    pub data: OwnerPreAuthorizationClosedEventData,
+}
@@ -1,7 +1,14 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token_interface::TokenAccount;
+use anchor_spl::token_interface::{self, Revoke, TokenAccount, TokenInterface};
 
-use crate::{errors::CustomProgramError, state::pre_authorization::PreAuthorization};
+use crate::{
+    errors::CustomProgramError,
+    instructions::{
+        approve_smart_delegate, compute_total_outstanding_amount_authorized,
+        load_pre_authorization_counter, load_token_account_pre_authorizations,
+    },
+    state::{pre_authorization::PreAuthorization, smart_delegate::SmartDelegate},
+};
 
 #[derive(Accounts)]
 pub struct ClosePreAuthorization<'info> {
@@ -25,8 +32,18 @@ pub struct ClosePreAuthorization<'info> {
     )]
     pub authority: Signer<'info>,
 
+    // The smart_delegate approval is revoked (if the authority is the owner) when the last pre_authorization is closed
+    #[account(mut)]
     pub token_account: InterfaceAccount<'info, TokenAccount>,
 
+    #[account(
+        seeds = [
+            b"smart-delegate",
+        ],
+        bump = smart_delegate.bump,
+    )]
+    pub smart_delegate: Account<'info, SmartDelegate>,
+
     #[account(
         mut,
         close = receiver,
@@ -39,9 +56,28 @@ pub struct ClosePreAuthorization<'info> {
         has_one = token_account @ CustomProgramError::PreAuthorizationTokenAccountMismatch,
     )]
     pub pre_authorization: Account<'info, PreAuthorization>,
+
+    // Always required (it can be uninitialized, see `load_pre_authorization_counter`)
+    /// CHECK: This is validated by the seeds and deserialized in the handler (if initialized)
+    #[account(
+        mut,
+        seeds = [
+            b"pre-authorization-counter",
+            token_account.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub pre_authorization_counter: Option<UncheckedAccount<'info>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    // In bounded delegation mode (if the owner closes a pre_authorization that is not the last one),
+    // the remaining accounts MUST be all the other open pre_authorization accounts of the token_account
+    // (the smart_delegate is re-approved for the sum of their outstanding amounts authorized)
 }
 
-pub fn handle_close_pre_authorization(ctx: Context<ClosePreAuthorization>) -> Result<()> {
+pub fn handle_close_pre_authorization<'info>(
+    ctx: Context<'_, '_, '_, 'info, ClosePreAuthorization<'info>>,
+) -> Result<()> {
     let event_data = PreAuthorizationClosedEventData {
         debit_authority: ctx.accounts.pre_authorization.debit_authority,
         closing_authority: ctx.accounts.authority.key(),
@@ -51,6 +87,67 @@ pub fn handle_close_pre_authorization(ctx: Context<ClosePreAuthorization>) -> Re
         pre_authorization: ctx.accounts.pre_authorization.key(),
     };
 
+    // Optional in the IDL for compatibility, it MUST be set such that the count can never be left too high
+    require!(
+        ctx.accounts.pre_authorization_counter.is_some(),
+        CustomProgramError::PreAuthorizationCounterRequired
+    );
+    let mut pre_authorization_counter =
+        load_pre_authorization_counter(ctx.accounts.pre_authorization_counter.as_ref())?;
+    let is_last_pre_authorization = match pre_authorization_counter.as_mut() {
+        Some(pre_authorization_counter) => {
+            let is_last_pre_authorization = pre_authorization_counter.decrement()?;
+            pre_authorization_counter.exit(&crate::ID)?;
+            is_last_pre_authorization
+        }
+        None => false,
+    };
+    let bounded_delegation = pre_authorization_counter
+        .as_ref()
+        .is_some_and(|pre_authorization_counter| pre_authorization_counter.bounded_delegation);
+    // Only the owner can revoke or re-approve, and only if the smart_delegate is still the delegate
+    // (the owner may have approved another delegate since)
+    if ctx
+        .accounts
+        .authority
+        .key
+        .eq(&ctx.accounts.token_account.owner)
+        && ctx.accounts.token_account.delegate == Some(ctx.accounts.smart_delegate.key()).into()
+    {
+        if is_last_pre_authorization {
+            token_interface::revoke(CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                Revoke {
+                    source: ctx.accounts.token_account.to_account_info(),
+                    authority: ctx.accounts.authority.to_account_info(),
+                },
+            ))?;
+        } else if bounded_delegation {
+            // The sum is recomputed from the other open pre_authorizations (instead of subtracting the
+            // outstanding amount of the closed one), this also removes any approval above the sum
+            // (e.g. after an amount accrued above max_accrued_cycles was forfeited in a debit)
+            let pre_authorizations = load_token_account_pre_authorizations(
+                ctx.remaining_accounts,
+                &ctx.accounts.token_account.key(),
+                Some(&ctx.accounts.pre_authorization.key()),
+            )?;
+            let delegated_amount = compute_total_outstanding_amount_authorized(
+                &pre_authorizations,
+                pre_authorization_counter
+                    .as_ref()
+                    .map(|pre_authorization_counter| pre_authorization_counter.count)
+                    .unwrap_or_default(),
+            )?;
+            approve_smart_delegate(
+                ctx.accounts.token_program.to_account_info(),
+                ctx.accounts.token_account.to_account_info(),
+                ctx.accounts.smart_delegate.to_account_info(),
+                ctx.accounts.authority.to_account_info(),
+                delegated_amount,
+            )?;
+        }
+    }
+
     match ctx.accounts.pre_authorization.variant {
         crate::state::pre_authorization::PreAuthorizationVariant::OneTime { .. } => {
             emit!(OneTimePreAuthorizationClosed { data: event_data })
@@ -58,6 +155,15 @@ pub fn handle_close_pre_authorization(ctx: Context<ClosePreAuthorization>) -> Re
         crate::state::pre_authorization::PreAuthorizationVariant::Recurring { .. } => {
             emit!(RecurringPreAuthorizationClosed { data: event_data })
         }
+        crate::state::pre_authorization::PreAuthorizationVariant::Scheduled { .. } => {
+            emit!(ScheduledPreAuthorizationClosed { data: event_data })
+        }
+        crate::state::pre_authorization::PreAuthorizationVariant::Installments { .. } => {
+            emit!(InstallmentsPreAuthorizationClosed { data: event_data })
+        }
+        crate::state::pre_authorization::PreAuthorizationVariant::RollingWindow { .. } => {
+            emit!(RollingWindowPreAuthorizationClosed { data: event_data })
+        }
     }
 
     Ok(())
@@ -82,3 +188,18 @@ pub struct OneTimePreAuthorizationClosed {
 pub struct RecurringPreAuthorizationClosed {
     pub data: PreAuthorizationClosedEventData,
 }
+
+#[event]
+pub struct ScheduledPreAuthorizationClosed {
+    pub data: PreAuthorizationClosedEventData,
+}
+
+#[event]
+pub struct InstallmentsPreAuthorizationClosed {
+    pub data: PreAuthorizationClosedEventData,
+}
+
+#[event]
+pub struct RollingWindowPreAuthorizationClosed {
+    pub data: PreAuthorizationClosedEventData,
+}
@@ -1,10 +1,18 @@
-use anchor_lang::prelude::*;
+use anchor_lang::{prelude::*, solana_program::program::invoke};
 use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
 
 use crate::{
     errors::CustomProgramError,
+    instructions::{OneTimePreAuthorizationClosed, PreAuthorizationClosedEventData},
+    math::{
+        compute_active_schedule_step, compute_amount_authorized_for_cycle,
+        compute_cumulative_amount_authorized, compute_max_accrued_amount,
+        compute_rolling_window_amount_debited,
+    },
     state::{
-        pre_authorization::{PreAuthorization, PreAuthorizationVariant},
+        debit_receipt::DebitReceipt,
+        pre_authorization::{PreAuthorization, PreAuthorizationScope, PreAuthorizationVariant},
+        pre_authorization_counter::PreAuthorizationCounter,
         smart_delegate::SmartDelegate,
     },
 };
@@ -29,75 +37,163 @@ pub struct Debit<'info> {
     )]
     pub smart_delegate: Account<'info, SmartDelegate>,
 
+    // The seeds depend on the pre_authorization.scope, the scope is checked instead
+    // (a pre_authorization can only be created at its PDA in init_pre_authorization or init_owner_pre_authorization)
     #[account(
         mut,
-        seeds = [
-            b"pre-authorization",
-            token_account.key().as_ref(),
-            debit_authority.key().as_ref(),
-        ],
-        bump = pre_authorization.bump,
         has_one = debit_authority @ CustomProgramError::DebitUnauthorized,
-        has_one = token_account @ CustomProgramError::PreAuthorizationTokenAccountMismatch
+        constraint = pre_authorization.covers_token_account(
+            &token_account.key(),
+            &token_account.owner,
+            &token_account.mint,
+        ) @ CustomProgramError::PreAuthorizationTokenAccountMismatch
     )]
     pub pre_authorization: Account<'info, PreAuthorization>,
 
     pub token_program: Interface<'info, TokenInterface>,
+
+    // Only required if the debit exhausts a one-time pre_authorization with close_on_exhaustion,
+    // the rent of the closed pre_authorization is refunded to the token account owner
+    /// CHECK: This is validated against token_account.owner
+    #[account(
+        mut,
+        constraint = receiver.key.eq(&token_account.owner)
+            @ CustomProgramError::OnlyTokenAccountOwnerCanReceiveClosePreAuthFunds
+    )]
+    pub receiver: Option<AccountInfo<'info>>,
+
+    // Only required if the debit closes a pre_authorization scoped to the token_account,
+    // or if the pre_authorization is owner scoped (it can be uninitialized, see `load_pre_authorization_counter`)
+    /// CHECK: This is validated by the seeds and deserialized in the handler (if initialized)
+    #[account(
+        mut,
+        seeds = [
+            b"pre-authorization-counter",
+            token_account.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub pre_authorization_counter: Option<UncheckedAccount<'info>>,
+
+    // Only set to log the params.reference in a memo (e.g. for a destination token account requiring memos)
+    /// CHECK: This is validated against the SPL Memo program id
+    #[account(
+        address = spl_memo::ID @ CustomProgramError::InvalidMemoProgram
+    )]
+    pub memo_program: Option<AccountInfo<'info>>,
+
+    // The debit_receipt is only created (paid by the receipt_payer) if set
+    #[account(mut)]
+    pub receipt_payer: Option<Signer<'info>>,
+
+    #[account(
+        init,
+        payer = receipt_payer,
+        space = 8 + DebitReceipt::INIT_SPACE,
+        seeds = [
+            b"debit-receipt",
+            pre_authorization.key().as_ref(),
+            pre_authorization.num_debits.to_le_bytes().as_ref(),
+        ],
+        bump,
+    )]
+    pub debit_receipt: Option<Account<'info, DebitReceipt>>,
+
+    pub system_program: Option<Program<'info, System>>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DebitAmountMode {
+    // debits exactly the amount (or fails)
+    Exact,
+    // debits the largest amount up to the amount that can be debited at execution time
+    // (see `compute_debit_amount`)
+    UpTo,
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize)]
 pub struct DebitParams {
     pub amount: u64,
+    pub amount_mode: DebitAmountMode,
+    // an opaque reference for the debit (e.g. an invoice id or hash), emitted in the `DebitEvent`
+    pub reference: Option<[u8; 32]>,
 }
 
 pub fn handle_debit(ctx: Context<Debit>, params: DebitParams) -> Result<()> {
-    validate_debit(&ctx, &params)?;
-
-    ctx.accounts.pre_authorization.variant = match ctx.accounts.pre_authorization.variant {
-        PreAuthorizationVariant::OneTime {
-            amount_authorized,
-            expiry_unix_timestamp,
-            amount_debited,
-        } => PreAuthorizationVariant::OneTime {
-            amount_authorized,
-            expiry_unix_timestamp,
-            amount_debited: amount_debited + params.amount,
-        },
-        PreAuthorizationVariant::Recurring {
-            repeat_frequency_seconds,
-            recurring_amount_authorized,
-            amount_debited_last_cycle,
-            amount_debited_total,
-            last_debited_cycle,
-            num_cycles,
-            reset_every_cycle,
-        } => {
-            let current_cycle = compute_current_cycle(
-                Clock::get()?.unix_timestamp,
-                ctx.accounts.pre_authorization.activation_unix_timestamp,
-                repeat_frequency_seconds,
-            );
-
-            let new_amount_debited_last_cycle = if current_cycle == last_debited_cycle {
-                amount_debited_last_cycle + params.amount
-            } else {
-                params.amount
-            };
+    let current_unix_timestamp = Clock::get()?.unix_timestamp;
+    let token_account = &ctx.accounts.token_account;
+    let amount = compute_debit_amount(
+        params.amount_mode,
+        params.amount,
+        &ctx.accounts.pre_authorization,
+        current_unix_timestamp,
+        token_account.amount,
+        // validate_debit reports a token account delegated to another delegate
+        (token_account.delegate == Some(ctx.accounts.smart_delegate.key()).into())
+            .then_some(token_account.delegated_amount),
+    )?;
 
-            PreAuthorizationVariant::Recurring {
-                repeat_frequency_seconds,
-                recurring_amount_authorized,
-                num_cycles,
-                reset_every_cycle,
-                amount_debited_last_cycle: new_amount_debited_last_cycle,
-                amount_debited_total: amount_debited_total + params.amount,
-                last_debited_cycle: current_cycle,
-            }
-        }
-    };
+    validate_debit(
+        &ctx.accounts.pre_authorization,
+        &ctx.accounts.token_account,
+        &ctx.accounts.destination_token_account,
+        &ctx.accounts.smart_delegate.key(),
+        amount,
+        current_unix_timestamp,
+    )?;
+    let debit_index = ctx.accounts.pre_authorization.num_debits;
+    record_debit(
+        &mut ctx.accounts.pre_authorization,
+        amount,
+        current_unix_timestamp,
+    )?;
+
+    require!(
+        ctx.accounts.memo_program.is_none() || params.reference.is_some(),
+        CustomProgramError::ReferenceRequiredForMemo
+    );
+
+    let close_on_exhaustion = ctx.accounts.pre_authorization.should_close_on_exhaustion();
+    require!(
+        !close_on_exhaustion || ctx.accounts.receiver.is_some(),
+        CustomProgramError::ReceiverRequiredForCloseOnExhaustion
+    );
+    // owner scoped pre_authorizations are not counted
+    let is_counted = ctx.accounts.pre_authorization.scope == PreAuthorizationScope::TokenAccount;
+    let mut pre_authorization_counter =
+        load_pre_authorization_counter(ctx.accounts.pre_authorization_counter.as_ref())?;
+    require!(
+        !close_on_exhaustion || !is_counted || pre_authorization_counter.is_some(),
+        CustomProgramError::PreAuthorizationCounterRequired
+    );
+    // owner scoped debits are not part of the outstanding amount authorized,
+    // they would consume the allowance of the token_account pre_authorizations in bounded delegation mode
+    if !is_counted {
+        require!(
+            ctx.accounts.pre_authorization_counter.is_some(),
+            CustomProgramError::PreAuthorizationCounterRequired
+        );
+        require!(
+            !pre_authorization_counter
+                .as_ref()
+                .is_some_and(
+                    |pre_authorization_counter| pre_authorization_counter.bounded_delegation
+                ),
+            CustomProgramError::OwnerScopedDebitInBoundedDelegation
+        );
+    }
+
+    // The memo MUST be right before the transfer for token accounts requiring memos on incoming transfers
+    if let (Some(memo_program), Some(reference)) = (&ctx.accounts.memo_program, &params.reference) {
+        invoke(
+            &spl_memo::build_memo(encode_reference_memo(reference).as_bytes(), &[]),
+            &[memo_program.to_account_info()],
+        )?;
+    }
 
     // NOTE: Since this reduces the delegated amount, in theory it is good to refresh the delegated amount of the smart delegate back to u64::MAX
     //       In practice, because we set it to u64::MAX, this is never necessary (unless token is weird)
+    //       In bounded delegation mode, the delegated amount is meant to decrease with the outstanding amount authorized
     token_interface::transfer_checked(
         CpiContext::new_with_signer(
             ctx.accounts.token_program.to_account_info(),
@@ -112,7 +208,7 @@ pub fn handle_debit(ctx: Context<Debit>, params: DebitParams) -> Result<()> {
                 &[ctx.accounts.smart_delegate.bump],
             ]],
         ),
-        params.amount,
+        amount,
         ctx.accounts.mint.decimals,
     )?;
 
@@ -126,26 +222,99 @@ pub fn handle_debit(ctx: Context<Debit>, params: DebitParams) -> Result<()> {
         destination_token_account_owner: ctx.accounts.destination_token_account.owner,
         source_token_account: ctx.accounts.token_account.key(),
         destination_token_account: ctx.accounts.destination_token_account.key(),
-        debit_variant: match ctx.accounts.pre_authorization.variant {
-            PreAuthorizationVariant::OneTime { .. } => DebitEventVariant::OneTime {
-                debit_amount: params.amount
-            },
-            PreAuthorizationVariant::Recurring {
-                last_debited_cycle, ..
-            } => DebitEventVariant::Recurring {
-                debit_amount: params.amount,
-                cycle: last_debited_cycle
-            },
-        },
+        requested_amount: params.amount,
+        reference: params.reference,
+        debit_receipt: ctx.accounts.debit_receipt.as_ref().map(|info| info.key()),
+        debit_variant: compute_debit_event_variant(&ctx.accounts.pre_authorization, amount)?,
     });
 
+    if let Some(debit_receipt) = ctx.accounts.debit_receipt.as_mut() {
+        debit_receipt.set_inner(DebitReceipt {
+            bump: *ctx
+                .bumps
+                .get("debit_receipt")
+                .expect("debit_receipt PDA bump access failed"),
+            pre_authorization: ctx.accounts.pre_authorization.key(),
+            debit_index,
+            debit_authority: ctx.accounts.debit_authority.key(),
+            payer: ctx
+                .accounts
+                .receipt_payer
+                .as_ref()
+                .ok_or(CustomProgramError::ReceiptPayerRequired)?
+                .key(),
+            source_token_account: ctx.accounts.token_account.key(),
+            destination_token_account: ctx.accounts.destination_token_account.key(),
+            amount,
+            cycle: ctx
+                .accounts
+                .pre_authorization
+                .current_cycle_at(current_unix_timestamp),
+            unix_timestamp: current_unix_timestamp,
+            reference: params.reference,
+        });
+    }
+
+    if close_on_exhaustion {
+        let receiver = ctx
+            .accounts
+            .receiver
+            .as_ref()
+            .ok_or(CustomProgramError::ReceiverRequiredForCloseOnExhaustion)?;
+        if is_counted {
+            let pre_authorization_counter = pre_authorization_counter
+                .as_mut()
+                .ok_or(CustomProgramError::PreAuthorizationCounterRequired)?;
+            pre_authorization_counter.decrement()?;
+            pre_authorization_counter.exit(&crate::ID)?;
+        }
+        ctx.accounts
+            .pre_authorization
+            .close(receiver.to_account_info())?;
+
+        emit!(OneTimePreAuthorizationClosed {
+            data: PreAuthorizationClosedEventData {
+                debit_authority: ctx.accounts.debit_authority.key(),
+                closing_authority: ctx.accounts.debit_authority.key(),
+                token_account_owner: ctx.accounts.token_account.owner,
+                receiver: receiver.key(),
+                token_account: ctx.accounts.token_account.key(),
+                pre_authorization: ctx.accounts.pre_authorization.key(),
+            }
+        });
+    }
+
     Ok(())
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize)]
 pub enum DebitEventVariant {
-    OneTime { debit_amount: u64 },
-    Recurring { debit_amount: u64, cycle: u64 },
+    OneTime {
+        debit_amount: u64,
+    },
+    Recurring {
+        debit_amount: u64,
+        cycle: u64,
+    },
+    Scheduled {
+        debit_amount: u64,
+        cycle: u64,
+        // the index of the `pre_authorization` step the debit was validated against
+        step_index: u8,
+        step_amount_authorized: u64,
+    },
+    Installments {
+        debit_amount: u64,
+        // the number of installments fully paid after the debit
+        installments_paid: u64,
+        installment_count: u64,
+    },
+    RollingWindow {
+        debit_amount: u64,
+        bucket: u64,
+        // the amount debited in the trailing window after the debit
+        amount_debited_in_window: u64,
+    },
 }
 
 #[event]
@@ -159,333 +328,591 @@ pub struct DebitEvent {
     pub destination_token_account_owner: Pubkey,
     pub source_token_account: Pubkey,
     pub destination_token_account: Pubkey,
+    // the amount requested in the `DebitParams` (the amount debited is in the `debit_variant`,
+    // it can be less than the amount requested with `DebitAmountMode::UpTo`)
+    pub requested_amount: u64,
+    pub reference: Option<[u8; 32]>,
+    // only set if a `debit_receipt` was created for the debit
+    pub debit_receipt: Option<Pubkey>,
     pub debit_variant: DebitEventVariant,
 }
 
-fn validate_debit(ctx: &Context<Debit>, params: &DebitParams) -> Result<()> {
-    let pre_authorization = &ctx.accounts.pre_authorization;
-
-    require!(
-        !pre_authorization.paused,
-        CustomProgramError::PreAuthorizationPaused
-    );
+/**
+  Returns the deserialized `pre_authorization_counter` (`None` if it is not set or not initialized,
+  i.e. no `pre_authorization` was ever created for the token account).
+  The `pre_authorization_counter` MUST be validated with its seeds by the caller.
+  Changes to the returned account MUST be persisted with `exit`.
+*/
+pub fn load_pre_authorization_counter<'info>(
+    pre_authorization_counter_info: Option<&UncheckedAccount<'info>>,
+) -> Result<Option<Account<'info, PreAuthorizationCounter>>> {
+    match pre_authorization_counter_info {
+        Some(info) if !info.data_is_empty() => Ok(Some(Account::try_from(info)?)),
+        _ => Ok(None),
+    }
+}
 
-    let current_unix_timestamp = Clock::get()?.unix_timestamp;
+/**
+  Returns the memo logged for a debit `reference` (the memo MUST be valid UTF-8): its lowercase hex encoding.
+*/
+pub fn encode_reference_memo(reference: &[u8; 32]) -> String {
+    reference.iter().map(|byte| format!("{byte:02x}")).collect()
+}
 
-    require!(
-        current_unix_timestamp >= pre_authorization.activation_unix_timestamp,
-        CustomProgramError::PreAuthorizationNotActive
-    );
+/**
+  Returns the amount to debit at `current_unix_timestamp` for the `requested_amount`:
+  - `DebitAmountMode::Exact`: the `requested_amount`
+  - `DebitAmountMode::UpTo`: the `requested_amount` capped to the amount available in the `pre_authorization`,
+    its `max_amount_per_debit` (if any), the `token_account_amount` and the `delegated_amount`
+    of the smart delegate (if it is the delegate), the capped amount MUST be greater than 0
+    and greater than or equal to the `min_amount_per_debit` (if any)
+*/
+pub fn compute_debit_amount(
+    amount_mode: DebitAmountMode,
+    requested_amount: u64,
+    pre_authorization: &PreAuthorization,
+    current_unix_timestamp: i64,
+    token_account_amount: u64,
+    delegated_amount: Option<u64>,
+) -> core::result::Result<u64, CustomProgramError> {
+    if amount_mode == DebitAmountMode::Exact {
+        return Ok(requested_amount);
+    }
 
-    match pre_authorization.variant {
-        PreAuthorizationVariant::OneTime { .. } => {
-            validate_one_time_pre_authorization(ctx, params)?
-        }
-        PreAuthorizationVariant::Recurring { .. } => {
-            validate_recurring_pre_authorization(ctx, params)?
-        }
+    let (max_amount_per_debit, min_amount_per_debit) = pre_authorization.amount_per_debit_limits();
+    let amount = requested_amount
+        .min(pre_authorization.available_amount_at(current_unix_timestamp)?)
+        .min(max_amount_per_debit.unwrap_or(u64::MAX))
+        .min(token_account_amount)
+        .min(delegated_amount.unwrap_or(u64::MAX));
+    if amount == 0 || amount < min_amount_per_debit.unwrap_or_default() {
+        return Err(CustomProgramError::NothingToDebit);
     }
 
-    Ok(())
+    Ok(amount)
 }
 
-fn validate_one_time_pre_authorization(ctx: &Context<Debit>, params: &DebitParams) -> Result<()> {
-    let pre_authorization = &ctx.accounts.pre_authorization;
-    let current_unix_timestamp = Clock::get()?.unix_timestamp;
+/**
+  Validates a debit of `amount` from the `token_account` to the `destination_token_account`
+  at `current_unix_timestamp` against the `pre_authorization` rules.
+*/
+pub fn validate_debit(
+    pre_authorization: &PreAuthorization,
+    token_account: &InterfaceAccount<TokenAccount>,
+    destination_token_account: &InterfaceAccount<TokenAccount>,
+    smart_delegate: &Pubkey,
+    amount: u64,
+    current_unix_timestamp: i64,
+) -> Result<()> {
+    let amount_available = pre_authorization.available_amount_at(current_unix_timestamp)?;
 
-    let (amount_authorized, expiry_unix_timestamp, amount_debited) = match pre_authorization.variant
-    {
-        PreAuthorizationVariant::OneTime {
-            amount_authorized,
-            expiry_unix_timestamp,
-            amount_debited,
-        } => (amount_authorized, expiry_unix_timestamp, amount_debited),
-        _ => panic!("Unreachable code path"),
-    };
+    require!(
+        pre_authorization.allows_destination(
+            &destination_token_account.key(),
+            &destination_token_account.owner,
+        ),
+        CustomProgramError::DestinationNotAllowed
+    );
 
     require!(
-        current_unix_timestamp < expiry_unix_timestamp,
-        CustomProgramError::PreAuthorizationNotActive
+        amount <= amount_available,
+        CustomProgramError::CannotDebitMoreThanAvailable
     );
 
-    let amount_available = amount_authorized - amount_debited;
+    // The owner may have approved another delegate on the token account since (see reapprove_smart_delegate)
+    if token_account.delegate != Some(*smart_delegate).into() {
+        msg!(
+            "Token account delegate: {:?}, delegated amount: {}",
+            Option::<Pubkey>::from(token_account.delegate),
+            token_account.delegated_amount
+        );
+        return err!(CustomProgramError::SmartDelegateNotApproved);
+    }
 
+    // In bounded delegation mode, the delegated amount decreases with each debit (as does the outstanding amount)
     require!(
-        params.amount <= amount_available,
-        CustomProgramError::CannotDebitMoreThanAvailable
+        amount <= token_account.delegated_amount,
+        CustomProgramError::DelegatedAmountExhausted
     );
 
+    pre_authorization.check_amount_per_debit(amount)?;
+
     Ok(())
 }
 
-fn validate_recurring_pre_authorization(ctx: &Context<Debit>, params: &DebitParams) -> Result<()> {
-    let pre_authorization = &ctx.accounts.pre_authorization;
-    let current_unix_timestamp = Clock::get()?.unix_timestamp;
+/**
+  Records a debit of `amount` at `current_unix_timestamp` in the `pre_authorization` (debited amounts, last
+  debited cycle etc.), forfeiting the amount accrued above `max_accrued_cycles` first.
+  The debit MUST have been validated with `validate_debit`.
+*/
+pub fn record_debit(
+    pre_authorization: &mut PreAuthorization,
+    amount: u64,
+    current_unix_timestamp: i64,
+) -> core::result::Result<(), CustomProgramError> {
+    let current_cycle = pre_authorization.current_cycle_at(current_unix_timestamp);
+    if let Some(current_cycle) = current_cycle {
+        forfeit_amount_accrued_above_max(&mut pre_authorization.variant, current_cycle)?;
+    }
 
-    let (
-        repeat_frequency_seconds,
-        recurring_amount_authorized,
-        num_cycles,
-        reset_every_cycle,
-        last_debited_cycle,
-        amount_debited_last_cycle,
-        amount_debited_total,
-    ) = match pre_authorization.variant {
+    pre_authorization.num_debits = pre_authorization
+        .num_debits
+        .checked_add(1)
+        .ok_or(CustomProgramError::ArithmeticOverflow)?;
+
+    match &mut pre_authorization.variant {
+        PreAuthorizationVariant::OneTime { amount_debited, .. }
+        | PreAuthorizationVariant::Installments { amount_debited, .. } => {
+            *amount_debited = amount_debited
+                .checked_add(amount)
+                .ok_or(CustomProgramError::ArithmeticOverflow)?;
+        }
         PreAuthorizationVariant::Recurring {
-            repeat_frequency_seconds,
-            recurring_amount_authorized,
-            num_cycles,
-            reset_every_cycle,
-            last_debited_cycle,
             amount_debited_last_cycle,
             amount_debited_total,
-        } => (
-            repeat_frequency_seconds,
-            recurring_amount_authorized,
-            num_cycles,
-            reset_every_cycle,
             last_debited_cycle,
+            ..
+        }
+        | PreAuthorizationVariant::Scheduled {
             amount_debited_last_cycle,
             amount_debited_total,
-        ),
-        _ => panic!("Unreachable code path"),
-    };
-
-    let current_cycle = compute_current_cycle(
-        current_unix_timestamp,
-        pre_authorization.activation_unix_timestamp,
-        repeat_frequency_seconds,
-    );
+            last_debited_cycle,
+            ..
+        } => {
+            // validate_debit already checked that the current cycle can be computed
+            let current_cycle = current_cycle.ok_or(CustomProgramError::InvalidCycle)?;
 
-    if let Some(num_cycles) = num_cycles {
-        require!(
-            current_cycle <= num_cycles,
-            CustomProgramError::PreAuthorizationNotActive
-        );
+            *amount_debited_last_cycle = if current_cycle == *last_debited_cycle {
+                amount_debited_last_cycle
+                    .checked_add(amount)
+                    .ok_or(CustomProgramError::ArithmeticOverflow)?
+            } else {
+                amount
+            };
+            *amount_debited_total = amount_debited_total
+                .checked_add(amount)
+                .ok_or(CustomProgramError::ArithmeticOverflow)?;
+            *last_debited_cycle = current_cycle;
+        }
+        PreAuthorizationVariant::RollingWindow {
+            debited_buckets,
+            last_debited_bucket,
+            amount_debited_total,
+            ..
+        } => {
+            // validate_debit already checked that the current bucket can be computed
+            let current_bucket = current_cycle.ok_or(CustomProgramError::InvalidCycle)?;
+
+            record_rolling_window_debit(
+                debited_buckets,
+                last_debited_bucket,
+                current_bucket,
+                amount,
+            )?;
+            *amount_debited_total = amount_debited_total
+                .checked_add(amount)
+                .ok_or(CustomProgramError::ArithmeticOverflow)?;
+        }
     }
 
-    // could happen if validator has decreasing timestamps in between TXs due to some weirdness
-    require!(
-        current_cycle >= last_debited_cycle,
-        CustomProgramError::LastDebitedCycleBeforeCurrentCycle
-    );
+    Ok(())
+}
 
-    let amount_available = compute_available_amount_for_recurring_debit(
-        current_cycle,
-        last_debited_cycle,
-        reset_every_cycle,
+/**
+  Returns the `DebitEventVariant` of a debit of `amount` recorded in the `pre_authorization` (see `record_debit`).
+*/
+pub fn compute_debit_event_variant(
+    pre_authorization: &PreAuthorization,
+    amount: u64,
+) -> core::result::Result<DebitEventVariant, CustomProgramError> {
+    Ok(match pre_authorization.variant {
+        PreAuthorizationVariant::OneTime { .. } => DebitEventVariant::OneTime {
+            debit_amount: amount,
+        },
+        PreAuthorizationVariant::Recurring {
+            last_debited_cycle, ..
+        } => DebitEventVariant::Recurring {
+            debit_amount: amount,
+            cycle: last_debited_cycle,
+        },
+        PreAuthorizationVariant::Scheduled {
+            ref steps,
+            last_debited_cycle,
+            ..
+        } => {
+            let step_index = compute_active_schedule_step(last_debited_cycle, steps)?;
+            DebitEventVariant::Scheduled {
+                debit_amount: amount,
+                cycle: last_debited_cycle,
+                step_index: step_index as u8,
+                step_amount_authorized: steps[step_index].amount_authorized,
+            }
+        }
+        PreAuthorizationVariant::Installments {
+            installment_count, ..
+        } => DebitEventVariant::Installments {
+            debit_amount: amount,
+            installments_paid: pre_authorization
+                .installments_paid()
+                .ok_or(CustomProgramError::InvalidCycle)?,
+            installment_count,
+        },
+        PreAuthorizationVariant::RollingWindow {
+            ref debited_buckets,
+            last_debited_bucket,
+            ..
+        } => DebitEventVariant::RollingWindow {
+            debit_amount: amount,
+            bucket: last_debited_bucket,
+            amount_debited_in_window: compute_rolling_window_amount_debited(
+                last_debited_bucket,
+                last_debited_bucket,
+                debited_buckets,
+            )?,
+        },
+    })
+}
+
+/**
+  Forfeits the amount accrued above `max_accrued_cycles` cycles worth of the amount authorized in `current_cycle`
+  (only for a recurring pre-authorization with `reset_every_cycle == false`), so that it never
+  becomes available again in later cycles.
+  The amendment baseline is re-based at `current_cycle` such that exactly the capped amount is available
+  in `current_cycle` (i.e. this does not change `available_amount_at` in `current_cycle`).
+  This MUST be called before the debited amounts are updated.
+  In bounded delegation mode, the `smart_delegate` approval is not lowered by the forfeited amount
+  (it requires the owner signature, see `reapprove_smart_delegate`).
+*/
+pub fn forfeit_amount_accrued_above_max(
+    variant: &mut PreAuthorizationVariant,
+    current_cycle: u64,
+) -> core::result::Result<(), CustomProgramError> {
+    if let PreAuthorizationVariant::Recurring {
         recurring_amount_authorized,
-        amount_debited_last_cycle,
         amount_debited_total,
-    );
+        reset_every_cycle: false,
+        amended_at_cycle,
+        amount_authorized_before_amendment,
+        max_accrued_cycles,
+        intro_schedule,
+        ..
+    } = variant
+    {
+        let amount_authorized_current_cycle = compute_amount_authorized_for_cycle(
+            current_cycle,
+            *recurring_amount_authorized,
+            intro_schedule.map(Into::into).as_ref(),
+        );
+        let Some(max_accrued_amount) =
+            compute_max_accrued_amount(amount_authorized_current_cycle, *max_accrued_cycles)
+        else {
+            return Ok(());
+        };
 
-    require!(
-        params.amount <= amount_available,
-        CustomProgramError::CannotDebitMoreThanAvailable
-    );
+        let amount_accrued = compute_cumulative_amount_authorized(
+            current_cycle,
+            *recurring_amount_authorized,
+            *amended_at_cycle,
+            *amount_authorized_before_amendment,
+            intro_schedule.map(Into::into).as_ref(),
+        )?
+        .checked_sub(*amount_debited_total)
+        .ok_or(CustomProgramError::ArithmeticUnderflow)?;
+
+        if amount_accrued > max_accrued_amount {
+            // cumulative amount authorized through current_cycle becomes amount_debited_total + max_accrued_amount
+            *amount_authorized_before_amendment = amount_debited_total
+                .checked_add(max_accrued_amount)
+                .and_then(|amount| amount.checked_sub(amount_authorized_current_cycle))
+                .ok_or(CustomProgramError::ArithmeticOverflow)?;
+            *amended_at_cycle = current_cycle;
+        }
+    }
 
     Ok(())
 }
 
-fn compute_available_amount_for_recurring_debit(
-    current_cycle: u64,
-    last_debited_cycle: u64,
-    reset_every_cycle: bool,
-    recurring_amount_authorized: u64,
-    amount_debited_last_cycle: u64,
-    amount_debited_total: u64,
-) -> u64 {
-    assert!(current_cycle > 0);
-    assert!(last_debited_cycle > 0);
-    assert!(current_cycle >= last_debited_cycle);
-    assert!(amount_debited_last_cycle <= amount_debited_total);
-    match (reset_every_cycle, current_cycle == last_debited_cycle) {
-        (false, _) => recurring_amount_authorized * current_cycle - amount_debited_total,
-        (true, false) => recurring_amount_authorized,
-        (true, true) => recurring_amount_authorized - amount_debited_last_cycle,
+/**
+  Records a debit of `amount` in the `current_bucket` of a rolling window pre-authorization:
+  the buckets elapsed since the `last_debited_bucket` are reset (their ring entries are reused)
+  before adding the `amount` to the `current_bucket`.
+*/
+pub fn record_rolling_window_debit(
+    debited_buckets: &mut [u64],
+    last_debited_bucket: &mut u64,
+    current_bucket: u64,
+    amount: u64,
+) -> core::result::Result<(), CustomProgramError> {
+    if current_bucket < *last_debited_bucket {
+        return Err(CustomProgramError::LastDebitedCycleBeforeCurrentCycle);
+    }
+    let ring_size = debited_buckets.len() as u64;
+    if ring_size == 0 {
+        return Err(CustomProgramError::InvalidCycle);
     }
-}
 
-fn compute_current_cycle(
-    current_unix_timestamp: i64,
-    activation_unix_timestamp: i64,
-    repeat_frequency_seconds: u64,
-) -> u64 {
-    let seconds_since_activation =
-        u64::try_from(current_unix_timestamp - activation_unix_timestamp).unwrap();
-    1 + (seconds_since_activation / repeat_frequency_seconds)
+    let last_bucket_to_reset = current_bucket.min(last_debited_bucket.saturating_add(ring_size));
+    for bucket in last_debited_bucket.saturating_add(1)..=last_bucket_to_reset {
+        debited_buckets[(bucket % ring_size) as usize] = 0;
+    }
+
+    let current_index = (current_bucket % ring_size) as usize;
+    debited_buckets[current_index] = debited_buckets[current_index]
+        .checked_add(amount)
+        .ok_or(CustomProgramError::ArithmeticOverflow)?;
+    *last_debited_bucket = current_bucket;
+
+    Ok(())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::state::pre_authorization::{IntroSchedule, RecurringFixture};
     use test_case::test_case;
 
-    // recurring pre-auth (available amount accrues across cycles)
-    #[test_case(1, 1, 0, 0, 0, 0)]
-    #[test_case(1, 1, 100, 0, 0, 100)]
-    #[test_case(1, 1, 100, 100, 100, 0)]
-    #[test_case(5, 1, 100, 0, 0, 500)]
-    #[test_case(5, 1, 100, 100, 100, 400)]
-    #[test_case(5, 4, 100, 100, 100, 400)]
-    #[test_case(5, 4, 100, 100, 400, 100)]
-    #[test_case(5, 4, 100, 400, 400, 100)]
-    #[test_case(5, 5, 100, 100, 100, 400)]
-    #[test_case(5, 5, 100, 100, 400, 100)]
-    #[test_case(5, 5, 100, 400, 400, 100)]
-    #[test_case(5, 5, 100, 0, 500, 0)]
-    #[test_case(5, 5, 100, 400, 500, 0)]
-    #[test_case(5, 5, 100, 500, 500, 0)]
-    #[test_case(5, 5, 100, 0, 100, 400)]
-    fn compute_available_amount_for_recurring_debit_cumulative_happy_path(
-        current_cycle: u64,
-        last_debited_cycle: u64,
-        recurring_amount_authorized: u64,
-        amount_debited_last_cycle: u64,
-        amount_debited_total: u64,
-        expected_amount_available: u64,
+    // activation: 100, 10s cycles, 100 per cycle, nothing debited
+    fn recurring_pre_authorization(
+        reset_every_cycle: bool,
+        max_accrued_cycles: Option<u64>,
+    ) -> PreAuthorization {
+        RecurringFixture {
+            recurring_amount_authorized: 100,
+            amount_debited_last_cycle: 0,
+            amount_debited_total: 0,
+            last_debited_cycle: 1,
+            reset_every_cycle,
+            max_accrued_cycles,
+            ..Default::default()
+        }
+        .build()
+    }
+
+    // debits 50 in cycle 5 (after forfeiting), returns the amounts available in cycles 5 and 6
+    #[test_case(false, None, (450, 550))]
+    #[test_case(false, Some(2), (150, 200))]
+    #[test_case(false, Some(5), (450, 500))]
+    #[test_case(false, Some(6), (450, 550))]
+    #[test_case(true, Some(2), (50, 100))]
+    fn forfeit_amount_accrued_above_max_then_debit(
+        reset_every_cycle: bool,
+        max_accrued_cycles: Option<u64>,
+        expected_amounts_available: (u64, u64),
     ) {
+        let mut pre_authorization =
+            recurring_pre_authorization(reset_every_cycle, max_accrued_cycles);
+        let amount_available_before = pre_authorization.available_amount_at(140);
+
+        forfeit_amount_accrued_above_max(&mut pre_authorization.variant, 5).unwrap();
         assert_eq!(
-            expected_amount_available,
-            compute_available_amount_for_recurring_debit(
-                current_cycle,
-                last_debited_cycle,
-                false,
-                recurring_amount_authorized,
-                amount_debited_last_cycle,
-                amount_debited_total
-            )
+            amount_available_before,
+            pre_authorization.available_amount_at(140)
         );
-    }
 
-    // recurring pre-auth (available amount resets every cycle)
-    #[test_case(1, 1, 0, 0, 0, 0)]
-    #[test_case(1, 1, 100, 0, 0, 100)]
-    #[test_case(1, 1, 100, 100, 100, 0)]
-    #[test_case(5, 1, 100, 0, 0, 100)]
-    #[test_case(5, 1, 100, 100, 100, 100)]
-    #[test_case(5, 4, 100, 100, 100, 100)]
-    #[test_case(5, 4, 100, 100, 400, 100)]
-    #[test_case(5, 4, 100, 400, 400, 100)]
-    #[test_case(5, 5, 100, 100, 100, 0)]
-    #[test_case(5, 5, 100, 100, 400, 0)]
-    #[test_case(5, 5, 100, 0, 500, 100)]
-    #[test_case(5, 5, 100, 0, 100, 100)]
-    fn compute_available_amount_for_recurring_debit_noncumulative_happy_path(
-        current_cycle: u64,
-        last_debited_cycle: u64,
-        recurring_amount_authorized: u64,
-        amount_debited_last_cycle: u64,
-        amount_debited_total: u64,
-        expected_amount_available: u64,
-    ) {
+        if let PreAuthorizationVariant::Recurring {
+            amount_debited_last_cycle,
+            amount_debited_total,
+            last_debited_cycle,
+            ..
+        } = &mut pre_authorization.variant
+        {
+            *amount_debited_last_cycle = 50;
+            *amount_debited_total = 50;
+            *last_debited_cycle = 5;
+        }
+
         assert_eq!(
-            expected_amount_available,
-            compute_available_amount_for_recurring_debit(
-                current_cycle,
-                last_debited_cycle,
-                true,
-                recurring_amount_authorized,
-                amount_debited_last_cycle,
-                amount_debited_total
+            (
+                Ok(expected_amounts_available.0),
+                Ok(expected_amounts_available.1)
+            ),
+            (
+                pre_authorization.available_amount_at(140),
+                pre_authorization.available_amount_at(150)
             )
         );
     }
 
-    // recurring pre-auth (available amount accrues across cycles)
-    // asserts
-    #[test_case(0, 1, 0, 0, 0)]
-    #[test_case(1, 0, 0, 0, 0)]
-    #[test_case(1, 2, 0, 0, 0)]
-    #[test_case(1, 1, 10, 10, 5)]
-    // other cases
-    #[test_case(1, 1, 100, 100, 0)]
-    #[test_case(5, 1, 100, 100, 0)]
-    #[test_case(5, 1, 100, 100, 600)]
-    #[test_case(5, 4, 100, 100, 600)]
-    #[test_case(5, 5, 100, 100, 600)]
-    #[should_panic]
-    fn compute_available_amount_for_recurring_debit_cumulative_panics(
-        current_cycle: u64,
-        last_debited_cycle: u64,
-        recurring_amount_authorized: u64,
-        amount_debited_last_cycle: u64,
-        amount_debited_total: u64,
-    ) {
-        compute_available_amount_for_recurring_debit(
-            current_cycle,
-            last_debited_cycle,
-            false,
-            recurring_amount_authorized,
+    // 3 intro cycles at 20 (at most 40 available at once), debits 10 in cycle 3 (after forfeiting)
+    #[test]
+    fn forfeit_amount_accrued_above_max_with_intro_schedule() {
+        let mut pre_authorization = RecurringFixture {
+            recurring_amount_authorized: 100,
+            amount_debited_last_cycle: 0,
+            amount_debited_total: 0,
+            last_debited_cycle: 1,
+            max_accrued_cycles: Some(2),
+            intro_schedule: Some(IntroSchedule {
+                num_intro_cycles: 3,
+                intro_amount_authorized: 20,
+            }),
+            ..Default::default()
+        }
+        .build();
+        assert_eq!(Ok(40), pre_authorization.available_amount_at(120));
+
+        forfeit_amount_accrued_above_max(&mut pre_authorization.variant, 3).unwrap();
+        assert_eq!(Ok(40), pre_authorization.available_amount_at(120));
+
+        if let PreAuthorizationVariant::Recurring {
             amount_debited_last_cycle,
             amount_debited_total,
+            last_debited_cycle,
+            ..
+        } = &mut pre_authorization.variant
+        {
+            *amount_debited_last_cycle = 10;
+            *amount_debited_total = 10;
+            *last_debited_cycle = 3;
+        }
+
+        assert_eq!(Ok(30), pre_authorization.available_amount_at(120));
+        assert_eq!(Ok(130), pre_authorization.available_amount_at(130));
+    }
+
+    // ring of 4 buckets, bucket n is at index n % 4, 15 debited in bucket 5
+    #[test_case(5, ([1, 2, 4, 8], 5), Ok([1, 17, 4, 8]))]
+    #[test_case(6, ([1, 2, 4, 8], 5), Ok([1, 2, 15, 8]))]
+    #[test_case(8, ([1, 2, 4, 8], 5), Ok([15, 2, 0, 0]))]
+    #[test_case(9, ([1, 2, 4, 8], 5), Ok([0, 15, 0, 0]))]
+    #[test_case(u64::MAX, ([1, 2, 4, 8], 5), Ok([0, 0, 0, 15]))]
+    #[test_case(4, ([1, 2, 4, 8], 5), Err(CustomProgramError::LastDebitedCycleBeforeCurrentCycle))]
+    #[test_case(5, ([0, u64::MAX, 0, 0], 5), Err(CustomProgramError::ArithmeticOverflow))]
+    fn record_rolling_window_debit_cases(
+        current_bucket: u64,
+        (debited_buckets, last_debited_bucket): ([u64; 4], u64),
+        expected_res: core::result::Result<[u64; 4], CustomProgramError>,
+    ) {
+        let mut debited_buckets = debited_buckets;
+        let mut last_debited_bucket = last_debited_bucket;
+        let res = record_rolling_window_debit(
+            &mut debited_buckets,
+            &mut last_debited_bucket,
+            current_bucket,
+            15,
         );
+
+        assert_eq!(expected_res, res.map(|_| debited_buckets));
+        if res.is_ok() {
+            assert_eq!(current_bucket, last_debited_bucket);
+        }
     }
 
-    // recurring pre-auth (available amount resets every cycle)
-    // asserts
-    #[test_case(0, 1, 0, 0, 0)]
-    #[test_case(1, 0, 0, 0, 0)]
-    #[test_case(1, 2, 0, 0, 0)]
-    #[test_case(1, 1, 10, 10, 5)]
-    // other cases
-    #[test_case(5, 5, 100, 400, 400)]
-    #[test_case(5, 5, 100, 400, 500)]
-    #[test_case(5, 5, 100, 500, 500)]
-    #[should_panic]
-    fn compute_available_amount_for_recurring_debit_noncumulative_panics(
-        current_cycle: u64,
-        last_debited_cycle: u64,
-        recurring_amount_authorized: u64,
-        amount_debited_last_cycle: u64,
-        amount_debited_total: u64,
+    // one-time: 100 authorized, 30 debited, active in [100, 200)
+    #[test_case(DebitAmountMode::Exact, 500, None, (1000, None), Ok(500))]
+    #[test_case(DebitAmountMode::UpTo, 50, None, (1000, Some(1000)), Ok(50))]
+    #[test_case(DebitAmountMode::UpTo, 500, None, (1000, Some(1000)), Ok(70))]
+    #[test_case(DebitAmountMode::UpTo, 500, Some(40), (1000, Some(1000)), Ok(40))]
+    #[test_case(DebitAmountMode::UpTo, 500, None, (20, Some(1000)), Ok(20))]
+    #[test_case(DebitAmountMode::UpTo, 500, None, (1000, Some(10)), Ok(10))]
+    #[test_case(DebitAmountMode::UpTo, 500, None, (1000, None), Ok(70))]
+    #[test_case(DebitAmountMode::UpTo, 500, None, (0, Some(1000)), Err(CustomProgramError::NothingToDebit))]
+    #[test_case(DebitAmountMode::UpTo, 0, None, (1000, Some(1000)), Err(CustomProgramError::NothingToDebit))]
+    fn compute_debit_amount_cases(
+        amount_mode: DebitAmountMode,
+        requested_amount: u64,
+        max_amount_per_debit: Option<u64>,
+        (token_account_amount, delegated_amount): (u64, Option<u64>),
+        expected_res: core::result::Result<u64, CustomProgramError>,
     ) {
-        compute_available_amount_for_recurring_debit(
-            current_cycle,
-            last_debited_cycle,
-            true,
-            recurring_amount_authorized,
-            amount_debited_last_cycle,
-            amount_debited_total,
+        let pre_authorization = PreAuthorization {
+            activation_unix_timestamp: 100,
+            variant: PreAuthorizationVariant::OneTime {
+                amount_authorized: 100,
+                expiry_unix_timestamp: 200,
+                amount_debited: 30,
+                max_amount_per_debit,
+                close_on_exhaustion: false,
+            },
+            ..Default::default()
+        };
+        assert_eq!(
+            expected_res,
+            compute_debit_amount(
+                amount_mode,
+                requested_amount,
+                &pre_authorization,
+                150,
+                token_account_amount,
+                delegated_amount,
+            )
         );
     }
 
-    #[test_case(100, 100, 1, 1)]
-    #[test_case(101, 100, 1, 2)]
-    #[test_case(102, 100, 1, 3)]
-    #[test_case(98, 0, 33, 3)]
-    #[test_case(100, 0, 33, 4)]
-    #[test_case(100, -100, 33, 7)]
-    #[test_case(i64::MAX, 0, 1, u64::try_from(i64::MAX).unwrap() + 1)]
-    fn compute_current_cycle_happy_path(
+    // recurring: 50 authorized per cycle (reset every cycle), 20 debited in cycle 2 ([110, 120)), nothing debited in cycle 3
+    #[test_case(500, None, 115, Ok(30))]
+    #[test_case(500, Some(30), 115, Ok(30))]
+    #[test_case(500, Some(40), 115, Err(CustomProgramError::NothingToDebit))]
+    #[test_case(500, Some(40), 125, Ok(50))]
+    #[test_case(10, Some(40), 125, Err(CustomProgramError::NothingToDebit))]
+    fn compute_debit_amount_up_to_min_amount_per_debit(
+        requested_amount: u64,
+        min_amount_per_debit: Option<u64>,
         current_unix_timestamp: i64,
-        activation_unix_timestamp: i64,
-        repeat_frequency_seconds: u64,
-        expected_res: u64,
+        expected_res: core::result::Result<u64, CustomProgramError>,
     ) {
+        let pre_authorization = RecurringFixture {
+            reset_every_cycle: true,
+            min_amount_per_debit,
+            ..Default::default()
+        }
+        .build();
         assert_eq!(
             expected_res,
-            compute_current_cycle(
+            compute_debit_amount(
+                DebitAmountMode::UpTo,
+                requested_amount,
+                &pre_authorization,
                 current_unix_timestamp,
-                activation_unix_timestamp,
-                repeat_frequency_seconds
+                1000,
+                None,
             )
         );
     }
 
-    #[test_case(0, 100, 1)]
-    #[test_case(-1, 100, 1)]
-    #[test_case(99, 100, 1)]
-    #[should_panic]
-    fn compute_current_cycle_errors(
-        current_unix_timestamp: i64,
-        activation_unix_timestamp: i64,
-        repeat_frequency_seconds: u64,
+    #[test_case([0; 32], "0000000000000000000000000000000000000000000000000000000000000000")]
+    #[test_case([0xab; 32], "abababababababababababababababababababababababababababababababab")]
+    #[test_case(
+        core::array::from_fn(|i| i as u8),
+        "000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f"
+    )]
+    fn encode_reference_memo_cases(reference: [u8; 32], expected_memo: &str) {
+        assert_eq!(expected_memo, encode_reference_memo(&reference));
+    }
+
+    #[test_case(0, Ok(1))]
+    #[test_case(7, Ok(8))]
+    #[test_case(u64::MAX, Err(CustomProgramError::ArithmeticOverflow))]
+    fn record_debit_increments_num_debits(
+        num_debits: u64,
+        expected_res: core::result::Result<u64, CustomProgramError>,
     ) {
-        compute_current_cycle(
-            current_unix_timestamp,
-            activation_unix_timestamp,
-            repeat_frequency_seconds,
+        let mut pre_authorization = PreAuthorization {
+            num_debits,
+            ..Default::default()
+        };
+        assert_eq!(
+            expected_res,
+            record_debit(&mut pre_authorization, 10, 150).map(|_| pre_authorization.num_debits)
+        );
+    }
+
+    #[test]
+    fn compute_debit_amount_up_to_inactive_pre_authorization() {
+        let pre_authorization = PreAuthorization {
+            activation_unix_timestamp: 100,
+            ..Default::default()
+        };
+        assert_eq!(
+            Err(CustomProgramError::PreAuthorizationNotActive),
+            compute_debit_amount(
+                DebitAmountMode::UpTo,
+                10,
+                &pre_authorization,
+                50,
+                1000,
+                None
+            )
         );
     }
 }
@@ -0,0 +1,298 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
+
+use crate::{
+    errors::CustomProgramError,
+    instructions::{compute_debit_event_variant, record_debit, validate_debit, DebitEvent},
+    state::{
+        pre_authorization::{PreAuthorization, PreAuthorizationScope},
+        smart_delegate::SmartDelegate,
+    },
+};
+
+// token_account, pre_authorization and destination_token_account
+pub const DEBIT_BATCH_ACCOUNTS_PER_ENTRY: usize = 3;
+
+#[derive(Accounts)]
+pub struct DebitBatch<'info> {
+    pub debit_authority: Signer<'info>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        seeds = [
+            b"smart-delegate"
+        ],
+        bump = smart_delegate.bump
+    )]
+    pub smart_delegate: Account<'info, SmartDelegate>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    // The remaining accounts are the entries of the batch, DEBIT_BATCH_ACCOUNTS_PER_ENTRY accounts per entry:
+    // token_account (writable), pre_authorization (writable) and destination_token_account (writable)
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DebitBatchMode {
+    // any failed entry fails the whole batch
+    Atomic,
+    // failed entries are skipped and reported in the `DebitBatchProcessed` event
+    // NOTE: A failed token transfer (e.g. a transfer hook) still fails the whole batch
+    SkipFailed,
+}
+
+// NOTE: The entries are debited without a reference (`DebitEvent.reference` is not set) and no `debit_receipt`
+//       is created for them, the `debit` instruction MUST be used to debit with a reference or a receipt
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct DebitBatchParams {
+    // the amount to debit for each entry of the batch (in the order of the remaining accounts)
+    pub amounts: Vec<u64>,
+    pub mode: DebitBatchMode,
+}
+
+pub fn handle_debit_batch<'info>(
+    ctx: Context<'_, '_, '_, 'info, DebitBatch<'info>>,
+    params: DebitBatchParams,
+) -> Result<()> {
+    // the entry indexes are reported as u16 in the DebitBatchProcessed event
+    let num_entries =
+        u16::try_from(params.amounts.len()).map_err(|_| CustomProgramError::InvalidDebitBatch)?;
+    require!(
+        num_entries > 0
+            && ctx.remaining_accounts.len()
+                == params.amounts.len() * DEBIT_BATCH_ACCOUNTS_PER_ENTRY,
+        CustomProgramError::InvalidDebitBatch
+    );
+    validate_debit_batch_accounts(
+        &ctx.remaining_accounts
+            .iter()
+            .map(|account_info| (account_info.key(), account_info.is_writable))
+            .collect::<Vec<_>>(),
+    )?;
+
+    let current_unix_timestamp = Clock::get()?.unix_timestamp;
+    let mut failed_entries = vec![];
+    for (index, (entry_accounts, &amount)) in (0..num_entries).zip(
+        ctx.remaining_accounts
+            .chunks_exact(DEBIT_BATCH_ACCOUNTS_PER_ENTRY)
+            .zip(params.amounts.iter()),
+    ) {
+        let res = debit_batch_entry(
+            ctx.accounts,
+            &entry_accounts[0],
+            &entry_accounts[1],
+            &entry_accounts[2],
+            amount,
+            current_unix_timestamp,
+        );
+
+        if let Err(err) = res {
+            if params.mode == DebitBatchMode::Atomic {
+                return Err(err);
+            }
+            failed_entries.push(DebitBatchFailedEntry {
+                index,
+                pre_authorization: entry_accounts[1].key(),
+                error_code: error_code(&err),
+            });
+        }
+    }
+
+    emit!(DebitBatchProcessed {
+        debit_authority: ctx.accounts.debit_authority.key(),
+        mint: ctx.accounts.mint.key(),
+        num_entries,
+        failed_entries,
+    });
+
+    Ok(())
+}
+
+/**
+  Checks the `(key, is_writable)` of the remaining accounts of a batch (`DEBIT_BATCH_ACCOUNTS_PER_ENTRY` per entry):
+  every account MUST be writable, the accounts of an entry MUST be distinct and a `pre_authorization`
+  MUST NOT be in more than one entry (the amounts of the same `pre_authorization` have to be summed instead).
+*/
+pub fn validate_debit_batch_accounts(
+    remaining_accounts: &[(Pubkey, bool)],
+) -> core::result::Result<(), CustomProgramError> {
+    let mut pre_authorization_keys = Vec::with_capacity(remaining_accounts.len());
+    for entry_accounts in remaining_accounts.chunks(DEBIT_BATCH_ACCOUNTS_PER_ENTRY) {
+        let [(token_account, _), (pre_authorization, _), (destination_token_account, _)] =
+            entry_accounts
+        else {
+            return Err(CustomProgramError::InvalidDebitBatch);
+        };
+        if entry_accounts.iter().any(|(_, is_writable)| !is_writable)
+            || token_account == pre_authorization
+            || token_account == destination_token_account
+            || pre_authorization == destination_token_account
+            || pre_authorization_keys.contains(pre_authorization)
+        {
+            return Err(CustomProgramError::InvalidDebitBatch);
+        }
+        pre_authorization_keys.push(*pre_authorization);
+    }
+
+    Ok(())
+}
+
+/**
+  Debits `amount` from the `token_account_info` to the `destination_token_account_info` with the same
+  rules as the `debit` instruction. Nothing is written if an error is returned before the transfer.
+  A one-time pre-authorization with `close_on_exhaustion` cannot be exhausted in a batch and an owner scoped
+  pre-authorization cannot be debited in a batch (the `debit` instruction MUST be used instead).
+*/
+fn debit_batch_entry<'info>(
+    accounts: &DebitBatch<'info>,
+    token_account_info: &AccountInfo<'info>,
+    pre_authorization_info: &AccountInfo<'info>,
+    destination_token_account_info: &AccountInfo<'info>,
+    amount: u64,
+    current_unix_timestamp: i64,
+) -> Result<()> {
+    let token_account = InterfaceAccount::<TokenAccount>::try_from(token_account_info)?;
+    let destination_token_account =
+        InterfaceAccount::<TokenAccount>::try_from(destination_token_account_info)?;
+    let mut pre_authorization = Account::<PreAuthorization>::try_from(pre_authorization_info)?;
+
+    // Same constraints as the accounts of the debit instruction
+    require!(
+        pre_authorization
+            .debit_authority
+            .eq(accounts.debit_authority.key),
+        CustomProgramError::DebitUnauthorized
+    );
+    require!(
+        pre_authorization.covers_token_account(
+            &token_account.key(),
+            &token_account.owner,
+            &token_account.mint,
+        ),
+        CustomProgramError::PreAuthorizationTokenAccountMismatch
+    );
+    // The pre_authorization_counter of the token_account is required to check the delegation mode
+    require!(
+        pre_authorization.scope == PreAuthorizationScope::TokenAccount,
+        CustomProgramError::PreAuthorizationCounterRequired
+    );
+    // Checked before the transfer so that such entries can be skipped
+    require!(
+        token_account.mint.eq(&accounts.mint.key())
+            && destination_token_account.mint.eq(&accounts.mint.key()),
+        CustomProgramError::MintMismatch
+    );
+    require!(
+        amount <= token_account.amount,
+        CustomProgramError::InsufficientTokenAccountBalance
+    );
+
+    validate_debit(
+        &pre_authorization,
+        &token_account,
+        &destination_token_account,
+        &accounts.smart_delegate.key(),
+        amount,
+        current_unix_timestamp,
+    )?;
+    record_debit(&mut pre_authorization, amount, current_unix_timestamp)?;
+    require!(
+        !pre_authorization.should_close_on_exhaustion(),
+        CustomProgramError::ReceiverRequiredForCloseOnExhaustion
+    );
+
+    token_interface::transfer_checked(
+        CpiContext::new_with_signer(
+            accounts.token_program.to_account_info(),
+            TransferChecked {
+                from: token_account_info.clone(),
+                to: destination_token_account_info.clone(),
+                authority: accounts.smart_delegate.to_account_info(),
+                mint: accounts.mint.to_account_info(),
+            },
+            &[&[b"smart-delegate".as_ref(), &[accounts.smart_delegate.bump]]],
+        ),
+        amount,
+        accounts.mint.decimals,
+    )?;
+
+    // Written right away, Anchor does not write the remaining accounts back
+    pre_authorization.exit(&crate::ID)?;
+
+    emit!(DebitEvent {
+        pre_authorization: pre_authorization.key(),
+        debit_authority: accounts.debit_authority.key(),
+        smart_delegate: accounts.smart_delegate.key(),
+        token_program: accounts.token_program.key(),
+        mint: token_account.mint,
+        source_token_account_owner: token_account.owner,
+        destination_token_account_owner: destination_token_account.owner,
+        source_token_account: token_account.key(),
+        destination_token_account: destination_token_account.key(),
+        requested_amount: amount,
+        reference: None,
+        debit_receipt: None,
+        debit_variant: compute_debit_event_variant(&pre_authorization, amount)?,
+    });
+
+    Ok(())
+}
+
+/**
+  Returns the error code reported for a failed batch entry (the custom error code for program errors).
+*/
+fn error_code(err: &Error) -> u64 {
+    match err {
+        Error::AnchorError(err) => err.error_code_number.into(),
+        Error::ProgramError(err) => err.program_error.clone().into(),
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct DebitBatchFailedEntry {
+    pub index: u16,
+    pub pre_authorization: Pubkey,
+    pub error_code: u64,
+}
+
+#[event]
+pub struct DebitBatchProcessed {
+    pub debit_authority: Pubkey,
+    pub mint: Pubkey,
+    pub num_entries: u16,
+    // only set in `DebitBatchMode::SkipFailed` (the batch fails on the first failed entry otherwise)
+    pub failed_entries: Vec<DebitBatchFailedEntry>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    fn key(n: u8) -> Pubkey {
+        Pubkey::new_from_array([n; 32])
+    }
+
+    // the same token_account and destination_token_account can be in many entries
+    #[test_case(&[(1, true), (2, true), (3, true), (1, true), (4, true), (3, true)], true)]
+    #[test_case(&[(1, true), (2, false), (3, true)], false)]
+    #[test_case(&[(1, true), (2, true), (3, false)], false)]
+    #[test_case(&[(1, true), (2, true), (1, true)], false)]
+    #[test_case(&[(1, true), (1, true), (3, true)], false)]
+    #[test_case(&[(1, true), (2, true), (2, true)], false)]
+    // the same pre_authorization in two entries
+    #[test_case(&[(1, true), (2, true), (3, true), (4, true), (2, true), (3, true)], false)]
+    // an incomplete entry
+    #[test_case(&[(1, true), (2, true), (3, true), (4, true)], false)]
+    fn validate_debit_batch_accounts(remaining_accounts: &[(u8, bool)], expected_valid: bool) {
+        let remaining_accounts = remaining_accounts
+            .iter()
+            .map(|&(n, is_writable)| (key(n), is_writable))
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            expected_valid,
+            super::validate_debit_batch_accounts(&remaining_accounts).is_ok()
+        );
+    }
+}
@@ -0,0 +1,96 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint;
+
+use crate::{
+    errors::CustomProgramError,
+    instructions::InitPreAuthorizationParams,
+    state::pre_authorization::{PreAuthorization, PreAuthorizationScope},
+};
+
+#[derive(Accounts)]
+#[instruction(params: InitPreAuthorizationParams)]
+pub struct InitOwnerPreAuthorization<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub owner: Signer<'info>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        init,
+        space = 8 + PreAuthorization::INIT_SPACE,
+        seeds = [
+            b"pre-authorization",
+            owner.key().as_ref(),
+            mint.key().as_ref(),
+            params.debit_authority.as_ref(),
+        ],
+        bump,
+        payer = payer,
+    )]
+    pub pre_authorization: Account<'info, PreAuthorization>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handle_init_owner_pre_authorization(
+    ctx: Context<InitOwnerPreAuthorization>,
+    params: InitPreAuthorizationParams,
+) -> Result<()> {
+    params.validate()?;
+    // The smart_delegate is approved by the owner on each token account instead
+    require!(
+        !params.bounded_delegation,
+        CustomProgramError::BoundedDelegationNotSupported
+    );
+
+    ctx.accounts.pre_authorization.token_account = Pubkey::default();
+    ctx.accounts.pre_authorization.variant = params.variant.to_pre_authorization_variant();
+    ctx.accounts.pre_authorization.paused = false;
+    ctx.accounts.pre_authorization.debit_authority = params.debit_authority;
+    ctx.accounts.pre_authorization.activation_unix_timestamp = params.activation_unix_timestamp;
+    ctx.accounts
+        .pre_authorization
+        .rotation_requires_owner_signature = params.rotation_requires_owner_signature;
+    ctx.accounts.pre_authorization.scope = PreAuthorizationScope::OwnerAndMint {
+        owner: ctx.accounts.owner.key(),
+        mint: ctx.accounts.mint.key(),
+    };
+    ctx.accounts.pre_authorization.destination_constraint = params.destination_constraint;
+    ctx.accounts.pre_authorization.num_debits = 0;
+    ctx.accounts.pre_authorization.bump = *ctx
+        .bumps
+        .get("pre_authorization")
+        .expect("pre_authorization PDA bump access failed");
+
+    // Unlike init_pre_authorization, no token account is approved here,
+    // the owner approves the smart_delegate on each token account they want to be debitable
+    emit!(OwnerPreAuthorizationCreated {
+        data: OwnerPreAuthorizationCreatedEventData {
+            debit_authority: params.debit_authority,
+            owner: ctx.accounts.owner.key(),
+            mint: ctx.accounts.mint.key(),
+            payer: ctx.accounts.payer.key(),
+            pre_authorization: ctx.accounts.pre_authorization.key(),
+            init_params: params,
+        }
+    });
+
+    Ok(())
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct OwnerPreAuthorizationCreatedEventData {
+    pub debit_authority: Pubkey,
+    pub owner: Pubkey,
+    pub mint: Pubkey,
+    pub payer: Pubkey,
+    pub pre_authorization: Pubkey,
+    pub init_params: InitPreAuthorizationParams,
+}
+
+#[event]
+pub struct OwnerPreAuthorizationCreated {
+    pub data: OwnerPreAuthorizationCreatedEventData,
+}
@@ -1,10 +1,22 @@
 use anchor_lang::prelude::*;
 use anchor_spl::token_interface::{self, Approve, TokenAccount, TokenInterface};
 
-use crate::state::smart_delegate::SmartDelegate;
+use crate::state::{
+    pre_authorization_counter::PreAuthorizationCounter, smart_delegate::SmartDelegate,
+};
 use crate::{
+    calendar::{CalendarSchedule, SECONDS_PER_DAY},
     errors::CustomProgramError,
-    state::pre_authorization::{PreAuthorization, PreAuthorizationVariant},
+    math::{
+        compute_bounded_delegated_amount, compute_cumulative_amount_authorized,
+        compute_cycle_bounds, compute_scheduled_cycle_bounds,
+        compute_scheduled_total_amount_authorized,
+    },
+    state::pre_authorization::{
+        DestinationConstraint, IntroSchedule, PreAuthorization, PreAuthorizationScope,
+        PreAuthorizationVariant, ScheduleStep, MAX_SCHEDULE_STEPS, ROLLING_WINDOW_BUCKETS,
+        ROLLING_WINDOW_RING_SIZE,
+    },
 };
 
 #[derive(Accounts)]
@@ -42,6 +54,18 @@ pub struct InitPreAuthorization<'info> {
     )]
     pub pre_authorization: Account<'info, PreAuthorization>,
 
+    #[account(
+        init_if_needed,
+        space = 8 + PreAuthorizationCounter::INIT_SPACE,
+        seeds = [
+            b"pre-authorization-counter",
+            token_account.key().as_ref(),
+        ],
+        bump,
+        payer = payer,
+    )]
+    pub pre_authorization_counter: Account<'info, PreAuthorizationCounter>,
+
     pub token_program: Interface<'info, TokenInterface>,
 
     pub system_program: Program<'info, System>,
@@ -52,6 +76,13 @@ pub struct InitPreAuthorizationParams {
     pub variant: InitPreAuthorizationVariant,
     pub debit_authority: Pubkey,
     pub activation_unix_timestamp: i64,
+    // true: the token account owner MUST co-sign `rotate_debit_authority`
+    pub rotation_requires_owner_signature: bool,
+    // None: debits can be sent to any destination token account
+    pub destination_constraint: Option<DestinationConstraint>,
+    // true: the smart_delegate is only approved for the outstanding amounts authorized of the token account's
+    // pre-authorizations instead of u64::MAX (MUST match the other open pre-authorizations of the token account)
+    pub bounded_delegation: bool,
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize)]
@@ -59,6 +90,11 @@ pub enum InitPreAuthorizationVariant {
     OneTime {
         amount_authorized: u64,
         expiry_unix_timestamp: i64,
+        // None: no limit per debit (other than the amount available)
+        max_amount_per_debit: Option<u64>,
+        // true: the debit that exhausts the amount authorized also closes the pre-authorization
+        // (the rent is refunded to the token account owner)
+        close_on_exhaustion: bool,
     },
     Recurring {
         repeat_frequency_seconds: u64,
@@ -69,34 +105,285 @@ pub enum InitPreAuthorizationVariant {
         // true: amount authorized is reset to "recurring_amount_authorized" each cycle
         // false: unused amounts from prev. cycles carries forward to new cycles (even when paused, cancel/close to stop)
         reset_every_cycle: bool,
+        // None: no limit per debit (other than the amount available)
+        max_amount_per_debit: Option<u64>,
+        // None: no minimum per debit
+        min_amount_per_debit: Option<u64>,
+        // None: unused amounts accrue indefinitely (only used when reset_every_cycle is false)
+        // Some(n): at most n cycles worth of "recurring_amount_authorized" is available at once
+        max_accrued_cycles: Option<u64>,
+        // None: cycles are "repeat_frequency_seconds" long
+        // Some(schedule): calendar cycles (e.g. monthly on the 1st), "repeat_frequency_seconds" MUST be 0
+        calendar_schedule: Option<CalendarSchedule>,
+        // None: debits can occur at any time during a cycle
+        // Some(n): debits can only occur in the n seconds starting "debit_window_offset_seconds" into each cycle
+        debit_window_seconds: Option<u64>,
+        // None: the debit window starts at the cycle start
+        // Some(n): the debit window starts n seconds into each cycle (only set with "debit_window_seconds")
+        debit_window_offset_seconds: Option<u64>,
+        // None: "recurring_amount_authorized" is authorized from the first cycle
+        // Some(intro): "intro.intro_amount_authorized" is authorized in each of the first "intro.num_intro_cycles" cycles
+        //   instead (e.g. 0 for a free trial), "recurring_amount_authorized" thereafter
+        intro_schedule: Option<IntroSchedule>,
+    },
+    Scheduled {
+        repeat_frequency_seconds: u64,
+        // the amount authorized per cycle (resets every cycle) is the "amount_authorized" of the last step
+        // with "cycle_start" <= current cycle, the first step MUST start at cycle 1 (at most MAX_SCHEDULE_STEPS steps)
+        steps: Vec<ScheduleStep>,
+        // None: infinitely recurring until cancelled / closed (the last step applies indefinitely)
+        // Some(n): approved for n cycles from activation
+        num_cycles: Option<u64>,
+    },
+    Installments {
+        // the installments add up to exactly "total_amount" (the last installment includes the remainder)
+        total_amount: u64,
+        installment_count: u64,
+        // installment n (1-indexed) is due (i.e. can be debited) from activation + (n - 1) * interval_seconds
+        interval_seconds: u64,
+        // an installment is overdue if it is not fully paid "grace_period_seconds" after its due date
+        grace_period_seconds: u64,
+    },
+    RollingWindow {
+        // at most "amount_authorized_per_window" can be debited in any trailing "window_seconds"
+        // (tracked in ROLLING_WINDOW_BUCKETS buckets, "window_seconds" MUST be a multiple of ROLLING_WINDOW_BUCKETS)
+        window_seconds: u64,
+        amount_authorized_per_window: u64,
     },
 }
 
-pub fn handle_init_pre_authorization(
-    ctx: Context<InitPreAuthorization>,
-    params: InitPreAuthorizationParams,
-) -> Result<()> {
-    ctx.accounts.pre_authorization.token_account = ctx.accounts.token_account.key();
-    ctx.accounts.pre_authorization.variant = match params.variant {
-        InitPreAuthorizationVariant::OneTime {
-            amount_authorized,
-            expiry_unix_timestamp,
-        } => PreAuthorizationVariant::OneTime {
-            amount_authorized,
-            expiry_unix_timestamp,
-            amount_debited: 0,
-        },
-        InitPreAuthorizationVariant::Recurring {
-            repeat_frequency_seconds,
-            recurring_amount_authorized,
-            num_cycles,
-            reset_every_cycle,
-        } => {
-            require!(
-                repeat_frequency_seconds <= i64::MAX as u64,
-                CustomProgramError::InvalidTimestamp
-            );
-            PreAuthorizationVariant::Recurring {
+impl InitPreAuthorizationParams {
+    /**
+      Rejects params that would create a pre-authorization that can never be debited as intended:
+      - one-time: `expiry_unix_timestamp` MUST be after `activation_unix_timestamp`
+      - recurring: `repeat_frequency_seconds` MUST be in `[1, i64::MAX]` (MUST be 0 with a `calendar_schedule`)
+      - recurring: the `calendar_schedule.day_of_month` (if set) MUST be in `[1, 31]`
+      - recurring: `num_cycles` (if set) MUST be greater than 0 and the last cycle MUST end before `i64::MAX`
+      - recurring: the total amount authorized across the `num_cycles` (if set) MUST fit in a `u64`
+      - recurring: the `intro_schedule.num_intro_cycles` (if set) MUST be greater than 0
+      - `max_amount_per_debit` (if set) MUST be greater than 0
+      - recurring: `min_amount_per_debit` (if set) MUST NOT be greater than `max_amount_per_debit` (if set)
+      - recurring: `max_accrued_cycles` (if set) MUST be greater than 0
+      - recurring: `debit_window_seconds` (if set) MUST be greater than 0 and `debit_window_offset_seconds`
+        (if set) MUST be less than the shortest cycle, `debit_window_offset_seconds` MUST NOT be set without
+        `debit_window_seconds` (`repeat_frequency_seconds` or `calendar_schedule.interval.min_days()`)
+      - scheduled: `repeat_frequency_seconds` MUST be in `[1, i64::MAX]`
+      - scheduled: there MUST be 1 to `MAX_SCHEDULE_STEPS` `steps`, the first step MUST start at cycle 1
+        and the `cycle_start` of the steps MUST be strictly increasing
+      - scheduled: `num_cycles` (if set) MUST be greater than 0, the last cycle MUST end before `i64::MAX`
+        and the last step MUST start before the end of the `num_cycles`
+      - scheduled: the total amount authorized across the `num_cycles` (if set) MUST fit in a `u64`
+      - installments: `interval_seconds` MUST be in `[1, i64::MAX]` and `grace_period_seconds` MUST be at most `i64::MAX`
+      - installments: `installment_count` MUST be greater than 0 and the last installment MUST be due before `i64::MAX`
+      - rolling window: `window_seconds` MUST be a non-zero multiple of `ROLLING_WINDOW_BUCKETS` and at most `i64::MAX`
+    */
+    pub fn validate(&self) -> core::result::Result<(), CustomProgramError> {
+        match self.variant {
+            InitPreAuthorizationVariant::OneTime {
+                expiry_unix_timestamp,
+                max_amount_per_debit,
+                ..
+            } => {
+                if expiry_unix_timestamp <= self.activation_unix_timestamp {
+                    return Err(CustomProgramError::ExpiryBeforeActivation);
+                }
+                if max_amount_per_debit == Some(0) {
+                    return Err(CustomProgramError::InvalidAmountPerDebitBounds);
+                }
+            }
+            InitPreAuthorizationVariant::Recurring {
+                repeat_frequency_seconds,
+                recurring_amount_authorized,
+                num_cycles,
+                max_amount_per_debit,
+                min_amount_per_debit,
+                max_accrued_cycles,
+                calendar_schedule,
+                debit_window_seconds,
+                debit_window_offset_seconds,
+                intro_schedule,
+                ..
+            } => {
+                if max_amount_per_debit == Some(0)
+                    || matches!(
+                        (min_amount_per_debit, max_amount_per_debit),
+                        (Some(min_amount_per_debit), Some(max_amount_per_debit))
+                            if min_amount_per_debit > max_amount_per_debit
+                    )
+                {
+                    return Err(CustomProgramError::InvalidAmountPerDebitBounds);
+                }
+                if max_accrued_cycles == Some(0) {
+                    return Err(CustomProgramError::InvalidMaxAccruedCycles);
+                }
+                if matches!(intro_schedule, Some(intro_schedule) if intro_schedule.num_intro_cycles == 0)
+                {
+                    return Err(CustomProgramError::InvalidIntroSchedule);
+                }
+                match calendar_schedule {
+                    Some(calendar_schedule) => {
+                        if repeat_frequency_seconds != 0 {
+                            return Err(CustomProgramError::InvalidRepeatFrequency);
+                        }
+                        calendar_schedule.validate()?;
+                    }
+                    None => {
+                        if repeat_frequency_seconds == 0 {
+                            return Err(CustomProgramError::InvalidRepeatFrequency);
+                        }
+                        if repeat_frequency_seconds > i64::MAX as u64 {
+                            return Err(CustomProgramError::InvalidTimestamp);
+                        }
+                    }
+                }
+                if debit_window_seconds.is_none() && debit_window_offset_seconds.is_some() {
+                    return Err(CustomProgramError::InvalidDebitWindow);
+                }
+                if let Some(debit_window_seconds) = debit_window_seconds {
+                    let min_cycle_seconds = match calendar_schedule {
+                        Some(calendar_schedule) => {
+                            (calendar_schedule.interval.min_days() * SECONDS_PER_DAY) as u64
+                        }
+                        None => repeat_frequency_seconds,
+                    };
+                    if debit_window_seconds == 0
+                        || debit_window_offset_seconds.unwrap_or(0) >= min_cycle_seconds
+                    {
+                        return Err(CustomProgramError::InvalidDebitWindow);
+                    }
+                }
+                if let Some(num_cycles) = num_cycles {
+                    if num_cycles == 0
+                        || compute_scheduled_cycle_bounds(
+                            num_cycles,
+                            self.activation_unix_timestamp,
+                            repeat_frequency_seconds,
+                            calendar_schedule.map(Into::into).as_ref(),
+                        )
+                        .is_none()
+                    {
+                        return Err(CustomProgramError::InvalidNumCycles);
+                    }
+                    if compute_cumulative_amount_authorized(
+                        num_cycles,
+                        recurring_amount_authorized,
+                        1,
+                        0,
+                        intro_schedule.map(Into::into).as_ref(),
+                    )
+                    .is_err()
+                    {
+                        return Err(CustomProgramError::TotalAmountAuthorizedOverflow);
+                    }
+                }
+            }
+            InitPreAuthorizationVariant::Scheduled {
+                repeat_frequency_seconds,
+                ref steps,
+                num_cycles,
+            } => {
+                if repeat_frequency_seconds == 0 {
+                    return Err(CustomProgramError::InvalidRepeatFrequency);
+                }
+                if repeat_frequency_seconds > i64::MAX as u64 {
+                    return Err(CustomProgramError::InvalidTimestamp);
+                }
+                if steps.is_empty()
+                    || steps.len() > MAX_SCHEDULE_STEPS
+                    || steps[0].cycle_start != 1
+                    || steps
+                        .windows(2)
+                        .any(|steps| steps[0].cycle_start >= steps[1].cycle_start)
+                {
+                    return Err(CustomProgramError::InvalidScheduleSteps);
+                }
+                if let Some(num_cycles) = num_cycles {
+                    if num_cycles == 0
+                        || compute_cycle_bounds(
+                            num_cycles,
+                            self.activation_unix_timestamp,
+                            repeat_frequency_seconds,
+                        )
+                        .is_none()
+                    {
+                        return Err(CustomProgramError::InvalidNumCycles);
+                    }
+                    if steps[steps.len() - 1].cycle_start > num_cycles {
+                        return Err(CustomProgramError::InvalidScheduleSteps);
+                    }
+                    if compute_scheduled_total_amount_authorized(num_cycles, steps).is_err() {
+                        return Err(CustomProgramError::TotalAmountAuthorizedOverflow);
+                    }
+                }
+            }
+            InitPreAuthorizationVariant::Installments {
+                installment_count,
+                interval_seconds,
+                grace_period_seconds,
+                ..
+            } => {
+                if interval_seconds == 0 {
+                    return Err(CustomProgramError::InvalidRepeatFrequency);
+                }
+                if interval_seconds > i64::MAX as u64 || grace_period_seconds > i64::MAX as u64 {
+                    return Err(CustomProgramError::InvalidTimestamp);
+                }
+                if installment_count == 0
+                    || compute_cycle_bounds(
+                        installment_count,
+                        self.activation_unix_timestamp,
+                        interval_seconds,
+                    )
+                    .is_none()
+                {
+                    return Err(CustomProgramError::InvalidNumCycles);
+                }
+            }
+            InitPreAuthorizationVariant::RollingWindow { window_seconds, .. } => {
+                if window_seconds == 0
+                    || window_seconds % ROLLING_WINDOW_BUCKETS as u64 != 0
+                    || window_seconds > i64::MAX as u64
+                {
+                    return Err(CustomProgramError::InvalidRollingWindow);
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl InitPreAuthorizationVariant {
+    /**
+      Returns the `pre_authorization.variant` for a newly created pre-authorization (nothing debited yet).
+    */
+    pub fn to_pre_authorization_variant(&self) -> PreAuthorizationVariant {
+        match *self {
+            InitPreAuthorizationVariant::OneTime {
+                amount_authorized,
+                expiry_unix_timestamp,
+                max_amount_per_debit,
+                close_on_exhaustion,
+            } => PreAuthorizationVariant::OneTime {
+                amount_authorized,
+                expiry_unix_timestamp,
+                amount_debited: 0,
+                max_amount_per_debit,
+                close_on_exhaustion,
+            },
+            InitPreAuthorizationVariant::Recurring {
+                repeat_frequency_seconds,
+                recurring_amount_authorized,
+                num_cycles,
+                reset_every_cycle,
+                max_amount_per_debit,
+                min_amount_per_debit,
+                max_accrued_cycles,
+                calendar_schedule,
+                debit_window_seconds,
+                debit_window_offset_seconds,
+                intro_schedule,
+            } => PreAuthorizationVariant::Recurring {
                 repeat_frequency_seconds,
                 recurring_amount_authorized,
                 amount_debited_last_cycle: 0,
@@ -104,18 +391,106 @@ pub fn handle_init_pre_authorization(
                 last_debited_cycle: 1, // first cycle
                 num_cycles,
                 reset_every_cycle,
-            }
+                amended_at_cycle: 1,
+                amount_authorized_before_amendment: 0,
+                max_amount_per_debit,
+                min_amount_per_debit,
+                max_accrued_cycles,
+                calendar_schedule,
+                debit_window_seconds,
+                debit_window_offset_seconds,
+                intro_schedule,
+            },
+            InitPreAuthorizationVariant::Scheduled {
+                repeat_frequency_seconds,
+                ref steps,
+                num_cycles,
+            } => PreAuthorizationVariant::Scheduled {
+                repeat_frequency_seconds,
+                steps: steps.clone(),
+                amount_debited_last_cycle: 0,
+                amount_debited_total: 0,
+                last_debited_cycle: 1, // first cycle
+                num_cycles,
+            },
+            InitPreAuthorizationVariant::Installments {
+                total_amount,
+                installment_count,
+                interval_seconds,
+                grace_period_seconds,
+            } => PreAuthorizationVariant::Installments {
+                total_amount,
+                installment_count,
+                interval_seconds,
+                grace_period_seconds,
+                amount_debited: 0,
+            },
+            InitPreAuthorizationVariant::RollingWindow {
+                window_seconds,
+                amount_authorized_per_window,
+            } => PreAuthorizationVariant::RollingWindow {
+                window_seconds,
+                amount_authorized_per_window,
+                debited_buckets: [0; ROLLING_WINDOW_RING_SIZE],
+                last_debited_bucket: 1, // first bucket
+                amount_debited_total: 0,
+            },
         }
-    };
+    }
+}
+
+pub fn handle_init_pre_authorization(
+    ctx: Context<InitPreAuthorization>,
+    params: InitPreAuthorizationParams,
+) -> Result<()> {
+    params.validate()?;
 
+    ctx.accounts.pre_authorization.token_account = ctx.accounts.token_account.key();
+    ctx.accounts.pre_authorization.variant = params.variant.to_pre_authorization_variant();
     ctx.accounts.pre_authorization.paused = false;
     ctx.accounts.pre_authorization.debit_authority = params.debit_authority;
     ctx.accounts.pre_authorization.activation_unix_timestamp = params.activation_unix_timestamp;
+    ctx.accounts
+        .pre_authorization
+        .rotation_requires_owner_signature = params.rotation_requires_owner_signature;
+    ctx.accounts.pre_authorization.scope = PreAuthorizationScope::TokenAccount;
+    ctx.accounts.pre_authorization.destination_constraint = params.destination_constraint;
+    ctx.accounts.pre_authorization.num_debits = 0;
     ctx.accounts.pre_authorization.bump = *ctx
         .bumps
         .get("pre_authorization")
         .expect("pre_authorization PDA bump access failed");
 
+    // The counter is created by the first pre_authorization for the token_account (re-setting these is a no-op)
+    ctx.accounts.pre_authorization_counter.token_account = ctx.accounts.token_account.key();
+    ctx.accounts.pre_authorization_counter.bump = *ctx
+        .bumps
+        .get("pre_authorization_counter")
+        .expect("pre_authorization_counter PDA bump access failed");
+    ctx.accounts
+        .pre_authorization_counter
+        .set_delegation_mode(params.bounded_delegation)?;
+    let delegated_amount = if params.bounded_delegation {
+        let outstanding_amount = ctx
+            .accounts
+            .pre_authorization
+            .outstanding_amount_authorized()
+            .ok_or(CustomProgramError::UnboundedPreAuthorization)?;
+        // Any amount still delegated when no pre_authorization is open is not carried over
+        let current_delegated_amount = if ctx.accounts.pre_authorization_counter.count == 0 {
+            0
+        } else {
+            smart_delegate_delegated_amount(
+                &ctx.accounts.token_account,
+                &ctx.accounts.smart_delegate.key(),
+            )
+        };
+        compute_bounded_delegated_amount(current_delegated_amount, 0, outstanding_amount)?
+    } else {
+        u64::MAX
+    };
+    ctx.accounts.pre_authorization_counter.increment()?;
+
     let event_data = PreAuthorizationCreatedEventData {
         debit_authority: params.debit_authority.key(),
         owner: ctx.accounts.owner.key(),
@@ -125,17 +500,13 @@ pub fn handle_init_pre_authorization(
         init_params: params,
     };
 
-    // This is idempotent
-    token_interface::approve(
-        CpiContext::new(
-            ctx.accounts.token_program.to_account_info(),
-            Approve {
-                to: ctx.accounts.token_account.to_account_info(),
-                delegate: ctx.accounts.smart_delegate.to_account_info(),
-                authority: ctx.accounts.owner.to_account_info(),
-            },
-        ),
-        u64::MAX,
+    // This is idempotent (for an unlimited delegation)
+    approve_smart_delegate(
+        ctx.accounts.token_program.to_account_info(),
+        ctx.accounts.token_account.to_account_info(),
+        ctx.accounts.smart_delegate.to_account_info(),
+        ctx.accounts.owner.to_account_info(),
+        delegated_amount,
     )?;
 
     match ctx.accounts.pre_authorization.variant {
@@ -145,11 +516,93 @@ pub fn handle_init_pre_authorization(
         PreAuthorizationVariant::Recurring { .. } => {
             emit!(RecurringPreAuthorizationCreated { data: event_data })
         }
+        PreAuthorizationVariant::Scheduled { .. } => {
+            emit!(ScheduledPreAuthorizationCreated { data: event_data })
+        }
+        PreAuthorizationVariant::Installments { .. } => {
+            emit!(InstallmentsPreAuthorizationCreated { data: event_data })
+        }
+        PreAuthorizationVariant::RollingWindow { .. } => {
+            emit!(RollingWindowPreAuthorizationCreated { data: event_data })
+        }
     }
 
     Ok(())
 }
 
+/**
+  Returns the amount the `token_account` currently delegates to the `smart_delegate`
+  (0 if another delegate is approved).
+*/
+pub fn smart_delegate_delegated_amount(
+    token_account: &TokenAccount,
+    smart_delegate: &Pubkey,
+) -> u64 {
+    if token_account.delegate == Some(*smart_delegate).into() {
+        token_account.delegated_amount
+    } else {
+        0
+    }
+}
+
+/**
+  Approves the `smart_delegate` for `amount` on the `token_account` (replacing any previous delegate).
+  The `owner` MUST be the `token_account.owner` and MUST sign.
+*/
+pub fn approve_smart_delegate<'info>(
+    token_program: AccountInfo<'info>,
+    token_account: AccountInfo<'info>,
+    smart_delegate: AccountInfo<'info>,
+    owner: AccountInfo<'info>,
+    amount: u64,
+) -> Result<()> {
+    token_interface::approve(
+        CpiContext::new(
+            token_program,
+            Approve {
+                to: token_account,
+                delegate: smart_delegate,
+                authority: owner,
+            },
+        ),
+        amount,
+    )
+}
+
+/**
+  Re-approves the `smart_delegate` in bounded delegation mode after the outstanding amount authorized
+  of a `pre_authorization` changed from `old_outstanding_amount` to `new_outstanding_amount`.
+  Nothing is approved if the `smart_delegate` is not the `token_account.delegate` (the owner approved another
+  delegate since): approving it would replace that delegate with the outstanding amount authorized of this
+  `pre_authorization` only (see `reapprove_smart_delegate` to approve it for all of them).
+  The `owner` MUST be the `token_account.owner` and MUST sign.
+*/
+pub fn reapprove_bounded_delegation<'info>(
+    old_outstanding_amount: u64,
+    new_outstanding_amount: u64,
+    token_program: &Interface<'info, TokenInterface>,
+    token_account: &InterfaceAccount<'info, TokenAccount>,
+    smart_delegate: &Account<'info, SmartDelegate>,
+    owner: AccountInfo<'info>,
+) -> Result<()> {
+    if token_account.delegate != Some(smart_delegate.key()).into() {
+        return Ok(());
+    }
+    let delegated_amount = compute_bounded_delegated_amount(
+        token_account.delegated_amount,
+        old_outstanding_amount,
+        new_outstanding_amount,
+    )?;
+
+    approve_smart_delegate(
+        token_program.to_account_info(),
+        token_account.to_account_info(),
+        smart_delegate.to_account_info(),
+        owner,
+        delegated_amount,
+    )
+}
+
 #[derive(AnchorSerialize, AnchorDeserialize)]
 pub struct PreAuthorizationCreatedEventData {
     pub debit_authority: Pubkey,
@@ -169,3 +622,423 @@ pub struct OneTimePreAuthorizationCreated {
 pub struct RecurringPreAuthorizationCreated {
     pub data: PreAuthorizationCreatedEventData,
 }
+
+#[event]
+pub struct ScheduledPreAuthorizationCreated {
+    pub data: PreAuthorizationCreatedEventData,
+}
+
+#[event]
+pub struct InstallmentsPreAuthorizationCreated {
+    pub data: PreAuthorizationCreatedEventData,
+}
+
+#[event]
+pub struct RollingWindowPreAuthorizationCreated {
+    pub data: PreAuthorizationCreatedEventData,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::calendar::CalendarInterval;
+    use test_case::test_case;
+
+    fn one_time(
+        activation_unix_timestamp: i64,
+        expiry_unix_timestamp: i64,
+    ) -> InitPreAuthorizationParams {
+        one_time_with_max_amount_per_debit(activation_unix_timestamp, expiry_unix_timestamp, None)
+    }
+
+    fn one_time_with_max_amount_per_debit(
+        activation_unix_timestamp: i64,
+        expiry_unix_timestamp: i64,
+        max_amount_per_debit: Option<u64>,
+    ) -> InitPreAuthorizationParams {
+        InitPreAuthorizationParams {
+            variant: InitPreAuthorizationVariant::OneTime {
+                amount_authorized: 100,
+                expiry_unix_timestamp,
+                max_amount_per_debit,
+                close_on_exhaustion: false,
+            },
+            debit_authority: Pubkey::default(),
+            activation_unix_timestamp,
+            rotation_requires_owner_signature: false,
+            destination_constraint: None,
+            bounded_delegation: false,
+        }
+    }
+
+    fn recurring(
+        activation_unix_timestamp: i64,
+        repeat_frequency_seconds: u64,
+        recurring_amount_authorized: u64,
+        num_cycles: Option<u64>,
+    ) -> InitPreAuthorizationParams {
+        recurring_with_caps(
+            activation_unix_timestamp,
+            repeat_frequency_seconds,
+            recurring_amount_authorized,
+            num_cycles,
+            (None, None, None),
+        )
+    }
+
+    fn recurring_calendar(
+        repeat_frequency_seconds: u64,
+        day_of_month: u8,
+        num_cycles: Option<u64>,
+    ) -> InitPreAuthorizationParams {
+        let mut params = recurring(0, repeat_frequency_seconds, 100, num_cycles);
+        if let InitPreAuthorizationVariant::Recurring {
+            calendar_schedule, ..
+        } = &mut params.variant
+        {
+            *calendar_schedule = Some(CalendarSchedule {
+                interval: CalendarInterval::Monthly,
+                day_of_month,
+            });
+        }
+        params
+    }
+
+    fn recurring_with_debit_window(
+        repeat_frequency_seconds: u64,
+        calendar_interval: Option<CalendarInterval>,
+        debit_window_seconds: u64,
+        debit_window_offset_seconds: Option<u64>,
+    ) -> InitPreAuthorizationParams {
+        let mut params = recurring(0, repeat_frequency_seconds, 100, None);
+        if let InitPreAuthorizationVariant::Recurring {
+            calendar_schedule,
+            debit_window_seconds: window_seconds,
+            debit_window_offset_seconds: window_offset_seconds,
+            ..
+        } = &mut params.variant
+        {
+            *calendar_schedule = calendar_interval.map(|interval| CalendarSchedule {
+                interval,
+                day_of_month: 1,
+            });
+            *window_seconds = Some(debit_window_seconds);
+            *window_offset_seconds = debit_window_offset_seconds;
+        }
+        params
+    }
+
+    fn recurring_with_debit_offset_only(
+        repeat_frequency_seconds: u64,
+        debit_window_offset_seconds: u64,
+    ) -> InitPreAuthorizationParams {
+        let mut params = recurring(0, repeat_frequency_seconds, 100, None);
+        if let InitPreAuthorizationVariant::Recurring {
+            debit_window_offset_seconds: window_offset_seconds,
+            ..
+        } = &mut params.variant
+        {
+            *window_offset_seconds = Some(debit_window_offset_seconds);
+        }
+        params
+    }
+
+    fn recurring_with_intro(
+        recurring_amount_authorized: u64,
+        num_cycles: Option<u64>,
+        num_intro_cycles: u64,
+        intro_amount_authorized: u64,
+    ) -> InitPreAuthorizationParams {
+        let mut params = recurring(0, 30, recurring_amount_authorized, num_cycles);
+        if let InitPreAuthorizationVariant::Recurring { intro_schedule, .. } = &mut params.variant {
+            *intro_schedule = Some(IntroSchedule {
+                num_intro_cycles,
+                intro_amount_authorized,
+            });
+        }
+        params
+    }
+
+    fn scheduled(
+        repeat_frequency_seconds: u64,
+        steps: &[(u64, u64)],
+        num_cycles: Option<u64>,
+    ) -> InitPreAuthorizationParams {
+        InitPreAuthorizationParams {
+            variant: InitPreAuthorizationVariant::Scheduled {
+                repeat_frequency_seconds,
+                steps: steps
+                    .iter()
+                    .map(|&(cycle_start, amount_authorized)| ScheduleStep {
+                        cycle_start,
+                        amount_authorized,
+                    })
+                    .collect(),
+                num_cycles,
+            },
+            debit_authority: Pubkey::default(),
+            activation_unix_timestamp: 100,
+            rotation_requires_owner_signature: false,
+            destination_constraint: None,
+            bounded_delegation: false,
+        }
+    }
+
+    fn installments(
+        installment_count: u64,
+        interval_seconds: u64,
+        grace_period_seconds: u64,
+    ) -> InitPreAuthorizationParams {
+        InitPreAuthorizationParams {
+            variant: InitPreAuthorizationVariant::Installments {
+                total_amount: 100,
+                installment_count,
+                interval_seconds,
+                grace_period_seconds,
+            },
+            debit_authority: Pubkey::default(),
+            activation_unix_timestamp: 100,
+            rotation_requires_owner_signature: false,
+            destination_constraint: None,
+            bounded_delegation: false,
+        }
+    }
+
+    fn rolling_window(window_seconds: u64) -> InitPreAuthorizationParams {
+        InitPreAuthorizationParams {
+            variant: InitPreAuthorizationVariant::RollingWindow {
+                window_seconds,
+                amount_authorized_per_window: 100,
+            },
+            debit_authority: Pubkey::default(),
+            activation_unix_timestamp: 100,
+            rotation_requires_owner_signature: false,
+            destination_constraint: None,
+            bounded_delegation: false,
+        }
+    }
+
+    // caps: (max_amount_per_debit, min_amount_per_debit, max_accrued_cycles)
+    fn recurring_with_caps(
+        activation_unix_timestamp: i64,
+        repeat_frequency_seconds: u64,
+        recurring_amount_authorized: u64,
+        num_cycles: Option<u64>,
+        caps: (Option<u64>, Option<u64>, Option<u64>),
+    ) -> InitPreAuthorizationParams {
+        let (max_amount_per_debit, min_amount_per_debit, max_accrued_cycles) = caps;
+        InitPreAuthorizationParams {
+            variant: InitPreAuthorizationVariant::Recurring {
+                repeat_frequency_seconds,
+                recurring_amount_authorized,
+                num_cycles,
+                reset_every_cycle: false,
+                max_amount_per_debit,
+                min_amount_per_debit,
+                max_accrued_cycles,
+                calendar_schedule: None,
+                debit_window_seconds: None,
+                debit_window_offset_seconds: None,
+                intro_schedule: None,
+            },
+            debit_authority: Pubkey::default(),
+            activation_unix_timestamp,
+            rotation_requires_owner_signature: false,
+            destination_constraint: None,
+            bounded_delegation: false,
+        }
+    }
+
+    #[test_case(one_time(100, 101))]
+    #[test_case(one_time(-1, 0))]
+    #[test_case(one_time(i64::MIN, i64::MAX))]
+    #[test_case(one_time_with_max_amount_per_debit(100, 101, Some(1)))]
+    #[test_case(recurring(100, 1, 100, None))]
+    #[test_case(recurring(100, i64::MAX as u64, u64::MAX, None))]
+    #[test_case(recurring(0, i64::MAX as u64, 100, Some(1)))]
+    #[test_case(recurring(-100, 30, 100, Some(1)))]
+    #[test_case(recurring(100, 30, u64::MAX, Some(1)))]
+    #[test_case(recurring(100, 30, u64::MAX / 3, Some(3)))]
+    #[test_case(recurring(100, 30, 0, Some((i64::MAX as u64 - 100) / 30)))]
+    #[test_case(recurring_with_caps(100, 30, 100, None, (Some(1), Some(1), Some(1))))]
+    #[test_case(recurring_with_caps(100, 30, 100, None, (None, Some(u64::MAX), Some(u64::MAX))))]
+    #[test_case(recurring_with_caps(100, 30, 100, None, (Some(50), None, None)))]
+    #[test_case(recurring_calendar(0, 1, None))]
+    #[test_case(recurring_calendar(0, 31, Some(1200)))]
+    #[test_case(recurring_with_debit_window(30, None, 1, None))]
+    #[test_case(recurring_with_debit_window(30, None, 1, Some(0)))]
+    #[test_case(recurring_with_debit_window(30, None, u64::MAX, Some(29)))]
+    #[test_case(recurring_with_debit_window(0, Some(CalendarInterval::Monthly), 86_400, Some(27 * 86_400)))]
+    #[test_case(recurring_with_debit_window(0, Some(CalendarInterval::Yearly), 86_400, Some(364 * 86_400)))]
+    #[test_case(recurring_with_intro(100, None, 1, 0))]
+    #[test_case(recurring_with_intro(100, Some(12), 3, 50))]
+    #[test_case(recurring_with_intro(100, Some(2), 5, 50))]
+    #[test_case(recurring_with_intro(u64::MAX, Some(2), 1, 0))]
+    #[test_case(scheduled(30, &[(1, 100)], None))]
+    #[test_case(scheduled(30, &[(1, 100), (4, 200), (13, 300)], Some(13)))]
+    #[test_case(scheduled(30, &[(1, 0), (2, u64::MAX)], Some(2)))]
+    #[test_case(scheduled(30, &[(1, 1), (2, 2), (3, 3), (4, 4), (5, 5), (6, 6), (7, 7), (8, 8), (9, 9), (10, 10), (11, 11), (12, 12)], None))]
+    #[test_case(installments(1, 1, 0))]
+    #[test_case(installments(4, 14 * 86_400, 3 * 86_400))]
+    #[test_case(installments(1, i64::MAX as u64 - 100, i64::MAX as u64))]
+    #[test_case(rolling_window(24))]
+    #[test_case(rolling_window(86_400))]
+    #[test_case(rolling_window(i64::MAX as u64 / 24 * 24))]
+    fn validate_happy_path(params: InitPreAuthorizationParams) {
+        assert_eq!(Ok(()), params.validate());
+    }
+
+    #[test_case(one_time(100, 99), CustomProgramError::ExpiryBeforeActivation)]
+    #[test_case(one_time(100, 100), CustomProgramError::ExpiryBeforeActivation)]
+    #[test_case(one_time(-1, -1), CustomProgramError::ExpiryBeforeActivation)]
+    #[test_case(
+        one_time(i64::MAX, i64::MIN),
+        CustomProgramError::ExpiryBeforeActivation
+    )]
+    #[test_case(
+        one_time_with_max_amount_per_debit(100, 101, Some(0)),
+        CustomProgramError::InvalidAmountPerDebitBounds
+    )]
+    #[test_case(
+        recurring(100, 0, 100, None),
+        CustomProgramError::InvalidRepeatFrequency
+    )]
+    #[test_case(
+        recurring(100, 0, 100, Some(0)),
+        CustomProgramError::InvalidRepeatFrequency
+    )]
+    #[test_case(recurring(100, i64::MAX as u64 + 1, 100, None), CustomProgramError::InvalidTimestamp)]
+    #[test_case(
+        recurring(100, u64::MAX, 100, Some(1)),
+        CustomProgramError::InvalidTimestamp
+    )]
+    #[test_case(recurring(100, 30, 100, Some(0)), CustomProgramError::InvalidNumCycles)]
+    #[test_case(recurring(1, i64::MAX as u64, 100, Some(1)), CustomProgramError::InvalidNumCycles)]
+    #[test_case(
+        recurring(100, 30, 100, Some(u64::MAX)),
+        CustomProgramError::InvalidNumCycles
+    )]
+    #[test_case(recurring(i64::MAX - 29, 30, 100, Some(1)), CustomProgramError::InvalidNumCycles)]
+    #[test_case(
+        recurring(100, 30, u64::MAX, Some(2)),
+        CustomProgramError::TotalAmountAuthorizedOverflow
+    )]
+    #[test_case(recurring(100, 30, u64::MAX / 3 + 1, Some(3)), CustomProgramError::TotalAmountAuthorizedOverflow)]
+    #[test_case(
+        recurring_with_caps(100, 30, 100, None, (Some(0), None, None)),
+        CustomProgramError::InvalidAmountPerDebitBounds
+    )]
+    #[test_case(
+        recurring_with_caps(100, 30, 100, None, (Some(10), Some(11), None)),
+        CustomProgramError::InvalidAmountPerDebitBounds
+    )]
+    #[test_case(
+        recurring_with_caps(100, 30, 100, None, (None, None, Some(0))),
+        CustomProgramError::InvalidMaxAccruedCycles
+    )]
+    #[test_case(
+        recurring_calendar(1, 1, None),
+        CustomProgramError::InvalidRepeatFrequency
+    )]
+    #[test_case(recurring_calendar(0, 0, None), CustomProgramError::InvalidDayOfMonth)]
+    #[test_case(recurring_calendar(0, 32, None), CustomProgramError::InvalidDayOfMonth)]
+    #[test_case(
+        recurring_calendar(0, 1, Some(0)),
+        CustomProgramError::InvalidNumCycles
+    )]
+    #[test_case(
+        recurring_calendar(0, 1, Some(u64::MAX)),
+        CustomProgramError::InvalidNumCycles
+    )]
+    #[test_case(
+        recurring_with_debit_window(30, None, 0, None),
+        CustomProgramError::InvalidDebitWindow
+    )]
+    #[test_case(
+        recurring_with_debit_window(30, None, 1, Some(30)),
+        CustomProgramError::InvalidDebitWindow
+    )]
+    #[test_case(
+        recurring_with_debit_offset_only(30, 5),
+        CustomProgramError::InvalidDebitWindow
+    )]
+    #[test_case(
+        recurring_with_debit_window(0, Some(CalendarInterval::Monthly), 1, Some(28 * 86_400)),
+        CustomProgramError::InvalidDebitWindow
+    )]
+    #[test_case(
+        recurring_with_debit_window(0, Some(CalendarInterval::Quarterly), 1, Some(89 * 86_400)),
+        CustomProgramError::InvalidDebitWindow
+    )]
+    #[test_case(
+        recurring_with_intro(100, None, 0, 50),
+        CustomProgramError::InvalidIntroSchedule
+    )]
+    #[test_case(
+        recurring_with_intro(u64::MAX, Some(3), 1, 0),
+        CustomProgramError::TotalAmountAuthorizedOverflow
+    )]
+    #[test_case(
+        recurring_with_intro(0, Some(2), 2, u64::MAX),
+        CustomProgramError::TotalAmountAuthorizedOverflow
+    )]
+    #[test_case(
+        scheduled(0, &[(1, 100)], None),
+        CustomProgramError::InvalidRepeatFrequency
+    )]
+    #[test_case(
+        scheduled(i64::MAX as u64 + 1, &[(1, 100)], None),
+        CustomProgramError::InvalidTimestamp
+    )]
+    #[test_case(scheduled(30, &[], None), CustomProgramError::InvalidScheduleSteps)]
+    #[test_case(
+        scheduled(30, &[(2, 100)], None),
+        CustomProgramError::InvalidScheduleSteps
+    )]
+    #[test_case(
+        scheduled(30, &[(1, 100), (3, 200), (3, 300)], None),
+        CustomProgramError::InvalidScheduleSteps
+    )]
+    #[test_case(
+        scheduled(30, &[(1, 100), (3, 200), (2, 300)], None),
+        CustomProgramError::InvalidScheduleSteps
+    )]
+    #[test_case(scheduled(30, &[(1, 1), (2, 2), (3, 3), (4, 4), (5, 5), (6, 6), (7, 7), (8, 8), (9, 9), (10, 10), (11, 11), (12, 12), (13, 13)], None), CustomProgramError::InvalidScheduleSteps)]
+    #[test_case(
+        scheduled(30, &[(1, 100), (4, 200)], Some(3)),
+        CustomProgramError::InvalidScheduleSteps
+    )]
+    #[test_case(
+        scheduled(30, &[(1, 100)], Some(0)),
+        CustomProgramError::InvalidNumCycles
+    )]
+    #[test_case(
+        scheduled(30, &[(1, 100)], Some(u64::MAX)),
+        CustomProgramError::InvalidNumCycles
+    )]
+    #[test_case(
+        scheduled(30, &[(1, 1), (2, u64::MAX)], Some(2)),
+        CustomProgramError::TotalAmountAuthorizedOverflow
+    )]
+    #[test_case(installments(4, 0, 0), CustomProgramError::InvalidRepeatFrequency)]
+    #[test_case(
+        installments(4, i64::MAX as u64 + 1, 0),
+        CustomProgramError::InvalidTimestamp
+    )]
+    #[test_case(
+        installments(4, 30, i64::MAX as u64 + 1),
+        CustomProgramError::InvalidTimestamp
+    )]
+    #[test_case(installments(0, 30, 0), CustomProgramError::InvalidNumCycles)]
+    #[test_case(installments(u64::MAX, 30, 0), CustomProgramError::InvalidNumCycles)]
+    #[test_case(rolling_window(0), CustomProgramError::InvalidRollingWindow)]
+    #[test_case(rolling_window(23), CustomProgramError::InvalidRollingWindow)]
+    #[test_case(rolling_window(86_401), CustomProgramError::InvalidRollingWindow)]
+    #[test_case(
+        rolling_window(i64::MAX as u64 / 24 * 24 + 24),
+        CustomProgramError::InvalidRollingWindow
+    )]
+    fn validate_errors(params: InitPreAuthorizationParams, expected_err: CustomProgramError) {
+        assert_eq!(Err(expected_err), params.validate());
+    }
+}
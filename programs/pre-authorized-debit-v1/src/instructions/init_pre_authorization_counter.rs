@@ -0,0 +1,73 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::TokenAccount;
+
+use crate::{
+    errors::CustomProgramError, state::pre_authorization_counter::PreAuthorizationCounter,
+};
+
+#[derive(Accounts)]
+pub struct InitPreAuthorizationCounter<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub owner: Signer<'info>,
+
+    #[account(
+        has_one = owner @ CustomProgramError::InitPreAuthorizationCounterUnauthorized
+    )]
+    pub token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        init_if_needed,
+        space = 8 + PreAuthorizationCounter::INIT_SPACE,
+        seeds = [
+            b"pre-authorization-counter",
+            token_account.key().as_ref(),
+        ],
+        bump,
+        payer = payer,
+    )]
+    pub pre_authorization_counter: Account<'info, PreAuthorizationCounter>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct InitPreAuthorizationCounterParams {
+    // the number of open pre_authorization accounts scoped to the token account
+    pub count: u64,
+}
+
+pub fn handle_init_pre_authorization_counter(
+    ctx: Context<InitPreAuthorizationCounter>,
+    params: InitPreAuthorizationCounterParams,
+) -> Result<()> {
+    ctx.accounts.pre_authorization_counter.token_account = ctx.accounts.token_account.key();
+    ctx.accounts.pre_authorization_counter.bump = *ctx
+        .bumps
+        .get("pre_authorization_counter")
+        .expect("pre_authorization_counter PDA bump access failed");
+    let old_count = ctx.accounts.pre_authorization_counter.count;
+    ctx.accounts
+        .pre_authorization_counter
+        .set_verified_count(params.count)?;
+
+    emit!(PreAuthorizationCounterInitialized {
+        owner: ctx.accounts.owner.key(),
+        token_account: ctx.accounts.token_account.key(),
+        pre_authorization_counter: ctx.accounts.pre_authorization_counter.key(),
+        old_count,
+        count: params.count,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct PreAuthorizationCounterInitialized {
+    pub owner: Pubkey,
+    pub token_account: Pubkey,
+    pub pre_authorization_counter: Pubkey,
+    pub old_count: u64,
+    pub count: u64,
+}
@@ -0,0 +1,81 @@
+use anchor_lang::{
+    prelude::*,
+    system_program::{self, Transfer},
+    Discriminator,
+};
+
+use crate::{
+    errors::CustomProgramError,
+    state::{
+        pre_authorization::PreAuthorization,
+        pre_authorization_v1::{PreAuthorizationV1, PRE_AUTHORIZATION_V1_SPACE},
+    },
+};
+
+#[derive(Accounts)]
+pub struct MigratePreAuthorization<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// CHECK: A v1.0.0 pre_authorization cannot be deserialized as a PreAuthorization,
+    /// the discriminator and the layout are validated in the handler
+    #[account(
+        mut,
+        owner = crate::ID,
+    )]
+    pub pre_authorization: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handle_migrate_pre_authorization(ctx: Context<MigratePreAuthorization>) -> Result<()> {
+    let pre_authorization_info = ctx.accounts.pre_authorization.to_account_info();
+
+    // Only the v1.0.0 program created PreAuthorization accounts of this size
+    let pre_authorization_v1 = {
+        let data = pre_authorization_info.try_borrow_data()?;
+        require!(
+            data.len() == PRE_AUTHORIZATION_V1_SPACE
+                && data[..8] == PreAuthorization::DISCRIMINATOR,
+            CustomProgramError::PreAuthorizationNotV1
+        );
+        PreAuthorizationV1::deserialize(&mut &data[8..])?
+    };
+    let pre_authorization = PreAuthorization::from(pre_authorization_v1);
+
+    let space = 8 + PreAuthorization::INIT_SPACE;
+    let lamports_required = Rent::get()?
+        .minimum_balance(space)
+        .saturating_sub(pre_authorization_info.lamports());
+    if lamports_required > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.payer.to_account_info(),
+                    to: pre_authorization_info.clone(),
+                },
+            ),
+            lamports_required,
+        )?;
+    }
+    pre_authorization_info.realloc(space, true)?;
+    pre_authorization.try_serialize(&mut &mut pre_authorization_info.try_borrow_mut_data()?[..])?;
+
+    emit!(PreAuthorizationMigrated {
+        payer: ctx.accounts.payer.key(),
+        token_account: pre_authorization.token_account,
+        debit_authority: pre_authorization.debit_authority,
+        pre_authorization: pre_authorization_info.key(),
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct PreAuthorizationMigrated {
+    pub payer: Pubkey,
+    pub token_account: Pubkey,
+    pub debit_authority: Pubkey,
+    pub pre_authorization: Pubkey,
+}
@@ -1,11 +1,37 @@
+pub mod accept_amendment;
+pub mod close_debit_receipt;
+pub mod close_expired_pre_authorization;
+pub mod close_owner_pre_authorization;
 pub mod close_pre_authorization;
 pub mod debit;
+pub mod debit_batch;
+pub mod init_owner_pre_authorization;
 pub mod init_pre_authorization;
+pub mod init_pre_authorization_counter;
 pub mod init_smart_delegate;
+pub mod migrate_pre_authorization;
+pub mod propose_amendment;
+pub mod reapprove_smart_delegate;
+pub mod reject_amendment;
+pub mod rotate_debit_authority;
 pub mod update_pause_pre_authorization;
+pub mod update_pre_authorization;
 
+pub use accept_amendment::*;
+pub use close_debit_receipt::*;
+pub use close_expired_pre_authorization::*;
+pub use close_owner_pre_authorization::*;
 pub use close_pre_authorization::*;
 pub use debit::*;
+pub use debit_batch::*;
+pub use init_owner_pre_authorization::*;
 pub use init_pre_authorization::*;
+pub use init_pre_authorization_counter::*;
 pub use init_smart_delegate::*;
+pub use migrate_pre_authorization::*;
+pub use propose_amendment::*;
+pub use reapprove_smart_delegate::*;
+pub use reject_amendment::*;
+pub use rotate_debit_authority::*;
 pub use update_pause_pre_authorization::*;
+pub use update_pre_authorization::*;
@@ -0,0 +1,104 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    errors::CustomProgramError,
+    instructions::UpdatePreAuthorizationVariant,
+    state::{
+        pending_amendment::PendingAmendment,
+        pre_authorization::{PreAuthorization, PreAuthorizationVariant},
+    },
+};
+
+#[derive(Accounts)]
+pub struct ProposeAmendment<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub debit_authority: Signer<'info>,
+
+    #[account(
+        has_one = debit_authority @ CustomProgramError::ProposeAmendmentUnauthorized,
+    )]
+    pub pre_authorization: Account<'info, PreAuthorization>,
+
+    #[account(
+        init,
+        space = 8 + PendingAmendment::INIT_SPACE,
+        seeds = [
+            b"pending-amendment",
+            pre_authorization.key().as_ref(),
+        ],
+        bump,
+        payer = payer,
+    )]
+    pub pending_amendment: Account<'info, PendingAmendment>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct ProposeAmendmentParams {
+    pub new_values: UpdatePreAuthorizationVariant,
+    pub acceptance_deadline_unix_timestamp: i64,
+}
+
+pub fn handle_propose_amendment(
+    ctx: Context<ProposeAmendment>,
+    params: ProposeAmendmentParams,
+) -> Result<()> {
+    require!(
+        params.acceptance_deadline_unix_timestamp > Clock::get()?.unix_timestamp,
+        CustomProgramError::InvalidAcceptanceDeadline
+    );
+
+    // the remaining rules are checked against the pre-authorization state when the amendment is accepted
+    let variant_matches = matches!(
+        (&ctx.accounts.pre_authorization.variant, &params.new_values),
+        (
+            PreAuthorizationVariant::OneTime { .. },
+            UpdatePreAuthorizationVariant::OneTime { .. }
+        ) | (
+            PreAuthorizationVariant::Recurring { .. },
+            UpdatePreAuthorizationVariant::Recurring { .. }
+        )
+    );
+    require!(
+        variant_matches,
+        CustomProgramError::PreAuthorizationVariantMismatch
+    );
+
+    ctx.accounts.pending_amendment.pre_authorization = ctx.accounts.pre_authorization.key();
+    ctx.accounts.pending_amendment.debit_authority = ctx.accounts.debit_authority.key();
+    ctx.accounts.pending_amendment.payer = ctx.accounts.payer.key();
+    ctx.accounts.pending_amendment.new_values = params.new_values.clone();
+    ctx.accounts
+        .pending_amendment
+        .acceptance_deadline_unix_timestamp = params.acceptance_deadline_unix_timestamp;
+    ctx.accounts.pending_amendment.bump = *ctx
+        .bumps
+        .get("pending_amendment")
+        .expect("pending_amendment PDA bump access failed");
+
+    emit!(AmendmentProposed {
+        payer: ctx.accounts.payer.key(),
+        debit_authority: ctx.accounts.debit_authority.key(),
+        token_account: ctx.accounts.pre_authorization.token_account,
+        pre_authorization: ctx.accounts.pre_authorization.key(),
+        pending_amendment: ctx.accounts.pending_amendment.key(),
+        new_values: params.new_values,
+        acceptance_deadline_unix_timestamp: params.acceptance_deadline_unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct AmendmentProposed {
+    pub payer: Pubkey,
+    pub debit_authority: Pubkey,
+    pub token_account: Pubkey,
+    pub pre_authorization: Pubkey,
+    pub pending_amendment: Pubkey,
+    pub new_values: UpdatePreAuthorizationVariant,
+    pub acceptance_deadline_unix_timestamp: i64,
+}
@@ -0,0 +1,195 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{TokenAccount, TokenInterface};
+
+use crate::{
+    errors::CustomProgramError,
+    instructions::approve_smart_delegate,
+    state::{
+        pre_authorization::PreAuthorization, pre_authorization_counter::PreAuthorizationCounter,
+        smart_delegate::SmartDelegate,
+    },
+};
+
+#[derive(Accounts)]
+pub struct ReapproveSmartDelegate<'info> {
+    pub owner: Signer<'info>,
+
+    #[account(
+        mut,
+        has_one = owner @ CustomProgramError::ReapproveSmartDelegateUnauthorized
+    )]
+    pub token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        seeds = [
+            b"smart-delegate",
+        ],
+        bump = smart_delegate.bump,
+    )]
+    pub smart_delegate: Account<'info, SmartDelegate>,
+
+    #[account(
+        seeds = [
+            b"pre-authorization-counter",
+            token_account.key().as_ref(),
+        ],
+        bump = pre_authorization_counter.bump,
+    )]
+    pub pre_authorization_counter: Account<'info, PreAuthorizationCounter>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    // In bounded delegation mode, the remaining accounts MUST be all the open pre_authorization accounts
+    // of the token_account (the smart_delegate is approved for the sum of their outstanding amounts authorized)
+}
+
+pub fn handle_reapprove_smart_delegate<'info>(
+    ctx: Context<'_, '_, '_, 'info, ReapproveSmartDelegate<'info>>,
+) -> Result<()> {
+    let delegated_amount = if ctx.accounts.pre_authorization_counter.bounded_delegation {
+        let pre_authorizations = load_token_account_pre_authorizations(
+            ctx.remaining_accounts,
+            &ctx.accounts.token_account.key(),
+            None,
+        )?;
+
+        compute_total_outstanding_amount_authorized(
+            &pre_authorizations,
+            ctx.accounts.pre_authorization_counter.count,
+        )?
+    } else {
+        u64::MAX
+    };
+
+    approve_smart_delegate(
+        ctx.accounts.token_program.to_account_info(),
+        ctx.accounts.token_account.to_account_info(),
+        ctx.accounts.smart_delegate.to_account_info(),
+        ctx.accounts.owner.to_account_info(),
+        delegated_amount,
+    )?;
+
+    emit!(SmartDelegateReapproved {
+        owner: ctx.accounts.owner.key(),
+        token_account: ctx.accounts.token_account.key(),
+        smart_delegate: ctx.accounts.smart_delegate.key(),
+        delegated_amount,
+    });
+
+    Ok(())
+}
+
+/**
+  Returns the `pre_authorization` accounts passed as remaining accounts.
+  They MUST be distinct `pre_authorization` accounts of the `token_account`,
+  the `excluded_pre_authorization` (if set, e.g. a `pre_authorization` being closed) MUST NOT be one of them.
+*/
+pub fn load_token_account_pre_authorizations<'info>(
+    remaining_accounts: &[AccountInfo<'info>],
+    token_account: &Pubkey,
+    excluded_pre_authorization: Option<&Pubkey>,
+) -> Result<Vec<PreAuthorization>> {
+    let mut pre_authorization_keys: Vec<Pubkey> =
+        excluded_pre_authorization.into_iter().copied().collect();
+    let mut pre_authorizations = Vec::with_capacity(remaining_accounts.len());
+    for account_info in remaining_accounts.iter() {
+        let pre_authorization = Account::<PreAuthorization>::try_from(account_info)?;
+        require!(
+            pre_authorization.token_account.eq(token_account),
+            CustomProgramError::PreAuthorizationTokenAccountMismatch
+        );
+        require!(
+            !pre_authorization_keys.contains(account_info.key),
+            CustomProgramError::MissingPreAuthorizations
+        );
+        pre_authorization_keys.push(account_info.key());
+        pre_authorizations.push(pre_authorization.into_inner());
+    }
+
+    Ok(pre_authorizations)
+}
+
+/**
+  Returns the sum of the outstanding amounts authorized of the (distinct) `pre_authorizations`
+  of a token account in bounded delegation mode.
+  All `num_pre_authorizations` open pre-authorizations of the token account MUST be provided.
+*/
+pub fn compute_total_outstanding_amount_authorized(
+    pre_authorizations: &[PreAuthorization],
+    num_pre_authorizations: u64,
+) -> core::result::Result<u64, CustomProgramError> {
+    if pre_authorizations.len() as u64 != num_pre_authorizations {
+        return Err(CustomProgramError::MissingPreAuthorizations);
+    }
+
+    pre_authorizations
+        .iter()
+        .try_fold(0u64, |total_amount, pre_authorization| {
+            total_amount
+                .checked_add(
+                    pre_authorization
+                        .outstanding_amount_authorized()
+                        .ok_or(CustomProgramError::UnboundedPreAuthorization)?,
+                )
+                .ok_or(CustomProgramError::ArithmeticOverflow)
+        })
+}
+
+#[event]
+pub struct SmartDelegateReapproved {
+    pub owner: Pubkey,
+    pub token_account: Pubkey,
+    pub smart_delegate: Pubkey,
+    pub delegated_amount: u64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::pre_authorization::PreAuthorizationVariant;
+    use test_case::test_case;
+
+    fn one_time(amount_authorized: u64, amount_debited: u64) -> PreAuthorization {
+        PreAuthorization {
+            variant: PreAuthorizationVariant::OneTime {
+                amount_authorized,
+                expiry_unix_timestamp: 200,
+                amount_debited,
+                max_amount_per_debit: None,
+                close_on_exhaustion: false,
+            },
+            ..Default::default()
+        }
+    }
+
+    fn rolling_window() -> PreAuthorization {
+        PreAuthorization {
+            variant: PreAuthorizationVariant::RollingWindow {
+                window_seconds: 240,
+                amount_authorized_per_window: 100,
+                debited_buckets: Default::default(),
+                last_debited_bucket: 1,
+                amount_debited_total: 0,
+            },
+            ..Default::default()
+        }
+    }
+
+    #[test_case(vec![], 0, Ok(0))]
+    #[test_case(vec![one_time(100, 30), one_time(50, 0)], 2, Ok(120))]
+    #[test_case(vec![one_time(100, 30)], 2, Err(CustomProgramError::MissingPreAuthorizations))]
+    #[test_case(vec![one_time(100, 30), rolling_window()], 2, Err(CustomProgramError::UnboundedPreAuthorization))]
+    #[test_case(vec![one_time(u64::MAX, 0), one_time(1, 0)], 2, Err(CustomProgramError::ArithmeticOverflow))]
+    fn compute_total_outstanding_amount_authorized_cases(
+        pre_authorizations: Vec<PreAuthorization>,
+        num_pre_authorizations: u64,
+        expected_res: core::result::Result<u64, CustomProgramError>,
+    ) {
+        assert_eq!(
+            expected_res,
+            compute_total_outstanding_amount_authorized(
+                &pre_authorizations,
+                num_pre_authorizations
+            )
+        );
+    }
+}
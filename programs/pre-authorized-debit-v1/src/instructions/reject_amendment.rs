@@ -0,0 +1,80 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::TokenAccount;
+
+use crate::{
+    errors::CustomProgramError,
+    state::{pending_amendment::PendingAmendment, pre_authorization::PreAuthorization},
+};
+
+#[derive(Accounts)]
+pub struct RejectAmendment<'info> {
+    // The token account owner rejects the amendment,
+    // or the debit authority withdraws it (e.g. after the acceptance deadline)
+    #[account(
+        constraint = (
+            authority.key.eq(&token_account.owner) ||
+            authority.key.eq(&pending_amendment.debit_authority)
+        ) @ CustomProgramError::RejectAmendmentUnauthorized
+    )]
+    pub authority: Signer<'info>,
+
+    /// CHECK: This is validated against pending_amendment.payer
+    #[account(
+        mut,
+        constraint = receiver.key.eq(&pending_amendment.payer)
+            @ CustomProgramError::OnlyAmendmentPayerCanReceiveFunds
+    )]
+    pub receiver: AccountInfo<'info>,
+
+    pub token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        seeds = [
+            b"pre-authorization",
+            pre_authorization.scope_seeds()[0],
+            pre_authorization.scope_seeds()[1],
+            pre_authorization.debit_authority.as_ref(),
+        ],
+        bump = pre_authorization.bump,
+        constraint = pre_authorization.covers_token_account(
+            &token_account.key(),
+            &token_account.owner,
+            &token_account.mint,
+        ) @ CustomProgramError::PreAuthorizationTokenAccountMismatch,
+    )]
+    pub pre_authorization: Account<'info, PreAuthorization>,
+
+    #[account(
+        mut,
+        close = receiver,
+        seeds = [
+            b"pending-amendment",
+            pre_authorization.key().as_ref(),
+        ],
+        bump = pending_amendment.bump,
+    )]
+    pub pending_amendment: Account<'info, PendingAmendment>,
+}
+
+pub fn handle_reject_amendment(ctx: Context<RejectAmendment>) -> Result<()> {
+    emit!(AmendmentRejected {
+        rejecting_authority: ctx.accounts.authority.key(),
+        debit_authority: ctx.accounts.pending_amendment.debit_authority,
+        token_account: ctx.accounts.token_account.key(),
+        pre_authorization: ctx.accounts.pre_authorization.key(),
+        pending_amendment: ctx.accounts.pending_amendment.key(),
+        receiver: ctx.accounts.receiver.key(),
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct AmendmentRejected {
+    pub rejecting_authority: Pubkey,
+    pub debit_authority: Pubkey,
+    pub token_account: Pubkey,
+    pub pre_authorization: Pubkey,
+    pub pending_amendment: Pubkey,
+    pub receiver: Pubkey,
+}
@@ -0,0 +1,193 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::TokenAccount;
+
+use crate::{errors::CustomProgramError, state::pre_authorization::PreAuthorization};
+
+#[derive(Accounts)]
+pub struct RotateDebitAuthority<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub debit_authority: Signer<'info>,
+
+    /// CHECK: This can be any account
+    #[account(
+        constraint = new_debit_authority.key.ne(debit_authority.key)
+            @ CustomProgramError::NewDebitAuthorityMatchesCurrent
+    )]
+    pub new_debit_authority: AccountInfo<'info>,
+
+    // Only required if pre_authorization.rotation_requires_owner_signature is true
+    #[account(
+        constraint = owner.key.eq(&token_account.owner)
+            @ CustomProgramError::OwnerSignatureRequiredForRotation
+    )]
+    pub owner: Option<Signer<'info>>,
+
+    // The rent of the old pre_authorization is refunded to the token account owner
+    /// CHECK: This is validated against token_account.owner
+    #[account(
+        mut,
+        constraint = receiver.key.eq(&token_account.owner)
+            @ CustomProgramError::OnlyTokenAccountOwnerCanReceiveClosePreAuthFunds
+    )]
+    pub receiver: AccountInfo<'info>,
+
+    pub token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        close = receiver,
+        seeds = [
+            b"pre-authorization",
+            pre_authorization.scope_seeds()[0],
+            pre_authorization.scope_seeds()[1],
+            debit_authority.key().as_ref(),
+        ],
+        bump = pre_authorization.bump,
+        constraint = pre_authorization.covers_token_account(
+            &token_account.key(),
+            &token_account.owner,
+            &token_account.mint,
+        ) @ CustomProgramError::PreAuthorizationTokenAccountMismatch,
+        has_one = debit_authority @ CustomProgramError::RotateDebitAuthorityUnauthorized,
+    )]
+    pub pre_authorization: Account<'info, PreAuthorization>,
+
+    // A pending amendment would be orphaned once the pre_authorization is closed
+    /// CHECK: This is only checked to be uninitialized
+    #[account(
+        seeds = [
+            b"pending-amendment",
+            pre_authorization.key().as_ref(),
+        ],
+        bump,
+        constraint = pending_amendment.data_is_empty() @ CustomProgramError::PendingAmendmentExists,
+    )]
+    pub pending_amendment: AccountInfo<'info>,
+
+    #[account(
+        init,
+        space = 8 + PreAuthorization::INIT_SPACE,
+        seeds = [
+            b"pre-authorization",
+            pre_authorization.scope_seeds()[0],
+            pre_authorization.scope_seeds()[1],
+            new_debit_authority.key().as_ref(),
+        ],
+        bump,
+        payer = payer,
+    )]
+    pub new_pre_authorization: Account<'info, PreAuthorization>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/**
+  Returns the `pre_authorization` state to store under the `new_debit_authority` PDA.
+  Everything but the `bump` and `debit_authority` is carried over (debited amounts, last debited cycle,
+  paused flag, amendment baseline etc.), so the rotation has no effect on the amount available to debit.
+*/
+pub fn rotate_pre_authorization(
+    pre_authorization: &PreAuthorization,
+    new_debit_authority: Pubkey,
+    new_bump: u8,
+) -> PreAuthorization {
+    PreAuthorization {
+        bump: new_bump,
+        debit_authority: new_debit_authority,
+        ..pre_authorization.clone()
+    }
+}
+
+pub fn handle_rotate_debit_authority(ctx: Context<RotateDebitAuthority>) -> Result<()> {
+    require!(
+        !ctx.accounts
+            .pre_authorization
+            .rotation_requires_owner_signature
+            || ctx.accounts.owner.is_some(),
+        CustomProgramError::OwnerSignatureRequiredForRotation
+    );
+
+    let new_bump = *ctx
+        .bumps
+        .get("new_pre_authorization")
+        .expect("new_pre_authorization PDA bump access failed");
+    ctx.accounts
+        .new_pre_authorization
+        .set_inner(rotate_pre_authorization(
+            &ctx.accounts.pre_authorization,
+            ctx.accounts.new_debit_authority.key(),
+            new_bump,
+        ));
+
+    emit!(DebitAuthorityRotated {
+        data: DebitAuthorityRotatedEventData {
+            owner: ctx.accounts.token_account.owner,
+            token_account: ctx.accounts.token_account.key(),
+            old_debit_authority: ctx.accounts.debit_authority.key(),
+            new_debit_authority: ctx.accounts.new_debit_authority.key(),
+            old_pre_authorization: ctx.accounts.pre_authorization.key(),
+            new_pre_authorization: ctx.accounts.new_pre_authorization.key(),
+        }
+    });
+
+    Ok(())
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct DebitAuthorityRotatedEventData {
+    pub owner: Pubkey,
+    pub token_account: Pubkey,
+    pub old_debit_authority: Pubkey,
+    pub new_debit_authority: Pubkey,
+    pub old_pre_authorization: Pubkey,
+    pub new_pre_authorization: Pubkey,
+}
+
+#[event]
+pub struct DebitAuthorityRotated {
+    pub data: DebitAuthorityRotatedEventData,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::pre_authorization::{PreAuthorizationScope, RecurringFixture};
+
+    #[test]
+    fn rotate_pre_authorization_carries_over_state() {
+        let token_account = Pubkey::new_unique();
+        let new_debit_authority = Pubkey::new_unique();
+        let pre_authorization = PreAuthorization {
+            bump: 254,
+            paused: true,
+            token_account,
+            debit_authority: Pubkey::new_unique(),
+            rotation_requires_owner_signature: true,
+            scope: PreAuthorizationScope::TokenAccount,
+            num_debits: 4,
+            ..RecurringFixture {
+                amount_debited_total: 70,
+                last_debited_cycle: 3,
+                num_cycles: Some(12),
+                amended_at_cycle: 2,
+                amount_authorized_before_amendment: 40,
+                ..Default::default()
+            }
+            .build()
+        };
+
+        let rotated = rotate_pre_authorization(&pre_authorization, new_debit_authority, 251);
+
+        assert_eq!(251, rotated.bump);
+        assert_eq!(new_debit_authority, rotated.debit_authority);
+        assert!(rotated.paused);
+        assert!(rotated.rotation_requires_owner_signature);
+        assert_eq!(token_account, rotated.token_account);
+        assert_eq!(100, rotated.activation_unix_timestamp);
+        assert_eq!(pre_authorization.variant, rotated.variant);
+        assert_eq!(pre_authorization.scope, rotated.scope);
+        assert_eq!(4, rotated.num_debits);
+    }
+}
@@ -16,11 +16,16 @@ pub struct UpdatePausePreAuthorization<'info> {
         mut,
         seeds = [
             b"pre-authorization",
-            token_account.key().as_ref(),
+            pre_authorization.scope_seeds()[0],
+            pre_authorization.scope_seeds()[1],
             pre_authorization.debit_authority.as_ref(),
         ],
         bump = pre_authorization.bump,
-        has_one = token_account @ CustomProgramError::PreAuthorizationTokenAccountMismatch,
+        constraint = pre_authorization.covers_token_account(
+            &token_account.key(),
+            &token_account.owner,
+            &token_account.mint,
+        ) @ CustomProgramError::PreAuthorizationTokenAccountMismatch,
     )]
     pub pre_authorization: Account<'info, PreAuthorization>,
 }
@@ -0,0 +1,543 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{TokenAccount, TokenInterface};
+
+use crate::{
+    errors::CustomProgramError,
+    instructions::{load_pre_authorization_counter, reapprove_bounded_delegation},
+    math::{
+        compute_amount_authorized_for_cycle, compute_cumulative_amount_authorized,
+        compute_current_scheduled_cycle, compute_scheduled_cycle_bounds,
+    },
+    state::{
+        pre_authorization::{PreAuthorization, PreAuthorizationScope, PreAuthorizationVariant},
+        smart_delegate::SmartDelegate,
+    },
+};
+
+#[derive(Accounts)]
+pub struct UpdatePreAuthorization<'info> {
+    pub owner: Signer<'info>,
+
+    // The smart_delegate is re-approved in bounded delegation mode
+    #[account(
+        mut,
+        has_one = owner @ CustomProgramError::UpdatePreAuthorizationUnauthorized
+    )]
+    pub token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"pre-authorization",
+            pre_authorization.scope_seeds()[0],
+            pre_authorization.scope_seeds()[1],
+            pre_authorization.debit_authority.as_ref(),
+        ],
+        bump = pre_authorization.bump,
+        constraint = pre_authorization.covers_token_account(
+            &token_account.key(),
+            &token_account.owner,
+            &token_account.mint,
+        ) @ CustomProgramError::PreAuthorizationTokenAccountMismatch,
+    )]
+    pub pre_authorization: Account<'info, PreAuthorization>,
+
+    #[account(
+        seeds = [
+            b"smart-delegate",
+        ],
+        bump = smart_delegate.bump,
+    )]
+    pub smart_delegate: Account<'info, SmartDelegate>,
+
+    // Only required if the pre_authorization is scoped to the token_account
+    // (it can be uninitialized, see `load_pre_authorization_counter`)
+    /// CHECK: This is validated by the seeds and deserialized in the handler (if initialized)
+    #[account(
+        seeds = [
+            b"pre-authorization-counter",
+            token_account.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub pre_authorization_counter: Option<UncheckedAccount<'info>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct UpdatePreAuthorizationParams {
+    pub variant: UpdatePreAuthorizationVariant,
+}
+
+#[derive(InitSpace, AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub enum UpdatePreAuthorizationVariant {
+    OneTime {
+        amount_authorized: u64,
+        expiry_unix_timestamp: i64,
+    },
+    Recurring {
+        recurring_amount_authorized: u64,
+        // None: infinitely recurring until cancelled / closed
+        // Some(n): approved for n cycles from activation (n MUST be >= current cycle)
+        num_cycles: Option<u64>,
+        // true: amount authorized is reset to "recurring_amount_authorized" each cycle
+        // false: unused amounts from prev. cycles carries forward to new cycles
+        reset_every_cycle: bool,
+    },
+}
+
+pub fn handle_update_pre_authorization(
+    ctx: Context<UpdatePreAuthorization>,
+    params: UpdatePreAuthorizationParams,
+) -> Result<()> {
+    let old_outstanding_amount = ctx
+        .accounts
+        .pre_authorization
+        .outstanding_amount_authorized();
+    let old_values = amend_pre_authorization(
+        &mut ctx.accounts.pre_authorization,
+        &params.variant,
+        Clock::get()?.unix_timestamp,
+    )?;
+    // owner scoped pre_authorizations are not counted (nor part of the bounded allowance)
+    let is_counted = ctx.accounts.pre_authorization.scope == PreAuthorizationScope::TokenAccount;
+    require!(
+        !is_counted || ctx.accounts.pre_authorization_counter.is_some(),
+        CustomProgramError::PreAuthorizationCounterRequired
+    );
+    let bounded_delegation = is_counted
+        && load_pre_authorization_counter(ctx.accounts.pre_authorization_counter.as_ref())?
+            .is_some_and(|pre_authorization_counter| pre_authorization_counter.bounded_delegation);
+    if bounded_delegation {
+        let new_outstanding_amount = ctx
+            .accounts
+            .pre_authorization
+            .outstanding_amount_authorized()
+            .ok_or(CustomProgramError::UnboundedPreAuthorization)?;
+        reapprove_bounded_delegation(
+            old_outstanding_amount.unwrap_or_default(),
+            new_outstanding_amount,
+            &ctx.accounts.token_program,
+            &ctx.accounts.token_account,
+            &ctx.accounts.smart_delegate,
+            ctx.accounts.owner.to_account_info(),
+        )?;
+    }
+
+    emit!(PreAuthorizationUpdated {
+        data: PreAuthorizationUpdatedEventData {
+            owner: ctx.accounts.owner.key(),
+            token_account: ctx.accounts.token_account.key(),
+            pre_authorization: ctx.accounts.pre_authorization.key(),
+            debit_authority: ctx.accounts.pre_authorization.debit_authority,
+            old_values,
+            new_values: params.variant,
+        }
+    });
+
+    Ok(())
+}
+
+/**
+  Applies the new terms to the `pre_authorization` and returns the old terms.
+
+  The new terms can never retroactively increase the amount authorized in already elapsed cycles.
+  For a recurring pre-authorization, the new terms apply from the current cycle (i.e. cycle 1 before activation)
+  onwards. The amount authorized across the elapsed cycles is settled with the old terms:
+  - `reset_every_cycle = true`: nothing carries over, only the amounts already debited are kept
+  - `reset_every_cycle = false`: the unused amounts accrued with the old terms carry over
+
+  The `intro_schedule` (if set) is kept, the new `recurring_amount_authorized` applies after the intro cycles.
+
+  The amount authorized in the current cycle (one-time: overall) cannot go below the amount already debited.
+*/
+pub fn amend_pre_authorization(
+    pre_authorization: &mut PreAuthorization,
+    new_values: &UpdatePreAuthorizationVariant,
+    current_unix_timestamp: i64,
+) -> core::result::Result<UpdatePreAuthorizationVariant, CustomProgramError> {
+    let activation_unix_timestamp = pre_authorization.activation_unix_timestamp;
+
+    match (&mut pre_authorization.variant, new_values) {
+        (
+            PreAuthorizationVariant::OneTime {
+                amount_authorized,
+                expiry_unix_timestamp,
+                amount_debited,
+                ..
+            },
+            UpdatePreAuthorizationVariant::OneTime {
+                amount_authorized: new_amount_authorized,
+                expiry_unix_timestamp: new_expiry_unix_timestamp,
+            },
+        ) => {
+            if *new_expiry_unix_timestamp <= activation_unix_timestamp {
+                return Err(CustomProgramError::ExpiryBeforeActivation);
+            }
+            if *new_amount_authorized < *amount_debited {
+                return Err(CustomProgramError::AmountAuthorizedBelowAmountDebited);
+            }
+
+            let old_values = UpdatePreAuthorizationVariant::OneTime {
+                amount_authorized: *amount_authorized,
+                expiry_unix_timestamp: *expiry_unix_timestamp,
+            };
+            *amount_authorized = *new_amount_authorized;
+            *expiry_unix_timestamp = *new_expiry_unix_timestamp;
+
+            Ok(old_values)
+        }
+        (
+            PreAuthorizationVariant::Recurring {
+                repeat_frequency_seconds,
+                recurring_amount_authorized,
+                amount_debited_last_cycle,
+                amount_debited_total,
+                last_debited_cycle,
+                num_cycles,
+                reset_every_cycle,
+                amended_at_cycle,
+                amount_authorized_before_amendment,
+                calendar_schedule,
+                intro_schedule,
+                ..
+            },
+            UpdatePreAuthorizationVariant::Recurring {
+                recurring_amount_authorized: new_recurring_amount_authorized,
+                num_cycles: new_num_cycles,
+                reset_every_cycle: new_reset_every_cycle,
+            },
+        ) => {
+            let current_cycle = if current_unix_timestamp < activation_unix_timestamp {
+                1
+            } else {
+                compute_current_scheduled_cycle(
+                    current_unix_timestamp,
+                    activation_unix_timestamp,
+                    *repeat_frequency_seconds,
+                    calendar_schedule.map(Into::into).as_ref(),
+                )?
+            };
+
+            // could happen if validator has decreasing timestamps in between TXs due to some weirdness
+            if current_cycle < *last_debited_cycle {
+                return Err(CustomProgramError::LastDebitedCycleBeforeCurrentCycle);
+            }
+
+            if let Some(new_num_cycles) = *new_num_cycles {
+                if new_num_cycles < current_cycle
+                    || compute_scheduled_cycle_bounds(
+                        new_num_cycles,
+                        activation_unix_timestamp,
+                        *repeat_frequency_seconds,
+                        calendar_schedule.map(Into::into).as_ref(),
+                    )
+                    .is_none()
+                {
+                    return Err(CustomProgramError::InvalidNumCycles);
+                }
+            }
+
+            let amount_debited_current_cycle = if *last_debited_cycle == current_cycle {
+                *amount_debited_last_cycle
+            } else {
+                0
+            };
+
+            let new_amount_authorized_before_amendment = if *reset_every_cycle {
+                amount_debited_total
+                    .checked_sub(amount_debited_current_cycle)
+                    .ok_or(CustomProgramError::InvalidAmountDebited)?
+            } else {
+                compute_cumulative_amount_authorized(
+                    current_cycle - 1,
+                    *recurring_amount_authorized,
+                    *amended_at_cycle,
+                    *amount_authorized_before_amendment,
+                    intro_schedule.map(Into::into).as_ref(),
+                )?
+            };
+
+            if let Some(new_num_cycles) = *new_num_cycles {
+                let total_amount_authorized = if *new_reset_every_cycle {
+                    compute_cumulative_amount_authorized(
+                        new_num_cycles,
+                        *new_recurring_amount_authorized,
+                        1,
+                        0,
+                        intro_schedule.map(Into::into).as_ref(),
+                    )
+                } else {
+                    compute_cumulative_amount_authorized(
+                        new_num_cycles,
+                        *new_recurring_amount_authorized,
+                        current_cycle,
+                        new_amount_authorized_before_amendment,
+                        intro_schedule.map(Into::into).as_ref(),
+                    )
+                };
+                if total_amount_authorized.is_err() {
+                    return Err(CustomProgramError::TotalAmountAuthorizedOverflow);
+                }
+            }
+
+            let covers_amount_debited = if *new_reset_every_cycle {
+                compute_amount_authorized_for_cycle(
+                    current_cycle,
+                    *new_recurring_amount_authorized,
+                    intro_schedule.map(Into::into).as_ref(),
+                ) >= amount_debited_current_cycle
+            } else {
+                compute_cumulative_amount_authorized(
+                    current_cycle,
+                    *new_recurring_amount_authorized,
+                    current_cycle,
+                    new_amount_authorized_before_amendment,
+                    intro_schedule.map(Into::into).as_ref(),
+                )? >= *amount_debited_total
+            };
+            if !covers_amount_debited {
+                return Err(CustomProgramError::AmountAuthorizedBelowAmountDebited);
+            }
+
+            let old_values = UpdatePreAuthorizationVariant::Recurring {
+                recurring_amount_authorized: *recurring_amount_authorized,
+                num_cycles: *num_cycles,
+                reset_every_cycle: *reset_every_cycle,
+            };
+            *recurring_amount_authorized = *new_recurring_amount_authorized;
+            *num_cycles = *new_num_cycles;
+            *reset_every_cycle = *new_reset_every_cycle;
+            *amended_at_cycle = current_cycle;
+            *amount_authorized_before_amendment = new_amount_authorized_before_amendment;
+
+            Ok(old_values)
+        }
+        _ => Err(CustomProgramError::PreAuthorizationVariantMismatch),
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct PreAuthorizationUpdatedEventData {
+    pub owner: Pubkey,
+    pub token_account: Pubkey,
+    pub pre_authorization: Pubkey,
+    pub debit_authority: Pubkey,
+    pub old_values: UpdatePreAuthorizationVariant,
+    pub new_values: UpdatePreAuthorizationVariant,
+}
+
+#[event]
+pub struct PreAuthorizationUpdated {
+    pub data: PreAuthorizationUpdatedEventData,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::pre_authorization::RecurringFixture;
+    use test_case::test_case;
+
+    fn one_time_pre_authorization() -> PreAuthorization {
+        PreAuthorization {
+            activation_unix_timestamp: 100,
+            variant: PreAuthorizationVariant::OneTime {
+                amount_authorized: 100,
+                expiry_unix_timestamp: 200,
+                amount_debited: 30,
+                max_amount_per_debit: None,
+                close_on_exhaustion: false,
+            },
+            ..Default::default()
+        }
+    }
+
+    // activation: 100, 10s cycles, 20 debited in cycle 2
+    fn recurring_pre_authorization(reset_every_cycle: bool) -> PreAuthorization {
+        RecurringFixture {
+            reset_every_cycle,
+            ..Default::default()
+        }
+        .build()
+    }
+
+    fn recurring(
+        recurring_amount_authorized: u64,
+        num_cycles: Option<u64>,
+        reset_every_cycle: bool,
+    ) -> UpdatePreAuthorizationVariant {
+        UpdatePreAuthorizationVariant::Recurring {
+            recurring_amount_authorized,
+            num_cycles,
+            reset_every_cycle,
+        }
+    }
+
+    #[test_case(30, 200, 150, Ok(0))]
+    #[test_case(500, 300, 250, Ok(470))]
+    #[test_case(500, 101, 101, Err(CustomProgramError::PreAuthorizationNotActive))]
+    fn amend_one_time(
+        amount_authorized: u64,
+        expiry_unix_timestamp: i64,
+        unix_timestamp: i64,
+        expected_amount_available: core::result::Result<u64, CustomProgramError>,
+    ) {
+        let mut pre_authorization = one_time_pre_authorization();
+        let old_values = amend_pre_authorization(
+            &mut pre_authorization,
+            &UpdatePreAuthorizationVariant::OneTime {
+                amount_authorized,
+                expiry_unix_timestamp,
+            },
+            150,
+        );
+        assert_eq!(
+            Ok(UpdatePreAuthorizationVariant::OneTime {
+                amount_authorized: 100,
+                expiry_unix_timestamp: 200,
+            }),
+            old_values
+        );
+        assert_eq!(
+            expected_amount_available,
+            pre_authorization.available_amount_at(unix_timestamp)
+        );
+    }
+
+    #[test_case(29, 200, CustomProgramError::AmountAuthorizedBelowAmountDebited)]
+    #[test_case(100, 99, CustomProgramError::ExpiryBeforeActivation)]
+    #[test_case(100, 100, CustomProgramError::ExpiryBeforeActivation)]
+    fn amend_one_time_errors(
+        amount_authorized: u64,
+        expiry_unix_timestamp: i64,
+        expected_err: CustomProgramError,
+    ) {
+        assert_eq!(
+            Err(expected_err),
+            amend_pre_authorization(
+                &mut one_time_pre_authorization(),
+                &UpdatePreAuthorizationVariant::OneTime {
+                    amount_authorized,
+                    expiry_unix_timestamp,
+                },
+                150,
+            )
+        );
+    }
+
+    // (reset_every_cycle, amended at timestamp, new terms, timestamp, expected available amount)
+    // accruing -> accruing: elapsed cycles keep their old amounts (2 * 50 - 20 = 80 carried over)
+    #[test_case(false, 125, recurring(100, None, false), 125, 180)]
+    #[test_case(false, 125, recurring(100, None, false), 135, 280)]
+    #[test_case(false, 125, recurring(0, None, false), 145, 80)]
+    #[test_case(false, 115, recurring(100, None, false), 115, 130)]
+    // accruing -> resetting: nothing carries over
+    #[test_case(false, 125, recurring(100, None, true), 125, 100)]
+    #[test_case(false, 115, recurring(100, None, true), 115, 80)]
+    // resetting -> accruing: nothing carried over from elapsed cycles
+    #[test_case(true, 125, recurring(100, None, false), 125, 100)]
+    #[test_case(true, 125, recurring(100, None, false), 135, 200)]
+    #[test_case(true, 115, recurring(100, None, false), 115, 80)]
+    // resetting -> resetting
+    #[test_case(true, 115, recurring(20, Some(3), true), 115, 0)]
+    #[test_case(true, 115, recurring(20, Some(3), true), 125, 20)]
+    fn amend_recurring(
+        reset_every_cycle: bool,
+        amended_at_unix_timestamp: i64,
+        new_values: UpdatePreAuthorizationVariant,
+        unix_timestamp: i64,
+        expected_amount_available: u64,
+    ) {
+        let mut pre_authorization = recurring_pre_authorization(reset_every_cycle);
+        assert_eq!(
+            Ok(recurring(50, None, reset_every_cycle)),
+            amend_pre_authorization(
+                &mut pre_authorization,
+                &new_values,
+                amended_at_unix_timestamp
+            )
+        );
+        assert_eq!(
+            Ok(expected_amount_available),
+            pre_authorization.available_amount_at(unix_timestamp)
+        );
+    }
+
+    #[test_case(
+        false,
+        125,
+        recurring(100, Some(2), false),
+        CustomProgramError::InvalidNumCycles
+    )]
+    #[test_case(
+        false,
+        125,
+        recurring(100, Some(u64::MAX), true),
+        CustomProgramError::InvalidNumCycles
+    )]
+    #[test_case(
+        false,
+        125,
+        recurring(u64::MAX, Some(5), false),
+        CustomProgramError::TotalAmountAuthorizedOverflow
+    )]
+    #[test_case(
+        true,
+        125,
+        recurring(u64::MAX, Some(5), true),
+        CustomProgramError::TotalAmountAuthorizedOverflow
+    )]
+    #[test_case(
+        false,
+        115,
+        recurring(0, None, true),
+        CustomProgramError::AmountAuthorizedBelowAmountDebited
+    )]
+    #[test_case(
+        true,
+        115,
+        recurring(19, None, true),
+        CustomProgramError::AmountAuthorizedBelowAmountDebited
+    )]
+    #[test_case(
+        true,
+        115,
+        recurring(0, None, false),
+        CustomProgramError::AmountAuthorizedBelowAmountDebited
+    )]
+    #[test_case(
+        false,
+        105,
+        recurring(100, None, false),
+        CustomProgramError::LastDebitedCycleBeforeCurrentCycle
+    )]
+    #[test_case(false, 125, UpdatePreAuthorizationVariant::OneTime { amount_authorized: 100, expiry_unix_timestamp: 200 }, CustomProgramError::PreAuthorizationVariantMismatch)]
+    fn amend_recurring_errors(
+        reset_every_cycle: bool,
+        unix_timestamp: i64,
+        new_values: UpdatePreAuthorizationVariant,
+        expected_err: CustomProgramError,
+    ) {
+        assert_eq!(
+            Err(expected_err),
+            amend_pre_authorization(
+                &mut recurring_pre_authorization(reset_every_cycle),
+                &new_values,
+                unix_timestamp
+            )
+        );
+    }
+
+    #[test]
+    fn amend_one_time_with_recurring_values_errors() {
+        assert_eq!(
+            Err(CustomProgramError::PreAuthorizationVariantMismatch),
+            amend_pre_authorization(
+                &mut one_time_pre_authorization(),
+                &recurring(100, None, false),
+                150,
+            )
+        );
+    }
+}
@@ -1,7 +1,9 @@
 use anchor_lang::prelude::*;
 
+pub mod calendar;
 pub mod errors;
 pub mod instructions;
+pub mod math;
 pub mod state;
 
 use instructions::*;
@@ -14,7 +16,7 @@ solana_security_txt::security_txt! {
     policy: "https://github.com/seabed-labs/pre-authorized-debit/blob/main/SECURITY.md",
     preferred_languages: "en",
     source_code: "https://github.com/seabed-labs/pre-authorized-debit",
-    source_revision: "v1.0.0",
+    source_revision: "v2.0.0",
     auditors: "None"
 }
 
@@ -51,8 +53,8 @@ pub mod pre_authorized_debit_v1 {
     The `pre_authorization` defines a set of rules.
     The `pre_authorization` rules/constraints are verified during a `debit` instruction.
     The `pre_authorization` in conjunction with a `smart_delegate` for the same `token_account`
-    can allow the `pre_authorization.debit_authority` to do a one-time or recurring debit from the
-    `token_account.
+    can allow the `pre_authorization.debit_authority` to do a one-time, recurring, scheduled, installments
+    or rolling window debit from the `token_account.
     For a pair of `debit_authority` and `token_account`, only a single `pre_authorization` account can exist.
     To create another `pre_authorization` for the same `token_account`, another `debit_authority` must be used.
 
@@ -63,13 +65,65 @@ pub mod pre_authorized_debit_v1 {
     The `payer` and `owner` may be the same account.
     The `token_account.owner` MUST be the `owner`.
     The `pre_authorization.token_account` must be the same as `token_account`.
+    If `rotation_requires_owner_signature` is set, the `owner` MUST co-sign any `rotate_debit_authority`.
+    The `max_amount_per_debit` (if set) MUST be greater than 0.
+    For a recurring pre-authorization, the `min_amount_per_debit` (if set) MUST NOT be greater than
+    the `max_amount_per_debit` (if set) and the `max_accrued_cycles` (if set) MUST be greater than 0.
+    If `destination_constraint` is set, the `debit` destination MUST be the given token account
+    (or a token account owned by the given owner).
+    For a one-time pre-authorization, the `expiry_unix_timestamp` MUST be after the `activation_unix_timestamp`.
+    For a one-time pre-authorization with `close_on_exhaustion` set, the `debit` that exhausts the amount authorized
+    also closes the `pre_authorization`.
+    For a recurring pre-authorization, the `repeat_frequency_seconds` MUST be greater than 0 and at most i64::MAX.
+    For a recurring pre-authorization with a `calendar_schedule` (monthly, quarterly or yearly cycles anchored to
+    a UTC day of month), the `repeat_frequency_seconds` MUST be 0 and the `day_of_month` MUST be in [1, 31].
+    For a recurring pre-authorization, the `debit_window_seconds` (if set) MUST be greater than 0 and the
+    `debit_window_offset_seconds` (if set) MUST be less than the shortest cycle,
+    the `debit_window_offset_seconds` MUST NOT be set without the `debit_window_seconds`.
+    For a recurring pre-authorization, the `intro_schedule` (if set) authorizes the `intro_amount_authorized`
+    (instead of the `recurring_amount_authorized`) in each of the first `num_intro_cycles` cycles,
+    the `num_intro_cycles` MUST be greater than 0.
+    For a recurring pre-authorization, the `num_cycles` (if set) MUST be greater than 0 and
+    the total amount authorized across the `num_cycles` MUST fit in a u64.
+    For a scheduled pre-authorization (a list of `steps`, each authorizing an amount per cycle from its
+    `cycle_start` onwards), the `repeat_frequency_seconds` MUST be greater than 0 and at most i64::MAX,
+    there MUST be 1 to 12 `steps`, the first step MUST start at cycle 1 and the `cycle_start` of the steps
+    MUST be strictly increasing.
+    For a scheduled pre-authorization, the `num_cycles` (if set) MUST be greater than 0, the last step MUST start
+    within the `num_cycles` and the total amount authorized across the `num_cycles` MUST fit in a u64.
+    For an installments pre-authorization (`total_amount` split into `installment_count` equal installments due every
+    `interval_seconds` from activation), the `interval_seconds` MUST be greater than 0 and at most i64::MAX,
+    the `grace_period_seconds` MUST be at most i64::MAX, the `installment_count` MUST be greater than 0
+    and the last installment MUST be due before i64::MAX.
+    For a rolling window pre-authorization (at most `amount_authorized_per_window` in any trailing `window_seconds`),
+    the `window_seconds` MUST be a non-zero multiple of 24 (the number of buckets the window is tracked in)
+    and at most i64::MAX.
+    The `pre_authorization_counter` of the `token_account` is created if it does not exist yet
+    and counts the open `pre_authorization` accounts of the `token_account`. A counter created here
+    cannot know about the `pre_authorization` accounts created before it, its count is unverified until the
+    `token_account.owner` sets it in `init_pre_authorization_counter`.
+    The `smart_delegate` is approved for `u64::MAX`, unless `bounded_delegation` is set: the `smart_delegate`
+    is then approved for the sum of the outstanding amounts authorized (total amount authorized minus the
+    amount debited) of the open `pre_authorization` accounts of the `token_account`.
+    The `bounded_delegation` MUST match the other open `pre_authorization` accounts of the `token_account`.
+    With `bounded_delegation`, the `pre_authorization_counter` count MUST be verified and the amount authorized
+    MUST be bounded (i.e. `num_cycles` MUST be set for a recurring or scheduled pre-authorization and it cannot
+    be a rolling window pre-authorization).
+    The approval can be above that sum when the outstanding amount authorized decreases without the
+    `token_account.owner` signature (required by the token program to lower it): a `pre_authorization` closed
+    by the `debit_authority` or in `close_expired_pre_authorization`, or an amount accrued above
+    `max_accrued_cycles` forfeited in a debit. It is approved for the exact sum again when the
+    `token_account.owner` closes a `pre_authorization` or calls `reapprove_smart_delegate`.
 
     Accounts expected by this instruction:
         0. `[writable]` payer
         1. `[]`         owner
-        2. `[writable]` token_account
-        3. `[writable]` pre_authorization
-        4. `[]`         system_program
+        2. `[]`         smart_delegate
+        3. `[writable]` token_account
+        4. `[writable]` pre_authorization
+        5. `[writable]` pre_authorization_counter
+        6. `[]`         token_program
+        7. `[]`         system_program
     */
     pub fn init_pre_authorization(
         ctx: Context<InitPreAuthorization>,
@@ -78,6 +132,38 @@ pub mod pre_authorized_debit_v1 {
         handle_init_pre_authorization(ctx, params)
     }
 
+    /**
+    The `InitPreAuthorizationCounter` instruction allows a `token_account.owner` to set the number of open
+    `pre_authorization` accounts scoped to the `token_account` (e.g. the ones created before the
+    `pre_authorization_counter` existed), the count is then verified.
+
+    Initializes the `pre_authorization_counter` account if it does not exist yet, derived with the seeds:
+    ['pre-authorization-counter', token_account].
+    Once the count is verified, the `smart_delegate` approval is revoked when the last `pre_authorization`
+    is closed by the `token_account.owner` and bounded delegation can be used.
+    The `count` MUST be the number of open `pre_authorization` accounts scoped to the `token_account`
+    (a lower count revokes the `smart_delegate` approval early, a higher one never revokes it).
+    The count can only be set once: the `pre_authorization_counter` MUST NOT be verified yet
+    (nor be in bounded delegation mode).
+
+    The `payer` MUST sign the transaction.
+    The `owner` MUST sign the transaction.
+    The `owner` MUST equal the `token_account.owner`.
+
+    Accounts expected by this instruction:
+        0. `[writable]` payer
+        1. `[]`         owner
+        2. `[]`         token_account
+        3. `[writable]` pre_authorization_counter
+        4. `[]`         system_program
+    */
+    pub fn init_pre_authorization_counter(
+        ctx: Context<InitPreAuthorizationCounter>,
+        params: InitPreAuthorizationCounterParams,
+    ) -> Result<()> {
+        handle_init_pre_authorization_counter(ctx, params)
+    }
+
     /**
     The `ClosePreAuthorization` instruction will close a `pre_authorization` account.
 
@@ -91,17 +177,124 @@ pub mod pre_authorized_debit_v1 {
     The `owner` MUST be the `token_account.owner`.
     The `token_account.owner` MUST be the `owner`.
     The `pre_authorization.token_account` must be the same as `token_account`.
+    The `pre_authorization_counter` of the `token_account` MUST be set, it can be uninitialized
+    (if initialized, it is decremented).
+    If this is the last open `pre_authorization` of the `token_account` (see `pre_authorization_counter`,
+    its count MUST be verified), the `authority` is the `token_account.owner` and the `token_account.delegate`
+    is the `smart_delegate`, the `smart_delegate` approval is revoked.
+    Otherwise, in bounded delegation mode, the `smart_delegate` is re-approved for the sum of the outstanding
+    amounts authorized of the other open `pre_authorization` accounts if the `authority` is the
+    `token_account.owner` and the `token_account.delegate` is the `smart_delegate`, the remaining accounts MUST then
+    be all the other open `pre_authorization` accounts of the `token_account` (each one exactly once).
 
     Accounts expected by this instruction:
         0. `[writable]` receiver
         1. `[]`         authority
-        2. `[]`         token_account
-        3. `[writable]` pre_authorization
+        2. `[writable]` token_account
+        3. `[]`         smart_delegate
+        4. `[writable]` pre_authorization
+        5. `[writable]` pre_authorization_counter (optional)
+        6. `[]`         token_program
+        7.. `[]`        pre_authorization (remaining accounts, bounded delegation mode only)
     */
-    pub fn close_pre_authorization(ctx: Context<ClosePreAuthorization>) -> Result<()> {
+    pub fn close_pre_authorization<'info>(
+        ctx: Context<'_, '_, '_, 'info, ClosePreAuthorization<'info>>,
+    ) -> Result<()> {
         handle_close_pre_authorization(ctx)
     }
 
+    /**
+    The `CloseExpiredPreAuthorization` instruction will close a `pre_authorization` account
+    that can never be debited again. Anyone can call it.
+
+    Closes an expired `pre_authorization` account and refunds the lamports
+    to the `token_account.owner` (`receiver`), minus a bounty of 5000 lamports if a `bounty_receiver` is set.
+
+    A `pre_authorization` is expired if:
+    - one-time: the validator time is at or after the `expiry_unix_timestamp`
+    - recurring or scheduled: `num_cycles` is set and the current cycle is after the last cycle
+    - installments: all installments have been paid
+    A rolling window `pre_authorization` never expires.
+
+    The `caller` MUST sign for the instruction.
+    The `receiver` MUST be the `token_account.owner`.
+    The `bounty_receiver` (if set) receives the bounty, it can be any account (e.g. the `caller`).
+    The `pre_authorization.token_account` must be the same as `token_account`
+    (for an owner scoped `pre_authorization`, the `token_account.owner` and `token_account.mint`
+    MUST equal the `owner` and `mint` of the `pre_authorization.scope` instead).
+    The `smart_delegate` approval is never revoked here, nor lowered in bounded delegation mode
+    (the `token_account.owner` has to sign for it, see `reapprove_smart_delegate`).
+    The `pre_authorization_counter` of the `token_account` MUST be set unless the `pre_authorization` is owner scoped,
+    it can be uninitialized (if initialized, it is decremented, unless the `pre_authorization` is owner scoped).
+
+    Accounts expected by this instruction:
+        0. `[]`         caller
+        1. `[writable]` receiver
+        2. `[writable]` bounty_receiver (optional)
+        3. `[]`         token_account
+        4. `[writable]` pre_authorization
+        5. `[writable]` pre_authorization_counter (optional)
+    */
+    pub fn close_expired_pre_authorization(
+        ctx: Context<CloseExpiredPreAuthorization>,
+    ) -> Result<()> {
+        handle_close_expired_pre_authorization(ctx)
+    }
+
+    /**
+    The `InitOwnerPreAuthorization` instruction will create a `pre_authorization` account scoped to
+    an `owner` and a `mint` (instead of a single `token_account`).
+
+    Initializes a new account (`pre_authorization`) derived with the seeds:
+    ['pre-authorization', owner, mint, debit_authority].
+    The `pre_authorization.debit_authority` can debit from any token account owned by the `owner`
+    with the `mint` (and delegated to the `smart_delegate`), the amounts authorized are tracked once
+    across all these token accounts.
+    Unlike `init_pre_authorization`, no token account is approved by this instruction.
+    The same `params` rules as in `init_pre_authorization` apply.
+    Only `debit` and `close_owner_pre_authorization` support owner scoped pre-authorizations.
+
+    The `payer` MUST sign the transaction.
+    The `payer` MUST have enough lamports to pay for the `pre_authorization` account.
+    The `owner` MUST sign the transaction.
+    The `payer` and `owner` may be the same account.
+    The `bounded_delegation` MUST NOT be set (the `owner` approves the `smart_delegate` on each token account).
+
+    Accounts expected by this instruction:
+        0. `[writable]` payer
+        1. `[]`         owner
+        2. `[]`         mint
+        3. `[writable]` pre_authorization
+        4. `[]`         system_program
+    */
+    pub fn init_owner_pre_authorization(
+        ctx: Context<InitOwnerPreAuthorization>,
+        params: InitPreAuthorizationParams,
+    ) -> Result<()> {
+        handle_init_owner_pre_authorization(ctx, params)
+    }
+
+    /**
+    The `CloseOwnerPreAuthorization` instruction will close a `pre_authorization` account
+    created with `init_owner_pre_authorization`.
+
+    The `receiver` will receive all lamports from the closed account.
+    The `receiver` MUST be the `owner` unless the `authority` is the `owner`.
+    The `authority` MUST sign for the instruction.
+    The `authority` MUST be either the `owner` or the `pre_authorization.debit_authority`.
+    The `owner` and `mint` MUST be the ones the `pre_authorization` was created with.
+
+    Accounts expected by this instruction:
+        0. `[writable]` receiver
+        1. `[]`         authority
+        2. `[]`         owner
+        3. `[]`         mint
+        4. `[writable]` pre_authorization
+    */
+    pub fn close_owner_pre_authorization(ctx: Context<CloseOwnerPreAuthorization>) -> Result<()> {
+        handle_close_owner_pre_authorization(ctx)
+    }
+
     /**
     The `Debit` instruction allows a `pre_authorization.debit_authority` to debit from the
     `pre_authorization.token_account` via the `smart_delegate` PDA. To successfully debit from
@@ -112,15 +305,65 @@ pub mod pre_authorized_debit_v1 {
 
     Common Rules:
     - The `pre_authorization` MUST not be paused.
+    - The validator time must be greater than or equal to the `PA.activation_unix_timestamp`
     - The amount being requested to debit must be less than or equal to the available amount for the current_cycle
-    - The current timestamp must be less than the `PA.expiry_unix_timestamp`
+      (see the rules of each variant below, several debits can occur in the same cycle)
+    - The `token_account.delegate` MUST be the `smart_delegate` (see `reapprove_smart_delegate`)
+    - The amount being requested to debit must be less than or equal to the `token_account.delegated_amount`
+      (in bounded delegation mode, it decreases with each debit along with the outstanding amount authorized)
     - If the PA has a `num_cycles` defined, the `current_cycle` must be less than or equal to `PA.num_cycles`
+    - If the PA has a `destination_constraint` defined, the `destination_token_account` must satisfy it
+    - If the PA has a `max_amount_per_debit` defined, the amount must be less than or equal to it
 
     For a recurring pre-authorization:
-    - The debit_authority must not have already done a debit in the current cycle
+    - If the PA resets every cycle, the available amount is the amount authorized in the current cycle minus
+      the amount debited in the current cycle
+    - Otherwise, the available amount is the amount authorized across the cycles up to the current cycle
+      (including the amount authorized before an amendment) minus the `PA.amount_debited_total`
+    - The amount authorized in a cycle is the `PA.recurring_amount_authorized`, or the
+      `intro_amount_authorized` in the first `num_intro_cycles` cycles if the PA has an `intro_schedule` defined
+    - If the PA has a `calendar_schedule` defined, the cycles are calendar cycles instead of
+      `PA.repeat_frequency_seconds` long
+    - If the PA has a `min_amount_per_debit` defined, the amount must be greater than or equal to it
+    - If the PA has a `max_accrued_cycles` defined (and does not reset every cycle), the available amount
+      is capped to `max_accrued_cycles` times the amount authorized in the current cycle,
+      the amount accrued above it is forfeited
+    - If the PA has a `debit_window_seconds` defined, the validator time must be within the debit window of the
+      current cycle (starting `debit_window_offset_seconds`, if set, into the cycle)
+
+    For a scheduled pre-authorization:
+    - The amount authorized in the current cycle is the `amount_authorized` of the step active in the current cycle
+      (the amount is reset every cycle), the `DebitEvent` reports the step the debit was validated against
+
+    For an installments pre-authorization:
+    - The amount of the installments due (i.e. past their due date) minus the amount already debited can be debited,
+      overdue installments (see `grace_period_seconds`) can still be debited
+
+    For a rolling window pre-authorization:
+    - The amount debited in the trailing window (rounded up to whole buckets of `window_seconds / 24` seconds)
+      plus the amount MUST NOT exceed the `amount_authorized_per_window`
 
     For a one-time pre-authorization:
-    - the validator time must be greater than or equal to the `pre_authorization.activation_unix_timestamp`
+    - The validator time must be less than the `PA.expiry_unix_timestamp`
+    - The available amount is the `PA.amount_authorized` minus the `PA.amount_debited`
+    - If the PA has `close_on_exhaustion` set and the debit exhausts the amount authorized, the `pre_authorization`
+      is closed (emitting a `OneTimePreAuthorizationClosed` event) and its rent is refunded to the `receiver`
+
+    Amount modes:
+    - `Exact`: exactly the `amount` is debited (or the debit fails)
+    - `UpTo`: the `amount` is capped to the amount available, the `max_amount_per_debit` (if any), the
+      `token_account.amount` and the `token_account.delegated_amount`, the capped amount MUST be greater than 0
+      and greater than or equal to the `min_amount_per_debit` (if any), or the debit fails with `NothingToDebit`
+      (and is then validated with the rules above). The `DebitEvent` reports the amount requested
+      (`requested_amount`) and the amount debited (`debit_variant`).
+
+    The `params.reference` (if set, e.g. an invoice id or hash) is emitted in the `DebitEvent`.
+    If the `memo_program` is set, the `params.reference` is also logged (hex encoded) in a SPL memo right before
+    the transfer (e.g. for a `destination_token_account` requiring memos on incoming transfers).
+
+    If the `debit_receipt` is set, a `debit_receipt` recording the debit (amount, cycle, timestamp, destination
+    and reference) is created at the PDA derived with the `pre_authorization.num_debits` before the debit,
+    paid by the `receipt_payer`. The `pre_authorization.num_debits` is incremented with every debit.
 
     For a more in-depth understanding around the constraints in a debit, it is recommended to read through
     the validation done for a `debit` instruction.
@@ -131,8 +374,20 @@ pub mod pre_authorized_debit_v1 {
     The `token_account.delegate` MUST equal the `smart_delegate`.
     The `token_account.mint` MUST equal the `mint`.
     The `destination_token_account.mint` MUST equal `mint`.
-    The `pre_authorization.token_account` MUST equal the `token_account`.
+    The `pre_authorization.token_account` MUST equal the `token_account`
+    (for an owner scoped `pre_authorization`, the `token_account.owner` and `token_account.mint`
+    MUST equal the `owner` and `mint` of the `pre_authorization.scope` instead).
     The `token_program` MUST equal the token program matching the `token_account`.
+    The `receiver` MUST equal the `token_account.owner` (only required if the debit closes the `pre_authorization`).
+    The `pre_authorization_counter` of the `token_account` is only required if the debit closes a `pre_authorization`
+    that is not owner scoped, or if the `pre_authorization` is owner scoped (it can be uninitialized then).
+    An owner scoped `pre_authorization` MUST NOT debit a `token_account` in bounded delegation mode
+    (its debits are not part of the outstanding amount authorized the `smart_delegate` is approved for).
+    The `memo_program` (optional) MUST equal the SPL Memo program and the `params.reference` MUST be set if it is.
+    The `receipt_payer` and `system_program` are only required if the `debit_receipt` is set.
+    The `receipt_payer` MUST sign the transaction and have enough lamports to pay for the `debit_receipt` account.
+    The `debit_receipt` MUST be the PDA derived with the seeds:
+    ['debit-receipt', pre_authorization, pre_authorization.num_debits (u64 little-endian)].
 
     Accounts expected by this instruction:
         0. `[]`         debit_authority
@@ -142,11 +397,78 @@ pub mod pre_authorized_debit_v1 {
         4. `[]`         smart_delegate
         5. `[writable]` pre_authorization
         6. `[]`         token_program
+        7. `[writable]` receiver (optional)
+        8. `[writable]` pre_authorization_counter (optional)
+        9. `[]`         memo_program (optional)
+        10. `[writable]` receipt_payer (optional)
+        11. `[writable]` debit_receipt (optional)
+        12. `[]`         system_program (optional)
     */
     pub fn debit(ctx: Context<Debit>, params: DebitParams) -> Result<()> {
         handle_debit(ctx, params)
     }
 
+    /**
+    The `DebitBatch` instruction allows a `debit_authority` to debit many `pre_authorization` accounts
+    of the same `mint` in a single instruction. Each entry of the batch is a `token_account`,
+    `pre_authorization` and `destination_token_account` (passed as remaining accounts) with the amount
+    to debit in `params.amounts`. Each entry is validated with the same rules as the `debit` instruction
+    and emits a `DebitEvent`. A `DebitBatchProcessed` event is emitted once all the entries are processed.
+
+    Modes:
+    - `Atomic`: the batch fails if any entry fails
+    - `SkipFailed`: an entry failing validation is skipped and reported (with its error code) in the
+      `DebitBatchProcessed` event, a failed token transfer (e.g. a transfer hook) still fails the batch
+
+    The `debit_authority` MUST sign the transaction.
+    The `params.amounts` MUST NOT be empty (nor have more than 65535 amounts)
+    and there MUST be 3 remaining accounts per amount.
+    The remaining accounts MUST be writable and the 3 accounts of an entry MUST be distinct.
+    A `pre_authorization` MUST NOT be in more than one entry.
+    The `debit_authority` MUST equal the `pre_authorization.debit_authority` of every entry.
+    The `token_account.mint` and `destination_token_account.mint` of every entry MUST equal the `mint`.
+    The `token_account.amount` of every entry MUST be greater than or equal to the amount debited.
+    The `pre_authorization.token_account` of every entry MUST equal the `token_account`.
+    An owner scoped `pre_authorization` MUST use the `debit` instruction (its `pre_authorization_counter` is required).
+    A debit exhausting a one-time `pre_authorization` with `close_on_exhaustion` set MUST use the `debit` instruction.
+    No `debit_receipt` is created for the entries (their `pre_authorization.num_debits` is still incremented)
+    and the entries have no reference (the `debit` instruction MUST be used for either).
+    The `token_program` MUST equal the token program matching the `token_account` of every entry.
+
+    Accounts expected by this instruction:
+        0. `[]`         debit_authority
+        1. `[]`         mint
+        2. `[]`         smart_delegate
+        3. `[]`         token_program
+        4.. `[writable]` token_account, `[writable]` pre_authorization, `[writable]` destination_token_account
+            (remaining accounts, one triple per entry)
+    */
+    pub fn debit_batch<'info>(
+        ctx: Context<'_, '_, '_, 'info, DebitBatch<'info>>,
+        params: DebitBatchParams,
+    ) -> Result<()> {
+        handle_debit_batch(ctx, params)
+    }
+
+    /**
+    The `CloseDebitReceipt` instruction will close a `debit_receipt` account.
+
+    Closes an existing `debit_receipt` account and refunds the lamports to the `debit_receipt.payer` (`receiver`).
+
+    The `authority` MUST sign the transaction.
+    The `authority` MUST be either the `debit_receipt.payer` or the `debit_receipt.debit_authority`.
+    The `receiver` MUST equal the `debit_receipt.payer`.
+    The retention period (`DEBIT_RECEIPT_RETENTION_SECONDS` after the debit) MUST be over.
+
+    Accounts expected by this instruction:
+        0. `[]`         authority
+        1. `[writable]` receiver
+        2. `[writable]` debit_receipt
+    */
+    pub fn close_debit_receipt(ctx: Context<CloseDebitReceipt>) -> Result<()> {
+        handle_close_debit_receipt(ctx)
+    }
+
     /**
     The `UpdatePausePreAuthorization` instruction allows a `token_account.owner` to pause a
     `pre_authorization`.
@@ -154,7 +476,9 @@ pub mod pre_authorized_debit_v1 {
     The `owner` MUST sign the transaction.
     The `owner` MUST equal the `token_account.owner`.
     The `token_account.owner` MUST equal the `owner`.
-    The `pre_authorization.token_account` MUST equal the `token_account`.
+    The `pre_authorization.token_account` MUST equal the `token_account`
+    (for an owner scoped `pre_authorization`, the `token_account.owner` and `token_account.mint`
+    MUST equal the `owner` and `mint` of the `pre_authorization.scope` instead).
 
     Accounts expected by this instruction:
         0. `[writable]` owner
@@ -167,4 +491,223 @@ pub mod pre_authorized_debit_v1 {
     ) -> Result<()> {
         handle_update_pause_pre_authorization(ctx, params)
     }
+
+    /**
+    The `ReapproveSmartDelegate` instruction allows a `token_account.owner` to restore the `smart_delegate`
+    approval on the `token_account` (e.g. after approving another delegate), no `pre_authorization` is modified.
+
+    The `smart_delegate` is approved for `u64::MAX`, or in bounded delegation mode, for the sum of the
+    outstanding amounts authorized of the open `pre_authorization` accounts of the `token_account`.
+
+    The `owner` MUST sign the transaction.
+    The `owner` MUST equal the `token_account.owner`.
+    In bounded delegation mode, the remaining accounts MUST be all the open `pre_authorization` accounts
+    of the `token_account` (each one exactly once, see `pre_authorization_counter.count`).
+
+    Accounts expected by this instruction:
+        0. `[]`         owner
+        1. `[writable]` token_account
+        2. `[]`         smart_delegate
+        3. `[]`         pre_authorization_counter
+        4. `[]`         token_program
+        5.. `[]`        pre_authorization (remaining accounts, bounded delegation mode only)
+    */
+    pub fn reapprove_smart_delegate<'info>(
+        ctx: Context<'_, '_, '_, 'info, ReapproveSmartDelegate<'info>>,
+    ) -> Result<()> {
+        handle_reapprove_smart_delegate(ctx)
+    }
+
+    /**
+    The `UpdatePreAuthorization` instruction allows a `token_account.owner` to amend the terms of a
+    `pre_authorization` without closing it (i.e. the debited amounts are kept).
+
+    For a one-time pre-authorization, the `amount_authorized` and `expiry_unix_timestamp` can be updated.
+    For a recurring pre-authorization, the `recurring_amount_authorized`, `num_cycles` and `reset_every_cycle`
+    can be updated. The new terms apply from the current cycle onwards, the amounts authorized in
+    already elapsed cycles are never increased.
+
+    Rules:
+    - The variant (one-time or recurring) of the `pre_authorization` cannot be changed.
+    - Scheduled, installments and rolling window pre-authorizations cannot be updated (close and re-create them instead).
+    - The amount authorized (for the current cycle if recurring) cannot be less than the amount already debited.
+    - For a one-time pre-authorization, the `expiry_unix_timestamp` MUST be after the `activation_unix_timestamp`.
+    - For a recurring pre-authorization, the `num_cycles` (if set) MUST be greater than or equal to the current cycle.
+    - In bounded delegation mode, the `num_cycles` MUST be set for a recurring pre-authorization and the
+      `smart_delegate` is re-approved for the new outstanding amount authorized if it is still the
+      `token_account.delegate` (owner scoped pre-authorizations are not part of the bounded allowance).
+    - The `pre_authorization_counter` of the `token_account` MUST be set unless the `pre_authorization` is owner
+      scoped, it can be uninitialized (the delegation mode is read from it).
+
+    The `owner` MUST sign the transaction.
+    The `owner` MUST equal the `token_account.owner`.
+    The `token_account.owner` MUST equal the `owner`.
+    The `pre_authorization.token_account` MUST equal the `token_account`
+    (for an owner scoped `pre_authorization`, the `token_account.owner` and `token_account.mint`
+    MUST equal the `owner` and `mint` of the `pre_authorization.scope` instead).
+
+    Accounts expected by this instruction:
+        0. `[]`         owner
+        1. `[writable]` token_account
+        2. `[writable]` pre_authorization
+        3. `[]`         smart_delegate
+        4. `[]`         pre_authorization_counter (optional)
+        5. `[]`         token_program
+    */
+    pub fn update_pre_authorization(
+        ctx: Context<UpdatePreAuthorization>,
+        params: UpdatePreAuthorizationParams,
+    ) -> Result<()> {
+        handle_update_pre_authorization(ctx, params)
+    }
+
+    /**
+    The `ProposeAmendment` instruction allows a `pre_authorization.debit_authority` to propose new terms
+    for a `pre_authorization` that the `token_account.owner` can accept or reject.
+
+    Initializes a new account (`pending_amendment`).
+    The `pending_amendment` PDA is derived with the seeds: ['pending-amendment', pre_authorization].
+    Only one `pending_amendment` can exist for a `pre_authorization` at a time.
+
+    The `payer` MUST sign the transaction.
+    The `payer` MUST have enough lamports to pay for the `pending_amendment` account.
+    The `debit_authority` MUST sign the transaction.
+    The `debit_authority` MUST equal the `pre_authorization.debit_authority`.
+    The `new_values` variant MUST match the `pre_authorization` variant.
+    The `acceptance_deadline_unix_timestamp` MUST be in the future.
+
+    Accounts expected by this instruction:
+        0. `[writable]` payer
+        1. `[]`         debit_authority
+        2. `[]`         pre_authorization
+        3. `[writable]` pending_amendment
+        4. `[]`         system_program
+    */
+    pub fn propose_amendment(
+        ctx: Context<ProposeAmendment>,
+        params: ProposeAmendmentParams,
+    ) -> Result<()> {
+        handle_propose_amendment(ctx, params)
+    }
+
+    /**
+    The `AcceptAmendment` instruction allows a `token_account.owner` to apply the terms of a
+    `pending_amendment` to the `pre_authorization` and closes the `pending_amendment`.
+
+    The new terms are applied with the same rules as the `update_pre_authorization` instruction.
+    The lamports of the closed `pending_amendment` are refunded to the `pending_amendment.payer` (`receiver`).
+
+    The `owner` MUST sign the transaction.
+    The `owner` MUST equal the `token_account.owner`.
+    The `receiver` MUST equal the `pending_amendment.payer`.
+    The `pre_authorization.token_account` MUST equal the `token_account`
+    (for an owner scoped `pre_authorization`, the `token_account.owner` and `token_account.mint`
+    MUST equal the `owner` and `mint` of the `pre_authorization.scope` instead).
+    The current timestamp MUST be less than or equal to the `pending_amendment.acceptance_deadline_unix_timestamp`.
+    The `expected_new_values` MUST equal the `pending_amendment.new_values`.
+    The `pre_authorization_counter` of the `token_account` MUST be set unless the `pre_authorization` is owner scoped,
+    it can be uninitialized (the delegation mode is read from it).
+
+    Accounts expected by this instruction:
+        0. `[]`         owner
+        1. `[writable]` receiver
+        2. `[writable]` token_account
+        3. `[writable]` pre_authorization
+        4. `[writable]` pending_amendment
+        5. `[]`         smart_delegate
+        6. `[]`         pre_authorization_counter (optional)
+        7. `[]`         token_program
+    */
+    pub fn accept_amendment(
+        ctx: Context<AcceptAmendment>,
+        params: AcceptAmendmentParams,
+    ) -> Result<()> {
+        handle_accept_amendment(ctx, params)
+    }
+
+    /**
+    The `RejectAmendment` instruction closes a `pending_amendment` without applying its terms.
+
+    The lamports of the closed `pending_amendment` are refunded to the `pending_amendment.payer` (`receiver`).
+
+    The `authority` MUST sign the transaction.
+    The `authority` MUST be either the `token_account.owner` or the `pending_amendment.debit_authority`.
+    The `receiver` MUST equal the `pending_amendment.payer`.
+    The `pre_authorization.token_account` MUST equal the `token_account`
+    (for an owner scoped `pre_authorization`, the `token_account.owner` and `token_account.mint`
+    MUST equal the `owner` and `mint` of the `pre_authorization.scope` instead).
+
+    Accounts expected by this instruction:
+        0. `[]`         authority
+        1. `[writable]` receiver
+        2. `[]`         token_account
+        3. `[]`         pre_authorization
+        4. `[writable]` pending_amendment
+    */
+    pub fn reject_amendment(ctx: Context<RejectAmendment>) -> Result<()> {
+        handle_reject_amendment(ctx)
+    }
+
+    /**
+    The `RotateDebitAuthority` instruction allows a `pre_authorization.debit_authority` to hand over
+    a `pre_authorization` to a `new_debit_authority` without the `token_account.owner` re-authorizing.
+
+    Initializes a new account (`new_pre_authorization`) derived with the seeds:
+    ['pre-authorization', token_account, new_debit_authority]
+    (['pre-authorization', owner, mint, new_debit_authority] for an owner scoped `pre_authorization`).
+    The state of the `pre_authorization` (including the debited amounts, `last_debited_cycle` and `paused`)
+    is carried over to the `new_pre_authorization` and the `pre_authorization` is closed.
+    The lamports of the closed `pre_authorization` are refunded to the `token_account.owner` (`receiver`).
+
+    The `payer` MUST sign the transaction.
+    The `payer` MUST have enough lamports to pay for the `new_pre_authorization` account.
+    The `debit_authority` MUST sign the transaction.
+    The `debit_authority` MUST equal the `pre_authorization.debit_authority`.
+    The `new_debit_authority` MUST NOT equal the `debit_authority`.
+    If `pre_authorization.rotation_requires_owner_signature` is set, the `owner` MUST be provided and sign the transaction.
+    The `owner` (if provided) MUST equal the `token_account.owner`.
+    The `receiver` MUST equal the `token_account.owner`.
+    The `pre_authorization.token_account` MUST equal the `token_account`
+    (for an owner scoped `pre_authorization`, the `token_account.owner` and `token_account.mint`
+    MUST equal the `owner` and `mint` of the `pre_authorization.scope` instead).
+    The `pre_authorization` MUST NOT have a `pending_amendment`.
+
+    Accounts expected by this instruction:
+        0. `[writable]` payer
+        1. `[]`         debit_authority
+        2. `[]`         new_debit_authority
+        3. `[]`         owner (optional)
+        4. `[writable]` receiver
+        5. `[]`         token_account
+        6. `[writable]` pre_authorization
+        7. `[]`         pending_amendment
+        8. `[writable]` new_pre_authorization
+        9. `[]`         system_program
+    */
+    pub fn rotate_debit_authority(ctx: Context<RotateDebitAuthority>) -> Result<()> {
+        handle_rotate_debit_authority(ctx)
+    }
+
+    /**
+    The `MigratePreAuthorization` instruction converts a `pre_authorization` account created by
+    the v1.0.0 program to the current layout, it can be called by anyone.
+
+    Reads the v1.0.0 layout of the `pre_authorization`, reallocates the account to the current size
+    and writes it back in the current layout. The state of the `pre_authorization` (including the debited
+    amounts, `last_debited_cycle` and `paused`) is kept and the fields added since v1.0.0 are set
+    such that the `pre_authorization` behaves as it did in v1.0.0 (e.g. no `max_amount_per_debit`).
+    The `pre_authorization` can then be debited, updated, paused and closed again.
+
+    The `payer` MUST sign the transaction.
+    The `payer` MUST have enough lamports to pay for the additional rent of the `pre_authorization`.
+    The `pre_authorization` MUST be a v1.0.0 `pre_authorization` (i.e. not migrated yet).
+
+    Accounts expected by this instruction:
+        0. `[writable]` payer
+        1. `[writable]` pre_authorization
+        2. `[]`         system_program
+    */
+    pub fn migrate_pre_authorization(ctx: Context<MigratePreAuthorization>) -> Result<()> {
+        handle_migrate_pre_authorization(ctx)
+    }
 }
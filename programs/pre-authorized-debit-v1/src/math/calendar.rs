@@ -0,0 +1,434 @@
+/*!
+  Deterministic UTC calendar computations for recurring pre-authorizations with a `CalendarSchedule`.
+  Dates are in the proleptic Gregorian calendar, the conversions between days since the unix epoch
+  and civil dates are the `days_from_civil` / `civil_from_days` algorithms by Howard Hinnant.
+
+  A calendar cycle starts at 00:00:00 UTC on the `day_of_month` (clamped to the last day of shorter months)
+  of every 1 (monthly), 3 (quarterly) or 12 (yearly) months. Cycle 1 is the cycle containing the
+  `activation_unix_timestamp`, i.e. it starts at the latest anchor date at or before the activation.
+  Like the `repeat_frequency_seconds` cycles, the cycles are 1-indexed and the bounds are half-open.
+*/
+
+use super::MathError;
+
+pub const SECONDS_PER_DAY: i64 = 86_400;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CalendarSchedule {
+    // the number of months of a cycle (1 for monthly, 3 for quarterly and 12 for yearly)
+    pub interval_months: u8,
+    // 1 to 31, clamped to the last day of shorter months (e.g. 31 is Feb 28 / Feb 29 and Apr 30)
+    pub day_of_month: u8,
+}
+
+impl CalendarSchedule {
+    pub fn validate(&self) -> Result<(), MathError> {
+        if !(1..=31).contains(&self.day_of_month) {
+            return Err(MathError::InvalidDayOfMonth);
+        }
+        if self.interval_months == 0 {
+            return Err(MathError::InvalidRepeatFrequency);
+        }
+        Ok(())
+    }
+}
+
+pub fn is_leap_year(year: i64) -> bool {
+    year % 4 == 0 && (year % 100 != 0 || year % 400 == 0)
+}
+
+/**
+  Returns the number of days in the (1-indexed) `month` of `year`.
+*/
+pub fn days_in_month(year: i64, month: u32) -> u32 {
+    match month {
+        2 if is_leap_year(year) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/**
+  Returns the number of days since 1970-01-01 of the civil date `year`-`month`-`day`.
+  Returns `None` if the result does not fit in an `i64`.
+*/
+pub fn days_from_civil(year: i64, month: u32, day: u32) -> Option<i64> {
+    let (month, day) = (i64::from(month), i64::from(day));
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year.rem_euclid(400); // [0, 399]
+    let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + day - 1; // [0, 365], March 1st is 0
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year; // [0, 146096]
+    era.checked_mul(146_097)?.checked_add(day_of_era - 719_468)
+}
+
+/**
+  Returns the civil date `(year, month, day)` of the given number of days since 1970-01-01.
+*/
+pub fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    // i128 so that this is defined for every i64
+    let days = i128::from(days) + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days.rem_euclid(146_097); // [0, 146096]
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146_096) / 365; // [0, 399]
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100); // [0, 365]
+    let shifted_month = (5 * day_of_year + 2) / 153; // [0, 11], March is 0
+    let day = (day_of_year - (153 * shifted_month + 2) / 5 + 1) as u32;
+    let month = if shifted_month < 10 {
+        shifted_month + 3
+    } else {
+        shifted_month - 9
+    } as u32;
+    let year = year_of_era + era * 400 + i128::from(month <= 2);
+    // |days| <= i64::MAX so |year| is far below i64::MAX
+    (year as i64, month, day)
+}
+
+// months since year 0 (i.e. year * 12 + month - 1)
+fn compute_month_index(unix_timestamp: i64) -> i64 {
+    let (year, month, _) = civil_from_days(unix_timestamp.div_euclid(SECONDS_PER_DAY));
+    year * 12 + i64::from(month) - 1
+}
+
+// 00:00:00 UTC on the (clamped) day_of_month of the month at month_index
+// (i128 since the anchor of the month of an i64 timestamp does not always fit in an i64)
+fn compute_anchor_seconds(month_index: i64, day_of_month: u8) -> Option<i128> {
+    let year = month_index.div_euclid(12);
+    let month = month_index.rem_euclid(12) as u32 + 1;
+    let day = u32::from(day_of_month).min(days_in_month(year, month));
+    Some(i128::from(days_from_civil(year, month, day)?) * i128::from(SECONDS_PER_DAY))
+}
+
+fn compute_anchor_unix_timestamp(month_index: i64, day_of_month: u8) -> Option<i64> {
+    i64::try_from(compute_anchor_seconds(month_index, day_of_month)?).ok()
+}
+
+// month index of the latest anchor at or before unix_timestamp
+fn compute_anchor_month_index(unix_timestamp: i64, day_of_month: u8) -> Option<i64> {
+    let month_index = compute_month_index(unix_timestamp);
+    if compute_anchor_seconds(month_index, day_of_month)? <= i128::from(unix_timestamp) {
+        Some(month_index)
+    } else {
+        month_index.checked_sub(1)
+    }
+}
+
+/**
+  Returns the (1-indexed) calendar cycle containing `current_unix_timestamp`.
+*/
+pub fn compute_current_calendar_cycle(
+    current_unix_timestamp: i64,
+    activation_unix_timestamp: i64,
+    calendar_schedule: &CalendarSchedule,
+) -> Result<u64, MathError> {
+    calendar_schedule.validate()?;
+    if current_unix_timestamp < activation_unix_timestamp {
+        return Err(MathError::CurrentTimestampBeforeActivation);
+    }
+    let first_month_index =
+        compute_anchor_month_index(activation_unix_timestamp, calendar_schedule.day_of_month)
+            .ok_or(MathError::ArithmeticOverflow)?;
+    let current_month_index =
+        compute_anchor_month_index(current_unix_timestamp, calendar_schedule.day_of_month)
+            .ok_or(MathError::ArithmeticOverflow)?;
+    let elapsed_cycles = (current_month_index - first_month_index)
+        .div_euclid(i64::from(calendar_schedule.interval_months));
+    u64::try_from(elapsed_cycles)
+        .map_err(|_| MathError::ArithmeticUnderflow)?
+        .checked_add(1)
+        .ok_or(MathError::ArithmeticOverflow)
+}
+
+/**
+  Returns the `[start, end)` unix timestamps of the given (1-indexed) calendar `cycle`.
+  Returns `None` if `cycle` is 0, the `calendar_schedule` is invalid or the bounds do not fit in an `i64`.
+*/
+pub fn compute_calendar_cycle_bounds(
+    cycle: u64,
+    activation_unix_timestamp: i64,
+    calendar_schedule: &CalendarSchedule,
+) -> Option<(i64, i64)> {
+    calendar_schedule.validate().ok()?;
+    let months = i64::from(calendar_schedule.interval_months);
+    let first_month_index =
+        compute_anchor_month_index(activation_unix_timestamp, calendar_schedule.day_of_month)?;
+    let start_month_index = i64::try_from(cycle.checked_sub(1)?)
+        .ok()?
+        .checked_mul(months)?
+        .checked_add(first_month_index)?;
+    let end_month_index = start_month_index.checked_add(months)?;
+    Some((
+        compute_anchor_unix_timestamp(start_month_index, calendar_schedule.day_of_month)?,
+        compute_anchor_unix_timestamp(end_month_index, calendar_schedule.day_of_month)?,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    fn unix_timestamp(year: i64, month: u32, day: u32, seconds: i64) -> i64 {
+        days_from_civil(year, month, day).unwrap() * SECONDS_PER_DAY + seconds
+    }
+
+    const MONTHLY: u8 = 1;
+    const QUARTERLY: u8 = 3;
+    const YEARLY: u8 = 12;
+
+    fn schedule(interval_months: u8, day_of_month: u8) -> CalendarSchedule {
+        CalendarSchedule {
+            interval_months,
+            day_of_month,
+        }
+    }
+
+    #[test_case(1900, false)]
+    #[test_case(1970, false)]
+    #[test_case(1972, true)]
+    #[test_case(2000, true)]
+    #[test_case(2023, false)]
+    #[test_case(2024, true)]
+    #[test_case(2100, false)]
+    #[test_case(2400, true)]
+    #[test_case(0, true)]
+    #[test_case(-4, true)]
+    #[test_case(-100, false)]
+    fn is_leap_year_cases(year: i64, expected_res: bool) {
+        assert_eq!(expected_res, is_leap_year(year));
+    }
+
+    #[test_case(2023, 2, 28)]
+    #[test_case(2024, 2, 29)]
+    #[test_case(1900, 2, 28)]
+    #[test_case(2000, 2, 29)]
+    #[test_case(2024, 1, 31)]
+    #[test_case(2024, 4, 30)]
+    #[test_case(2024, 6, 30)]
+    #[test_case(2024, 9, 30)]
+    #[test_case(2024, 11, 30)]
+    #[test_case(2024, 12, 31)]
+    fn days_in_month_cases(year: i64, month: u32, expected_res: u32) {
+        assert_eq!(expected_res, days_in_month(year, month));
+    }
+
+    #[test_case(1970, 1, 1, 0)]
+    #[test_case(1969, 12, 31, -1)]
+    #[test_case(2000, 3, 1, 11_017)]
+    #[test_case(2024, 2, 29, 19_782)]
+    #[test_case(2024, 3, 1, 19_783)]
+    #[test_case(1600, 1, 1, -135_140)]
+    fn days_from_civil_cases(year: i64, month: u32, day: u32, expected_days: i64) {
+        assert_eq!(Some(expected_days), days_from_civil(year, month, day));
+        assert_eq!((year, month, day), civil_from_days(expected_days));
+    }
+
+    #[test]
+    fn civil_from_days_round_trips_every_day_from_1600_to_2400() {
+        let first_day = days_from_civil(1600, 1, 1).unwrap();
+        let last_day = days_from_civil(2400, 12, 31).unwrap();
+        let mut expected = (1600, 1, 1);
+        for days in first_day..=last_day {
+            let (year, month, day) = civil_from_days(days);
+            assert_eq!(expected, (year, month, day));
+            assert_eq!(Some(days), days_from_civil(year, month, day));
+
+            expected = if day < days_in_month(year, month) {
+                (year, month, day + 1)
+            } else if month < 12 {
+                (year, month + 1, 1)
+            } else {
+                (year + 1, 1, 1)
+            };
+        }
+    }
+
+    #[test]
+    fn civil_from_days_extremes() {
+        for days in [i64::MIN, i64::MIN + 1, -1, 0, i64::MAX - 1, i64::MAX] {
+            let (year, month, day) = civil_from_days(days);
+            assert!((1..=12).contains(&month));
+            assert!(day >= 1 && day <= days_in_month(year, month));
+        }
+        assert_eq!(None, days_from_civil(i64::MAX, 12, 31));
+    }
+
+    // monthly on the 31st: the anchor is clamped to the last day of shorter months
+    #[test_case(2023, 1, 31)]
+    #[test_case(2023, 2, 28)]
+    #[test_case(2024, 2, 29)]
+    #[test_case(2024, 4, 30)]
+    #[test_case(2100, 2, 28)]
+    #[test_case(2000, 2, 29)]
+    fn anchor_is_clamped_to_end_of_month(year: i64, month: u32, expected_day: u32) {
+        assert_eq!(
+            Some(unix_timestamp(year, month, expected_day, 0)),
+            compute_anchor_unix_timestamp(year * 12 + i64::from(month) - 1, 31)
+        );
+    }
+
+    // activation: 2024-01-15 12:00, monthly on the 1st (cycle 1 is January 2024)
+    #[test_case(unix_timestamp(2024, 1, 15, 43_200), Ok(1))]
+    #[test_case(unix_timestamp(2024, 1, 31, 86_399), Ok(1))]
+    #[test_case(unix_timestamp(2024, 2, 1, 0), Ok(2))]
+    #[test_case(unix_timestamp(2024, 2, 29, 86_399), Ok(2))]
+    #[test_case(unix_timestamp(2024, 3, 1, 0), Ok(3))]
+    #[test_case(unix_timestamp(2024, 12, 31, 0), Ok(12))]
+    #[test_case(unix_timestamp(2025, 1, 1, 0), Ok(13))]
+    #[test_case(unix_timestamp(2034, 1, 1, 0), Ok(121))]
+    #[test_case(
+        unix_timestamp(2024, 1, 15, 43_199),
+        Err(MathError::CurrentTimestampBeforeActivation)
+    )]
+    fn compute_current_calendar_cycle_monthly(
+        current_unix_timestamp: i64,
+        expected_res: Result<u64, MathError>,
+    ) {
+        assert_eq!(
+            expected_res,
+            compute_current_calendar_cycle(
+                current_unix_timestamp,
+                unix_timestamp(2024, 1, 15, 43_200),
+                &schedule(MONTHLY, 1),
+            )
+        );
+    }
+
+    // activation: 2024-01-31 00:00, monthly on the 31st
+    #[test_case(unix_timestamp(2024, 1, 31, 0), 1)]
+    #[test_case(unix_timestamp(2024, 2, 28, 86_399), 1)]
+    #[test_case(unix_timestamp(2024, 2, 29, 0), 2)]
+    #[test_case(unix_timestamp(2024, 3, 30, 86_399), 2)]
+    #[test_case(unix_timestamp(2024, 3, 31, 0), 3)]
+    #[test_case(unix_timestamp(2024, 4, 30, 0), 4)]
+    #[test_case(unix_timestamp(2024, 5, 30, 86_399), 4)]
+    #[test_case(unix_timestamp(2025, 2, 28, 0), 14)]
+    fn compute_current_calendar_cycle_monthly_end_of_month(
+        current_unix_timestamp: i64,
+        expected_cycle: u64,
+    ) {
+        assert_eq!(
+            Ok(expected_cycle),
+            compute_current_calendar_cycle(
+                current_unix_timestamp,
+                unix_timestamp(2024, 1, 31, 0),
+                &schedule(MONTHLY, 31),
+            )
+        );
+    }
+
+    // activation: 2024-02-29 00:00, yearly on the 29th (of February)
+    #[test_case(unix_timestamp(2024, 2, 29, 0), 1)]
+    #[test_case(unix_timestamp(2025, 2, 27, 86_399), 1)]
+    #[test_case(unix_timestamp(2025, 2, 28, 0), 2)]
+    #[test_case(unix_timestamp(2026, 2, 28, 0), 3)]
+    #[test_case(unix_timestamp(2028, 2, 28, 86_399), 4)]
+    #[test_case(unix_timestamp(2028, 2, 29, 0), 5)]
+    #[test_case(unix_timestamp(2100, 2, 28, 0), 77)]
+    #[test_case(unix_timestamp(2400, 2, 29, 0), 377)]
+    fn compute_current_calendar_cycle_yearly_leap_day(
+        current_unix_timestamp: i64,
+        expected_cycle: u64,
+    ) {
+        assert_eq!(
+            Ok(expected_cycle),
+            compute_current_calendar_cycle(
+                current_unix_timestamp,
+                unix_timestamp(2024, 2, 29, 0),
+                &schedule(YEARLY, 29),
+            )
+        );
+    }
+
+    // activation: 2023-12-10, quarterly on the 15th (cycle 1 starts 2023-11-15)
+    #[test_case(1, (unix_timestamp(2023, 11, 15, 0), unix_timestamp(2024, 2, 15, 0)))]
+    #[test_case(2, (unix_timestamp(2024, 2, 15, 0), unix_timestamp(2024, 5, 15, 0)))]
+    #[test_case(3, (unix_timestamp(2024, 5, 15, 0), unix_timestamp(2024, 8, 15, 0)))]
+    #[test_case(5, (unix_timestamp(2024, 11, 15, 0), unix_timestamp(2025, 2, 15, 0)))]
+    fn compute_calendar_cycle_bounds_quarterly(cycle: u64, expected_bounds: (i64, i64)) {
+        let activation_unix_timestamp = unix_timestamp(2023, 12, 10, 0);
+        let calendar_schedule = schedule(QUARTERLY, 15);
+        assert_eq!(
+            Some(expected_bounds),
+            compute_calendar_cycle_bounds(cycle, activation_unix_timestamp, &calendar_schedule)
+        );
+        // the bounds and the current cycle agree
+        assert_eq!(
+            Ok(cycle),
+            compute_current_calendar_cycle(
+                expected_bounds.0.max(activation_unix_timestamp),
+                activation_unix_timestamp,
+                &calendar_schedule
+            )
+        );
+        assert_eq!(
+            Ok(cycle),
+            compute_current_calendar_cycle(
+                expected_bounds.1 - 1,
+                activation_unix_timestamp,
+                &calendar_schedule
+            )
+        );
+    }
+
+    #[test_case(0, schedule(MONTHLY, 1))]
+    #[test_case(u64::MAX, schedule(MONTHLY, 1))]
+    #[test_case(1, schedule(MONTHLY, 0))]
+    #[test_case(1, schedule(YEARLY, 32))]
+    fn compute_calendar_cycle_bounds_none(cycle: u64, calendar_schedule: CalendarSchedule) {
+        assert_eq!(
+            None,
+            compute_calendar_cycle_bounds(cycle, 0, &calendar_schedule)
+        );
+    }
+
+    #[test_case(schedule(MONTHLY, 0), Err(MathError::InvalidDayOfMonth))]
+    #[test_case(schedule(MONTHLY, 32), Err(MathError::InvalidDayOfMonth))]
+    #[test_case(schedule(0, 1), Err(MathError::InvalidRepeatFrequency))]
+    #[test_case(schedule(MONTHLY, 1), Ok(1))]
+    #[test_case(schedule(QUARTERLY, 31), Ok(1))]
+    fn compute_current_calendar_cycle_validates_schedule(
+        calendar_schedule: CalendarSchedule,
+        expected_res: Result<u64, MathError>,
+    ) {
+        assert_eq!(
+            expected_res,
+            compute_current_calendar_cycle(0, 0, &calendar_schedule)
+        );
+    }
+
+    #[test]
+    fn compute_current_calendar_cycle_before_1970() {
+        // activation: 1969-12-31 23:59:59, monthly on the 1st
+        let calendar_schedule = schedule(MONTHLY, 1);
+        assert_eq!(
+            Ok(1),
+            compute_current_calendar_cycle(-1, -1, &calendar_schedule)
+        );
+        assert_eq!(
+            Ok(2),
+            compute_current_calendar_cycle(0, -1, &calendar_schedule)
+        );
+        assert_eq!(
+            Some((unix_timestamp(1969, 12, 1, 0), 0)),
+            compute_calendar_cycle_bounds(1, -1, &calendar_schedule)
+        );
+    }
+
+    #[test]
+    fn compute_current_calendar_cycle_extremes() {
+        for day_of_month in [1, 15, 31] {
+            let calendar_schedule = schedule(MONTHLY, day_of_month);
+            for activation_unix_timestamp in [i64::MIN, 0, i64::MAX] {
+                assert!(compute_current_calendar_cycle(
+                    i64::MAX,
+                    activation_unix_timestamp,
+                    &calendar_schedule
+                )
+                .is_ok());
+            }
+        }
+    }
+}
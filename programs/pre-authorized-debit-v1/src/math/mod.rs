@@ -0,0 +1,1072 @@
+/*!
+  Pure functions used to validate debits against a `pre_authorization`.
+  Nothing in this module reads sysvars (i.e. `Clock`), every function takes the unix timestamp
+  as an argument so that off-chain clients can compute exactly what the program will allow
+  at any given time.
+
+  Recurring cycles are 1-indexed, cycle `n` spans the half-open interval:
+  [activation + (n - 1) * repeat_frequency_seconds, activation + n * repeat_frequency_seconds).
+  Recurring pre-authorizations with a `CalendarSchedule` use calendar cycles instead (see `calendar`).
+
+  All arithmetic is checked, invalid inputs and overflows are reported as a `MathError`
+  (the program converts it to the `MathError` of the same name).
+
+  This module only depends on `core` (no Anchor, no Solana types): the plain `IntroSchedule`,
+  `ScheduleStep` and `calendar::CalendarSchedule` mirror the types stored in the `pre_authorization`.
+*/
+
+pub mod calendar;
+
+use self::calendar::{
+    compute_calendar_cycle_bounds, compute_current_calendar_cycle, CalendarSchedule,
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MathError {
+    ArithmeticOverflow,
+    ArithmeticUnderflow,
+    CurrentTimestampBeforeActivation,
+    InvalidAmountDebited,
+    InvalidCycle,
+    InvalidDayOfMonth,
+    InvalidRepeatFrequency,
+    LastDebitedCycleBeforeCurrentCycle,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct IntroSchedule {
+    // the number of cycles (from the first cycle) authorized "intro_amount_authorized"
+    pub num_intro_cycles: u64,
+    // the amount authorized in each intro cycle (replaces "recurring_amount_authorized")
+    pub intro_amount_authorized: u64,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ScheduleStep {
+    // the (1-indexed) cycle from which this step applies
+    pub cycle_start: u64,
+    // the amount authorized in each cycle of this step
+    pub amount_authorized: u64,
+}
+
+pub fn compute_available_amount_for_one_time_debit(
+    amount_authorized: u64,
+    amount_debited: u64,
+) -> Result<u64, MathError> {
+    amount_authorized
+        .checked_sub(amount_debited)
+        .ok_or(MathError::ArithmeticUnderflow)
+}
+
+pub fn compute_available_amount_for_recurring_debit(
+    current_cycle: u64,
+    last_debited_cycle: u64,
+    reset_every_cycle: bool,
+    recurring_amount_authorized: u64,
+    amount_debited_last_cycle: u64,
+    amount_debited_total: u64,
+    amended_at_cycle: u64,
+    amount_authorized_before_amendment: u64,
+    intro_schedule: Option<&IntroSchedule>,
+) -> Result<u64, MathError> {
+    if current_cycle == 0 || last_debited_cycle == 0 {
+        return Err(MathError::InvalidCycle);
+    }
+    if current_cycle < last_debited_cycle {
+        return Err(MathError::LastDebitedCycleBeforeCurrentCycle);
+    }
+    if amount_debited_last_cycle > amount_debited_total {
+        return Err(MathError::InvalidAmountDebited);
+    }
+    match (reset_every_cycle, current_cycle == last_debited_cycle) {
+        (false, _) => compute_cumulative_amount_authorized(
+            current_cycle,
+            recurring_amount_authorized,
+            amended_at_cycle,
+            amount_authorized_before_amendment,
+            intro_schedule,
+        )?
+        .checked_sub(amount_debited_total)
+        .ok_or(MathError::ArithmeticUnderflow),
+        (true, false) => Ok(compute_amount_authorized_for_cycle(
+            current_cycle,
+            recurring_amount_authorized,
+            intro_schedule,
+        )),
+        (true, true) => compute_amount_authorized_for_cycle(
+            current_cycle,
+            recurring_amount_authorized,
+            intro_schedule,
+        )
+        .checked_sub(amount_debited_last_cycle)
+        .ok_or(MathError::ArithmeticUnderflow),
+    }
+}
+
+/**
+  Returns the amount authorized in the given (1-indexed) `cycle`: the `intro_amount_authorized` during the
+  first `num_intro_cycles` cycles of the `intro_schedule` (if set), the `recurring_amount_authorized` thereafter.
+*/
+pub fn compute_amount_authorized_for_cycle(
+    cycle: u64,
+    recurring_amount_authorized: u64,
+    intro_schedule: Option<&IntroSchedule>,
+) -> u64 {
+    match intro_schedule {
+        Some(intro_schedule) if cycle <= intro_schedule.num_intro_cycles => {
+            intro_schedule.intro_amount_authorized
+        }
+        _ => recurring_amount_authorized,
+    }
+}
+
+/**
+  Returns the total amount authorized across cycles 1 through `cycle` (inclusive) for a recurring
+  pre-authorization that accrues unused amounts (i.e. `reset_every_cycle == false`).
+  The `recurring_amount_authorized` applies from `amended_at_cycle` onwards, the cycles before it
+  are accounted for by `amount_authorized_before_amendment`.
+  The intro cycles of the `intro_schedule` (if set) from `amended_at_cycle` onwards are authorized
+  the `intro_amount_authorized` instead (see `compute_amount_authorized_for_cycle`).
+*/
+pub fn compute_cumulative_amount_authorized(
+    cycle: u64,
+    recurring_amount_authorized: u64,
+    amended_at_cycle: u64,
+    amount_authorized_before_amendment: u64,
+    intro_schedule: Option<&IntroSchedule>,
+) -> Result<u64, MathError> {
+    let cycles_before_amendment = amended_at_cycle
+        .checked_sub(1)
+        .ok_or(MathError::InvalidCycle)?;
+    let cycles_since_amendment = cycle
+        .checked_sub(cycles_before_amendment)
+        .ok_or(MathError::InvalidCycle)?;
+    let (intro_cycles_since_amendment, intro_amount_authorized) =
+        intro_schedule.map_or((0, 0), |intro_schedule| {
+            (
+                intro_schedule
+                    .num_intro_cycles
+                    .min(cycle)
+                    .saturating_sub(cycles_before_amendment),
+                intro_schedule.intro_amount_authorized,
+            )
+        });
+    intro_amount_authorized
+        .checked_mul(intro_cycles_since_amendment)
+        .and_then(|intro_amount| {
+            recurring_amount_authorized
+                .checked_mul(cycles_since_amendment - intro_cycles_since_amendment)?
+                .checked_add(intro_amount)
+        })
+        .and_then(|amount| amount.checked_add(amount_authorized_before_amendment))
+        .ok_or(MathError::ArithmeticOverflow)
+}
+
+/**
+  Returns the index of the step that applies in the given (1-indexed) `cycle`:
+  the last step with `cycle_start <= cycle` (the `steps` are sorted by `cycle_start`).
+*/
+pub fn compute_active_schedule_step(
+    cycle: u64,
+    steps: &[impl Copy + Into<ScheduleStep>],
+) -> Result<usize, MathError> {
+    steps
+        .partition_point(|&step| Into::<ScheduleStep>::into(step).cycle_start <= cycle)
+        .checked_sub(1)
+        .ok_or(MathError::InvalidCycle)
+}
+
+/**
+  Returns the total amount authorized across cycles 1 through `num_cycles` (inclusive) by the `steps`
+  of a scheduled pre-authorization (see `compute_active_schedule_step`).
+*/
+pub fn compute_scheduled_total_amount_authorized(
+    num_cycles: u64,
+    steps: &[impl Copy + Into<ScheduleStep>],
+) -> Result<u64, MathError> {
+    steps
+        .iter()
+        .map(|&step| Into::<ScheduleStep>::into(step))
+        .enumerate()
+        .take_while(|(_, step)| step.cycle_start <= num_cycles)
+        .try_fold(0u64, |total_amount, (i, step)| {
+            let step_end = steps.get(i + 1).map_or(num_cycles, |&next_step| {
+                num_cycles.min(
+                    Into::<ScheduleStep>::into(next_step)
+                        .cycle_start
+                        .saturating_sub(1),
+                )
+            });
+            let step_cycles = step_end
+                .checked_sub(step.cycle_start)
+                .and_then(|step_cycles| step_cycles.checked_add(1))
+                .ok_or(MathError::InvalidCycle)?;
+            step.amount_authorized
+                .checked_mul(step_cycles)
+                .and_then(|step_amount| step_amount.checked_add(total_amount))
+                .ok_or(MathError::ArithmeticOverflow)
+        })
+}
+
+/**
+  Returns the total amount of the first `installments` installments of an installment plan:
+  each installment is `total_amount / installment_count`, the last installment also includes the remainder
+  (i.e. all the `installment_count` installments add up to exactly `total_amount`).
+*/
+pub fn compute_cumulative_installment_amount(
+    installments: u64,
+    total_amount: u64,
+    installment_count: u64,
+) -> Result<u64, MathError> {
+    if installment_count == 0 || installments > installment_count {
+        return Err(MathError::InvalidCycle);
+    }
+    if installments == installment_count {
+        return Ok(total_amount);
+    }
+    // cannot overflow since installments < installment_count
+    Ok(total_amount / installment_count * installments)
+}
+
+/**
+  Returns the number of installments of an installment plan fully paid by `amount_debited`
+  (see `compute_cumulative_installment_amount`).
+*/
+pub fn compute_installments_paid(
+    amount_debited: u64,
+    total_amount: u64,
+    installment_count: u64,
+) -> Result<u64, MathError> {
+    if installment_count == 0 {
+        return Err(MathError::InvalidCycle);
+    }
+    if amount_debited >= total_amount {
+        return Ok(installment_count);
+    }
+    match total_amount / installment_count {
+        // all but the last installment are 0
+        0 => Ok(installment_count - 1),
+        installment_amount => Ok((amount_debited / installment_amount).min(installment_count - 1)),
+    }
+}
+
+/**
+  Returns the number of installments of an installment plan due at `current_unix_timestamp`:
+  installment `n` (1-indexed) is due at `activation + (n - 1) * interval_seconds`.
+  Returns 0 before `activation_unix_timestamp`.
+*/
+pub fn compute_installments_due(
+    current_unix_timestamp: i64,
+    activation_unix_timestamp: i64,
+    interval_seconds: u64,
+    installment_count: u64,
+) -> Result<u64, MathError> {
+    if current_unix_timestamp < activation_unix_timestamp {
+        return Ok(0);
+    }
+    Ok(compute_current_cycle(
+        current_unix_timestamp,
+        activation_unix_timestamp,
+        interval_seconds,
+    )?
+    .min(installment_count))
+}
+
+/**
+  Returns the total amount debited in the trailing rolling window at `current_bucket`, i.e. in the
+  `current_bucket` and the `debited_buckets.len() - 1` preceding buckets.
+  Bucket `n` (1-indexed) is stored at index `n % debited_buckets.len()` and the `debited_buckets`
+  only hold the buckets up to `last_debited_bucket`.
+*/
+pub fn compute_rolling_window_amount_debited(
+    current_bucket: u64,
+    last_debited_bucket: u64,
+    debited_buckets: &[u64],
+) -> Result<u64, MathError> {
+    if current_bucket < last_debited_bucket {
+        return Err(MathError::LastDebitedCycleBeforeCurrentCycle);
+    }
+    let ring_size = debited_buckets.len() as u64;
+    if ring_size == 0 {
+        return Err(MathError::InvalidCycle);
+    }
+    // buckets are 1-indexed
+    let first_bucket_in_window = current_bucket.saturating_sub(ring_size - 1).max(1);
+    (first_bucket_in_window..=last_debited_bucket).try_fold(0u64, |amount_debited, bucket| {
+        amount_debited
+            .checked_add(debited_buckets[(bucket % ring_size) as usize])
+            .ok_or(MathError::ArithmeticOverflow)
+    })
+}
+
+/**
+  Returns the maximum amount that can be available at once for a recurring pre-authorization
+  that accrues unused amounts (i.e. `reset_every_cycle == false`): `max_accrued_cycles` cycles
+  worth of `recurring_amount_authorized` (the amount authorized in the current cycle, see
+  `compute_amount_authorized_for_cycle`).
+  Returns `None` if there is no cap (`max_accrued_cycles` is not set or the cap does not fit in a `u64`).
+*/
+pub fn compute_max_accrued_amount(
+    recurring_amount_authorized: u64,
+    max_accrued_cycles: Option<u64>,
+) -> Option<u64> {
+    recurring_amount_authorized.checked_mul(max_accrued_cycles?)
+}
+
+pub fn compute_current_cycle(
+    current_unix_timestamp: i64,
+    activation_unix_timestamp: i64,
+    repeat_frequency_seconds: u64,
+) -> Result<u64, MathError> {
+    if repeat_frequency_seconds == 0 {
+        return Err(MathError::InvalidRepeatFrequency);
+    }
+    if current_unix_timestamp < activation_unix_timestamp {
+        return Err(MathError::CurrentTimestampBeforeActivation);
+    }
+    // i128 since the difference of two i64s does not always fit in an i64
+    let seconds_since_activation =
+        u64::try_from(i128::from(current_unix_timestamp) - i128::from(activation_unix_timestamp))
+            .map_err(|_| MathError::ArithmeticOverflow)?;
+    (seconds_since_activation / repeat_frequency_seconds)
+        .checked_add(1)
+        .ok_or(MathError::ArithmeticOverflow)
+}
+
+/**
+  Returns the `[start, end)` unix timestamps of the given (1-indexed) `cycle`.
+  Returns `None` if `cycle` is 0 or the bounds do not fit in an `i64`.
+*/
+pub fn compute_cycle_bounds(
+    cycle: u64,
+    activation_unix_timestamp: i64,
+    repeat_frequency_seconds: u64,
+) -> Option<(i64, i64)> {
+    let cycle_start =
+        compute_cycle_start(cycle, activation_unix_timestamp, repeat_frequency_seconds)?;
+    let cycle_end = cycle_start.checked_add(i64::try_from(repeat_frequency_seconds).ok()?)?;
+    Some((cycle_start, cycle_end))
+}
+
+/**
+  Same as `compute_current_cycle`, with the cycles defined by the `calendar_schedule` (if set)
+  instead of `repeat_frequency_seconds`.
+*/
+pub fn compute_current_scheduled_cycle(
+    current_unix_timestamp: i64,
+    activation_unix_timestamp: i64,
+    repeat_frequency_seconds: u64,
+    calendar_schedule: Option<&CalendarSchedule>,
+) -> Result<u64, MathError> {
+    match calendar_schedule {
+        Some(calendar_schedule) => compute_current_calendar_cycle(
+            current_unix_timestamp,
+            activation_unix_timestamp,
+            calendar_schedule,
+        ),
+        None => compute_current_cycle(
+            current_unix_timestamp,
+            activation_unix_timestamp,
+            repeat_frequency_seconds,
+        ),
+    }
+}
+
+/**
+  Same as `compute_cycle_bounds`, with the cycles defined by the `calendar_schedule` (if set)
+  instead of `repeat_frequency_seconds`.
+*/
+pub fn compute_scheduled_cycle_bounds(
+    cycle: u64,
+    activation_unix_timestamp: i64,
+    repeat_frequency_seconds: u64,
+    calendar_schedule: Option<&CalendarSchedule>,
+) -> Option<(i64, i64)> {
+    match calendar_schedule {
+        Some(calendar_schedule) => {
+            compute_calendar_cycle_bounds(cycle, activation_unix_timestamp, calendar_schedule)
+        }
+        None => compute_cycle_bounds(cycle, activation_unix_timestamp, repeat_frequency_seconds),
+    }
+}
+
+/**
+  Returns the `[start, end)` unix timestamps during which debits can occur in a cycle with the given `cycle_bounds`:
+  starting `debit_window_offset_seconds` after the cycle start and lasting `debit_window_seconds`
+  (the window never extends past the end of the cycle).
+  Returns `None` if the window does not fit in the cycle.
+*/
+pub fn compute_debit_window_bounds(
+    cycle_bounds: (i64, i64),
+    debit_window_offset_seconds: u64,
+    debit_window_seconds: u64,
+) -> Option<(i64, i64)> {
+    let (cycle_start, cycle_end) = cycle_bounds;
+    let window_start = cycle_start.checked_add(i64::try_from(debit_window_offset_seconds).ok()?)?;
+    if window_start >= cycle_end {
+        return None;
+    }
+    let window_end = i64::try_from(debit_window_seconds)
+        .ok()
+        .and_then(|debit_window_seconds| window_start.checked_add(debit_window_seconds))
+        .map_or(cycle_end, |window_end| window_end.min(cycle_end));
+    Some((window_start, window_end))
+}
+
+fn compute_cycle_start(
+    cycle: u64,
+    activation_unix_timestamp: i64,
+    repeat_frequency_seconds: u64,
+) -> Option<i64> {
+    let seconds_since_activation = cycle
+        .checked_sub(1)?
+        .checked_mul(repeat_frequency_seconds)?;
+    activation_unix_timestamp.checked_add(i64::try_from(seconds_since_activation).ok()?)
+}
+
+/**
+  Returns the amount to approve to the `smart_delegate` in bounded delegation mode when the outstanding amount
+  authorized of a pre-authorization changes from `old_outstanding_amount` to `new_outstanding_amount`
+  (0 for a created or closed pre-authorization).
+  The `current_delegated_amount` is expected to be the sum of the outstanding amounts of all pre-authorizations
+  (debits decrease both by the same amount).
+*/
+pub fn compute_bounded_delegated_amount(
+    current_delegated_amount: u64,
+    old_outstanding_amount: u64,
+    new_outstanding_amount: u64,
+) -> Result<u64, MathError> {
+    current_delegated_amount
+        .saturating_sub(old_outstanding_amount)
+        .checked_add(new_outstanding_amount)
+        .ok_or(MathError::ArithmeticOverflow)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    // recurring pre-auth (available amount accrues across cycles)
+    #[test_case(1, 1, 0, 0, 0, 0)]
+    #[test_case(1, 1, 100, 0, 0, 100)]
+    #[test_case(1, 1, 100, 100, 100, 0)]
+    #[test_case(5, 1, 100, 0, 0, 500)]
+    #[test_case(5, 1, 100, 100, 100, 400)]
+    #[test_case(5, 4, 100, 100, 100, 400)]
+    #[test_case(5, 4, 100, 100, 400, 100)]
+    #[test_case(5, 4, 100, 400, 400, 100)]
+    #[test_case(5, 5, 100, 100, 100, 400)]
+    #[test_case(5, 5, 100, 100, 400, 100)]
+    #[test_case(5, 5, 100, 400, 400, 100)]
+    #[test_case(5, 5, 100, 0, 500, 0)]
+    #[test_case(5, 5, 100, 400, 500, 0)]
+    #[test_case(5, 5, 100, 500, 500, 0)]
+    #[test_case(5, 5, 100, 0, 100, 400)]
+    fn compute_available_amount_for_recurring_debit_cumulative_happy_path(
+        current_cycle: u64,
+        last_debited_cycle: u64,
+        recurring_amount_authorized: u64,
+        amount_debited_last_cycle: u64,
+        amount_debited_total: u64,
+        expected_amount_available: u64,
+    ) {
+        assert_eq!(
+            Ok(expected_amount_available),
+            compute_available_amount_for_recurring_debit(
+                current_cycle,
+                last_debited_cycle,
+                false,
+                recurring_amount_authorized,
+                amount_debited_last_cycle,
+                amount_debited_total,
+                1,
+                0,
+                None,
+            )
+        );
+    }
+
+    // recurring pre-auth (available amount resets every cycle)
+    #[test_case(1, 1, 0, 0, 0, 0)]
+    #[test_case(1, 1, 100, 0, 0, 100)]
+    #[test_case(1, 1, 100, 100, 100, 0)]
+    #[test_case(5, 1, 100, 0, 0, 100)]
+    #[test_case(5, 1, 100, 100, 100, 100)]
+    #[test_case(5, 4, 100, 100, 100, 100)]
+    #[test_case(5, 4, 100, 100, 400, 100)]
+    #[test_case(5, 4, 100, 400, 400, 100)]
+    #[test_case(5, 5, 100, 100, 100, 0)]
+    #[test_case(5, 5, 100, 100, 400, 0)]
+    #[test_case(5, 5, 100, 0, 500, 100)]
+    #[test_case(5, 5, 100, 0, 100, 100)]
+    fn compute_available_amount_for_recurring_debit_noncumulative_happy_path(
+        current_cycle: u64,
+        last_debited_cycle: u64,
+        recurring_amount_authorized: u64,
+        amount_debited_last_cycle: u64,
+        amount_debited_total: u64,
+        expected_amount_available: u64,
+    ) {
+        assert_eq!(
+            Ok(expected_amount_available),
+            compute_available_amount_for_recurring_debit(
+                current_cycle,
+                last_debited_cycle,
+                true,
+                recurring_amount_authorized,
+                amount_debited_last_cycle,
+                amount_debited_total,
+                1,
+                0,
+                None,
+            )
+        );
+    }
+
+    // recurring pre-auth (available amount accrues across cycles)
+    // invalid state
+    #[test_case(0, 1, 0, 0, 0, MathError::InvalidCycle)]
+    #[test_case(1, 0, 0, 0, 0, MathError::InvalidCycle)]
+    #[test_case(1, 2, 0, 0, 0, MathError::LastDebitedCycleBeforeCurrentCycle)]
+    #[test_case(1, 1, 10, 10, 5, MathError::InvalidAmountDebited)]
+    // other cases
+    #[test_case(1, 1, 100, 100, 0, MathError::InvalidAmountDebited)]
+    #[test_case(5, 1, 100, 100, 0, MathError::InvalidAmountDebited)]
+    #[test_case(5, 1, 100, 100, 600, MathError::ArithmeticUnderflow)]
+    #[test_case(5, 4, 100, 100, 600, MathError::ArithmeticUnderflow)]
+    #[test_case(5, 5, 100, 100, 600, MathError::ArithmeticUnderflow)]
+    #[test_case(u64::MAX, 1, 2, 0, 0, MathError::ArithmeticOverflow)]
+    fn compute_available_amount_for_recurring_debit_cumulative_errors(
+        current_cycle: u64,
+        last_debited_cycle: u64,
+        recurring_amount_authorized: u64,
+        amount_debited_last_cycle: u64,
+        amount_debited_total: u64,
+        expected_err: MathError,
+    ) {
+        assert_eq!(
+            Err(expected_err),
+            compute_available_amount_for_recurring_debit(
+                current_cycle,
+                last_debited_cycle,
+                false,
+                recurring_amount_authorized,
+                amount_debited_last_cycle,
+                amount_debited_total,
+                1,
+                0,
+                None,
+            )
+        );
+    }
+
+    // recurring pre-auth (available amount resets every cycle)
+    // invalid state
+    #[test_case(0, 1, 0, 0, 0, MathError::InvalidCycle)]
+    #[test_case(1, 0, 0, 0, 0, MathError::InvalidCycle)]
+    #[test_case(1, 2, 0, 0, 0, MathError::LastDebitedCycleBeforeCurrentCycle)]
+    #[test_case(1, 1, 10, 10, 5, MathError::InvalidAmountDebited)]
+    // other cases
+    #[test_case(5, 5, 100, 400, 400, MathError::ArithmeticUnderflow)]
+    #[test_case(5, 5, 100, 400, 500, MathError::ArithmeticUnderflow)]
+    #[test_case(5, 5, 100, 500, 500, MathError::ArithmeticUnderflow)]
+    fn compute_available_amount_for_recurring_debit_noncumulative_errors(
+        current_cycle: u64,
+        last_debited_cycle: u64,
+        recurring_amount_authorized: u64,
+        amount_debited_last_cycle: u64,
+        amount_debited_total: u64,
+        expected_err: MathError,
+    ) {
+        assert_eq!(
+            Err(expected_err),
+            compute_available_amount_for_recurring_debit(
+                current_cycle,
+                last_debited_cycle,
+                true,
+                recurring_amount_authorized,
+                amount_debited_last_cycle,
+                amount_debited_total,
+                1,
+                0,
+                None,
+            )
+        );
+    }
+
+    // recurring pre-auth (available amount accrues across cycles) amended at a later cycle
+    #[test_case(3, 3, 50, 0, 0, 3, 200, 250)]
+    #[test_case(3, 3, 50, 20, 220, 3, 200, 30)]
+    #[test_case(5, 3, 50, 20, 220, 3, 200, 130)]
+    #[test_case(5, 5, 50, 50, 270, 3, 200, 80)]
+    #[test_case(5, 5, 0, 0, 200, 3, 200, 0)]
+    fn compute_available_amount_for_recurring_debit_cumulative_amended(
+        current_cycle: u64,
+        last_debited_cycle: u64,
+        recurring_amount_authorized: u64,
+        amount_debited_last_cycle: u64,
+        amount_debited_total: u64,
+        amended_at_cycle: u64,
+        amount_authorized_before_amendment: u64,
+        expected_amount_available: u64,
+    ) {
+        assert_eq!(
+            Ok(expected_amount_available),
+            compute_available_amount_for_recurring_debit(
+                current_cycle,
+                last_debited_cycle,
+                false,
+                recurring_amount_authorized,
+                amount_debited_last_cycle,
+                amount_debited_total,
+                amended_at_cycle,
+                amount_authorized_before_amendment,
+                None,
+            )
+        );
+    }
+
+    #[test_case(0, 100, 1, 0, Ok(0))]
+    #[test_case(5, 100, 1, 0, Ok(500))]
+    #[test_case(2, 100, 3, 250, Ok(250))]
+    #[test_case(3, 100, 3, 250, Ok(350))]
+    #[test_case(5, 100, 3, 250, Ok(550))]
+    #[test_case(1, 100, 3, 250, Err(MathError::InvalidCycle))]
+    #[test_case(1, 100, 0, 0, Err(MathError::InvalidCycle))]
+    #[test_case(u64::MAX, 2, 1, 0, Err(MathError::ArithmeticOverflow))]
+    #[test_case(1, 1, 1, u64::MAX, Err(MathError::ArithmeticOverflow))]
+    fn compute_cumulative_amount_authorized_cases(
+        cycle: u64,
+        recurring_amount_authorized: u64,
+        amended_at_cycle: u64,
+        amount_authorized_before_amendment: u64,
+        expected_res: Result<u64, MathError>,
+    ) {
+        assert_eq!(
+            expected_res,
+            compute_cumulative_amount_authorized(
+                cycle,
+                recurring_amount_authorized,
+                amended_at_cycle,
+                amount_authorized_before_amendment,
+                None,
+            )
+        );
+    }
+
+    // intro: 2 cycles at 10, then 100 per cycle
+    #[test_case(1, 1, 10)]
+    #[test_case(2, 1, 20)]
+    #[test_case(3, 1, 120)]
+    #[test_case(5, 1, 320)]
+    #[test_case(5, 2, 310)]
+    #[test_case(5, 3, 300)]
+    #[test_case(5, 4, 200)]
+    #[test_case(2, 3, 0)]
+    fn compute_cumulative_amount_authorized_intro(
+        cycle: u64,
+        amended_at_cycle: u64,
+        expected_amount: u64,
+    ) {
+        let intro_schedule = IntroSchedule {
+            num_intro_cycles: 2,
+            intro_amount_authorized: 10,
+        };
+        assert_eq!(
+            Ok(expected_amount),
+            compute_cumulative_amount_authorized(
+                cycle,
+                100,
+                amended_at_cycle,
+                0,
+                Some(&intro_schedule),
+            )
+        );
+    }
+
+    #[test]
+    fn compute_cumulative_amount_authorized_intro_overflow() {
+        let intro_schedule = IntroSchedule {
+            num_intro_cycles: 2,
+            intro_amount_authorized: u64::MAX / 2 + 1,
+        };
+        assert_eq!(
+            Ok(u64::MAX / 2 + 1),
+            compute_cumulative_amount_authorized(1, 1, 1, 0, Some(&intro_schedule))
+        );
+        assert_eq!(
+            Err(MathError::ArithmeticOverflow),
+            compute_cumulative_amount_authorized(2, 1, 1, 0, Some(&intro_schedule))
+        );
+    }
+
+    // intro: 2 free cycles, then 100 per cycle
+    #[test_case(false, 1, 1, 0, 0, 0)]
+    #[test_case(false, 2, 1, 0, 0, 0)]
+    #[test_case(false, 3, 1, 0, 0, 100)]
+    #[test_case(false, 4, 3, 30, 30, 170)]
+    #[test_case(true, 2, 1, 0, 0, 0)]
+    #[test_case(true, 3, 1, 0, 0, 100)]
+    #[test_case(true, 3, 3, 30, 30, 70)]
+    fn compute_available_amount_for_recurring_debit_intro(
+        reset_every_cycle: bool,
+        current_cycle: u64,
+        last_debited_cycle: u64,
+        amount_debited_last_cycle: u64,
+        amount_debited_total: u64,
+        expected_amount_available: u64,
+    ) {
+        let intro_schedule = IntroSchedule {
+            num_intro_cycles: 2,
+            intro_amount_authorized: 0,
+        };
+        assert_eq!(
+            Ok(expected_amount_available),
+            compute_available_amount_for_recurring_debit(
+                current_cycle,
+                last_debited_cycle,
+                reset_every_cycle,
+                100,
+                amount_debited_last_cycle,
+                amount_debited_total,
+                1,
+                0,
+                Some(&intro_schedule),
+            )
+        );
+    }
+
+    #[test_case(100, 30, Ok(70))]
+    #[test_case(100, 100, Ok(0))]
+    #[test_case(100, 101, Err(MathError::ArithmeticUnderflow))]
+    fn compute_available_amount_for_one_time_debit_cases(
+        amount_authorized: u64,
+        amount_debited: u64,
+        expected_res: Result<u64, MathError>,
+    ) {
+        assert_eq!(
+            expected_res,
+            compute_available_amount_for_one_time_debit(amount_authorized, amount_debited)
+        );
+    }
+
+    #[test_case(100, 100, 1, 1)]
+    #[test_case(101, 100, 1, 2)]
+    #[test_case(102, 100, 1, 3)]
+    #[test_case(98, 0, 33, 3)]
+    #[test_case(100, 0, 33, 4)]
+    #[test_case(100, -100, 33, 7)]
+    #[test_case(i64::MAX, 0, 1, u64::try_from(i64::MAX).unwrap() + 1)]
+    fn compute_current_cycle_happy_path(
+        current_unix_timestamp: i64,
+        activation_unix_timestamp: i64,
+        repeat_frequency_seconds: u64,
+        expected_res: u64,
+    ) {
+        assert_eq!(
+            Ok(expected_res),
+            compute_current_cycle(
+                current_unix_timestamp,
+                activation_unix_timestamp,
+                repeat_frequency_seconds
+            )
+        );
+    }
+
+    #[test_case(0, 100, 1, MathError::CurrentTimestampBeforeActivation)]
+    #[test_case(-1, 100, 1, MathError::CurrentTimestampBeforeActivation)]
+    #[test_case(99, 100, 1, MathError::CurrentTimestampBeforeActivation)]
+    #[test_case(100, 100, 0, MathError::InvalidRepeatFrequency)]
+    #[test_case(i64::MAX, i64::MIN, 1, MathError::ArithmeticOverflow)]
+    fn compute_current_cycle_errors(
+        current_unix_timestamp: i64,
+        activation_unix_timestamp: i64,
+        repeat_frequency_seconds: u64,
+        expected_err: MathError,
+    ) {
+        assert_eq!(
+            Err(expected_err),
+            compute_current_cycle(
+                current_unix_timestamp,
+                activation_unix_timestamp,
+                repeat_frequency_seconds,
+            )
+        );
+    }
+
+    #[test_case(1, 100, 10, Some((100, 110)))]
+    #[test_case(2, 100, 10, Some((110, 120)))]
+    #[test_case(7, -100, 33, Some((98, 131)))]
+    #[test_case(0, 100, 10, None)]
+    #[test_case(2, i64::MAX - 5, 10, None)]
+    #[test_case(1, 0, u64::MAX, None)]
+    #[test_case(u64::MAX, 0, 2, None)]
+    fn compute_cycle_bounds_cases(
+        cycle: u64,
+        activation_unix_timestamp: i64,
+        repeat_frequency_seconds: u64,
+        expected_res: Option<(i64, i64)>,
+    ) {
+        assert_eq!(
+            expected_res,
+            compute_cycle_bounds(cycle, activation_unix_timestamp, repeat_frequency_seconds)
+        );
+    }
+
+    // every timestamp in a cycle's bounds maps back to that cycle
+    #[test_case(1, 100, 1)]
+    #[test_case(3, 100, 10)]
+    #[test_case(7, -100, 33)]
+    fn compute_cycle_bounds_round_trip(
+        cycle: u64,
+        activation_unix_timestamp: i64,
+        repeat_frequency_seconds: u64,
+    ) {
+        let (start, end) =
+            compute_cycle_bounds(cycle, activation_unix_timestamp, repeat_frequency_seconds)
+                .unwrap();
+        for timestamp in start..end {
+            assert_eq!(
+                Ok(cycle),
+                compute_current_cycle(
+                    timestamp,
+                    activation_unix_timestamp,
+                    repeat_frequency_seconds
+                )
+            );
+        }
+        assert_eq!(
+            Ok(cycle + 1),
+            compute_current_cycle(end, activation_unix_timestamp, repeat_frequency_seconds)
+        );
+    }
+
+    #[test_case(100, None, None)]
+    #[test_case(100, Some(1), Some(100))]
+    #[test_case(100, Some(3), Some(300))]
+    #[test_case(0, Some(3), Some(0))]
+    #[test_case(u64::MAX, Some(1), Some(u64::MAX))]
+    #[test_case(u64::MAX, Some(2), None)]
+    fn compute_max_accrued_amount_cases(
+        recurring_amount_authorized: u64,
+        max_accrued_cycles: Option<u64>,
+        expected_res: Option<u64>,
+    ) {
+        assert_eq!(
+            expected_res,
+            compute_max_accrued_amount(recurring_amount_authorized, max_accrued_cycles)
+        );
+    }
+
+    #[test]
+    fn scheduled_cycle_uses_calendar_schedule_if_set() {
+        let calendar_schedule = CalendarSchedule {
+            interval_months: 1,
+            day_of_month: 1,
+        };
+        // 1970-01-01 to 1970-02-01 is cycle 1 (31 days)
+        assert_eq!(
+            Ok(1),
+            compute_current_scheduled_cycle(30 * 86_400, 0, 0, Some(&calendar_schedule))
+        );
+        assert_eq!(
+            Some((0, 31 * 86_400)),
+            compute_scheduled_cycle_bounds(1, 0, 0, Some(&calendar_schedule))
+        );
+        assert_eq!(
+            Ok(31),
+            compute_current_scheduled_cycle(30 * 86_400, 0, 86_400, None)
+        );
+        assert_eq!(
+            Some((86_400, 2 * 86_400)),
+            compute_scheduled_cycle_bounds(2, 0, 86_400, None)
+        );
+    }
+
+    #[test_case((100, 200), 0, 10, Some((100, 110)))]
+    #[test_case((100, 200), 20, 10, Some((120, 130)))]
+    #[test_case((100, 200), 95, 10, Some((195, 200)))]
+    #[test_case((100, 200), 0, u64::MAX, Some((100, 200)))]
+    #[test_case((100, 200), 99, 1, Some((199, 200)))]
+    #[test_case((100, 200), 100, 1, None)]
+    #[test_case((100, 200), u64::MAX, 1, None)]
+    #[test_case((i64::MAX - 10, i64::MAX), 5, u64::MAX, Some((i64::MAX - 5, i64::MAX)))]
+    fn compute_debit_window_bounds_cases(
+        cycle_bounds: (i64, i64),
+        debit_window_offset_seconds: u64,
+        debit_window_seconds: u64,
+        expected_res: Option<(i64, i64)>,
+    ) {
+        assert_eq!(
+            expected_res,
+            compute_debit_window_bounds(
+                cycle_bounds,
+                debit_window_offset_seconds,
+                debit_window_seconds
+            )
+        );
+    }
+
+    fn steps(steps: &[(u64, u64)]) -> Vec<ScheduleStep> {
+        steps
+            .iter()
+            .map(|&(cycle_start, amount_authorized)| ScheduleStep {
+                cycle_start,
+                amount_authorized,
+            })
+            .collect()
+    }
+
+    #[test_case(1, &[(1, 10)], Ok(0))]
+    #[test_case(u64::MAX, &[(1, 10)], Ok(0))]
+    #[test_case(2, &[(1, 10), (3, 20), (6, 30)], Ok(0))]
+    #[test_case(3, &[(1, 10), (3, 20), (6, 30)], Ok(1))]
+    #[test_case(5, &[(1, 10), (3, 20), (6, 30)], Ok(1))]
+    #[test_case(6, &[(1, 10), (3, 20), (6, 30)], Ok(2))]
+    #[test_case(0, &[(1, 10), (3, 20), (6, 30)], Err(MathError::InvalidCycle))]
+    #[test_case(1, &[], Err(MathError::InvalidCycle))]
+    fn compute_active_schedule_step_cases(
+        cycle: u64,
+        schedule_steps: &[(u64, u64)],
+        expected_res: Result<usize, MathError>,
+    ) {
+        assert_eq!(
+            expected_res,
+            compute_active_schedule_step(cycle, &steps(schedule_steps))
+        );
+    }
+
+    #[test_case(1, &[(1, 10)], Ok(10))]
+    #[test_case(12, &[(1, 10)], Ok(120))]
+    #[test_case(2, &[(1, 10), (3, 20), (6, 30)], Ok(20))]
+    #[test_case(4, &[(1, 10), (3, 20), (6, 30)], Ok(60))]
+    #[test_case(7, &[(1, 10), (3, 20), (6, 30)], Ok(140))]
+    #[test_case(u64::MAX, &[(1, 0), (u64::MAX, 5)], Ok(5))]
+    #[test_case(2, &[(1, u64::MAX)], Err(MathError::ArithmeticOverflow))]
+    #[test_case(2, &[(1, u64::MAX / 2), (2, u64::MAX / 2 + 2)], Err(MathError::ArithmeticOverflow))]
+    fn compute_scheduled_total_amount_authorized_cases(
+        num_cycles: u64,
+        schedule_steps: &[(u64, u64)],
+        expected_res: Result<u64, MathError>,
+    ) {
+        assert_eq!(
+            expected_res,
+            compute_scheduled_total_amount_authorized(num_cycles, &steps(schedule_steps))
+        );
+    }
+
+    #[test_case(0, 100, 3, Ok(0))]
+    #[test_case(1, 100, 3, Ok(33))]
+    #[test_case(2, 100, 3, Ok(66))]
+    #[test_case(3, 100, 3, Ok(100))]
+    #[test_case(1, 2, 3, Ok(0))]
+    #[test_case(3, 2, 3, Ok(2))]
+    #[test_case(u64::MAX - 1, u64::MAX, u64::MAX, Ok(u64::MAX - 1))]
+    #[test_case(4, 100, 3, Err(MathError::InvalidCycle))]
+    #[test_case(0, 100, 0, Err(MathError::InvalidCycle))]
+    fn compute_cumulative_installment_amount_cases(
+        installments: u64,
+        total_amount: u64,
+        installment_count: u64,
+        expected_res: Result<u64, MathError>,
+    ) {
+        assert_eq!(
+            expected_res,
+            compute_cumulative_installment_amount(installments, total_amount, installment_count)
+        );
+    }
+
+    #[test_case(0, 100, 3, Ok(0))]
+    #[test_case(32, 100, 3, Ok(0))]
+    #[test_case(33, 100, 3, Ok(1))]
+    #[test_case(99, 100, 3, Ok(2))]
+    #[test_case(100, 100, 3, Ok(3))]
+    #[test_case(0, 2, 3, Ok(2))]
+    #[test_case(2, 2, 3, Ok(3))]
+    #[test_case(0, 100, 0, Err(MathError::InvalidCycle))]
+    fn compute_installments_paid_cases(
+        amount_debited: u64,
+        total_amount: u64,
+        installment_count: u64,
+        expected_res: Result<u64, MathError>,
+    ) {
+        assert_eq!(
+            expected_res,
+            compute_installments_paid(amount_debited, total_amount, installment_count)
+        );
+    }
+
+    #[test_case(99, 100, 10, 3, Ok(0))]
+    #[test_case(100, 100, 10, 3, Ok(1))]
+    #[test_case(119, 100, 10, 3, Ok(2))]
+    #[test_case(120, 100, 10, 3, Ok(3))]
+    #[test_case(i64::MAX, 100, 10, 3, Ok(3))]
+    #[test_case(100, 100, 0, 3, Err(MathError::InvalidRepeatFrequency))]
+    fn compute_installments_due_cases(
+        current_unix_timestamp: i64,
+        activation_unix_timestamp: i64,
+        interval_seconds: u64,
+        installment_count: u64,
+        expected_res: Result<u64, MathError>,
+    ) {
+        assert_eq!(
+            expected_res,
+            compute_installments_due(
+                current_unix_timestamp,
+                activation_unix_timestamp,
+                interval_seconds,
+                installment_count
+            )
+        );
+    }
+
+    // ring of 4 buckets, bucket n is at index n % 4
+    #[test_case(5, 5, [1, 2, 4, 8], Ok(15))]
+    #[test_case(6, 5, [1, 2, 4, 8], Ok(11))]
+    #[test_case(7, 5, [1, 2, 4, 8], Ok(3))]
+    #[test_case(8, 5, [1, 2, 4, 8], Ok(2))]
+    #[test_case(9, 5, [1, 2, 4, 8], Ok(0))]
+    #[test_case(u64::MAX, 5, [1, 2, 4, 8], Ok(0))]
+    #[test_case(2, 2, [0, 0, 5, 0], Ok(5))]
+    #[test_case(4, 5, [1, 2, 4, 8], Err(MathError::LastDebitedCycleBeforeCurrentCycle))]
+    #[test_case(5, 5, [u64::MAX, 1, 0, 0], Err(MathError::ArithmeticOverflow))]
+    fn compute_rolling_window_amount_debited_cases(
+        current_bucket: u64,
+        last_debited_bucket: u64,
+        debited_buckets: [u64; 4],
+        expected_res: Result<u64, MathError>,
+    ) {
+        assert_eq!(
+            expected_res,
+            compute_rolling_window_amount_debited(
+                current_bucket,
+                last_debited_bucket,
+                &debited_buckets
+            )
+        );
+    }
+
+    #[test_case(0, 0, 100, Ok(100))]
+    #[test_case(150, 0, 100, Ok(250))]
+    #[test_case(150, 100, 40, Ok(90))]
+    #[test_case(150, 100, 0, Ok(50))]
+    // the delegated amount was decreased outside of the program
+    #[test_case(50, 100, 0, Ok(0))]
+    #[test_case(u64::MAX, 0, 1, Err(MathError::ArithmeticOverflow))]
+    fn compute_bounded_delegated_amount_cases(
+        current_delegated_amount: u64,
+        old_outstanding_amount: u64,
+        new_outstanding_amount: u64,
+        expected_res: Result<u64, MathError>,
+    ) {
+        assert_eq!(
+            expected_res,
+            compute_bounded_delegated_amount(
+                current_delegated_amount,
+                old_outstanding_amount,
+                new_outstanding_amount
+            )
+        );
+    }
+}
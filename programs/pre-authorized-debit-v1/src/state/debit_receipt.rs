@@ -0,0 +1,122 @@
+use anchor_lang::prelude::*;
+
+// The minimum time a `debit_receipt` is kept before it can be closed
+pub const DEBIT_RECEIPT_RETENTION_SECONDS: i64 = 60 * 60 * 24 * 365;
+
+// PDA Seeds: ['debit-receipt', pre_authorization, debit_index (u64 little-endian)]
+#[account]
+#[derive(Default, InitSpace)]
+/**
+ The `debit_receipt` is a PDA account derived with the seeds:
+ ['debit-receipt', pre_authorization, debit_index (u64 little-endian)].
+ The `debit_receipt` is an on-chain record of a single `debit` (opt-in, see the `debit` instruction),
+ the `debit_index` is the `pre_authorization.num_debits` before the debit.
+ The `debit_receipt` can be closed by its `payer` or `debit_authority` once the retention period is over.
+*/
+pub struct DebitReceipt {
+    /**
+      The `bump` is the canonical PDA bump when derived with seeds:
+      ['debit-receipt', pre_authorization, debit_index (u64 little-endian)].
+      This field is initialized in `debit`.
+      This field is never updated in any instruction.
+    */
+    pub bump: u8,
+    /**
+      The `pre_authorization` the debit was done with (it may be closed since).
+      This field is initialized in `debit`.
+      This field is never updated in any instruction.
+    */
+    pub pre_authorization: Pubkey,
+    /**
+      The index of the debit in the `pre_authorization` (its `num_debits` before the debit).
+      This field is initialized in `debit`.
+      This field is never updated in any instruction.
+    */
+    pub debit_index: u64,
+    /**
+      The `debit_authority` that did the debit.
+      This field is initialized in `debit`.
+      This field is never updated in any instruction.
+    */
+    pub debit_authority: Pubkey,
+    /**
+      The `payer` that paid for the `debit_receipt` account and receives the lamports when it is closed.
+      This field is initialized in `debit`.
+      This field is never updated in any instruction.
+    */
+    pub payer: Pubkey,
+    /**
+      The token account the amount was debited from.
+      This field is initialized in `debit`.
+      This field is never updated in any instruction.
+    */
+    pub source_token_account: Pubkey,
+    /**
+      The token account the amount was transferred to.
+      This field is initialized in `debit`.
+      This field is never updated in any instruction.
+    */
+    pub destination_token_account: Pubkey,
+    /**
+      The amount debited.
+      This field is initialized in `debit`.
+      This field is never updated in any instruction.
+    */
+    pub amount: u64,
+    /**
+      The cycle (or rolling window bucket) the debit was done in, `None` for a one-time pre-authorization.
+      This field is initialized in `debit`.
+      This field is never updated in any instruction.
+    */
+    pub cycle: Option<u64>,
+    /**
+      The unix timestamp of the debit.
+      This field is initialized in `debit`.
+      This field is never updated in any instruction.
+    */
+    pub unix_timestamp: i64,
+    /**
+      The `reference` of the debit (see `DebitParams`).
+      This field is initialized in `debit`.
+      This field is never updated in any instruction.
+    */
+    pub reference: Option<[u8; 32]>,
+}
+
+impl DebitReceipt {
+    /**
+      Returns true if the retention period (`DEBIT_RECEIPT_RETENTION_SECONDS` after the debit) is over
+      at `unix_timestamp`, i.e. the `debit_receipt` can be closed.
+    */
+    pub fn is_retention_period_over_at(&self, unix_timestamp: i64) -> bool {
+        unix_timestamp
+            >= self
+                .unix_timestamp
+                .saturating_add(DEBIT_RECEIPT_RETENTION_SECONDS)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    #[test_case(100, 100, false)]
+    #[test_case(100, 99 + DEBIT_RECEIPT_RETENTION_SECONDS, false)]
+    #[test_case(100, 100 + DEBIT_RECEIPT_RETENTION_SECONDS, true)]
+    #[test_case(i64::MAX - 1, i64::MAX, true)]
+    fn is_retention_period_over_at(
+        debit_unix_timestamp: i64,
+        unix_timestamp: i64,
+        expected_res: bool,
+    ) {
+        let debit_receipt = DebitReceipt {
+            unix_timestamp: debit_unix_timestamp,
+            ..Default::default()
+        };
+        assert_eq!(
+            expected_res,
+            debit_receipt.is_retention_period_over_at(unix_timestamp)
+        );
+    }
+}
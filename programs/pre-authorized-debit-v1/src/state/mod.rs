@@ -1,2 +1,6 @@
+pub mod debit_receipt;
+pub mod pending_amendment;
 pub mod pre_authorization;
+pub mod pre_authorization_counter;
+pub mod pre_authorization_v1;
 pub mod smart_delegate;
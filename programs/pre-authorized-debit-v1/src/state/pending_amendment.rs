@@ -0,0 +1,53 @@
+use anchor_lang::prelude::*;
+
+use crate::instructions::UpdatePreAuthorizationVariant;
+
+// PDA Seeds: ['pending-amendment', pre_authorization]
+#[account]
+#[derive(InitSpace)]
+/**
+ The `pending_amendment` is a PDA account derived with the seeds:
+ ['pending-amendment', pre_authorization].
+ The `pending_amendment` holds new terms for a `pre_authorization` proposed by its `debit_authority`.
+ The new terms are only applied to the `pre_authorization` once accepted by the `token_account.owner`.
+ There can only be one `pending_amendment` for a given `pre_authorization` at a time.
+*/
+pub struct PendingAmendment {
+    /**
+      The `bump` is the canonical PDA bump when derived with seeds:
+      ['pending-amendment', pre_authorization].
+      This field is initialized in `propose_amendment`.
+      This field is never updated in any instruction.
+    */
+    pub bump: u8,
+    /**
+      The `pre_authorization` the new terms are proposed for.
+      This field is initialized in `propose_amendment`.
+      This field is never updated in any instruction.
+    */
+    pub pre_authorization: Pubkey,
+    /**
+      The `debit_authority` of the `pre_authorization` that proposed the new terms.
+      This field is initialized in `propose_amendment`.
+      This field is never updated in any instruction.
+    */
+    pub debit_authority: Pubkey,
+    /**
+      The `payer` that paid for the `pending_amendment` account and receives the lamports when it is closed.
+      This field is initialized in `propose_amendment`.
+      This field is never updated in any instruction.
+    */
+    pub payer: Pubkey,
+    /**
+      The proposed terms, applied to the `pre_authorization` the same way as in `update_pre_authorization`.
+      This field is initialized in `propose_amendment`.
+      This field is never updated in any instruction.
+    */
+    pub new_values: UpdatePreAuthorizationVariant,
+    /**
+      The `acceptance_deadline_unix_timestamp` is the last unix timestamp at which the amendment can be accepted.
+      This field is initialized in `propose_amendment`.
+      This field is never updated in any instruction.
+    */
+    pub acceptance_deadline_unix_timestamp: i64,
+}
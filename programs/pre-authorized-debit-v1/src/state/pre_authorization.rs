@@ -1,22 +1,42 @@
 use anchor_lang::prelude::*;
 
-// PDA Seeds: ['pre-authorization', token_account, debit_authority]
+use crate::{
+    calendar::CalendarSchedule,
+    errors::CustomProgramError,
+    math::{
+        self, compute_active_schedule_step, compute_amount_authorized_for_cycle,
+        compute_available_amount_for_one_time_debit, compute_available_amount_for_recurring_debit,
+        compute_cumulative_amount_authorized, compute_cumulative_installment_amount,
+        compute_current_cycle, compute_current_scheduled_cycle, compute_cycle_bounds,
+        compute_debit_window_bounds, compute_installments_due, compute_installments_paid,
+        compute_max_accrued_amount, compute_rolling_window_amount_debited,
+        compute_scheduled_cycle_bounds, compute_scheduled_total_amount_authorized,
+    },
+};
+
+// PDA Seeds: ['pre-authorization', token_account, debit_authority] (`TokenAccount` scope)
+//         or ['pre-authorization', owner, mint, debit_authority] (`OwnerAndMint` scope)
 #[account]
 #[derive(Default, InitSpace)]
 /**
- The `pre_authorization` is a PDA account derived with the seeds:
- ['pre-authorization', token_account, debit_authority].
+ The `pre_authorization` is a PDA account derived with the seeds (see `scope_seeds`):
+ ['pre-authorization', token_account, debit_authority] for a `TokenAccount` scope, or
+ ['pre-authorization', owner, mint, debit_authority] for an `OwnerAndMint` scope.
  The `pre_authorization` can be thought of as the rule for the `smart_delegate`.
- The `pre_authorization` can be used to validate a recurring or one-time debit from the `token_account`.
+ The `pre_authorization` can be used to validate a one-time, recurring, scheduled, installments
+ or rolling window debit from the token account(s) covered by its `scope`.
  The `smart_delegate` will validate the rules of the `pre_authorization` in the `debit` instruction.
- A `pre_authorization` is associated many:1 with a `token_account`,
- however, for a given `debit_authority` and `token_account` there can only be one `pre_authorization`.
+ A `pre_authorization` is associated many:1 with a `token_account` (or with an `owner` and `mint`),
+ however, for a given `debit_authority` and `token_account` (or `owner` and `mint`)
+ there can only be one `pre_authorization`.
 */
 pub struct PreAuthorization {
     /**
-      The `bump` is the canonical PDA bump when derived with seeds:
-      ['pre-authorization', token_account, debit_authority].
-      This field is initialized in `init_pre_authorization`.
+      The `bump` is the canonical PDA bump when derived with the seeds of the `scope`:
+      ['pre-authorization', token_account, debit_authority] or
+      ['pre-authorization', owner, mint, debit_authority].
+      This field is initialized in `init_pre_authorization` (or `init_owner_pre_authorization`,
+        and in `rotate_debit_authority` for the `new_pre_authorization`).
       This field is never updated in any instruction.
     */
     pub bump: u8,
@@ -30,21 +50,25 @@ pub struct PreAuthorization {
     pub paused: bool,
     /**
       The `token_account` is the account the `debit_authority` will be able to debit from.
-      This field is initialized in `init_pre_authorization`.
+      This field is initialized in `init_pre_authorization`
+        (to `Pubkey::default()` in `init_owner_pre_authorization`, see `scope`).
       This field is never updated in any instruction.
     */
     pub token_account: Pubkey,
     /**
-      The `variant` contains the data specific to a one-time
-      or recurring debit.
+      The `variant` contains the data specific to a one-time,
+      recurring, scheduled, installments or rolling window debit.
       This field is initialized in `init_pre_authorization`.
-      This field is never updated in any instruction.
+      The debited amounts are updated in `debit`.
+      The authorized amounts, expiry and cycle configuration can be updated by the
+      `token_account.owner` in `update_pre_authorization`.
     */
     pub variant: PreAuthorizationVariant,
     /**
       The `debit_authority` is the signer that can debit from the `token_account`.
       This field is initialized in `init_pre_authorization`.
-      This field is never updated in any instruction.
+      This field is never updated in any instruction
+        (`rotate_debit_authority` moves the state to a new `pre_authorization` PDA instead).
     */
     pub debit_authority: Pubkey,
     /**
@@ -52,14 +76,621 @@ pub struct PreAuthorization {
       The field is initialized in `init_pre_authorization`.
     */
     pub activation_unix_timestamp: i64,
+    /**
+      If `rotation_requires_owner_signature === true`, then the `token_account.owner` MUST co-sign
+      the `rotate_debit_authority` instruction.
+      This field is initialized in `init_pre_authorization`.
+      This field is never updated in any instruction.
+    */
+    pub rotation_requires_owner_signature: bool,
+    /**
+      The `scope` defines the token accounts the `debit_authority` can debit from.
+      This field is initialized to `TokenAccount` in `init_pre_authorization`
+        and to `OwnerAndMint` in `init_owner_pre_authorization`.
+      This field is never updated in any instruction.
+    */
+    pub scope: PreAuthorizationScope,
+    /**
+      The `destination_constraint` (if set) restricts the `destination_token_account` of a `debit`.
+      This field is initialized in `init_pre_authorization` (or `init_owner_pre_authorization`).
+      This field is never updated in any instruction.
+    */
+    pub destination_constraint: Option<DestinationConstraint>,
+    /**
+      The number of debits done with the `pre_authorization` (the index of the next `debit_receipt`).
+      This field is initialized to 0 in `init_pre_authorization` (or `init_owner_pre_authorization`).
+      This field is incremented in `debit` and `debit_batch`.
+    */
+    pub num_debits: u64,
+}
+
+impl PreAuthorization {
+    /**
+      Returns the amount the `debit_authority` can debit at `unix_timestamp`, or the error
+      the `debit` instruction would fail with (regardless of the debit amount) at that time.
+      This is the exact computation used by the `debit` instruction.
+    */
+    pub fn available_amount_at(
+        &self,
+        unix_timestamp: i64,
+    ) -> core::result::Result<u64, CustomProgramError> {
+        if self.paused {
+            return Err(CustomProgramError::PreAuthorizationPaused);
+        }
+
+        if unix_timestamp < self.activation_unix_timestamp {
+            return Err(CustomProgramError::PreAuthorizationNotActive);
+        }
+
+        match self.variant {
+            PreAuthorizationVariant::OneTime {
+                amount_authorized,
+                expiry_unix_timestamp,
+                amount_debited,
+                ..
+            } => {
+                if unix_timestamp >= expiry_unix_timestamp {
+                    return Err(CustomProgramError::PreAuthorizationNotActive);
+                }
+
+                Ok(compute_available_amount_for_one_time_debit(
+                    amount_authorized,
+                    amount_debited,
+                )?)
+            }
+            PreAuthorizationVariant::Recurring {
+                repeat_frequency_seconds,
+                recurring_amount_authorized,
+                amount_debited_last_cycle,
+                amount_debited_total,
+                last_debited_cycle,
+                num_cycles,
+                reset_every_cycle,
+                amended_at_cycle,
+                amount_authorized_before_amendment,
+                max_accrued_cycles,
+                calendar_schedule,
+                debit_window_seconds,
+                intro_schedule,
+                ..
+            } => {
+                let current_cycle = compute_current_scheduled_cycle(
+                    unix_timestamp,
+                    self.activation_unix_timestamp,
+                    repeat_frequency_seconds,
+                    calendar_schedule.map(Into::into).as_ref(),
+                )?;
+
+                if let Some(num_cycles) = num_cycles {
+                    if current_cycle > num_cycles {
+                        return Err(CustomProgramError::PreAuthorizationNotActive);
+                    }
+                }
+
+                if debit_window_seconds.is_some() {
+                    let (window_start, window_end) = self
+                        .debit_window_bounds(current_cycle)
+                        .ok_or(CustomProgramError::OutsideDebitWindow)?;
+                    if unix_timestamp < window_start || unix_timestamp >= window_end {
+                        return Err(CustomProgramError::OutsideDebitWindow);
+                    }
+                }
+
+                // could happen if validator has decreasing timestamps in between TXs due to some weirdness
+                if current_cycle < last_debited_cycle {
+                    return Err(CustomProgramError::LastDebitedCycleBeforeCurrentCycle);
+                }
+
+                let amount_available = compute_available_amount_for_recurring_debit(
+                    current_cycle,
+                    last_debited_cycle,
+                    reset_every_cycle,
+                    recurring_amount_authorized,
+                    amount_debited_last_cycle,
+                    amount_debited_total,
+                    amended_at_cycle,
+                    amount_authorized_before_amendment,
+                    intro_schedule.map(Into::into).as_ref(),
+                )?;
+
+                let amount_authorized_current_cycle = compute_amount_authorized_for_cycle(
+                    current_cycle,
+                    recurring_amount_authorized,
+                    intro_schedule.map(Into::into).as_ref(),
+                );
+                match compute_max_accrued_amount(
+                    amount_authorized_current_cycle,
+                    max_accrued_cycles,
+                ) {
+                    Some(max_accrued_amount) if !reset_every_cycle => {
+                        Ok(amount_available.min(max_accrued_amount))
+                    }
+                    _ => Ok(amount_available),
+                }
+            }
+            PreAuthorizationVariant::Scheduled {
+                repeat_frequency_seconds,
+                ref steps,
+                amount_debited_last_cycle,
+                amount_debited_total,
+                last_debited_cycle,
+                num_cycles,
+            } => {
+                let current_cycle = compute_current_cycle(
+                    unix_timestamp,
+                    self.activation_unix_timestamp,
+                    repeat_frequency_seconds,
+                )?;
+
+                if let Some(num_cycles) = num_cycles {
+                    if current_cycle > num_cycles {
+                        return Err(CustomProgramError::PreAuthorizationNotActive);
+                    }
+                }
+
+                // could happen if validator has decreasing timestamps in between TXs due to some weirdness
+                if current_cycle < last_debited_cycle {
+                    return Err(CustomProgramError::LastDebitedCycleBeforeCurrentCycle);
+                }
+
+                let active_step = compute_active_schedule_step(current_cycle, steps)?;
+
+                // the amount authorized by the active step resets every cycle
+                Ok(compute_available_amount_for_recurring_debit(
+                    current_cycle,
+                    last_debited_cycle,
+                    true,
+                    steps[active_step].amount_authorized,
+                    amount_debited_last_cycle,
+                    amount_debited_total,
+                    1,
+                    0,
+                    None,
+                )?)
+            }
+            PreAuthorizationVariant::Installments {
+                total_amount,
+                installment_count,
+                interval_seconds,
+                amount_debited,
+                ..
+            } => {
+                let installments_due = compute_installments_due(
+                    unix_timestamp,
+                    self.activation_unix_timestamp,
+                    interval_seconds,
+                    installment_count,
+                )?;
+
+                compute_cumulative_installment_amount(
+                    installments_due,
+                    total_amount,
+                    installment_count,
+                )?
+                .checked_sub(amount_debited)
+                .ok_or(CustomProgramError::ArithmeticUnderflow)
+            }
+            PreAuthorizationVariant::RollingWindow {
+                window_seconds,
+                amount_authorized_per_window,
+                ref debited_buckets,
+                last_debited_bucket,
+                ..
+            } => {
+                let current_bucket = compute_current_cycle(
+                    unix_timestamp,
+                    self.activation_unix_timestamp,
+                    window_seconds / ROLLING_WINDOW_BUCKETS as u64,
+                )?;
+
+                let amount_debited_in_window = compute_rolling_window_amount_debited(
+                    current_bucket,
+                    last_debited_bucket,
+                    debited_buckets,
+                )?;
+
+                Ok(amount_authorized_per_window.saturating_sub(amount_debited_in_window))
+            }
+        }
+    }
+
+    /**
+      Returns the error the `debit` instruction would fail with if `amount` is outside of the
+      per-debit bounds (`max_amount_per_debit` and, for recurring, `min_amount_per_debit`).
+      Scheduled, installments and rolling window pre-authorizations have no per-debit bounds.
+      The amount available is checked separately (see `available_amount_at`).
+    */
+    pub fn check_amount_per_debit(
+        &self,
+        amount: u64,
+    ) -> core::result::Result<(), CustomProgramError> {
+        let (max_amount_per_debit, min_amount_per_debit) = self.amount_per_debit_limits();
+
+        if matches!(max_amount_per_debit, Some(max_amount_per_debit) if amount > max_amount_per_debit)
+        {
+            return Err(CustomProgramError::DebitAmountAboveMaximum);
+        }
+        if matches!(min_amount_per_debit, Some(min_amount_per_debit) if amount < min_amount_per_debit)
+        {
+            return Err(CustomProgramError::DebitAmountBelowMinimum);
+        }
+
+        Ok(())
+    }
+
+    /**
+      Returns the `(max_amount_per_debit, min_amount_per_debit)` of the pre-authorization (if any).
+    */
+    pub fn amount_per_debit_limits(&self) -> (Option<u64>, Option<u64>) {
+        match self.variant {
+            PreAuthorizationVariant::OneTime {
+                max_amount_per_debit,
+                ..
+            } => (max_amount_per_debit, None),
+            PreAuthorizationVariant::Recurring {
+                max_amount_per_debit,
+                min_amount_per_debit,
+                ..
+            } => (max_amount_per_debit, min_amount_per_debit),
+            PreAuthorizationVariant::Scheduled { .. }
+            | PreAuthorizationVariant::Installments { .. }
+            | PreAuthorizationVariant::RollingWindow { .. } => (None, None),
+        }
+    }
+
+    /**
+      Returns true if the `debit_authority` can debit from the token account
+      (with the given `token_account_owner` and `token_account_mint`) under this pre-authorization's `scope`.
+      The amount limits are tracked once across all the token accounts covered by the `scope`.
+    */
+    pub fn covers_token_account(
+        &self,
+        token_account: &Pubkey,
+        token_account_owner: &Pubkey,
+        token_account_mint: &Pubkey,
+    ) -> bool {
+        match self.scope {
+            PreAuthorizationScope::TokenAccount => self.token_account.eq(token_account),
+            PreAuthorizationScope::OwnerAndMint { owner, mint } => {
+                owner.eq(token_account_owner) && mint.eq(token_account_mint)
+            }
+        }
+    }
+
+    /**
+      Returns the PDA seeds between the 'pre-authorization' prefix and the `debit_authority` for this `scope`:
+      [token_account, ''] for `TokenAccount` and [owner, mint] for `OwnerAndMint`.
+      The empty seed does not change the derived address, so the seeds always have the same length.
+    */
+    pub fn scope_seeds(&self) -> [&[u8]; 2] {
+        match &self.scope {
+            PreAuthorizationScope::TokenAccount => [self.token_account.as_ref(), &[]],
+            PreAuthorizationScope::OwnerAndMint { owner, mint } => [owner.as_ref(), mint.as_ref()],
+        }
+    }
+
+    /**
+      Returns true if the `debit_authority` can debit to the `destination_token_account`
+      (owned by `destination_token_account_owner`) under this pre-authorization's `destination_constraint`.
+    */
+    pub fn allows_destination(
+        &self,
+        destination_token_account: &Pubkey,
+        destination_token_account_owner: &Pubkey,
+    ) -> bool {
+        match self.destination_constraint {
+            None => true,
+            Some(DestinationConstraint::TokenAccount { token_account }) => {
+                token_account.eq(destination_token_account)
+            }
+            Some(DestinationConstraint::Owner { owner }) => {
+                owner.eq(destination_token_account_owner)
+            }
+        }
+    }
+
+    /**
+      Returns the (1-indexed) recurring cycle at `unix_timestamp`
+      (for an installments pre-authorization, cycle `n` starts at the due date of installment `n`,
+      for a rolling window pre-authorization, cycle `n` is the bucket `n`).
+      Returns `None` for one-time pre-authorizations, before `activation_unix_timestamp`,
+      or if the cycle cannot be computed (see `math::compute_current_scheduled_cycle`).
+    */
+    pub fn current_cycle_at(&self, unix_timestamp: i64) -> Option<u64> {
+        match self.variant {
+            PreAuthorizationVariant::OneTime { .. } => None,
+            PreAuthorizationVariant::Recurring {
+                repeat_frequency_seconds,
+                calendar_schedule,
+                ..
+            } => compute_current_scheduled_cycle(
+                unix_timestamp,
+                self.activation_unix_timestamp,
+                repeat_frequency_seconds,
+                calendar_schedule.map(Into::into).as_ref(),
+            )
+            .ok(),
+            PreAuthorizationVariant::Scheduled {
+                repeat_frequency_seconds: interval_seconds,
+                ..
+            }
+            | PreAuthorizationVariant::Installments {
+                interval_seconds, ..
+            } => compute_current_cycle(
+                unix_timestamp,
+                self.activation_unix_timestamp,
+                interval_seconds,
+            )
+            .ok(),
+            PreAuthorizationVariant::RollingWindow { window_seconds, .. } => compute_current_cycle(
+                unix_timestamp,
+                self.activation_unix_timestamp,
+                window_seconds / ROLLING_WINDOW_BUCKETS as u64,
+            )
+            .ok(),
+        }
+    }
+
+    /**
+      Returns the `[start, end)` unix timestamps of the given (1-indexed) recurring `cycle`.
+      Returns `None` for one-time pre-authorizations, for cycle 0, or if the bounds do not fit in an `i64`.
+      NOTE: With a `calendar_schedule`, cycle 1 can start before `activation_unix_timestamp`.
+    */
+    pub fn cycle_bounds(&self, cycle: u64) -> Option<(i64, i64)> {
+        match self.variant {
+            PreAuthorizationVariant::OneTime { .. } => None,
+            PreAuthorizationVariant::Recurring {
+                repeat_frequency_seconds,
+                calendar_schedule,
+                ..
+            } => compute_scheduled_cycle_bounds(
+                cycle,
+                self.activation_unix_timestamp,
+                repeat_frequency_seconds,
+                calendar_schedule.map(Into::into).as_ref(),
+            ),
+            PreAuthorizationVariant::Scheduled {
+                repeat_frequency_seconds: interval_seconds,
+                ..
+            }
+            | PreAuthorizationVariant::Installments {
+                interval_seconds, ..
+            } => compute_cycle_bounds(cycle, self.activation_unix_timestamp, interval_seconds),
+            PreAuthorizationVariant::RollingWindow { window_seconds, .. } => compute_cycle_bounds(
+                cycle,
+                self.activation_unix_timestamp,
+                window_seconds / ROLLING_WINDOW_BUCKETS as u64,
+            ),
+        }
+    }
+
+    /**
+      Returns the index of the step of a scheduled pre-authorization that applies at `unix_timestamp`.
+      Returns `None` for one-time and recurring pre-authorizations, before `activation_unix_timestamp`,
+      or if the cycle cannot be computed.
+    */
+    pub fn active_schedule_step_at(&self, unix_timestamp: i64) -> Option<usize> {
+        match self.variant {
+            PreAuthorizationVariant::Scheduled { ref steps, .. } => {
+                compute_active_schedule_step(self.current_cycle_at(unix_timestamp)?, steps).ok()
+            }
+            _ => None,
+        }
+    }
+
+    /**
+      Returns the `[start, end)` unix timestamps during which debits can occur in the given (1-indexed) recurring `cycle`
+      (the whole cycle if `debit_window_seconds` is not set).
+      Returns `None` for one-time pre-authorizations or if the bounds cannot be computed (see `cycle_bounds`).
+    */
+    pub fn debit_window_bounds(&self, cycle: u64) -> Option<(i64, i64)> {
+        let cycle_bounds = self.cycle_bounds(cycle)?;
+        match self.variant {
+            PreAuthorizationVariant::Recurring {
+                debit_window_seconds: Some(debit_window_seconds),
+                debit_window_offset_seconds,
+                ..
+            } => compute_debit_window_bounds(
+                cycle_bounds,
+                debit_window_offset_seconds.unwrap_or(0),
+                debit_window_seconds,
+            ),
+            _ => Some(cycle_bounds),
+        }
+    }
+
+    /**
+      Returns the number of installments of an installments pre-authorization fully paid so far.
+      Returns `None` for the other variants.
+    */
+    pub fn installments_paid(&self) -> Option<u64> {
+        match self.variant {
+            PreAuthorizationVariant::Installments {
+                total_amount,
+                installment_count,
+                amount_debited,
+                ..
+            } => compute_installments_paid(amount_debited, total_amount, installment_count).ok(),
+            _ => None,
+        }
+    }
+
+    /**
+      Returns the number of installments of an installments pre-authorization that are overdue at `unix_timestamp`
+      (i.e. not fully paid `grace_period_seconds` after their due date).
+      Returns `None` for the other variants.
+    */
+    pub fn installments_overdue_at(&self, unix_timestamp: i64) -> Option<u64> {
+        match self.variant {
+            PreAuthorizationVariant::Installments {
+                installment_count,
+                interval_seconds,
+                grace_period_seconds,
+                ..
+            } => {
+                let installments_past_grace_period = compute_installments_due(
+                    unix_timestamp
+                        .saturating_sub(i64::try_from(grace_period_seconds).unwrap_or(i64::MAX)),
+                    self.activation_unix_timestamp,
+                    interval_seconds,
+                    installment_count,
+                )
+                .ok()?;
+                Some(installments_past_grace_period.saturating_sub(self.installments_paid()?))
+            }
+            _ => None,
+        }
+    }
+
+    /**
+      Returns true if this is a one-time pre-authorization with `close_on_exhaustion` set
+      that has been fully debited (i.e. it MUST be closed by the debit that exhausted it).
+    */
+    pub fn should_close_on_exhaustion(&self) -> bool {
+        match self.variant {
+            PreAuthorizationVariant::OneTime {
+                amount_authorized,
+                amount_debited,
+                close_on_exhaustion,
+                ..
+            } => close_on_exhaustion && amount_debited >= amount_authorized,
+            _ => false,
+        }
+    }
+
+    /**
+      Returns true if the pre-authorization can never be debited again at or after `unix_timestamp`
+      (i.e. it can be closed by anyone, see `close_expired_pre_authorization`):
+      - one-time: `unix_timestamp` is at or after the `expiry_unix_timestamp`
+      - recurring or scheduled: the current cycle is after the last cycle (`num_cycles`)
+      - installments: all installments have been paid
+
+      A rolling window pre-authorization never expires.
+    */
+    pub fn is_expired_at(&self, unix_timestamp: i64) -> bool {
+        match self.variant {
+            PreAuthorizationVariant::OneTime {
+                expiry_unix_timestamp,
+                ..
+            } => unix_timestamp >= expiry_unix_timestamp,
+            PreAuthorizationVariant::Recurring {
+                num_cycles: Some(num_cycles),
+                ..
+            }
+            | PreAuthorizationVariant::Scheduled {
+                num_cycles: Some(num_cycles),
+                ..
+            } => self
+                .current_cycle_at(unix_timestamp)
+                .is_some_and(|current_cycle| current_cycle > num_cycles),
+            PreAuthorizationVariant::Installments {
+                total_amount,
+                amount_debited,
+                ..
+            } => amount_debited >= total_amount,
+            PreAuthorizationVariant::Recurring { .. }
+            | PreAuthorizationVariant::Scheduled { .. }
+            | PreAuthorizationVariant::RollingWindow { .. } => false,
+        }
+    }
+
+    /**
+      Returns the amount authorized that can still be debited over the lifetime of the pre-authorization
+      (i.e. the total amount authorized minus the total amount debited), this is the amount the `smart_delegate`
+      is approved for in bounded delegation mode.
+      Returns `None` if the amount authorized is unbounded (infinitely recurring or rolling window).
+    */
+    pub fn outstanding_amount_authorized(&self) -> Option<u64> {
+        let (total_amount_authorized, total_amount_debited) = match self.variant {
+            PreAuthorizationVariant::OneTime {
+                amount_authorized,
+                amount_debited,
+                ..
+            } => (amount_authorized, amount_debited),
+            PreAuthorizationVariant::Recurring {
+                recurring_amount_authorized,
+                amount_debited_total,
+                num_cycles: Some(num_cycles),
+                amended_at_cycle,
+                amount_authorized_before_amendment,
+                ref intro_schedule,
+                ..
+            } => (
+                compute_cumulative_amount_authorized(
+                    num_cycles,
+                    recurring_amount_authorized,
+                    amended_at_cycle,
+                    amount_authorized_before_amendment,
+                    intro_schedule.map(Into::into).as_ref(),
+                )
+                .ok()?,
+                amount_debited_total,
+            ),
+            PreAuthorizationVariant::Scheduled {
+                ref steps,
+                amount_debited_total,
+                num_cycles: Some(num_cycles),
+                ..
+            } => (
+                compute_scheduled_total_amount_authorized(num_cycles, steps).ok()?,
+                amount_debited_total,
+            ),
+            PreAuthorizationVariant::Installments {
+                total_amount,
+                amount_debited,
+                ..
+            } => (total_amount, amount_debited),
+            PreAuthorizationVariant::Recurring { .. }
+            | PreAuthorizationVariant::Scheduled { .. }
+            | PreAuthorizationVariant::RollingWindow { .. } => return None,
+        };
+
+        Some(total_amount_authorized.saturating_sub(total_amount_debited))
+    }
+
+    /**
+      Returns the unix timestamp at which the recurring cycle following `unix_timestamp` starts
+      (i.e. the activation time if `unix_timestamp` is before activation).
+      Returns `None` for one-time pre-authorizations or if there is no next cycle (`num_cycles` elapsed).
+    */
+    pub fn next_cycle_start(&self, unix_timestamp: i64) -> Option<i64> {
+        let num_cycles = match self.variant {
+            PreAuthorizationVariant::OneTime { .. } => return None,
+            PreAuthorizationVariant::Recurring { num_cycles, .. }
+            | PreAuthorizationVariant::Scheduled { num_cycles, .. } => num_cycles,
+            PreAuthorizationVariant::Installments {
+                installment_count, ..
+            } => Some(installment_count),
+            PreAuthorizationVariant::RollingWindow { .. } => None,
+        };
+
+        if unix_timestamp < self.activation_unix_timestamp {
+            return Some(self.activation_unix_timestamp);
+        }
+
+        let next_cycle = self.current_cycle_at(unix_timestamp)?.checked_add(1)?;
+
+        if let Some(num_cycles) = num_cycles {
+            if next_cycle > num_cycles {
+                return None;
+            }
+        }
+
+        self.cycle_bounds(next_cycle)
+            .map(|(cycle_start, _)| cycle_start)
+    }
 }
 
-#[derive(InitSpace, AnchorSerialize, AnchorDeserialize, Clone)]
+#[derive(InitSpace, AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
 pub enum PreAuthorizationVariant {
     OneTime {
         amount_authorized: u64,
         expiry_unix_timestamp: i64,
         amount_debited: u64,
+        // None: no limit per debit (other than the amount available)
+        max_amount_per_debit: Option<u64>,
+        // true: the pre-authorization is closed by the debit that exhausts it (rent refunded to the token account owner)
+        close_on_exhaustion: bool,
     },
     Recurring {
         repeat_frequency_seconds: u64,
@@ -73,15 +704,921 @@ pub enum PreAuthorizationVariant {
         // true: amount authorized is reset to "recurring_amount_authorized" each cycle
         // false: unused amounts from prev. cycles carries forward to new cycles
         reset_every_cycle: bool,
+        // the cycle from which "recurring_amount_authorized" applies (1 unless amended in update_pre_authorization)
+        amended_at_cycle: u64,
+        // the total amount authorized across the cycles before "amended_at_cycle" (only used when reset_every_cycle is false)
+        amount_authorized_before_amendment: u64,
+        // None: no limit per debit (other than the amount available)
+        max_amount_per_debit: Option<u64>,
+        // None: no minimum per debit
+        min_amount_per_debit: Option<u64>,
+        // None: unused amounts accrue indefinitely (only used when reset_every_cycle is false)
+        // Some(n): at most n cycles worth of the amount authorized in the current cycle is available at once,
+        //   the amount accrued above that is forfeited
+        max_accrued_cycles: Option<u64>,
+        // None: cycles are "repeat_frequency_seconds" long
+        // Some(schedule): calendar cycles (e.g. monthly on the 1st), "repeat_frequency_seconds" is 0
+        calendar_schedule: Option<CalendarSchedule>,
+        // None: debits can occur at any time during a cycle
+        // Some(n): debits can only occur in the n seconds starting "debit_window_offset_seconds" into each cycle
+        debit_window_seconds: Option<u64>,
+        // None: the debit window starts at the cycle start (only used when "debit_window_seconds" is set)
+        // Some(n): the debit window starts n seconds into each cycle
+        debit_window_offset_seconds: Option<u64>,
+        // None: "recurring_amount_authorized" is authorized from the first cycle
+        // Some(intro): "intro.intro_amount_authorized" is authorized in each of the first "intro.num_intro_cycles" cycles
+        //   instead (e.g. 0 for a free trial), "recurring_amount_authorized" thereafter
+        intro_schedule: Option<IntroSchedule>,
+    },
+    Scheduled {
+        repeat_frequency_seconds: u64,
+        // the amount authorized per cycle (resets every cycle) is the "amount_authorized" of the last step
+        // with "cycle_start" <= current cycle, the first step starts at cycle 1 (at most MAX_SCHEDULE_STEPS steps)
+        #[max_len(12)]
+        steps: Vec<ScheduleStep>,
+        amount_debited_last_cycle: u64,
+        amount_debited_total: u64,
+        last_debited_cycle: u64,
+        // None: infinite recurring (the last step applies indefinitely)
+        // Some(n): approved for n cycles from activation
+        num_cycles: Option<u64>,
+    },
+    Installments {
+        // the installments add up to exactly "total_amount" (the last installment includes the remainder)
+        total_amount: u64,
+        installment_count: u64,
+        // installment n (1-indexed) is due (i.e. can be debited) from activation + (n - 1) * interval_seconds
+        interval_seconds: u64,
+        // an installment is overdue if it is not fully paid "grace_period_seconds" after its due date
+        // (overdue installments can still be debited)
+        grace_period_seconds: u64,
+        amount_debited: u64,
+    },
+    RollingWindow {
+        // at most "amount_authorized_per_window" can be debited in any trailing "window_seconds"
+        window_seconds: u64,
+        amount_authorized_per_window: u64,
+        // the amounts debited in the last ROLLING_WINDOW_RING_SIZE buckets of (window_seconds / ROLLING_WINDOW_BUCKETS)
+        // seconds each, bucket n (1-indexed from activation) is at index n % ROLLING_WINDOW_RING_SIZE
+        // NOTE: The length is a literal since the IDL cannot resolve constants (MUST match ROLLING_WINDOW_RING_SIZE)
+        debited_buckets: [u64; 25],
+        last_debited_bucket: u64,
+        amount_debited_total: u64,
     },
 }
 
+// MUST match the max_len of `PreAuthorizationVariant::Scheduled.steps`
+pub const MAX_SCHEDULE_STEPS: usize = 12;
+
+// The number of buckets a rolling window is split into
+pub const ROLLING_WINDOW_BUCKETS: usize = 24;
+// The debits in the current (partially elapsed) bucket and the ROLLING_WINDOW_BUCKETS preceding buckets
+// count towards the rolling window cap (i.e. the window is rounded up to whole buckets)
+pub const ROLLING_WINDOW_RING_SIZE: usize = ROLLING_WINDOW_BUCKETS + 1;
+
 impl Default for PreAuthorizationVariant {
     fn default() -> Self {
         PreAuthorizationVariant::OneTime {
             amount_authorized: Default::default(),
             expiry_unix_timestamp: Default::default(),
             amount_debited: Default::default(),
+            max_amount_per_debit: Default::default(),
+            close_on_exhaustion: Default::default(),
+        }
+    }
+}
+
+#[derive(InitSpace, AnchorSerialize, AnchorDeserialize, Clone, Debug, Default, PartialEq, Eq)]
+pub enum PreAuthorizationScope {
+    // only `token_account` can be debited
+    #[default]
+    TokenAccount,
+    // any token account owned by `owner` with the mint `mint` can be debited
+    // (the token account still has to be delegated to the `smart_delegate`)
+    OwnerAndMint {
+        owner: Pubkey,
+        mint: Pubkey,
+    },
+}
+
+#[derive(InitSpace, AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct IntroSchedule {
+    // the number of cycles (from the first cycle) authorized "intro_amount_authorized"
+    pub num_intro_cycles: u64,
+    // the amount authorized in each intro cycle (replaces "recurring_amount_authorized")
+    pub intro_amount_authorized: u64,
+}
+
+#[derive(InitSpace, AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ScheduleStep {
+    // the (1-indexed) cycle from which this step applies
+    pub cycle_start: u64,
+    // the amount authorized in each cycle of this step
+    pub amount_authorized: u64,
+}
+
+impl From<IntroSchedule> for math::IntroSchedule {
+    fn from(intro_schedule: IntroSchedule) -> Self {
+        math::IntroSchedule {
+            num_intro_cycles: intro_schedule.num_intro_cycles,
+            intro_amount_authorized: intro_schedule.intro_amount_authorized,
+        }
+    }
+}
+
+impl From<ScheduleStep> for math::ScheduleStep {
+    fn from(step: ScheduleStep) -> Self {
+        math::ScheduleStep {
+            cycle_start: step.cycle_start,
+            amount_authorized: step.amount_authorized,
+        }
+    }
+}
+
+#[derive(InitSpace, AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DestinationConstraint {
+    // only `token_account` can receive the debited funds
+    TokenAccount { token_account: Pubkey },
+    // any token account owned by `owner` can receive the debited funds
+    Owner { owner: Pubkey },
+}
+
+/**
+  Test fixture for a recurring pre-authorization shared by the test modules, tests only set the fields they are about.
+  Default: activation 100, 10s cycles, 50 per cycle accrued (not reset), 20 debited in cycle 2, never amended.
+*/
+#[cfg(test)]
+pub(crate) struct RecurringFixture {
+    pub(crate) activation_unix_timestamp: i64,
+    pub(crate) repeat_frequency_seconds: u64,
+    pub(crate) recurring_amount_authorized: u64,
+    pub(crate) amount_debited_last_cycle: u64,
+    pub(crate) amount_debited_total: u64,
+    pub(crate) last_debited_cycle: u64,
+    pub(crate) num_cycles: Option<u64>,
+    pub(crate) reset_every_cycle: bool,
+    pub(crate) amended_at_cycle: u64,
+    pub(crate) amount_authorized_before_amendment: u64,
+    pub(crate) max_amount_per_debit: Option<u64>,
+    pub(crate) min_amount_per_debit: Option<u64>,
+    pub(crate) max_accrued_cycles: Option<u64>,
+    pub(crate) calendar_schedule: Option<CalendarSchedule>,
+    pub(crate) debit_window_seconds: Option<u64>,
+    pub(crate) debit_window_offset_seconds: Option<u64>,
+    pub(crate) intro_schedule: Option<IntroSchedule>,
+}
+
+#[cfg(test)]
+impl Default for RecurringFixture {
+    fn default() -> Self {
+        Self {
+            activation_unix_timestamp: 100,
+            repeat_frequency_seconds: 10,
+            recurring_amount_authorized: 50,
+            amount_debited_last_cycle: 20,
+            amount_debited_total: 20,
+            last_debited_cycle: 2,
+            num_cycles: None,
+            reset_every_cycle: false,
+            amended_at_cycle: 1,
+            amount_authorized_before_amendment: 0,
+            max_amount_per_debit: None,
+            min_amount_per_debit: None,
+            max_accrued_cycles: None,
+            calendar_schedule: None,
+            debit_window_seconds: None,
+            debit_window_offset_seconds: None,
+            intro_schedule: None,
+        }
+    }
+}
+
+#[cfg(test)]
+impl RecurringFixture {
+    pub(crate) fn build(self) -> PreAuthorization {
+        PreAuthorization {
+            activation_unix_timestamp: self.activation_unix_timestamp,
+            variant: PreAuthorizationVariant::Recurring {
+                repeat_frequency_seconds: self.repeat_frequency_seconds,
+                recurring_amount_authorized: self.recurring_amount_authorized,
+                amount_debited_last_cycle: self.amount_debited_last_cycle,
+                amount_debited_total: self.amount_debited_total,
+                last_debited_cycle: self.last_debited_cycle,
+                num_cycles: self.num_cycles,
+                reset_every_cycle: self.reset_every_cycle,
+                amended_at_cycle: self.amended_at_cycle,
+                amount_authorized_before_amendment: self.amount_authorized_before_amendment,
+                max_amount_per_debit: self.max_amount_per_debit,
+                min_amount_per_debit: self.min_amount_per_debit,
+                max_accrued_cycles: self.max_accrued_cycles,
+                calendar_schedule: self.calendar_schedule,
+                debit_window_seconds: self.debit_window_seconds,
+                debit_window_offset_seconds: self.debit_window_offset_seconds,
+                intro_schedule: self.intro_schedule,
+            },
+            ..Default::default()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::calendar::CalendarInterval;
+    use test_case::test_case;
+
+    fn recurring_pre_authorization(num_cycles: Option<u64>) -> PreAuthorization {
+        RecurringFixture {
+            num_cycles,
+            ..Default::default()
+        }
+        .build()
+    }
+
+    fn scheduled_pre_authorization(num_cycles: Option<u64>) -> PreAuthorization {
+        PreAuthorization {
+            activation_unix_timestamp: 100,
+            variant: PreAuthorizationVariant::Scheduled {
+                repeat_frequency_seconds: 10,
+                steps: vec![
+                    ScheduleStep {
+                        cycle_start: 1,
+                        amount_authorized: 100,
+                    },
+                    ScheduleStep {
+                        cycle_start: 3,
+                        amount_authorized: 200,
+                    },
+                ],
+                amount_debited_last_cycle: 20,
+                amount_debited_total: 20,
+                last_debited_cycle: 2,
+                num_cycles,
+            },
+            ..Default::default()
+        }
+    }
+
+    // activation: 100, 3 installments (33, 33, 34) every 10s with a 5s grace period
+    fn installments_pre_authorization(amount_debited: u64) -> PreAuthorization {
+        PreAuthorization {
+            activation_unix_timestamp: 100,
+            variant: PreAuthorizationVariant::Installments {
+                total_amount: 100,
+                installment_count: 3,
+                interval_seconds: 10,
+                grace_period_seconds: 5,
+                amount_debited,
+            },
+            ..Default::default()
+        }
+    }
+
+    // activation: 100, at most 100 per 240s (24 buckets of 10s), 30 debited in bucket 2 and 20 in bucket 5
+    fn rolling_window_pre_authorization() -> PreAuthorization {
+        let mut debited_buckets = [0; ROLLING_WINDOW_RING_SIZE];
+        debited_buckets[2] = 30;
+        debited_buckets[5] = 20;
+        PreAuthorization {
+            activation_unix_timestamp: 100,
+            variant: PreAuthorizationVariant::RollingWindow {
+                window_seconds: 240,
+                amount_authorized_per_window: 100,
+                debited_buckets,
+                last_debited_bucket: 5,
+                amount_debited_total: 50,
+            },
+            ..Default::default()
+        }
+    }
+
+    #[test_case(false, 99, Err(CustomProgramError::PreAuthorizationNotActive))]
+    #[test_case(false, 100, Ok(70))]
+    #[test_case(false, 199, Ok(70))]
+    #[test_case(false, 200, Err(CustomProgramError::PreAuthorizationNotActive))]
+    #[test_case(true, 100, Err(CustomProgramError::PreAuthorizationPaused))]
+    fn available_amount_at_one_time(
+        paused: bool,
+        unix_timestamp: i64,
+        expected_res: core::result::Result<u64, CustomProgramError>,
+    ) {
+        let pre_authorization = PreAuthorization {
+            paused,
+            activation_unix_timestamp: 100,
+            variant: PreAuthorizationVariant::OneTime {
+                amount_authorized: 100,
+                expiry_unix_timestamp: 200,
+                amount_debited: 30,
+                max_amount_per_debit: None,
+                close_on_exhaustion: false,
+            },
+            ..Default::default()
+        };
+        assert_eq!(
+            expected_res,
+            pre_authorization.available_amount_at(unix_timestamp)
+        );
+    }
+
+    #[test_case(None, 99, Err(CustomProgramError::PreAuthorizationNotActive))]
+    #[test_case(None, 100, Err(CustomProgramError::LastDebitedCycleBeforeCurrentCycle))]
+    #[test_case(None, 110, Ok(80))]
+    #[test_case(None, 125, Ok(130))]
+    #[test_case(Some(3), 129, Ok(130))]
+    #[test_case(Some(3), 130, Err(CustomProgramError::PreAuthorizationNotActive))]
+    fn available_amount_at_recurring(
+        num_cycles: Option<u64>,
+        unix_timestamp: i64,
+        expected_res: core::result::Result<u64, CustomProgramError>,
+    ) {
+        assert_eq!(
+            expected_res,
+            recurring_pre_authorization(num_cycles).available_amount_at(unix_timestamp)
+        );
+    }
+
+    #[test_case(None, 0, Some(100))]
+    #[test_case(None, 100, Some(110))]
+    #[test_case(None, 119, Some(120))]
+    #[test_case(Some(3), 119, Some(120))]
+    #[test_case(Some(3), 120, None)]
+    fn next_cycle_start(num_cycles: Option<u64>, unix_timestamp: i64, expected_res: Option<i64>) {
+        assert_eq!(
+            expected_res,
+            recurring_pre_authorization(num_cycles).next_cycle_start(unix_timestamp)
+        );
+    }
+
+    #[test]
+    fn one_time_has_no_cycles() {
+        let pre_authorization = PreAuthorization::default();
+        assert_eq!(None, pre_authorization.current_cycle_at(0));
+        assert_eq!(None, pre_authorization.cycle_bounds(1));
+        assert_eq!(None, pre_authorization.next_cycle_start(0));
+    }
+
+    // token account 1 (owner 2, mint 3) is the only account covered by the token account scope
+    #[test_case(false, 1, 2, 3, true)]
+    #[test_case(false, 4, 2, 3, false)]
+    // any token account of owner 2 with mint 3 is covered by the owner and mint scope
+    #[test_case(true, 1, 2, 3, true)]
+    #[test_case(true, 4, 2, 3, true)]
+    #[test_case(true, 1, 4, 3, false)]
+    #[test_case(true, 1, 2, 4, false)]
+    fn covers_token_account(
+        owner_and_mint_scope: bool,
+        token_account: u8,
+        token_account_owner: u8,
+        token_account_mint: u8,
+        expected_res: bool,
+    ) {
+        let key = |n: u8| Pubkey::new_from_array([n; 32]);
+        let pre_authorization = if owner_and_mint_scope {
+            PreAuthorization {
+                scope: PreAuthorizationScope::OwnerAndMint {
+                    owner: key(2),
+                    mint: key(3),
+                },
+                ..Default::default()
+            }
+        } else {
+            PreAuthorization {
+                token_account: key(1),
+                ..Default::default()
+            }
+        };
+
+        assert_eq!(
+            expected_res,
+            pre_authorization.covers_token_account(
+                &key(token_account),
+                &key(token_account_owner),
+                &key(token_account_mint),
+            )
+        );
+    }
+
+    #[test_case(false)]
+    #[test_case(true)]
+    fn scope_seeds(owner_and_mint_scope: bool) {
+        let key = |n: u8| Pubkey::new_from_array([n; 32]);
+        let pre_authorization = if owner_and_mint_scope {
+            PreAuthorization {
+                scope: PreAuthorizationScope::OwnerAndMint {
+                    owner: key(2),
+                    mint: key(3),
+                },
+                ..Default::default()
+            }
+        } else {
+            PreAuthorization {
+                token_account: key(1),
+                ..Default::default()
+            }
+        };
+        let expected_pda = if owner_and_mint_scope {
+            Pubkey::find_program_address(
+                &[
+                    b"pre-authorization",
+                    key(2).as_ref(),
+                    key(3).as_ref(),
+                    key(4).as_ref(),
+                ],
+                &crate::ID,
+            )
+        } else {
+            Pubkey::find_program_address(
+                &[b"pre-authorization", key(1).as_ref(), key(4).as_ref()],
+                &crate::ID,
+            )
+        };
+
+        let [first_seed, second_seed] = pre_authorization.scope_seeds();
+        assert_eq!(
+            expected_pda,
+            Pubkey::find_program_address(
+                &[
+                    b"pre-authorization",
+                    first_seed,
+                    second_seed,
+                    key(4).as_ref(),
+                ],
+                &crate::ID,
+            )
+        );
+    }
+
+    // destination 1 is owned by 2
+    #[test_case(None, true)]
+    #[test_case(Some(DestinationConstraint::TokenAccount { token_account: Pubkey::new_from_array([1; 32]) }), true)]
+    #[test_case(Some(DestinationConstraint::TokenAccount { token_account: Pubkey::new_from_array([2; 32]) }), false)]
+    #[test_case(Some(DestinationConstraint::Owner { owner: Pubkey::new_from_array([2; 32]) }), true)]
+    #[test_case(Some(DestinationConstraint::Owner { owner: Pubkey::new_from_array([1; 32]) }), false)]
+    fn allows_destination(
+        destination_constraint: Option<DestinationConstraint>,
+        expected_res: bool,
+    ) {
+        let pre_authorization = PreAuthorization {
+            destination_constraint,
+            ..Default::default()
+        };
+
+        assert_eq!(
+            expected_res,
+            pre_authorization.allows_destination(
+                &Pubkey::new_from_array([1; 32]),
+                &Pubkey::new_from_array([2; 32]),
+            )
+        );
+    }
+
+    #[test_case(None, None, 1, Ok(()))]
+    #[test_case(Some(10), Some(5), 5, Ok(()))]
+    #[test_case(Some(10), Some(5), 10, Ok(()))]
+    #[test_case(Some(10), Some(5), 4, Err(CustomProgramError::DebitAmountBelowMinimum))]
+    #[test_case(
+        Some(10),
+        Some(5),
+        11,
+        Err(CustomProgramError::DebitAmountAboveMaximum)
+    )]
+    #[test_case(None, Some(5), u64::MAX, Ok(()))]
+    fn check_amount_per_debit_recurring(
+        max_amount_per_debit: Option<u64>,
+        min_amount_per_debit: Option<u64>,
+        amount: u64,
+        expected_res: core::result::Result<(), CustomProgramError>,
+    ) {
+        let pre_authorization = RecurringFixture {
+            max_amount_per_debit,
+            min_amount_per_debit,
+            ..Default::default()
         }
+        .build();
+
+        assert_eq!(
+            expected_res,
+            pre_authorization.check_amount_per_debit(amount)
+        );
+    }
+
+    #[test_case(None, 1, Ok(()))]
+    #[test_case(Some(10), 10, Ok(()))]
+    #[test_case(Some(10), 11, Err(CustomProgramError::DebitAmountAboveMaximum))]
+    fn check_amount_per_debit_one_time(
+        max_amount_per_debit: Option<u64>,
+        amount: u64,
+        expected_res: core::result::Result<(), CustomProgramError>,
+    ) {
+        let pre_authorization = PreAuthorization {
+            variant: PreAuthorizationVariant::OneTime {
+                amount_authorized: 100,
+                expiry_unix_timestamp: 200,
+                amount_debited: 0,
+                max_amount_per_debit,
+                close_on_exhaustion: false,
+            },
+            ..Default::default()
+        };
+
+        assert_eq!(
+            expected_res,
+            pre_authorization.check_amount_per_debit(amount)
+        );
+    }
+
+    // 50 per cycle accrued over 3 cycles with 20 debited
+    #[test_case(None, Ok(130))]
+    #[test_case(Some(1), Ok(50))]
+    #[test_case(Some(2), Ok(100))]
+    #[test_case(Some(3), Ok(130))]
+    fn available_amount_at_recurring_max_accrued_cycles(
+        max_accrued_cycles: Option<u64>,
+        expected_res: core::result::Result<u64, CustomProgramError>,
+    ) {
+        let pre_authorization = RecurringFixture {
+            max_accrued_cycles,
+            ..Default::default()
+        }
+        .build();
+
+        assert_eq!(expected_res, pre_authorization.available_amount_at(125));
+    }
+
+    // activation: 2024-01-15 00:00, monthly on the 1st, 50 per cycle accrued, 20 debited in cycle 2 (February)
+    #[test_case(1_705_276_800 - 1, Err(CustomProgramError::PreAuthorizationNotActive))]
+    #[test_case(
+        1_706_745_599,
+        Err(CustomProgramError::LastDebitedCycleBeforeCurrentCycle)
+    )]
+    #[test_case(1_706_745_600, Ok(80))]
+    #[test_case(1_709_251_199, Ok(80))]
+    #[test_case(1_709_251_200, Ok(130))]
+    fn available_amount_at_recurring_calendar(
+        unix_timestamp: i64,
+        expected_res: core::result::Result<u64, CustomProgramError>,
+    ) {
+        let pre_authorization = RecurringFixture {
+            activation_unix_timestamp: 1_705_276_800,
+            repeat_frequency_seconds: 0,
+            calendar_schedule: Some(CalendarSchedule {
+                interval: CalendarInterval::Monthly,
+                day_of_month: 1,
+            }),
+            ..Default::default()
+        }
+        .build();
+
+        assert_eq!(
+            expected_res,
+            pre_authorization.available_amount_at(unix_timestamp)
+        );
+        // 2024-02-01 to 2024-03-01
+        assert_eq!(
+            Some((1_706_745_600, 1_709_251_200)),
+            pre_authorization.cycle_bounds(2)
+        );
+        assert_eq!(Some(1_705_276_800), pre_authorization.next_cycle_start(0));
+        assert_eq!(
+            Some(1_706_745_600),
+            pre_authorization.next_cycle_start(1_705_276_800)
+        );
+    }
+
+    // cycle 3: [120, 130)
+    #[test_case(None, None, 121, Ok(130))]
+    #[test_case(Some(5), Some(2), 121, Err(CustomProgramError::OutsideDebitWindow))]
+    #[test_case(Some(5), Some(2), 122, Ok(130))]
+    #[test_case(Some(5), Some(2), 126, Ok(130))]
+    #[test_case(Some(5), Some(2), 127, Err(CustomProgramError::OutsideDebitWindow))]
+    #[test_case(Some(5), Some(8), 129, Ok(130))]
+    #[test_case(Some(u64::MAX), None, 129, Ok(130))]
+    #[test_case(Some(1), Some(10), 120, Err(CustomProgramError::OutsideDebitWindow))]
+    fn available_amount_at_recurring_debit_window(
+        debit_window_seconds: Option<u64>,
+        debit_window_offset_seconds: Option<u64>,
+        unix_timestamp: i64,
+        expected_res: core::result::Result<u64, CustomProgramError>,
+    ) {
+        let pre_authorization = RecurringFixture {
+            debit_window_seconds,
+            debit_window_offset_seconds,
+            ..Default::default()
+        }
+        .build();
+
+        assert_eq!(
+            expected_res,
+            pre_authorization.available_amount_at(unix_timestamp)
+        );
+    }
+
+    #[test]
+    fn debit_window_bounds() {
+        let pre_authorization = recurring_pre_authorization(None);
+        assert_eq!(Some((120, 130)), pre_authorization.debit_window_bounds(3));
+
+        let pre_authorization = RecurringFixture {
+            debit_window_seconds: Some(5),
+            debit_window_offset_seconds: Some(2),
+            ..Default::default()
+        }
+        .build();
+        assert_eq!(Some((122, 127)), pre_authorization.debit_window_bounds(3));
+        assert_eq!(None, pre_authorization.debit_window_bounds(0));
+        assert_eq!(None, PreAuthorization::default().debit_window_bounds(1));
+    }
+
+    // 50 per cycle accrued over 3 cycles with 20 debited, the first 2 cycles are free
+    #[test_case(None, Ok(30))]
+    #[test_case(Some(1), Ok(30))]
+    fn available_amount_at_recurring_intro(
+        max_accrued_cycles: Option<u64>,
+        expected_res: core::result::Result<u64, CustomProgramError>,
+    ) {
+        let pre_authorization = RecurringFixture {
+            max_accrued_cycles,
+            intro_schedule: Some(IntroSchedule {
+                num_intro_cycles: 2,
+                intro_amount_authorized: 0,
+            }),
+            ..Default::default()
+        }
+        .build();
+
+        assert_eq!(expected_res, pre_authorization.available_amount_at(125));
+    }
+
+    // activation: 100, 10s cycles, 100 per cycle in cycles [1, 3), 200 per cycle from cycle 3, 20 debited in cycle 2
+    #[test_case(None, 99, Err(CustomProgramError::PreAuthorizationNotActive))]
+    #[test_case(None, 100, Err(CustomProgramError::LastDebitedCycleBeforeCurrentCycle))]
+    #[test_case(None, 110, Ok(80))]
+    #[test_case(None, 120, Ok(200))]
+    #[test_case(None, 1_000, Ok(200))]
+    #[test_case(Some(3), 129, Ok(200))]
+    #[test_case(Some(3), 130, Err(CustomProgramError::PreAuthorizationNotActive))]
+    fn available_amount_at_scheduled(
+        num_cycles: Option<u64>,
+        unix_timestamp: i64,
+        expected_res: core::result::Result<u64, CustomProgramError>,
+    ) {
+        let pre_authorization = scheduled_pre_authorization(num_cycles);
+        assert_eq!(
+            expected_res,
+            pre_authorization.available_amount_at(unix_timestamp)
+        );
+        assert_eq!(Ok(()), pre_authorization.check_amount_per_debit(u64::MAX));
+    }
+
+    #[test_case(99, None)]
+    #[test_case(100, Some(0))]
+    #[test_case(119, Some(0))]
+    #[test_case(120, Some(1))]
+    #[test_case(1_000, Some(1))]
+    fn active_schedule_step_at(unix_timestamp: i64, expected_res: Option<usize>) {
+        assert_eq!(
+            expected_res,
+            scheduled_pre_authorization(None).active_schedule_step_at(unix_timestamp)
+        );
+        assert_eq!(
+            None,
+            recurring_pre_authorization(None).active_schedule_step_at(unix_timestamp)
+        );
+    }
+
+    #[test]
+    fn scheduled_cycles() {
+        let pre_authorization = scheduled_pre_authorization(Some(3));
+        assert_eq!(Some(3), pre_authorization.current_cycle_at(125));
+        assert_eq!(Some((120, 130)), pre_authorization.cycle_bounds(3));
+        assert_eq!(Some(120), pre_authorization.next_cycle_start(115));
+        assert_eq!(None, pre_authorization.next_cycle_start(125));
+    }
+
+    #[test]
+    fn init_space_fits_max_schedule_steps() {
+        let pre_authorization = PreAuthorization {
+            variant: PreAuthorizationVariant::Scheduled {
+                repeat_frequency_seconds: 10,
+                steps: vec![
+                    ScheduleStep {
+                        cycle_start: 1,
+                        amount_authorized: 100,
+                    };
+                    MAX_SCHEDULE_STEPS
+                ],
+                amount_debited_last_cycle: 0,
+                amount_debited_total: 0,
+                last_debited_cycle: 1,
+                num_cycles: Some(12),
+            },
+            scope: PreAuthorizationScope::OwnerAndMint {
+                owner: Pubkey::new_unique(),
+                mint: Pubkey::new_unique(),
+            },
+            destination_constraint: Some(DestinationConstraint::Owner {
+                owner: Pubkey::new_unique(),
+            }),
+            ..Default::default()
+        };
+
+        assert!(pre_authorization.try_to_vec().unwrap().len() <= PreAuthorization::INIT_SPACE);
+    }
+
+    #[test_case(0, 99, Err(CustomProgramError::PreAuthorizationNotActive), 0)]
+    #[test_case(0, 100, Ok(33), 0)]
+    #[test_case(0, 105, Ok(33), 1)]
+    #[test_case(33, 105, Ok(0), 0)]
+    #[test_case(0, 110, Ok(66), 1)]
+    #[test_case(40, 115, Ok(26), 1)]
+    #[test_case(0, 120, Ok(100), 2)]
+    #[test_case(0, 125, Ok(100), 3)]
+    #[test_case(66, 1_000, Ok(34), 1)]
+    #[test_case(100, 1_000, Ok(0), 0)]
+    fn available_amount_at_installments(
+        amount_debited: u64,
+        unix_timestamp: i64,
+        expected_res: core::result::Result<u64, CustomProgramError>,
+        expected_installments_overdue: u64,
+    ) {
+        let pre_authorization = installments_pre_authorization(amount_debited);
+        assert_eq!(
+            expected_res,
+            pre_authorization.available_amount_at(unix_timestamp)
+        );
+        assert_eq!(
+            Some(expected_installments_overdue),
+            pre_authorization.installments_overdue_at(unix_timestamp)
+        );
+    }
+
+    #[test_case(0, 0)]
+    #[test_case(32, 0)]
+    #[test_case(33, 1)]
+    #[test_case(99, 2)]
+    #[test_case(100, 3)]
+    fn installments_paid(amount_debited: u64, expected_installments_paid: u64) {
+        assert_eq!(
+            Some(expected_installments_paid),
+            installments_pre_authorization(amount_debited).installments_paid()
+        );
+        assert_eq!(None, recurring_pre_authorization(None).installments_paid());
+        assert_eq!(
+            None,
+            recurring_pre_authorization(None).installments_overdue_at(1_000)
+        );
+    }
+
+    #[test]
+    fn installments_due_dates() {
+        let pre_authorization = installments_pre_authorization(0);
+        assert_eq!(Some(100), pre_authorization.next_cycle_start(0));
+        assert_eq!(Some(110), pre_authorization.next_cycle_start(100));
+        assert_eq!(Some(120), pre_authorization.next_cycle_start(110));
+        assert_eq!(None, pre_authorization.next_cycle_start(120));
+    }
+
+    // bucket n spans [100 + (n - 1) * 10, 100 + n * 10)
+    #[test_case(99, Err(CustomProgramError::PreAuthorizationNotActive))]
+    #[test_case(139, Err(CustomProgramError::LastDebitedCycleBeforeCurrentCycle))]
+    #[test_case(140, Ok(50))]
+    #[test_case(359, Ok(50))]
+    #[test_case(360, Ok(80))]
+    #[test_case(389, Ok(80))]
+    #[test_case(390, Ok(100))]
+    #[test_case(i64::MAX, Ok(100))]
+    fn available_amount_at_rolling_window(
+        unix_timestamp: i64,
+        expected_res: core::result::Result<u64, CustomProgramError>,
+    ) {
+        assert_eq!(
+            expected_res,
+            rolling_window_pre_authorization().available_amount_at(unix_timestamp)
+        );
+    }
+
+    #[test]
+    fn rolling_window_buckets() {
+        let pre_authorization = rolling_window_pre_authorization();
+        assert_eq!(Some(5), pre_authorization.current_cycle_at(145));
+        assert_eq!(Some((140, 150)), pre_authorization.cycle_bounds(5));
+        assert_eq!(Some(150), pre_authorization.next_cycle_start(145));
+        assert_eq!(Ok(()), pre_authorization.check_amount_per_debit(u64::MAX));
+    }
+
+    #[test_case(false, 100, false)]
+    #[test_case(true, 99, false)]
+    #[test_case(true, 100, true)]
+    fn should_close_on_exhaustion_one_time(
+        close_on_exhaustion: bool,
+        amount_debited: u64,
+        expected: bool,
+    ) {
+        let pre_authorization = PreAuthorization {
+            variant: PreAuthorizationVariant::OneTime {
+                amount_authorized: 100,
+                expiry_unix_timestamp: 200,
+                amount_debited,
+                max_amount_per_debit: None,
+                close_on_exhaustion,
+            },
+            ..Default::default()
+        };
+
+        assert_eq!(expected, pre_authorization.should_close_on_exhaustion());
+    }
+
+    #[test]
+    fn should_close_on_exhaustion_only_one_time() {
+        assert!(!recurring_pre_authorization(None).should_close_on_exhaustion());
+        assert!(!installments_pre_authorization(100).should_close_on_exhaustion());
+    }
+
+    #[test_case(199, false)]
+    #[test_case(200, true)]
+    fn is_expired_at_one_time(unix_timestamp: i64, expected: bool) {
+        let pre_authorization = PreAuthorization {
+            activation_unix_timestamp: 100,
+            variant: PreAuthorizationVariant::OneTime {
+                amount_authorized: 100,
+                expiry_unix_timestamp: 200,
+                amount_debited: 0,
+                max_amount_per_debit: None,
+                close_on_exhaustion: false,
+            },
+            ..Default::default()
+        };
+
+        assert_eq!(expected, pre_authorization.is_expired_at(unix_timestamp));
+    }
+
+    // activation: 100, 10s cycles
+    #[test_case(None, 1_000_000, false)]
+    #[test_case(Some(3), 50, false)]
+    #[test_case(Some(3), 129, false)]
+    #[test_case(Some(3), 130, true)]
+    fn is_expired_at_recurring(num_cycles: Option<u64>, unix_timestamp: i64, expected: bool) {
+        assert_eq!(
+            expected,
+            recurring_pre_authorization(num_cycles).is_expired_at(unix_timestamp)
+        );
+    }
+
+    #[test_case(99, false)]
+    #[test_case(100, true)]
+    fn is_expired_at_installments(amount_debited: u64, expected: bool) {
+        assert_eq!(
+            expected,
+            installments_pre_authorization(amount_debited).is_expired_at(i64::MAX)
+        );
+    }
+
+    #[test]
+    fn is_expired_at_rolling_window() {
+        assert!(!rolling_window_pre_authorization().is_expired_at(i64::MAX));
+    }
+
+    #[test]
+    fn outstanding_amount_authorized_one_time() {
+        let pre_authorization = PreAuthorization {
+            variant: PreAuthorizationVariant::OneTime {
+                amount_authorized: 100,
+                expiry_unix_timestamp: 200,
+                amount_debited: 30,
+                max_amount_per_debit: None,
+                close_on_exhaustion: false,
+            },
+            ..Default::default()
+        };
+
+        assert_eq!(Some(70), pre_authorization.outstanding_amount_authorized());
+    }
+
+    // 50 per cycle, 20 debited
+    #[test_case(None, None)]
+    #[test_case(Some(1), Some(30))]
+    #[test_case(Some(4), Some(180))]
+    fn outstanding_amount_authorized_recurring(num_cycles: Option<u64>, expected: Option<u64>) {
+        assert_eq!(
+            expected,
+            recurring_pre_authorization(num_cycles).outstanding_amount_authorized()
+        );
+    }
+
+    #[test_case(30, Some(70))]
+    #[test_case(100, Some(0))]
+    fn outstanding_amount_authorized_installments(amount_debited: u64, expected: Option<u64>) {
+        assert_eq!(
+            expected,
+            installments_pre_authorization(amount_debited).outstanding_amount_authorized()
+        );
+    }
+
+    #[test]
+    fn outstanding_amount_authorized_rolling_window() {
+        assert_eq!(
+            None,
+            rolling_window_pre_authorization().outstanding_amount_authorized()
+        );
     }
 }
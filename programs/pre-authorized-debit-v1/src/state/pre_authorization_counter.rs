@@ -0,0 +1,251 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::CustomProgramError;
+
+// PDA Seeds: ['pre-authorization-counter', token_account]
+#[account]
+#[derive(Default, InitSpace)]
+/**
+ The `pre_authorization_counter` is a PDA account derived with the seeds:
+ ['pre-authorization-counter', token_account].
+ The `pre_authorization_counter` tracks the number of open `pre_authorization` accounts for a `token_account`
+ (owner scoped `pre_authorization` accounts are not counted),
+ such that the `smart_delegate` approval can be revoked once the last one is closed.
+ A `pre_authorization_counter` created by `init_pre_authorization` cannot know about the `pre_authorization`
+ accounts created before it existed, its `count` is only relied on once `count_verified` is set
+ (see `init_pre_authorization_counter`).
+*/
+pub struct PreAuthorizationCounter {
+    /**
+      The `bump` is the canonical PDA bump when derived with seeds:
+      ['pre-authorization-counter', token_account].
+      This field is initialized in `init_pre_authorization` or `init_pre_authorization_counter`
+        (if the account does not exist yet).
+      This field is never updated in any instruction.
+    */
+    pub bump: u8,
+    /**
+      The `token_account` the `pre_authorization` accounts are counted for.
+      This field is initialized in `init_pre_authorization` or `init_pre_authorization_counter`
+        (if the account does not exist yet).
+      This field is never updated in any instruction.
+    */
+    pub token_account: Pubkey,
+    /**
+      The number of open `pre_authorization` accounts for the `token_account`.
+      This field is incremented in `init_pre_authorization`.
+      This field is decremented whenever a `pre_authorization` for the `token_account` is closed
+      (`close_pre_authorization`, `close_expired_pre_authorization` or a `debit` with `close_on_exhaustion`,
+      all of them require the `pre_authorization_counter`).
+      This field can be set once by the `token_account.owner` in `init_pre_authorization_counter`
+        (while `count_verified` is false).
+    */
+    pub count: u64,
+    /**
+      true: the `smart_delegate` is approved for the sum of the outstanding amounts authorized
+      of the open `pre_authorization` accounts (see `PreAuthorization::outstanding_amount_authorized`),
+        it can be above the sum until the owner re-approves it (see `reapprove_smart_delegate`)
+      false: the `smart_delegate` is approved for `u64::MAX`
+      This field is set in `init_pre_authorization` when no `pre_authorization` is open for the `token_account`.
+      This field is never updated while a `pre_authorization` is open for the `token_account`.
+      This field can only be set to true if `count_verified` is true.
+    */
+    pub bounded_delegation: bool,
+    /**
+      true: the `count` was set by the `token_account.owner` (in `init_pre_authorization_counter`),
+        the `smart_delegate` approval is revoked when it reaches 0
+      false: the `count` may not include the `pre_authorization` accounts created before this account,
+        the `smart_delegate` approval is never revoked and bounded delegation is not supported
+      This field is initialized to false in `init_pre_authorization` (if the account does not exist yet).
+      This field is set to true in `init_pre_authorization_counter`.
+    */
+    pub count_verified: bool,
+}
+
+impl PreAuthorizationCounter {
+    /**
+      Sets the delegation mode if no `pre_authorization` is open for the `token_account`,
+      otherwise the delegation mode MUST match the current one.
+      Bounded delegation requires a verified `count`.
+    */
+    pub fn set_delegation_mode(
+        &mut self,
+        bounded_delegation: bool,
+    ) -> core::result::Result<(), CustomProgramError> {
+        if bounded_delegation && !self.count_verified {
+            return Err(CustomProgramError::PreAuthorizationCounterNotVerified);
+        }
+        if self.count == 0 {
+            self.bounded_delegation = bounded_delegation;
+        }
+        if self.bounded_delegation != bounded_delegation {
+            return Err(CustomProgramError::DelegationModeMismatch);
+        }
+        Ok(())
+    }
+
+    pub fn increment(&mut self) -> core::result::Result<(), CustomProgramError> {
+        self.count = self
+            .count
+            .checked_add(1)
+            .ok_or(CustomProgramError::ArithmeticOverflow)?;
+        Ok(())
+    }
+
+    /**
+      Decrements the `count` and returns true if no `pre_authorization` is left open for the `token_account`.
+      An unverified `count` can be below the number of open `pre_authorization` accounts,
+      it never goes below 0 and never reports the last one.
+    */
+    pub fn decrement(&mut self) -> core::result::Result<bool, CustomProgramError> {
+        if !self.count_verified {
+            self.count = self.count.saturating_sub(1);
+            return Ok(false);
+        }
+        self.count = self
+            .count
+            .checked_sub(1)
+            .ok_or(CustomProgramError::ArithmeticUnderflow)?;
+        Ok(self.count == 0)
+    }
+
+    /**
+      Sets the `count` of open `pre_authorization` accounts for the `token_account` (as provided by the owner).
+      The `count` can only be set once (while it is not verified yet),
+      it MUST NOT be set in bounded delegation mode.
+    */
+    pub fn set_verified_count(
+        &mut self,
+        count: u64,
+    ) -> core::result::Result<(), CustomProgramError> {
+        if self.bounded_delegation {
+            return Err(CustomProgramError::PreAuthorizationCounterInBoundedDelegation);
+        }
+        if self.count_verified {
+            return Err(CustomProgramError::PreAuthorizationCounterAlreadyVerified);
+        }
+        self.count = count;
+        self.count_verified = true;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    #[test_case(0, false, true, true, Ok(true))]
+    #[test_case(0, true, false, true, Ok(false))]
+    #[test_case(1, true, true, true, Ok(true))]
+    #[test_case(1, false, false, true, Ok(false))]
+    #[test_case(1, false, true, true, Err(CustomProgramError::DelegationModeMismatch))]
+    #[test_case(1, true, false, true, Err(CustomProgramError::DelegationModeMismatch))]
+    #[test_case(0, false, false, false, Ok(false))]
+    #[test_case(
+        0,
+        false,
+        true,
+        false,
+        Err(CustomProgramError::PreAuthorizationCounterNotVerified)
+    )]
+    fn set_delegation_mode(
+        count: u64,
+        current_bounded_delegation: bool,
+        bounded_delegation: bool,
+        count_verified: bool,
+        expected_res: core::result::Result<bool, CustomProgramError>,
+    ) {
+        let mut counter = PreAuthorizationCounter {
+            count,
+            bounded_delegation: current_bounded_delegation,
+            count_verified,
+            ..Default::default()
+        };
+        assert_eq!(
+            expected_res,
+            counter
+                .set_delegation_mode(bounded_delegation)
+                .map(|_| counter.bounded_delegation)
+        );
+    }
+
+    #[test_case(0, Ok(1))]
+    #[test_case(u64::MAX, Err(CustomProgramError::ArithmeticOverflow))]
+    fn increment(count: u64, expected_res: core::result::Result<u64, CustomProgramError>) {
+        let mut counter = PreAuthorizationCounter {
+            count,
+            ..Default::default()
+        };
+        assert_eq!(expected_res, counter.increment().map(|_| counter.count));
+    }
+
+    #[test_case(2, true, Ok((1, false)))]
+    #[test_case(1, true, Ok((0, true)))]
+    #[test_case(0, true, Err(CustomProgramError::ArithmeticUnderflow))]
+    #[test_case(2, false, Ok((1, false)))]
+    #[test_case(1, false, Ok((0, false)))]
+    #[test_case(0, false, Ok((0, false)))]
+    fn decrement(
+        count: u64,
+        count_verified: bool,
+        expected_res: core::result::Result<(u64, bool), CustomProgramError>,
+    ) {
+        let mut counter = PreAuthorizationCounter {
+            count,
+            count_verified,
+            ..Default::default()
+        };
+        assert_eq!(
+            expected_res,
+            counter.decrement().map(|is_last| (counter.count, is_last))
+        );
+    }
+
+    #[test_case(1, false, false, Ok((3, true)))]
+    #[test_case(0, false, false, Ok((3, true)))]
+    #[test_case(
+        2,
+        true,
+        false,
+        Err(CustomProgramError::PreAuthorizationCounterAlreadyVerified)
+    )]
+    #[test_case(
+        2,
+        true,
+        true,
+        Err(CustomProgramError::PreAuthorizationCounterInBoundedDelegation)
+    )]
+    fn set_verified_count(
+        count: u64,
+        count_verified: bool,
+        bounded_delegation: bool,
+        expected_res: core::result::Result<(u64, bool), CustomProgramError>,
+    ) {
+        let mut counter = PreAuthorizationCounter {
+            count,
+            count_verified,
+            bounded_delegation,
+            ..Default::default()
+        };
+        assert_eq!(
+            expected_res,
+            counter
+                .set_verified_count(3)
+                .map(|_| (counter.count, counter.count_verified))
+        );
+    }
+
+    #[test]
+    fn set_verified_count_then_close_all() {
+        // created with a pre_authorization while 2 were already open
+        let mut counter = PreAuthorizationCounter {
+            count: 1,
+            ..Default::default()
+        };
+        assert_eq!(Ok(()), counter.set_verified_count(3));
+        assert_eq!(Ok(false), counter.decrement());
+        assert_eq!(Ok(false), counter.decrement());
+        assert_eq!(Ok(true), counter.decrement());
+    }
+}
@@ -0,0 +1,160 @@
+use anchor_lang::prelude::*;
+
+use crate::state::pre_authorization::{
+    PreAuthorization, PreAuthorizationScope, PreAuthorizationVariant,
+};
+
+/**
+ The layout of a `pre_authorization` account created by the v1.0.0 program
+ (after the 8 byte `PreAuthorization` discriminator, which is unchanged).
+ It is only read in `migrate_pre_authorization`, see `PreAuthorization` for the fields.
+*/
+#[derive(InitSpace, AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct PreAuthorizationV1 {
+    pub bump: u8,
+    pub paused: bool,
+    pub token_account: Pubkey,
+    pub variant: PreAuthorizationVariantV1,
+    pub debit_authority: Pubkey,
+    pub activation_unix_timestamp: i64,
+}
+
+#[derive(InitSpace, AnchorSerialize, AnchorDeserialize, Clone)]
+pub enum PreAuthorizationVariantV1 {
+    OneTime {
+        amount_authorized: u64,
+        expiry_unix_timestamp: i64,
+        amount_debited: u64,
+    },
+    Recurring {
+        repeat_frequency_seconds: u64,
+        recurring_amount_authorized: u64,
+        amount_debited_last_cycle: u64,
+        amount_debited_total: u64,
+        last_debited_cycle: u64,
+        num_cycles: Option<u64>,
+        reset_every_cycle: bool,
+    },
+}
+
+// The space of a v1.0.0 `pre_authorization` account (including the discriminator)
+pub const PRE_AUTHORIZATION_V1_SPACE: usize = 8 + PreAuthorizationV1::INIT_SPACE;
+
+impl From<PreAuthorizationV1> for PreAuthorization {
+    /**
+      The fields added in v2.0.0 are set to the values that keep the v1.0.0 behavior
+      (i.e. the values `init_pre_authorization` sets when the new params are not used).
+    */
+    fn from(pre_authorization: PreAuthorizationV1) -> Self {
+        PreAuthorization {
+            bump: pre_authorization.bump,
+            paused: pre_authorization.paused,
+            token_account: pre_authorization.token_account,
+            variant: match pre_authorization.variant {
+                PreAuthorizationVariantV1::OneTime {
+                    amount_authorized,
+                    expiry_unix_timestamp,
+                    amount_debited,
+                } => PreAuthorizationVariant::OneTime {
+                    amount_authorized,
+                    expiry_unix_timestamp,
+                    amount_debited,
+                    max_amount_per_debit: None,
+                    close_on_exhaustion: false,
+                },
+                PreAuthorizationVariantV1::Recurring {
+                    repeat_frequency_seconds,
+                    recurring_amount_authorized,
+                    amount_debited_last_cycle,
+                    amount_debited_total,
+                    last_debited_cycle,
+                    num_cycles,
+                    reset_every_cycle,
+                } => PreAuthorizationVariant::Recurring {
+                    repeat_frequency_seconds,
+                    recurring_amount_authorized,
+                    amount_debited_last_cycle,
+                    amount_debited_total,
+                    last_debited_cycle,
+                    num_cycles,
+                    reset_every_cycle,
+                    amended_at_cycle: 1,
+                    amount_authorized_before_amendment: 0,
+                    max_amount_per_debit: None,
+                    min_amount_per_debit: None,
+                    max_accrued_cycles: None,
+                    calendar_schedule: None,
+                    debit_window_seconds: None,
+                    debit_window_offset_seconds: None,
+                    intro_schedule: None,
+                },
+            },
+            debit_authority: pre_authorization.debit_authority,
+            activation_unix_timestamp: pre_authorization.activation_unix_timestamp,
+            rotation_requires_owner_signature: false,
+            scope: PreAuthorizationScope::TokenAccount,
+            destination_constraint: None,
+            num_debits: 0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anchor_lang::Discriminator;
+
+    #[test]
+    fn pre_authorization_v1_space() {
+        // bump, paused, token_account, variant (tag + recurring), debit_authority, activation_unix_timestamp
+        assert_eq!(
+            8 + 1 + 1 + 32 + (1 + 5 * 8 + 9 + 1) + 32 + 8,
+            PRE_AUTHORIZATION_V1_SPACE
+        );
+    }
+
+    #[test]
+    fn pre_authorization_from_v1() {
+        let pre_authorization_v1 = PreAuthorizationV1 {
+            bump: 254,
+            paused: false,
+            token_account: Pubkey::new_unique(),
+            variant: PreAuthorizationVariantV1::Recurring {
+                repeat_frequency_seconds: 3600,
+                recurring_amount_authorized: 100,
+                amount_debited_last_cycle: 40,
+                amount_debited_total: 140,
+                last_debited_cycle: 2,
+                num_cycles: Some(10),
+                reset_every_cycle: false,
+            },
+            debit_authority: Pubkey::new_unique(),
+            activation_unix_timestamp: 1_000,
+        };
+        let mut data = PreAuthorization::DISCRIMINATOR.to_vec();
+        pre_authorization_v1.serialize(&mut data).unwrap();
+        assert_eq!(PRE_AUTHORIZATION_V1_SPACE, data.len());
+
+        let pre_authorization =
+            PreAuthorization::from(PreAuthorizationV1::deserialize(&mut &data[8..]).unwrap());
+
+        assert_eq!(254, pre_authorization.bump);
+        assert!(!pre_authorization.paused);
+        assert_eq!(
+            pre_authorization_v1.token_account,
+            pre_authorization.token_account
+        );
+        assert_eq!(
+            pre_authorization_v1.debit_authority,
+            pre_authorization.debit_authority
+        );
+        assert_eq!(1_000, pre_authorization.activation_unix_timestamp);
+        assert_eq!(PreAuthorizationScope::TokenAccount, pre_authorization.scope);
+        // the amounts debited carry over (unused amounts from prev. cycles carry forward)
+        assert_eq!(Ok(60), pre_authorization.available_amount_at(1_000 + 3600));
+        assert_eq!(
+            Ok(160),
+            pre_authorization.available_amount_at(1_000 + 2 * 3600)
+        );
+    }
+}
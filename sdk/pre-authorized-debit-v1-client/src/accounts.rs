@@ -0,0 +1,46 @@
+use anchor_lang::prelude::*;
+use pre_authorized_debit_v1::state::{
+    debit_receipt::DebitReceipt, pending_amendment::PendingAmendment,
+    pre_authorization::PreAuthorization, pre_authorization_counter::PreAuthorizationCounter,
+    smart_delegate::SmartDelegate,
+};
+
+/**
+  Deserializes the raw data of a `pre_authorization` account.
+  The data MUST start with the `PreAuthorization` account discriminator.
+*/
+pub fn deserialize_pre_authorization(data: &[u8]) -> Result<PreAuthorization> {
+    PreAuthorization::try_deserialize(&mut &data[..])
+}
+
+/**
+  Deserializes the raw data of the `smart_delegate` account.
+  The data MUST start with the `SmartDelegate` account discriminator.
+*/
+pub fn deserialize_smart_delegate(data: &[u8]) -> Result<SmartDelegate> {
+    SmartDelegate::try_deserialize(&mut &data[..])
+}
+
+/**
+  Deserializes the raw data of a `pending_amendment` account.
+  The data MUST start with the `PendingAmendment` account discriminator.
+*/
+pub fn deserialize_pending_amendment(data: &[u8]) -> Result<PendingAmendment> {
+    PendingAmendment::try_deserialize(&mut &data[..])
+}
+
+/**
+  Deserializes the raw data of a `pre_authorization_counter` account.
+  The data MUST start with the `PreAuthorizationCounter` account discriminator.
+*/
+pub fn deserialize_pre_authorization_counter(data: &[u8]) -> Result<PreAuthorizationCounter> {
+    PreAuthorizationCounter::try_deserialize(&mut &data[..])
+}
+
+/**
+  Deserializes the raw data of a `debit_receipt` account.
+  The data MUST start with the `DebitReceipt` account discriminator.
+*/
+pub fn deserialize_debit_receipt(data: &[u8]) -> Result<DebitReceipt> {
+    DebitReceipt::try_deserialize(&mut &data[..])
+}
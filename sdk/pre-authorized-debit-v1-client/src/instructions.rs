@@ -0,0 +1,1915 @@
+use anchor_lang::{prelude::*, solana_program::instruction::Instruction, InstructionData};
+use anchor_spl::token_2022::spl_token_2022;
+use pre_authorized_debit_v1::{
+    accounts, instruction,
+    instructions::{
+        AcceptAmendmentParams, DebitBatchParams, DebitParams, InitPreAuthorizationCounterParams,
+        InitPreAuthorizationParams, ProposeAmendmentParams, UpdatePausePreAuthorizationParams,
+        UpdatePreAuthorizationParams,
+    },
+};
+
+use pre_authorized_debit_v1::state::pre_authorization::PreAuthorizationScope;
+
+use crate::pda::{
+    find_debit_receipt_address, find_owner_pre_authorization_address,
+    find_pending_amendment_address, find_pre_authorization_address,
+    find_pre_authorization_counter_address, find_scoped_pre_authorization_address,
+    find_smart_delegate_address,
+};
+
+/**
+  The `InstructionFactory` builds the instructions of the `pre_authorized_debit_v1` program
+  (and the SPL token approve instruction for the `smart_delegate`).
+  All PDAs are derived from the `program_id` the factory was created with.
+  The factory is purely offline, the caller is expected to provide any on-chain state
+  (e.g. `token_account.owner` or the token program of a `token_account`).
+*/
+#[derive(Clone, Copy, Debug)]
+pub struct InstructionFactory {
+    program_id: Pubkey,
+}
+
+impl Default for InstructionFactory {
+    fn default() -> Self {
+        Self::mainnet()
+    }
+}
+
+impl InstructionFactory {
+    pub fn custom(program_id: Pubkey) -> Self {
+        Self { program_id }
+    }
+
+    pub fn mainnet() -> Self {
+        Self::custom(pre_authorized_debit_v1::ID)
+    }
+
+    pub fn devnet() -> Self {
+        Self::custom(pre_authorized_debit_v1::ID)
+    }
+
+    pub fn program_id(&self) -> Pubkey {
+        self.program_id
+    }
+
+    pub fn smart_delegate(&self) -> Pubkey {
+        find_smart_delegate_address(&self.program_id).0
+    }
+
+    pub fn pre_authorization(&self, token_account: &Pubkey, debit_authority: &Pubkey) -> Pubkey {
+        find_pre_authorization_address(&self.program_id, token_account, debit_authority).0
+    }
+
+    pub fn owner_pre_authorization(
+        &self,
+        owner: &Pubkey,
+        mint: &Pubkey,
+        debit_authority: &Pubkey,
+    ) -> Pubkey {
+        find_owner_pre_authorization_address(&self.program_id, owner, mint, debit_authority).0
+    }
+
+    pub fn scoped_pre_authorization(
+        &self,
+        scope: &PreAuthorizationScope,
+        token_account: &Pubkey,
+        debit_authority: &Pubkey,
+    ) -> Pubkey {
+        find_scoped_pre_authorization_address(
+            &self.program_id,
+            scope,
+            token_account,
+            debit_authority,
+        )
+        .0
+    }
+
+    pub fn pending_amendment(&self, pre_authorization: &Pubkey) -> Pubkey {
+        find_pending_amendment_address(&self.program_id, pre_authorization).0
+    }
+
+    pub fn pre_authorization_counter(&self, token_account: &Pubkey) -> Pubkey {
+        find_pre_authorization_counter_address(&self.program_id, token_account).0
+    }
+
+    pub fn debit_receipt(&self, pre_authorization: &Pubkey, debit_index: u64) -> Pubkey {
+        find_debit_receipt_address(&self.program_id, pre_authorization, debit_index).0
+    }
+
+    /**
+      Builds the `init_smart_delegate` instruction.
+      Expected signers: `payer`.
+    */
+    pub fn init_smart_delegate(&self, payer: Pubkey) -> Instruction {
+        self.build(
+            accounts::InitSmartDelegate {
+                payer,
+                smart_delegate: self.smart_delegate(),
+                system_program: System::id(),
+            },
+            instruction::InitSmartDelegate {},
+        )
+    }
+
+    /**
+      Builds the `init_pre_authorization` instruction.
+      The `pre_authorization` PDA is derived from `token_account` and `params.debit_authority`.
+      Expected signers: `payer` and `owner` (the `token_account.owner`).
+    */
+    pub fn init_pre_authorization(
+        &self,
+        payer: Pubkey,
+        owner: Pubkey,
+        token_account: Pubkey,
+        token_program: Pubkey,
+        params: InitPreAuthorizationParams,
+    ) -> Instruction {
+        self.build(
+            accounts::InitPreAuthorization {
+                payer,
+                owner,
+                smart_delegate: self.smart_delegate(),
+                token_account,
+                pre_authorization: self.pre_authorization(&token_account, &params.debit_authority),
+                pre_authorization_counter: self.pre_authorization_counter(&token_account),
+                token_program,
+                system_program: System::id(),
+            },
+            instruction::InitPreAuthorization { params },
+        )
+    }
+
+    /**
+      Builds the `init_pre_authorization_counter` instruction.
+      `params.count` MUST be the number of open `pre_authorization` accounts scoped to the `token_account`.
+      The count can only be set once (the `pre_authorization_counter` MUST NOT be verified yet).
+      Expected signers: `payer` and `owner` (the `token_account.owner`).
+    */
+    pub fn init_pre_authorization_counter(
+        &self,
+        payer: Pubkey,
+        owner: Pubkey,
+        token_account: Pubkey,
+        params: InitPreAuthorizationCounterParams,
+    ) -> Instruction {
+        self.build(
+            accounts::InitPreAuthorizationCounter {
+                payer,
+                owner,
+                token_account,
+                pre_authorization_counter: self.pre_authorization_counter(&token_account),
+                system_program: System::id(),
+            },
+            instruction::InitPreAuthorizationCounter { params },
+        )
+    }
+
+    /**
+      Builds the `init_owner_pre_authorization` instruction.
+      The `pre_authorization` PDA is derived from `owner`, `mint` and `params.debit_authority`.
+      NOTE: The `smart_delegate` still has to be approved on each token account to debit from
+      (see `approve_smart_delegate`).
+      Expected signers: `payer` and `owner`.
+    */
+    pub fn init_owner_pre_authorization(
+        &self,
+        payer: Pubkey,
+        owner: Pubkey,
+        mint: Pubkey,
+        params: InitPreAuthorizationParams,
+    ) -> Instruction {
+        self.build(
+            accounts::InitOwnerPreAuthorization {
+                payer,
+                owner,
+                mint,
+                pre_authorization: self.owner_pre_authorization(
+                    &owner,
+                    &mint,
+                    &params.debit_authority,
+                ),
+                system_program: System::id(),
+            },
+            instruction::InitOwnerPreAuthorization { params },
+        )
+    }
+
+    /**
+      Builds the `debit` instruction.
+      The `receiver` MUST be set to the `token_account.owner` if the debit exhausts a one-time
+      `pre_authorization` with `close_on_exhaustion` set.
+      If `memo` is set, the `params.reference` (which MUST be set) is also logged in a SPL memo.
+      If `receipt` is set to `(receipt_payer, pre_authorization.num_debits)`, a `debit_receipt` is created.
+      Expected signers: `debit_authority` (and `receipt_payer` if set).
+    */
+    pub fn debit(
+        &self,
+        debit_authority: Pubkey,
+        mint: Pubkey,
+        token_account: Pubkey,
+        destination_token_account: Pubkey,
+        token_program: Pubkey,
+        receiver: Option<Pubkey>,
+        memo: bool,
+        receipt: Option<(Pubkey, u64)>,
+        params: DebitParams,
+    ) -> Instruction {
+        let pre_authorization = self.pre_authorization(&token_account, &debit_authority);
+        self.build(
+            accounts::Debit {
+                debit_authority,
+                mint,
+                token_account,
+                destination_token_account,
+                smart_delegate: self.smart_delegate(),
+                pre_authorization,
+                token_program,
+                receiver,
+                // only needed alongside the receiver (when the debit closes the pre_authorization)
+                pre_authorization_counter: receiver
+                    .map(|_| self.pre_authorization_counter(&token_account)),
+                memo_program: memo.then_some(spl_memo::ID),
+                receipt_payer: receipt.map(|(receipt_payer, _)| receipt_payer),
+                debit_receipt: receipt
+                    .map(|(_, debit_index)| self.debit_receipt(&pre_authorization, debit_index)),
+                system_program: receipt.map(|_| System::id()),
+            },
+            instruction::Debit { params },
+        )
+    }
+
+    /**
+      Builds the `debit_batch` instruction, `entries` are the
+      `(token_account, pre_authorization, destination_token_account)` of each entry
+      (in the same order as `params.amounts`), a `pre_authorization` MUST NOT be in more than one entry.
+      The entries are debited without a reference or a `debit_receipt` (see `debit`).
+      Expected signers: `debit_authority`.
+    */
+    pub fn debit_batch(
+        &self,
+        debit_authority: Pubkey,
+        mint: Pubkey,
+        token_program: Pubkey,
+        entries: &[(Pubkey, Pubkey, Pubkey)],
+        params: DebitBatchParams,
+    ) -> Instruction {
+        let mut ix = self.build(
+            accounts::DebitBatch {
+                debit_authority,
+                mint,
+                smart_delegate: self.smart_delegate(),
+                token_program,
+            },
+            instruction::DebitBatch { params },
+        );
+        ix.accounts.extend(entries.iter().flat_map(
+            |(token_account, pre_authorization, destination_token_account)| {
+                [
+                    AccountMeta::new(*token_account, false),
+                    AccountMeta::new(*pre_authorization, false),
+                    AccountMeta::new(*destination_token_account, false),
+                ]
+            },
+        ));
+        ix
+    }
+
+    /**
+      Builds the `debit` instruction for an owner scoped `pre_authorization`
+      (`token_account` MUST be owned by `owner` and have the mint `mint`).
+      The `receiver` MUST be set to the `owner` if the debit exhausts a one-time
+      `pre_authorization` with `close_on_exhaustion` set.
+      If `memo` is set, the `params.reference` (which MUST be set) is also logged in a SPL memo.
+      If `receipt` is set to `(receipt_payer, pre_authorization.num_debits)`, a `debit_receipt` is created.
+      Expected signers: `debit_authority` (and `receipt_payer` if set).
+    */
+    pub fn debit_owner_pre_authorization(
+        &self,
+        debit_authority: Pubkey,
+        owner: Pubkey,
+        mint: Pubkey,
+        token_account: Pubkey,
+        destination_token_account: Pubkey,
+        token_program: Pubkey,
+        receiver: Option<Pubkey>,
+        memo: bool,
+        receipt: Option<(Pubkey, u64)>,
+        params: DebitParams,
+    ) -> Instruction {
+        let pre_authorization = self.owner_pre_authorization(&owner, &mint, &debit_authority);
+        self.build(
+            accounts::Debit {
+                debit_authority,
+                mint,
+                token_account,
+                destination_token_account,
+                smart_delegate: self.smart_delegate(),
+                pre_authorization,
+                token_program,
+                receiver,
+                // checked to not be in bounded delegation mode (it can be uninitialized)
+                pre_authorization_counter: Some(self.pre_authorization_counter(&token_account)),
+                memo_program: memo.then_some(spl_memo::ID),
+                receipt_payer: receipt.map(|(receipt_payer, _)| receipt_payer),
+                debit_receipt: receipt
+                    .map(|(_, debit_index)| self.debit_receipt(&pre_authorization, debit_index)),
+                system_program: receipt.map(|_| System::id()),
+            },
+            instruction::Debit { params },
+        )
+    }
+
+    /**
+      Builds the `close_pre_authorization` instruction.
+      The `authority` MUST be either the `token_account.owner` or the `debit_authority`.
+      If the `authority` is the `debit_authority`, the `receiver` MUST be the `token_account.owner`.
+      If the `authority` is the `token_account.owner` and this is the last `pre_authorization` of the
+      `token_account` (with a verified count), the `smart_delegate` approval is revoked.
+      In bounded delegation mode (if the `authority` is the `token_account.owner`), `other_pre_authorizations`
+      MUST be all the other open `pre_authorization` accounts of the `token_account` (it is ignored otherwise).
+      Expected signers: `authority`.
+    */
+    pub fn close_pre_authorization(
+        &self,
+        receiver: Pubkey,
+        authority: Pubkey,
+        token_account: Pubkey,
+        token_program: Pubkey,
+        debit_authority: Pubkey,
+        other_pre_authorizations: &[Pubkey],
+    ) -> Instruction {
+        let mut ix = self.build(
+            accounts::ClosePreAuthorization {
+                receiver,
+                authority,
+                token_account,
+                smart_delegate: self.smart_delegate(),
+                pre_authorization: self.pre_authorization(&token_account, &debit_authority),
+                pre_authorization_counter: Some(self.pre_authorization_counter(&token_account)),
+                token_program,
+            },
+            instruction::ClosePreAuthorization {},
+        );
+        ix.accounts.extend(
+            other_pre_authorizations
+                .iter()
+                .map(|pre_authorization| AccountMeta::new_readonly(*pre_authorization, false)),
+        );
+        ix
+    }
+
+    /**
+      Builds the `close_expired_pre_authorization` instruction.
+      The `pre_authorization` MUST be expired (i.e. it can never be debited again).
+      The `bounty_receiver` (if set) receives the bounty for closing it.
+      Expected signers: `caller`.
+    */
+    pub fn close_expired_pre_authorization(
+        &self,
+        caller: Pubkey,
+        token_account_owner: Pubkey,
+        bounty_receiver: Option<Pubkey>,
+        token_account: Pubkey,
+        debit_authority: Pubkey,
+    ) -> Instruction {
+        self.build(
+            accounts::CloseExpiredPreAuthorization {
+                caller,
+                receiver: token_account_owner,
+                bounty_receiver,
+                token_account,
+                pre_authorization: self.pre_authorization(&token_account, &debit_authority),
+                pre_authorization_counter: Some(self.pre_authorization_counter(&token_account)),
+            },
+            instruction::CloseExpiredPreAuthorization {},
+        )
+    }
+
+    /**
+      Builds the `close_expired_pre_authorization` instruction for a `pre_authorization` scoped to
+      the `owner` and `mint` (`token_account` MUST be owned by `owner` and have the mint `mint`).
+      The `pre_authorization` MUST be expired (i.e. it can never be debited again).
+      The `bounty_receiver` (if set) receives the bounty for closing it.
+      Expected signers: `caller`.
+    */
+    pub fn close_expired_owner_pre_authorization(
+        &self,
+        caller: Pubkey,
+        owner: Pubkey,
+        bounty_receiver: Option<Pubkey>,
+        mint: Pubkey,
+        token_account: Pubkey,
+        debit_authority: Pubkey,
+    ) -> Instruction {
+        self.build(
+            accounts::CloseExpiredPreAuthorization {
+                caller,
+                receiver: owner,
+                bounty_receiver,
+                token_account,
+                pre_authorization: self.owner_pre_authorization(&owner, &mint, &debit_authority),
+                // owner scoped pre_authorizations are not counted
+                pre_authorization_counter: None,
+            },
+            instruction::CloseExpiredPreAuthorization {},
+        )
+    }
+
+    /**
+      Builds the `close_owner_pre_authorization` instruction.
+      The `authority` MUST be either the `owner` or the `debit_authority`.
+      If the `authority` is the `debit_authority`, the `receiver` MUST be the `owner`.
+      Expected signers: `authority`.
+    */
+    pub fn close_owner_pre_authorization(
+        &self,
+        receiver: Pubkey,
+        authority: Pubkey,
+        owner: Pubkey,
+        mint: Pubkey,
+        debit_authority: Pubkey,
+    ) -> Instruction {
+        self.build(
+            accounts::CloseOwnerPreAuthorization {
+                receiver,
+                authority,
+                owner,
+                mint,
+                pre_authorization: self.owner_pre_authorization(&owner, &mint, &debit_authority),
+            },
+            instruction::CloseOwnerPreAuthorization {},
+        )
+    }
+
+    /**
+      Builds the `update_pause_pre_authorization` instruction.
+      Expected signers: `owner` (the `token_account.owner`).
+    */
+    pub fn update_pause_pre_authorization(
+        &self,
+        owner: Pubkey,
+        token_account: Pubkey,
+        debit_authority: Pubkey,
+        params: UpdatePausePreAuthorizationParams,
+    ) -> Instruction {
+        self.build(
+            accounts::UpdatePausePreAuthorization {
+                owner,
+                token_account,
+                pre_authorization: self.pre_authorization(&token_account, &debit_authority),
+            },
+            instruction::UpdatePausePreAuthorization { params },
+        )
+    }
+
+    /**
+      Builds the `update_pause_pre_authorization` instruction for a `pre_authorization` scoped to
+      the `owner` and `mint` (`token_account` MUST be owned by `owner` and have the mint `mint`).
+      Expected signers: `owner`.
+    */
+    pub fn update_pause_owner_pre_authorization(
+        &self,
+        owner: Pubkey,
+        mint: Pubkey,
+        token_account: Pubkey,
+        debit_authority: Pubkey,
+        params: UpdatePausePreAuthorizationParams,
+    ) -> Instruction {
+        self.build(
+            accounts::UpdatePausePreAuthorization {
+                owner,
+                token_account,
+                pre_authorization: self.owner_pre_authorization(&owner, &mint, &debit_authority),
+            },
+            instruction::UpdatePausePreAuthorization { params },
+        )
+    }
+
+    /**
+      Builds the `reapprove_smart_delegate` instruction.
+      In bounded delegation mode, `pre_authorizations` MUST be all the open `pre_authorization` accounts
+      of the `token_account` (it is ignored otherwise).
+      Expected signers: `owner` (the `token_account.owner`).
+    */
+    pub fn reapprove_smart_delegate(
+        &self,
+        owner: Pubkey,
+        token_account: Pubkey,
+        token_program: Pubkey,
+        pre_authorizations: &[Pubkey],
+    ) -> Instruction {
+        let mut ix = self.build(
+            accounts::ReapproveSmartDelegate {
+                owner,
+                token_account,
+                smart_delegate: self.smart_delegate(),
+                pre_authorization_counter: self.pre_authorization_counter(&token_account),
+                token_program,
+            },
+            instruction::ReapproveSmartDelegate {},
+        );
+        ix.accounts.extend(
+            pre_authorizations
+                .iter()
+                .map(|pre_authorization| AccountMeta::new_readonly(*pre_authorization, false)),
+        );
+        ix
+    }
+
+    /**
+      Builds the `update_pre_authorization` instruction.
+      Expected signers: `owner` (the `token_account.owner`).
+    */
+    pub fn update_pre_authorization(
+        &self,
+        owner: Pubkey,
+        token_account: Pubkey,
+        token_program: Pubkey,
+        debit_authority: Pubkey,
+        params: UpdatePreAuthorizationParams,
+    ) -> Instruction {
+        self.build(
+            accounts::UpdatePreAuthorization {
+                owner,
+                token_account,
+                pre_authorization: self.pre_authorization(&token_account, &debit_authority),
+                smart_delegate: self.smart_delegate(),
+                pre_authorization_counter: Some(self.pre_authorization_counter(&token_account)),
+                token_program,
+            },
+            instruction::UpdatePreAuthorization { params },
+        )
+    }
+
+    /**
+      Builds the `update_pre_authorization` instruction for a `pre_authorization` scoped to
+      the `owner` and `mint` (`token_account` MUST be owned by `owner` and have the mint `mint`).
+      Expected signers: `owner`.
+    */
+    pub fn update_owner_pre_authorization(
+        &self,
+        owner: Pubkey,
+        mint: Pubkey,
+        token_account: Pubkey,
+        token_program: Pubkey,
+        debit_authority: Pubkey,
+        params: UpdatePreAuthorizationParams,
+    ) -> Instruction {
+        self.build(
+            accounts::UpdatePreAuthorization {
+                owner,
+                token_account,
+                pre_authorization: self.scoped_pre_authorization(
+                    &PreAuthorizationScope::OwnerAndMint { owner, mint },
+                    &token_account,
+                    &debit_authority,
+                ),
+                smart_delegate: self.smart_delegate(),
+                // owner scoped pre_authorizations are not counted
+                pre_authorization_counter: None,
+                token_program,
+            },
+            instruction::UpdatePreAuthorization { params },
+        )
+    }
+
+    /**
+      Builds the `propose_amendment` instruction.
+      Expected signers: `payer` and `debit_authority`.
+    */
+    pub fn propose_amendment(
+        &self,
+        payer: Pubkey,
+        debit_authority: Pubkey,
+        token_account: Pubkey,
+        params: ProposeAmendmentParams,
+    ) -> Instruction {
+        let pre_authorization = self.pre_authorization(&token_account, &debit_authority);
+        self.build(
+            accounts::ProposeAmendment {
+                payer,
+                debit_authority,
+                pre_authorization,
+                pending_amendment: self.pending_amendment(&pre_authorization),
+                system_program: System::id(),
+            },
+            instruction::ProposeAmendment { params },
+        )
+    }
+
+    /**
+      Builds the `propose_amendment` instruction for a `pre_authorization` scoped to
+      the `owner` and `mint`.
+      Expected signers: `payer` and `debit_authority`.
+    */
+    pub fn propose_owner_pre_authorization_amendment(
+        &self,
+        payer: Pubkey,
+        debit_authority: Pubkey,
+        owner: Pubkey,
+        mint: Pubkey,
+        params: ProposeAmendmentParams,
+    ) -> Instruction {
+        let pre_authorization = self.owner_pre_authorization(&owner, &mint, &debit_authority);
+        self.build(
+            accounts::ProposeAmendment {
+                payer,
+                debit_authority,
+                pre_authorization,
+                pending_amendment: self.pending_amendment(&pre_authorization),
+                system_program: System::id(),
+            },
+            instruction::ProposeAmendment { params },
+        )
+    }
+
+    /**
+      Builds the `accept_amendment` instruction.
+      The `receiver` MUST be the `pending_amendment.payer`.
+      Expected signers: `owner` (the `token_account.owner`).
+    */
+    pub fn accept_amendment(
+        &self,
+        owner: Pubkey,
+        receiver: Pubkey,
+        token_account: Pubkey,
+        token_program: Pubkey,
+        debit_authority: Pubkey,
+        params: AcceptAmendmentParams,
+    ) -> Instruction {
+        let pre_authorization = self.pre_authorization(&token_account, &debit_authority);
+        self.build(
+            accounts::AcceptAmendment {
+                owner,
+                receiver,
+                token_account,
+                pre_authorization,
+                pending_amendment: self.pending_amendment(&pre_authorization),
+                smart_delegate: self.smart_delegate(),
+                pre_authorization_counter: Some(self.pre_authorization_counter(&token_account)),
+                token_program,
+            },
+            instruction::AcceptAmendment { params },
+        )
+    }
+
+    /**
+      Builds the `accept_amendment` instruction for a `pre_authorization` scoped to
+      the `owner` and `mint` (`token_account` MUST be owned by `owner` and have the mint `mint`).
+      The `receiver` MUST be the `pending_amendment.payer`.
+      Expected signers: `owner`.
+    */
+    pub fn accept_owner_pre_authorization_amendment(
+        &self,
+        owner: Pubkey,
+        receiver: Pubkey,
+        mint: Pubkey,
+        token_account: Pubkey,
+        token_program: Pubkey,
+        debit_authority: Pubkey,
+        params: AcceptAmendmentParams,
+    ) -> Instruction {
+        let pre_authorization = self.scoped_pre_authorization(
+            &PreAuthorizationScope::OwnerAndMint { owner, mint },
+            &token_account,
+            &debit_authority,
+        );
+        self.build(
+            accounts::AcceptAmendment {
+                owner,
+                receiver,
+                token_account,
+                pre_authorization,
+                pending_amendment: self.pending_amendment(&pre_authorization),
+                smart_delegate: self.smart_delegate(),
+                // owner scoped pre_authorizations are not counted
+                pre_authorization_counter: None,
+                token_program,
+            },
+            instruction::AcceptAmendment { params },
+        )
+    }
+
+    /**
+      Builds the `close_debit_receipt` instruction.
+      The `authority` MUST be either the `debit_receipt.payer` or the `debit_receipt.debit_authority`.
+      The `receiver` MUST be the `debit_receipt.payer`.
+      Expected signers: `authority`.
+    */
+    pub fn close_debit_receipt(
+        &self,
+        authority: Pubkey,
+        receiver: Pubkey,
+        pre_authorization: Pubkey,
+        debit_index: u64,
+    ) -> Instruction {
+        self.build(
+            accounts::CloseDebitReceipt {
+                authority,
+                receiver,
+                debit_receipt: self.debit_receipt(&pre_authorization, debit_index),
+            },
+            instruction::CloseDebitReceipt {},
+        )
+    }
+
+    /**
+      Builds the `reject_amendment` instruction.
+      The `authority` MUST be either the `token_account.owner` or the `debit_authority`.
+      The `receiver` MUST be the `pending_amendment.payer`.
+      Expected signers: `authority`.
+    */
+    pub fn reject_amendment(
+        &self,
+        authority: Pubkey,
+        receiver: Pubkey,
+        token_account: Pubkey,
+        debit_authority: Pubkey,
+    ) -> Instruction {
+        let pre_authorization = self.pre_authorization(&token_account, &debit_authority);
+        self.build(
+            accounts::RejectAmendment {
+                authority,
+                receiver,
+                token_account,
+                pre_authorization,
+                pending_amendment: self.pending_amendment(&pre_authorization),
+            },
+            instruction::RejectAmendment {},
+        )
+    }
+
+    /**
+      Builds the `reject_amendment` instruction for a `pre_authorization` scoped to
+      the `owner` and `mint` (`token_account` MUST be owned by `owner` and have the mint `mint`).
+      The `authority` MUST be either the `owner` or the `debit_authority`.
+      The `receiver` MUST be the `pending_amendment.payer`.
+      Expected signers: `authority`.
+    */
+    pub fn reject_owner_pre_authorization_amendment(
+        &self,
+        authority: Pubkey,
+        receiver: Pubkey,
+        owner: Pubkey,
+        mint: Pubkey,
+        token_account: Pubkey,
+        debit_authority: Pubkey,
+    ) -> Instruction {
+        let pre_authorization = self.scoped_pre_authorization(
+            &PreAuthorizationScope::OwnerAndMint { owner, mint },
+            &token_account,
+            &debit_authority,
+        );
+        self.build(
+            accounts::RejectAmendment {
+                authority,
+                receiver,
+                token_account,
+                pre_authorization,
+                pending_amendment: self.pending_amendment(&pre_authorization),
+            },
+            instruction::RejectAmendment {},
+        )
+    }
+
+    /**
+      Builds the `rotate_debit_authority` instruction.
+      The `owner` MUST be set if `pre_authorization.rotation_requires_owner_signature` is set.
+      The `receiver` of the closed `pre_authorization` lamports is the `token_account_owner`.
+      Expected signers: `payer`, `debit_authority` and `owner` (if set).
+    */
+    pub fn rotate_debit_authority(
+        &self,
+        payer: Pubkey,
+        debit_authority: Pubkey,
+        new_debit_authority: Pubkey,
+        owner: Option<Pubkey>,
+        token_account_owner: Pubkey,
+        token_account: Pubkey,
+    ) -> Instruction {
+        let pre_authorization = self.pre_authorization(&token_account, &debit_authority);
+        self.build(
+            accounts::RotateDebitAuthority {
+                payer,
+                debit_authority,
+                new_debit_authority,
+                owner,
+                receiver: token_account_owner,
+                token_account,
+                pre_authorization,
+                pending_amendment: self.pending_amendment(&pre_authorization),
+                new_pre_authorization: self.pre_authorization(&token_account, &new_debit_authority),
+                system_program: System::id(),
+            },
+            instruction::RotateDebitAuthority {},
+        )
+    }
+
+    /**
+      Builds the `rotate_debit_authority` instruction for a `pre_authorization` scoped to
+      the `owner` and `mint` (`token_account` MUST be owned by `owner` and have the mint `mint`),
+      the `new_pre_authorization` has the same scope.
+      The `owner_signer` MUST be set (to the `owner`) if `pre_authorization.rotation_requires_owner_signature` is set.
+      The `receiver` of the closed `pre_authorization` lamports is the `owner`.
+      Expected signers: `payer`, `debit_authority` and `owner_signer` (if set).
+    */
+    pub fn rotate_owner_pre_authorization_debit_authority(
+        &self,
+        payer: Pubkey,
+        debit_authority: Pubkey,
+        new_debit_authority: Pubkey,
+        owner_signer: Option<Pubkey>,
+        owner: Pubkey,
+        mint: Pubkey,
+        token_account: Pubkey,
+    ) -> Instruction {
+        let scope = PreAuthorizationScope::OwnerAndMint { owner, mint };
+        let pre_authorization =
+            self.scoped_pre_authorization(&scope, &token_account, &debit_authority);
+        self.build(
+            accounts::RotateDebitAuthority {
+                payer,
+                debit_authority,
+                new_debit_authority,
+                owner: owner_signer,
+                receiver: owner,
+                token_account,
+                pre_authorization,
+                pending_amendment: self.pending_amendment(&pre_authorization),
+                new_pre_authorization: self.scoped_pre_authorization(
+                    &scope,
+                    &token_account,
+                    &new_debit_authority,
+                ),
+                system_program: System::id(),
+            },
+            instruction::RotateDebitAuthority {},
+        )
+    }
+
+    /**
+      Builds the `migrate_pre_authorization` instruction for a `pre_authorization` created by the
+      v1.0.0 program (always scoped to the `token_account`).
+      Expected signers: `payer`.
+    */
+    pub fn migrate_pre_authorization(
+        &self,
+        payer: Pubkey,
+        token_account: Pubkey,
+        debit_authority: Pubkey,
+    ) -> Instruction {
+        self.build(
+            accounts::MigratePreAuthorization {
+                payer,
+                pre_authorization: self.pre_authorization(&token_account, &debit_authority),
+                system_program: System::id(),
+            },
+            instruction::MigratePreAuthorization {},
+        )
+    }
+
+    /**
+      Builds the approve instruction (on the SPL Token or SPL Token2022 program) that sets the
+      `token_account.delegate` to the `smart_delegate` with a delegated amount of `u64::MAX`.
+      NOTE: This instruction does not call the `pre_authorized_debit_v1` program.
+      Expected signers: `owner` (the `token_account.owner`).
+    */
+    pub fn approve_smart_delegate(
+        &self,
+        token_program: Pubkey,
+        token_account: Pubkey,
+        owner: Pubkey,
+    ) -> Result<Instruction> {
+        Ok(spl_token_2022::instruction::approve(
+            &token_program,
+            &token_account,
+            &self.smart_delegate(),
+            &owner,
+            &[],
+            u64::MAX,
+        )?)
+    }
+
+    fn build(&self, accounts: impl ToAccountMetas, data: impl InstructionData) -> Instruction {
+        let mut accounts = accounts.to_account_metas(None);
+        // Unused optional accounts are set to the declared program id,
+        // the program only treats them as unused if they are set to the deployed program id
+        for meta in accounts.iter_mut() {
+            if meta.pubkey == pre_authorized_debit_v1::ID {
+                meta.pubkey = self.program_id;
+            }
+        }
+
+        Instruction {
+            program_id: self.program_id,
+            accounts,
+            data: data.data(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anchor_lang::Discriminator;
+    use pre_authorized_debit_v1::instructions::{
+        DebitAmountMode, DebitBatchMode, InitPreAuthorizationVariant, UpdatePreAuthorizationVariant,
+    };
+
+    const ONE_TIME: UpdatePreAuthorizationVariant = UpdatePreAuthorizationVariant::OneTime {
+        amount_authorized: 100,
+        expiry_unix_timestamp: i64::MAX,
+    };
+
+    fn unique_keys<const N: usize>() -> [Pubkey; N] {
+        std::array::from_fn(|_| Pubkey::new_unique())
+    }
+
+    // The (pubkey, is_signer, is_writable) of each account meta
+    fn account_metas(ix: &Instruction) -> Vec<(Pubkey, bool, bool)> {
+        ix.accounts
+            .iter()
+            .map(|meta| (meta.pubkey, meta.is_signer, meta.is_writable))
+            .collect()
+    }
+
+    fn owner_pre_authorization_pda(
+        owner: &Pubkey,
+        mint: &Pubkey,
+        debit_authority: &Pubkey,
+    ) -> Pubkey {
+        Pubkey::find_program_address(
+            &[
+                b"pre-authorization",
+                owner.as_ref(),
+                mint.as_ref(),
+                debit_authority.as_ref(),
+            ],
+            &pre_authorized_debit_v1::ID,
+        )
+        .0
+    }
+
+    #[test]
+    fn init_pre_authorization_derives_pre_authorization_pda() {
+        let factory = InstructionFactory::mainnet();
+        let (payer, owner, token_account, debit_authority) = (
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+        );
+
+        let ix = factory.init_pre_authorization(
+            payer,
+            owner,
+            token_account,
+            anchor_spl::token::ID,
+            InitPreAuthorizationParams {
+                variant: InitPreAuthorizationVariant::OneTime {
+                    amount_authorized: 100,
+                    expiry_unix_timestamp: i64::MAX,
+                    max_amount_per_debit: None,
+                    close_on_exhaustion: false,
+                },
+                debit_authority,
+                activation_unix_timestamp: 0,
+                rotation_requires_owner_signature: false,
+                destination_constraint: None,
+                bounded_delegation: false,
+            },
+        );
+
+        let (expected_pre_authorization, _) = Pubkey::find_program_address(
+            &[
+                b"pre-authorization",
+                token_account.as_ref(),
+                debit_authority.as_ref(),
+            ],
+            &pre_authorized_debit_v1::ID,
+        );
+        assert_eq!(pre_authorized_debit_v1::ID, ix.program_id);
+        assert_eq!(expected_pre_authorization, ix.accounts[4].pubkey);
+        assert!(ix.accounts[0].is_signer && ix.accounts[1].is_signer);
+        assert_eq!(
+            instruction::InitPreAuthorization::DISCRIMINATOR,
+            ix.data[..8]
+        );
+    }
+
+    #[test]
+    fn update_pause_owner_pre_authorization_derives_owner_pre_authorization_pda() {
+        let factory = InstructionFactory::mainnet();
+        let (owner, mint, token_account, debit_authority) = (
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+        );
+
+        let ix = factory.update_pause_owner_pre_authorization(
+            owner,
+            mint,
+            token_account,
+            debit_authority,
+            UpdatePausePreAuthorizationParams { pause: true },
+        );
+
+        let (expected_pre_authorization, _) = Pubkey::find_program_address(
+            &[
+                b"pre-authorization",
+                owner.as_ref(),
+                mint.as_ref(),
+                debit_authority.as_ref(),
+            ],
+            &pre_authorized_debit_v1::ID,
+        );
+        assert_eq!(
+            vec![owner, token_account, expected_pre_authorization],
+            ix.accounts
+                .iter()
+                .map(|meta| meta.pubkey)
+                .collect::<Vec<_>>()
+        );
+        assert!(ix.accounts[0].is_signer && ix.accounts[2].is_writable);
+        assert_eq!(
+            instruction::UpdatePausePreAuthorization::DISCRIMINATOR,
+            ix.data[..8]
+        );
+    }
+
+    #[test]
+    fn migrate_pre_authorization_derives_token_account_pre_authorization_pda() {
+        let factory = InstructionFactory::mainnet();
+        let (payer, token_account, debit_authority) = (
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+        );
+
+        let ix = factory.migrate_pre_authorization(payer, token_account, debit_authority);
+
+        assert_eq!(
+            vec![
+                payer,
+                factory.pre_authorization(&token_account, &debit_authority),
+                System::id(),
+            ],
+            ix.accounts
+                .iter()
+                .map(|meta| meta.pubkey)
+                .collect::<Vec<_>>()
+        );
+        assert!(ix.accounts[0].is_signer && ix.accounts[0].is_writable);
+        assert!(!ix.accounts[1].is_signer && ix.accounts[1].is_writable);
+        assert_eq!(
+            instruction::MigratePreAuthorization::DISCRIMINATOR,
+            ix.data[..8]
+        );
+    }
+
+    #[test]
+    fn debit_sets_accounts_in_program_order() {
+        let factory = InstructionFactory::custom(Pubkey::new_unique());
+        let (debit_authority, mint, token_account, destination_token_account) = (
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+        );
+
+        let ix = factory.debit(
+            debit_authority,
+            mint,
+            token_account,
+            destination_token_account,
+            anchor_spl::token_2022::ID,
+            None,
+            false,
+            None,
+            DebitParams {
+                amount: 42,
+                amount_mode: DebitAmountMode::Exact,
+                reference: None,
+            },
+        );
+
+        assert_eq!(factory.program_id(), ix.program_id);
+        assert_eq!(
+            vec![
+                debit_authority,
+                mint,
+                token_account,
+                destination_token_account,
+                factory.smart_delegate(),
+                factory.pre_authorization(&token_account, &debit_authority),
+                anchor_spl::token_2022::ID,
+                // the unused optional accounts (receiver, pre_authorization_counter, memo_program,
+                // receipt_payer, debit_receipt and system_program) are set to the program id
+                factory.program_id(),
+                factory.program_id(),
+                factory.program_id(),
+                factory.program_id(),
+                factory.program_id(),
+                factory.program_id(),
+            ],
+            ix.accounts
+                .iter()
+                .map(|meta| meta.pubkey)
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(instruction::Debit::DISCRIMINATOR, ix.data[..8]);
+        assert_eq!(42u64.to_le_bytes(), ix.data[8..16]);
+        assert_eq!([DebitAmountMode::Exact as u8, 0], ix.data[16..]);
+    }
+
+    #[test]
+    fn debit_batch_appends_entries_as_writable_remaining_accounts() {
+        let factory = InstructionFactory::mainnet();
+        let (debit_authority, mint) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let entries = [
+            (
+                Pubkey::new_unique(),
+                Pubkey::new_unique(),
+                Pubkey::new_unique(),
+            ),
+            (
+                Pubkey::new_unique(),
+                Pubkey::new_unique(),
+                Pubkey::new_unique(),
+            ),
+        ];
+
+        let ix = factory.debit_batch(
+            debit_authority,
+            mint,
+            anchor_spl::token::ID,
+            &entries,
+            DebitBatchParams {
+                amounts: vec![1, 2],
+                mode: DebitBatchMode::SkipFailed,
+            },
+        );
+
+        assert_eq!(4 + 3 * entries.len(), ix.accounts.len());
+        assert_eq!(factory.smart_delegate(), ix.accounts[2].pubkey);
+        for (entry_metas, (token_account, pre_authorization, destination_token_account)) in
+            ix.accounts[4..].chunks(3).zip(entries.iter())
+        {
+            assert_eq!(
+                vec![
+                    *token_account,
+                    *pre_authorization,
+                    *destination_token_account
+                ],
+                entry_metas
+                    .iter()
+                    .map(|meta| meta.pubkey)
+                    .collect::<Vec<_>>()
+            );
+            assert!(entry_metas
+                .iter()
+                .all(|meta| meta.is_writable && !meta.is_signer));
+        }
+        assert_eq!(instruction::DebitBatch::DISCRIMINATOR, ix.data[..8]);
+    }
+
+    #[test]
+    fn init_smart_delegate_sets_account_metas() {
+        let factory = InstructionFactory::mainnet();
+        let [payer] = unique_keys();
+
+        let ix = factory.init_smart_delegate(payer);
+
+        assert_eq!(
+            vec![
+                (payer, true, true),
+                (factory.smart_delegate(), false, true),
+                (System::id(), false, false),
+            ],
+            account_metas(&ix)
+        );
+        assert_eq!(instruction::InitSmartDelegate::DISCRIMINATOR, ix.data[..8]);
+    }
+
+    #[test]
+    fn init_pre_authorization_counter_sets_account_metas() {
+        let factory = InstructionFactory::mainnet();
+        let [payer, owner, token_account] = unique_keys();
+
+        let ix = factory.init_pre_authorization_counter(
+            payer,
+            owner,
+            token_account,
+            InitPreAuthorizationCounterParams { count: 2 },
+        );
+
+        assert_eq!(
+            vec![
+                (payer, true, true),
+                (owner, true, false),
+                (token_account, false, false),
+                (
+                    factory.pre_authorization_counter(&token_account),
+                    false,
+                    true
+                ),
+                (System::id(), false, false),
+            ],
+            account_metas(&ix)
+        );
+        assert_eq!(
+            instruction::InitPreAuthorizationCounter::DISCRIMINATOR,
+            ix.data[..8]
+        );
+    }
+
+    #[test]
+    fn init_owner_pre_authorization_sets_account_metas() {
+        let factory = InstructionFactory::mainnet();
+        let [payer, owner, mint, debit_authority] = unique_keys();
+
+        let ix = factory.init_owner_pre_authorization(
+            payer,
+            owner,
+            mint,
+            InitPreAuthorizationParams {
+                variant: InitPreAuthorizationVariant::OneTime {
+                    amount_authorized: 100,
+                    expiry_unix_timestamp: i64::MAX,
+                    max_amount_per_debit: None,
+                    close_on_exhaustion: false,
+                },
+                debit_authority,
+                activation_unix_timestamp: 0,
+                rotation_requires_owner_signature: false,
+                destination_constraint: None,
+                bounded_delegation: false,
+            },
+        );
+
+        assert_eq!(
+            vec![
+                (payer, true, true),
+                (owner, true, false),
+                (mint, false, false),
+                (
+                    owner_pre_authorization_pda(&owner, &mint, &debit_authority),
+                    false,
+                    true
+                ),
+                (System::id(), false, false),
+            ],
+            account_metas(&ix)
+        );
+        assert_eq!(
+            instruction::InitOwnerPreAuthorization::DISCRIMINATOR,
+            ix.data[..8]
+        );
+    }
+
+    #[test]
+    fn debit_owner_pre_authorization_sets_account_metas() {
+        let factory = InstructionFactory::mainnet();
+        let [debit_authority, owner, mint, token_account, destination_token_account, receipt_payer] =
+            unique_keys();
+
+        let ix = factory.debit_owner_pre_authorization(
+            debit_authority,
+            owner,
+            mint,
+            token_account,
+            destination_token_account,
+            anchor_spl::token::ID,
+            Some(owner),
+            true,
+            Some((receipt_payer, 3)),
+            DebitParams {
+                amount: 42,
+                amount_mode: DebitAmountMode::Exact,
+                reference: Some([1; 32]),
+            },
+        );
+
+        let pre_authorization = owner_pre_authorization_pda(&owner, &mint, &debit_authority);
+        assert_eq!(
+            vec![
+                (debit_authority, true, false),
+                (mint, false, false),
+                (token_account, false, true),
+                (destination_token_account, false, true),
+                (factory.smart_delegate(), false, false),
+                (pre_authorization, false, true),
+                (anchor_spl::token::ID, false, false),
+                (owner, false, true),
+                (
+                    factory.pre_authorization_counter(&token_account),
+                    false,
+                    true
+                ),
+                (spl_memo::ID, false, false),
+                (receipt_payer, true, true),
+                (factory.debit_receipt(&pre_authorization, 3), false, true),
+                (System::id(), false, false),
+            ],
+            account_metas(&ix)
+        );
+        assert_eq!(instruction::Debit::DISCRIMINATOR, ix.data[..8]);
+    }
+
+    #[test]
+    fn close_pre_authorization_appends_other_pre_authorizations_as_readonly() {
+        let factory = InstructionFactory::mainnet();
+        let [owner, token_account, debit_authority, other_pre_authorization] = unique_keys();
+
+        let ix = factory.close_pre_authorization(
+            owner,
+            owner,
+            token_account,
+            anchor_spl::token::ID,
+            debit_authority,
+            &[other_pre_authorization],
+        );
+
+        assert_eq!(
+            vec![
+                (owner, false, true),
+                (owner, true, false),
+                (token_account, false, true),
+                (factory.smart_delegate(), false, false),
+                (
+                    factory.pre_authorization(&token_account, &debit_authority),
+                    false,
+                    true
+                ),
+                (
+                    factory.pre_authorization_counter(&token_account),
+                    false,
+                    true
+                ),
+                (anchor_spl::token::ID, false, false),
+                (other_pre_authorization, false, false),
+            ],
+            account_metas(&ix)
+        );
+        assert_eq!(
+            instruction::ClosePreAuthorization::DISCRIMINATOR,
+            ix.data[..8]
+        );
+    }
+
+    #[test]
+    fn close_expired_pre_authorization_sets_account_metas() {
+        let factory = InstructionFactory::mainnet();
+        let [caller, token_account_owner, token_account, debit_authority] = unique_keys();
+
+        let ix = factory.close_expired_pre_authorization(
+            caller,
+            token_account_owner,
+            Some(caller),
+            token_account,
+            debit_authority,
+        );
+
+        assert_eq!(
+            vec![
+                (caller, true, false),
+                (token_account_owner, false, true),
+                (caller, false, true),
+                (token_account, false, false),
+                (
+                    factory.pre_authorization(&token_account, &debit_authority),
+                    false,
+                    true
+                ),
+                (
+                    factory.pre_authorization_counter(&token_account),
+                    false,
+                    true
+                ),
+            ],
+            account_metas(&ix)
+        );
+        assert_eq!(
+            instruction::CloseExpiredPreAuthorization::DISCRIMINATOR,
+            ix.data[..8]
+        );
+    }
+
+    #[test]
+    fn close_expired_owner_pre_authorization_sets_account_metas() {
+        let factory = InstructionFactory::mainnet();
+        let [caller, owner, mint, token_account, debit_authority] = unique_keys();
+
+        let ix = factory.close_expired_owner_pre_authorization(
+            caller,
+            owner,
+            None,
+            mint,
+            token_account,
+            debit_authority,
+        );
+
+        assert_eq!(
+            vec![
+                (caller, true, false),
+                (owner, false, true),
+                (factory.program_id(), false, false),
+                (token_account, false, false),
+                (
+                    owner_pre_authorization_pda(&owner, &mint, &debit_authority),
+                    false,
+                    true
+                ),
+                (factory.program_id(), false, false),
+            ],
+            account_metas(&ix)
+        );
+        assert_eq!(
+            instruction::CloseExpiredPreAuthorization::DISCRIMINATOR,
+            ix.data[..8]
+        );
+    }
+
+    #[test]
+    fn close_owner_pre_authorization_sets_account_metas() {
+        let factory = InstructionFactory::mainnet();
+        let [owner, mint, debit_authority] = unique_keys();
+
+        let ix = factory.close_owner_pre_authorization(
+            owner,
+            debit_authority,
+            owner,
+            mint,
+            debit_authority,
+        );
+
+        assert_eq!(
+            vec![
+                (owner, false, true),
+                (debit_authority, true, false),
+                (owner, false, false),
+                (mint, false, false),
+                (
+                    owner_pre_authorization_pda(&owner, &mint, &debit_authority),
+                    false,
+                    true
+                ),
+            ],
+            account_metas(&ix)
+        );
+        assert_eq!(
+            instruction::CloseOwnerPreAuthorization::DISCRIMINATOR,
+            ix.data[..8]
+        );
+    }
+
+    #[test]
+    fn update_pause_pre_authorization_sets_account_metas() {
+        let factory = InstructionFactory::mainnet();
+        let [owner, token_account, debit_authority] = unique_keys();
+
+        let ix = factory.update_pause_pre_authorization(
+            owner,
+            token_account,
+            debit_authority,
+            UpdatePausePreAuthorizationParams { pause: false },
+        );
+
+        assert_eq!(
+            vec![
+                (owner, true, false),
+                (token_account, false, false),
+                (
+                    factory.pre_authorization(&token_account, &debit_authority),
+                    false,
+                    true
+                ),
+            ],
+            account_metas(&ix)
+        );
+        assert_eq!(
+            instruction::UpdatePausePreAuthorization::DISCRIMINATOR,
+            ix.data[..8]
+        );
+    }
+
+    #[test]
+    fn reapprove_smart_delegate_appends_pre_authorizations_as_readonly() {
+        let factory = InstructionFactory::mainnet();
+        let [owner, token_account, pre_authorization_a, pre_authorization_b] = unique_keys();
+
+        let ix = factory.reapprove_smart_delegate(
+            owner,
+            token_account,
+            anchor_spl::token_2022::ID,
+            &[pre_authorization_a, pre_authorization_b],
+        );
+
+        assert_eq!(
+            vec![
+                (owner, true, false),
+                (token_account, false, true),
+                (factory.smart_delegate(), false, false),
+                (
+                    factory.pre_authorization_counter(&token_account),
+                    false,
+                    false
+                ),
+                (anchor_spl::token_2022::ID, false, false),
+                (pre_authorization_a, false, false),
+                (pre_authorization_b, false, false),
+            ],
+            account_metas(&ix)
+        );
+        assert_eq!(
+            instruction::ReapproveSmartDelegate::DISCRIMINATOR,
+            ix.data[..8]
+        );
+    }
+
+    #[test]
+    fn update_pre_authorization_sets_account_metas() {
+        let factory = InstructionFactory::mainnet();
+        let [owner, token_account, debit_authority] = unique_keys();
+
+        let ix = factory.update_pre_authorization(
+            owner,
+            token_account,
+            anchor_spl::token::ID,
+            debit_authority,
+            UpdatePreAuthorizationParams { variant: ONE_TIME },
+        );
+
+        assert_eq!(
+            vec![
+                (owner, true, false),
+                (token_account, false, true),
+                (
+                    factory.pre_authorization(&token_account, &debit_authority),
+                    false,
+                    true
+                ),
+                (factory.smart_delegate(), false, false),
+                (
+                    factory.pre_authorization_counter(&token_account),
+                    false,
+                    false
+                ),
+                (anchor_spl::token::ID, false, false),
+            ],
+            account_metas(&ix)
+        );
+        assert_eq!(
+            instruction::UpdatePreAuthorization::DISCRIMINATOR,
+            ix.data[..8]
+        );
+    }
+
+    #[test]
+    fn update_owner_pre_authorization_derives_owner_pre_authorization_pda() {
+        let factory = InstructionFactory::mainnet();
+        let [owner, mint, token_account, debit_authority] = unique_keys();
+
+        let ix = factory.update_owner_pre_authorization(
+            owner,
+            mint,
+            token_account,
+            anchor_spl::token::ID,
+            debit_authority,
+            UpdatePreAuthorizationParams { variant: ONE_TIME },
+        );
+
+        assert_eq!(
+            vec![
+                (owner, true, false),
+                (token_account, false, true),
+                (
+                    owner_pre_authorization_pda(&owner, &mint, &debit_authority),
+                    false,
+                    true
+                ),
+                (factory.smart_delegate(), false, false),
+                // owner scoped pre_authorizations are not counted
+                (factory.program_id(), false, false),
+                (anchor_spl::token::ID, false, false),
+            ],
+            account_metas(&ix)
+        );
+        assert_eq!(
+            instruction::UpdatePreAuthorization::DISCRIMINATOR,
+            ix.data[..8]
+        );
+    }
+
+    #[test]
+    fn propose_amendment_sets_account_metas() {
+        let factory = InstructionFactory::mainnet();
+        let [payer, debit_authority, token_account] = unique_keys();
+
+        let ix = factory.propose_amendment(
+            payer,
+            debit_authority,
+            token_account,
+            ProposeAmendmentParams {
+                new_values: ONE_TIME,
+                acceptance_deadline_unix_timestamp: i64::MAX,
+            },
+        );
+
+        let pre_authorization = factory.pre_authorization(&token_account, &debit_authority);
+        assert_eq!(
+            vec![
+                (payer, true, true),
+                (debit_authority, true, false),
+                (pre_authorization, false, false),
+                (factory.pending_amendment(&pre_authorization), false, true),
+                (System::id(), false, false),
+            ],
+            account_metas(&ix)
+        );
+        assert_eq!(instruction::ProposeAmendment::DISCRIMINATOR, ix.data[..8]);
+    }
+
+    #[test]
+    fn propose_owner_pre_authorization_amendment_derives_owner_pre_authorization_pda() {
+        let factory = InstructionFactory::mainnet();
+        let [payer, debit_authority, owner, mint] = unique_keys();
+
+        let ix = factory.propose_owner_pre_authorization_amendment(
+            payer,
+            debit_authority,
+            owner,
+            mint,
+            ProposeAmendmentParams {
+                new_values: ONE_TIME,
+                acceptance_deadline_unix_timestamp: i64::MAX,
+            },
+        );
+
+        let pre_authorization = owner_pre_authorization_pda(&owner, &mint, &debit_authority);
+        assert_eq!(
+            vec![
+                (payer, true, true),
+                (debit_authority, true, false),
+                (pre_authorization, false, false),
+                (factory.pending_amendment(&pre_authorization), false, true),
+                (System::id(), false, false),
+            ],
+            account_metas(&ix)
+        );
+        assert_eq!(instruction::ProposeAmendment::DISCRIMINATOR, ix.data[..8]);
+    }
+
+    #[test]
+    fn accept_amendment_sets_account_metas() {
+        let factory = InstructionFactory::mainnet();
+        let [owner, receiver, token_account, debit_authority] = unique_keys();
+
+        let ix = factory.accept_amendment(
+            owner,
+            receiver,
+            token_account,
+            anchor_spl::token::ID,
+            debit_authority,
+            AcceptAmendmentParams {
+                expected_new_values: ONE_TIME,
+            },
+        );
+
+        let pre_authorization = factory.pre_authorization(&token_account, &debit_authority);
+        assert_eq!(
+            vec![
+                (owner, true, false),
+                (receiver, false, true),
+                (token_account, false, true),
+                (pre_authorization, false, true),
+                (factory.pending_amendment(&pre_authorization), false, true),
+                (factory.smart_delegate(), false, false),
+                (
+                    factory.pre_authorization_counter(&token_account),
+                    false,
+                    false
+                ),
+                (anchor_spl::token::ID, false, false),
+            ],
+            account_metas(&ix)
+        );
+        assert_eq!(instruction::AcceptAmendment::DISCRIMINATOR, ix.data[..8]);
+    }
+
+    #[test]
+    fn accept_owner_pre_authorization_amendment_derives_owner_pre_authorization_pda() {
+        let factory = InstructionFactory::mainnet();
+        let [owner, receiver, mint, token_account, debit_authority] = unique_keys();
+
+        let ix = factory.accept_owner_pre_authorization_amendment(
+            owner,
+            receiver,
+            mint,
+            token_account,
+            anchor_spl::token::ID,
+            debit_authority,
+            AcceptAmendmentParams {
+                expected_new_values: ONE_TIME,
+            },
+        );
+
+        let pre_authorization = owner_pre_authorization_pda(&owner, &mint, &debit_authority);
+        assert_eq!(
+            vec![
+                (owner, true, false),
+                (receiver, false, true),
+                (token_account, false, true),
+                (pre_authorization, false, true),
+                (factory.pending_amendment(&pre_authorization), false, true),
+                (factory.smart_delegate(), false, false),
+                // owner scoped pre_authorizations are not counted
+                (factory.program_id(), false, false),
+                (anchor_spl::token::ID, false, false),
+            ],
+            account_metas(&ix)
+        );
+        assert_eq!(instruction::AcceptAmendment::DISCRIMINATOR, ix.data[..8]);
+    }
+
+    #[test]
+    fn close_debit_receipt_derives_debit_receipt_pda() {
+        let factory = InstructionFactory::mainnet();
+        let [authority, receiver, pre_authorization] = unique_keys();
+
+        let ix = factory.close_debit_receipt(authority, receiver, pre_authorization, 7);
+
+        let (expected_debit_receipt, _) = Pubkey::find_program_address(
+            &[
+                b"debit-receipt",
+                pre_authorization.as_ref(),
+                &7u64.to_le_bytes(),
+            ],
+            &pre_authorized_debit_v1::ID,
+        );
+        assert_eq!(
+            vec![
+                (authority, true, false),
+                (receiver, false, true),
+                (expected_debit_receipt, false, true),
+            ],
+            account_metas(&ix)
+        );
+        assert_eq!(instruction::CloseDebitReceipt::DISCRIMINATOR, ix.data[..8]);
+    }
+
+    #[test]
+    fn reject_amendment_sets_account_metas() {
+        let factory = InstructionFactory::mainnet();
+        let [receiver, token_account, debit_authority] = unique_keys();
+
+        let ix =
+            factory.reject_amendment(debit_authority, receiver, token_account, debit_authority);
+
+        let pre_authorization = factory.pre_authorization(&token_account, &debit_authority);
+        assert_eq!(
+            vec![
+                (debit_authority, true, false),
+                (receiver, false, true),
+                (token_account, false, false),
+                (pre_authorization, false, false),
+                (factory.pending_amendment(&pre_authorization), false, true),
+            ],
+            account_metas(&ix)
+        );
+        assert_eq!(instruction::RejectAmendment::DISCRIMINATOR, ix.data[..8]);
+    }
+
+    #[test]
+    fn reject_owner_pre_authorization_amendment_derives_owner_pre_authorization_pda() {
+        let factory = InstructionFactory::mainnet();
+        let [receiver, owner, mint, token_account, debit_authority] = unique_keys();
+
+        let ix = factory.reject_owner_pre_authorization_amendment(
+            owner,
+            receiver,
+            owner,
+            mint,
+            token_account,
+            debit_authority,
+        );
+
+        let pre_authorization = owner_pre_authorization_pda(&owner, &mint, &debit_authority);
+        assert_eq!(
+            vec![
+                (owner, true, false),
+                (receiver, false, true),
+                (token_account, false, false),
+                (pre_authorization, false, false),
+                (factory.pending_amendment(&pre_authorization), false, true),
+            ],
+            account_metas(&ix)
+        );
+        assert_eq!(instruction::RejectAmendment::DISCRIMINATOR, ix.data[..8]);
+    }
+
+    #[test]
+    fn rotate_debit_authority_sets_account_metas() {
+        let factory = InstructionFactory::mainnet();
+        let [payer, debit_authority, new_debit_authority, owner, token_account] = unique_keys();
+
+        let ix = factory.rotate_debit_authority(
+            payer,
+            debit_authority,
+            new_debit_authority,
+            Some(owner),
+            owner,
+            token_account,
+        );
+
+        let pre_authorization = factory.pre_authorization(&token_account, &debit_authority);
+        assert_eq!(
+            vec![
+                (payer, true, true),
+                (debit_authority, true, false),
+                (new_debit_authority, false, false),
+                (owner, true, false),
+                (owner, false, true),
+                (token_account, false, false),
+                (pre_authorization, false, true),
+                (factory.pending_amendment(&pre_authorization), false, false),
+                (
+                    factory.pre_authorization(&token_account, &new_debit_authority),
+                    false,
+                    true
+                ),
+                (System::id(), false, false),
+            ],
+            account_metas(&ix)
+        );
+        assert_eq!(
+            instruction::RotateDebitAuthority::DISCRIMINATOR,
+            ix.data[..8]
+        );
+    }
+
+    #[test]
+    fn rotate_owner_pre_authorization_debit_authority_derives_owner_pre_authorization_pdas() {
+        let factory = InstructionFactory::mainnet();
+        let [payer, debit_authority, new_debit_authority, owner, mint, token_account] =
+            unique_keys();
+
+        let ix = factory.rotate_owner_pre_authorization_debit_authority(
+            payer,
+            debit_authority,
+            new_debit_authority,
+            None,
+            owner,
+            mint,
+            token_account,
+        );
+
+        let pre_authorization = owner_pre_authorization_pda(&owner, &mint, &debit_authority);
+        assert_eq!(
+            vec![
+                (payer, true, true),
+                (debit_authority, true, false),
+                (new_debit_authority, false, false),
+                (factory.program_id(), false, false),
+                (owner, false, true),
+                (token_account, false, false),
+                (pre_authorization, false, true),
+                (factory.pending_amendment(&pre_authorization), false, false),
+                (
+                    owner_pre_authorization_pda(&owner, &mint, &new_debit_authority),
+                    false,
+                    true
+                ),
+                (System::id(), false, false),
+            ],
+            account_metas(&ix)
+        );
+        assert_eq!(
+            instruction::RotateDebitAuthority::DISCRIMINATOR,
+            ix.data[..8]
+        );
+    }
+
+    #[test]
+    fn approve_smart_delegate_approves_max_amount_on_token_program() {
+        let factory = InstructionFactory::mainnet();
+        let [token_account, owner] = unique_keys();
+
+        let ix = factory
+            .approve_smart_delegate(anchor_spl::token_2022::ID, token_account, owner)
+            .unwrap();
+
+        assert_eq!(anchor_spl::token_2022::ID, ix.program_id);
+        assert_eq!(
+            vec![
+                (token_account, false, true),
+                (factory.smart_delegate(), false, false),
+                (owner, true, false),
+            ],
+            account_metas(&ix)
+        );
+        assert_eq!(u64::MAX.to_le_bytes(), ix.data[1..]);
+    }
+}
@@ -0,0 +1,44 @@
+/*!
+  Rust client for the `pre_authorized_debit_v1` program.
+
+  - `instructions`: the `InstructionFactory` to build the program instructions
+  - `pda`: derivation of the `smart_delegate`, `pre_authorization` (token account or owner scoped),
+    `pending_amendment`, `pre_authorization_counter` and `debit_receipt` PDAs
+  - `accounts`: deserializers for the `PreAuthorization`, `SmartDelegate`, `PendingAmendment`,
+    `PreAuthorizationCounter` and `DebitReceipt` accounts
+*/
+
+pub mod accounts;
+pub mod instructions;
+pub mod pda;
+
+pub use accounts::*;
+pub use instructions::*;
+pub use pda::*;
+
+// Compiles the README example
+#[cfg(doctest)]
+#[doc = include_str!("../README.md")]
+pub struct ReadmeDoctests;
+
+pub use pre_authorized_debit_v1::{
+    self,
+    calendar::{CalendarInterval, CalendarSchedule},
+    instructions::{
+        AcceptAmendmentParams, DebitAmountMode, DebitBatchMode, DebitBatchParams, DebitParams,
+        InitPreAuthorizationCounterParams, InitPreAuthorizationParams, InitPreAuthorizationVariant,
+        ProposeAmendmentParams, UpdatePausePreAuthorizationParams, UpdatePreAuthorizationParams,
+        UpdatePreAuthorizationVariant,
+    },
+    state::{
+        debit_receipt::{DebitReceipt, DEBIT_RECEIPT_RETENTION_SECONDS},
+        pending_amendment::PendingAmendment,
+        pre_authorization::{
+            DestinationConstraint, IntroSchedule, PreAuthorization, PreAuthorizationScope,
+            PreAuthorizationVariant, ScheduleStep,
+        },
+        pre_authorization_counter::PreAuthorizationCounter,
+        smart_delegate::SmartDelegate,
+    },
+    ID,
+};
@@ -0,0 +1,187 @@
+use anchor_lang::prelude::Pubkey;
+use pre_authorized_debit_v1::state::pre_authorization::{PreAuthorization, PreAuthorizationScope};
+
+pub const SMART_DELEGATE_SEED: &[u8] = b"smart-delegate";
+pub const PRE_AUTHORIZATION_SEED: &[u8] = b"pre-authorization";
+pub const PENDING_AMENDMENT_SEED: &[u8] = b"pending-amendment";
+pub const PRE_AUTHORIZATION_COUNTER_SEED: &[u8] = b"pre-authorization-counter";
+pub const DEBIT_RECEIPT_SEED: &[u8] = b"debit-receipt";
+
+/**
+  Derives the global `smart_delegate` PDA with the seeds: ['smart-delegate'].
+  Returns the PDA and its canonical bump.
+*/
+pub fn find_smart_delegate_address(program_id: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[SMART_DELEGATE_SEED], program_id)
+}
+
+/**
+  Derives a `pre_authorization` PDA with the seeds:
+  ['pre-authorization', token_account, debit_authority].
+  Returns the PDA and its canonical bump.
+*/
+pub fn find_pre_authorization_address(
+    program_id: &Pubkey,
+    token_account: &Pubkey,
+    debit_authority: &Pubkey,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            PRE_AUTHORIZATION_SEED,
+            token_account.as_ref(),
+            debit_authority.as_ref(),
+        ],
+        program_id,
+    )
+}
+
+/**
+  Derives an owner scoped `pre_authorization` PDA with the seeds:
+  ['pre-authorization', owner, mint, debit_authority].
+  Returns the PDA and its canonical bump.
+*/
+pub fn find_owner_pre_authorization_address(
+    program_id: &Pubkey,
+    owner: &Pubkey,
+    mint: &Pubkey,
+    debit_authority: &Pubkey,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            PRE_AUTHORIZATION_SEED,
+            owner.as_ref(),
+            mint.as_ref(),
+            debit_authority.as_ref(),
+        ],
+        program_id,
+    )
+}
+
+/**
+  Derives a `pre_authorization` PDA of the given `scope` with the same seeds as the program
+  (see `PreAuthorization::scope_seeds`): ['pre-authorization', token_account, debit_authority]
+  for `TokenAccount` and ['pre-authorization', owner, mint, debit_authority] for `OwnerAndMint`.
+  The `token_account` is ignored for `OwnerAndMint` (it is `Pubkey::default()` in owner scoped
+  pre-authorizations), so the `scope` and `token_account` of a fetched `PreAuthorization` can be passed as is.
+  Returns the PDA and its canonical bump.
+*/
+pub fn find_scoped_pre_authorization_address(
+    program_id: &Pubkey,
+    scope: &PreAuthorizationScope,
+    token_account: &Pubkey,
+    debit_authority: &Pubkey,
+) -> (Pubkey, u8) {
+    let pre_authorization = PreAuthorization {
+        scope: scope.clone(),
+        token_account: *token_account,
+        ..Default::default()
+    };
+    let [scope_seed_0, scope_seed_1] = pre_authorization.scope_seeds();
+    Pubkey::find_program_address(
+        &[
+            PRE_AUTHORIZATION_SEED,
+            scope_seed_0,
+            scope_seed_1,
+            debit_authority.as_ref(),
+        ],
+        program_id,
+    )
+}
+
+/**
+  Derives a `pending_amendment` PDA with the seeds: ['pending-amendment', pre_authorization].
+  Returns the PDA and its canonical bump.
+*/
+pub fn find_pending_amendment_address(
+    program_id: &Pubkey,
+    pre_authorization: &Pubkey,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[PENDING_AMENDMENT_SEED, pre_authorization.as_ref()],
+        program_id,
+    )
+}
+
+/**
+  Derives the `pre_authorization_counter` PDA of a token account with the seeds:
+  ['pre-authorization-counter', token_account].
+  Returns the PDA and its canonical bump.
+*/
+pub fn find_pre_authorization_counter_address(
+    program_id: &Pubkey,
+    token_account: &Pubkey,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[PRE_AUTHORIZATION_COUNTER_SEED, token_account.as_ref()],
+        program_id,
+    )
+}
+
+/**
+  Derives a `debit_receipt` PDA with the seeds:
+  ['debit-receipt', pre_authorization, debit_index (u64 little-endian)].
+  The `debit_index` of the next debit is the `pre_authorization.num_debits`.
+  Returns the PDA and its canonical bump.
+*/
+pub fn find_debit_receipt_address(
+    program_id: &Pubkey,
+    pre_authorization: &Pubkey,
+    debit_index: u64,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            DEBIT_RECEIPT_SEED,
+            pre_authorization.as_ref(),
+            &debit_index.to_le_bytes(),
+        ],
+        program_id,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scoped_pre_authorization_address_matches_token_account_scope() {
+        let (token_account, debit_authority) = (Pubkey::new_unique(), Pubkey::new_unique());
+
+        assert_eq!(
+            find_pre_authorization_address(
+                &pre_authorized_debit_v1::ID,
+                &token_account,
+                &debit_authority
+            ),
+            find_scoped_pre_authorization_address(
+                &pre_authorized_debit_v1::ID,
+                &PreAuthorizationScope::TokenAccount,
+                &token_account,
+                &debit_authority,
+            )
+        );
+    }
+
+    #[test]
+    fn scoped_pre_authorization_address_matches_owner_and_mint_scope() {
+        let (owner, mint, debit_authority) = (
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+        );
+
+        assert_eq!(
+            find_owner_pre_authorization_address(
+                &pre_authorized_debit_v1::ID,
+                &owner,
+                &mint,
+                &debit_authority
+            ),
+            find_scoped_pre_authorization_address(
+                &pre_authorized_debit_v1::ID,
+                &PreAuthorizationScope::OwnerAndMint { owner, mint },
+                &Pubkey::default(),
+                &debit_authority,
+            )
+        );
+    }
+}
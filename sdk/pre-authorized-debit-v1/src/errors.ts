@@ -65,3 +65,12 @@ export class TransactionFeesPayerNotProvided extends Error {
     super("TX fees payer not provided");
   }
 }
+
+export class UnsupportedPreAuthorization extends CustomError {
+  constructor(rpcUrl: string, pubkey: PublicKey) {
+    super(
+      rpcUrl,
+      `Pre-authorization is not a one-time or recurring pre-authorization scoped to a token account: ${pubkey.toBase58()}`,
+    );
+  }
+}
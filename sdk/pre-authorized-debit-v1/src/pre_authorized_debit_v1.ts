@@ -1,5 +1,5 @@
 export type PreAuthorizedDebitV1 = {
-  version: "1.0.0";
+  version: "2.0.0";
   name: "pre_authorized_debit_v1";
   instructions: [
     {
@@ -29,7 +29,7 @@ export type PreAuthorizedDebitV1 = {
     {
       name: "initPreAuthorization";
       docs: [
-        "The `InitPreAuthorization` instruction will create a `pre_authorization` account.\n\n    Initializes a new account (`pre_authorization`).\n    The `pre_authorization` defines a set of rules.\n    The `pre_authorization` rules/constraints are verified during a `debit` instruction.\n    The `pre_authorization` in conjunction with a `smart_delegate` for the same `token_account`\n    can allow the `pre_authorization.debit_authority` to do a one-time or recurring debit from the\n    `token_account.\n    For a pair of `debit_authority` and `token_account`, only a single `pre_authorization` account can exist.\n    To create another `pre_authorization` for the same `token_account`, another `debit_authority` must be used.\n\n    The `payer` MUST sign the transaction.\n    The `payer` MUST have enough lamports to pay for the `pre_authorization` account.\n    The `owner` MUST sign the transaction.\n    The `owner` MUST be the `token_account.owner`.\n    The `payer` and `owner` may be the same account.\n    The `token_account.owner` MUST be the `owner`.\n    The `pre_authorization.token_account` must be the same as `token_account`.\n\n    Accounts expected by this instruction:\n        0. `[writable]` payer\n        1. `[]`         owner\n        2. `[writable]` token_account\n        3. `[writable]` pre_authorization\n        4. `[]`         system_program",
+        "The `InitPreAuthorization` instruction will create a `pre_authorization` account.\n\n    Initializes a new account (`pre_authorization`).\n    The `pre_authorization` defines a set of rules.\n    The `pre_authorization` rules/constraints are verified during a `debit` instruction.\n    The `pre_authorization` in conjunction with a `smart_delegate` for the same `token_account`\n    can allow the `pre_authorization.debit_authority` to do a one-time, recurring, scheduled, installments\n    or rolling window debit from the `token_account.\n    For a pair of `debit_authority` and `token_account`, only a single `pre_authorization` account can exist.\n    To create another `pre_authorization` for the same `token_account`, another `debit_authority` must be used.\n\n    The `payer` MUST sign the transaction.\n    The `payer` MUST have enough lamports to pay for the `pre_authorization` account.\n    The `owner` MUST sign the transaction.\n    The `owner` MUST be the `token_account.owner`.\n    The `payer` and `owner` may be the same account.\n    The `token_account.owner` MUST be the `owner`.\n    The `pre_authorization.token_account` must be the same as `token_account`.\n    If `rotation_requires_owner_signature` is set, the `owner` MUST co-sign any `rotate_debit_authority`.\n    The `max_amount_per_debit` (if set) MUST be greater than 0.\n    For a recurring pre-authorization, the `min_amount_per_debit` (if set) MUST NOT be greater than\n    the `max_amount_per_debit` (if set) and the `max_accrued_cycles` (if set) MUST be greater than 0.\n    If `destination_constraint` is set, the `debit` destination MUST be the given token account\n    (or a token account owned by the given owner).\n    For a one-time pre-authorization, the `expiry_unix_timestamp` MUST be after the `activation_unix_timestamp`.\n    For a one-time pre-authorization with `close_on_exhaustion` set, the `debit` that exhausts the amount authorized\n    also closes the `pre_authorization`.\n    For a recurring pre-authorization, the `repeat_frequency_seconds` MUST be greater than 0 and at most i64::MAX.\n    For a recurring pre-authorization with a `calendar_schedule` (monthly, quarterly or yearly cycles anchored to\n    a UTC day of month), the `repeat_frequency_seconds` MUST be 0 and the `day_of_month` MUST be in [1, 31].\n    For a recurring pre-authorization, the `debit_window_seconds` (if set) MUST be greater than 0 and the\n    `debit_window_offset_seconds` (if set) MUST be less than the shortest cycle,\n    the `debit_window_offset_seconds` MUST NOT be set without the `debit_window_seconds`.\n    For a recurring pre-authorization, the `intro_schedule` (if set) authorizes the `intro_amount_authorized`\n    (instead of the `recurring_amount_authorized`) in each of the first `num_intro_cycles` cycles,\n    the `num_intro_cycles` MUST be greater than 0.\n    For a recurring pre-authorization, the `num_cycles` (if set) MUST be greater than 0 and\n    the total amount authorized across the `num_cycles` MUST fit in a u64.\n    For a scheduled pre-authorization (a list of `steps`, each authorizing an amount per cycle from its\n    `cycle_start` onwards), the `repeat_frequency_seconds` MUST be greater than 0 and at most i64::MAX,\n    there MUST be 1 to 12 `steps`, the first step MUST start at cycle 1 and the `cycle_start` of the steps\n    MUST be strictly increasing.\n    For a scheduled pre-authorization, the `num_cycles` (if set) MUST be greater than 0, the last step MUST start\n    within the `num_cycles` and the total amount authorized across the `num_cycles` MUST fit in a u64.\n    For an installments pre-authorization (`total_amount` split into `installment_count` equal installments due every\n    `interval_seconds` from activation), the `interval_seconds` MUST be greater than 0 and at most i64::MAX,\n    the `grace_period_seconds` MUST be at most i64::MAX, the `installment_count` MUST be greater than 0\n    and the last installment MUST be due before i64::MAX.\n    For a rolling window pre-authorization (at most `amount_authorized_per_window` in any trailing `window_seconds`),\n    the `window_seconds` MUST be a non-zero multiple of 24 (the number of buckets the window is tracked in)\n    and at most i64::MAX.\n    The `pre_authorization_counter` of the `token_account` is created if it does not exist yet\n    and counts the open `pre_authorization` accounts of the `token_account`. A counter created here\n    cannot know about the `pre_authorization` accounts created before it, its count is unverified until the\n    `token_account.owner` sets it in `init_pre_authorization_counter`.\n    The `smart_delegate` is approved for `u64::MAX`, unless `bounded_delegation` is set: the `smart_delegate`\n    is then approved for the sum of the outstanding amounts authorized (total amount authorized minus the\n    amount debited) of the open `pre_authorization` accounts of the `token_account`.\n    The `bounded_delegation` MUST match the other open `pre_authorization` accounts of the `token_account`.\n    With `bounded_delegation`, the `pre_authorization_counter` count MUST be verified and the amount authorized\n    MUST be bounded (i.e. `num_cycles` MUST be set for a recurring or scheduled pre-authorization and it cannot\n    be a rolling window pre-authorization).\n    The approval can be above that sum when the outstanding amount authorized decreases without the\n    `token_account.owner` signature (required by the token program to lower it): a `pre_authorization` closed\n    by the `debit_authority` or in `close_expired_pre_authorization`, or an amount accrued above\n    `max_accrued_cycles` forfeited in a debit. It is approved for the exact sum again when the\n    `token_account.owner` closes a `pre_authorization` or calls `reapprove_smart_delegate`.\n\n    Accounts expected by this instruction:\n        0. `[writable]` payer\n        1. `[]`         owner\n        2. `[]`         smart_delegate\n        3. `[writable]` token_account\n        4. `[writable]` pre_authorization\n        5. `[writable]` pre_authorization_counter\n        6. `[]`         token_program\n        7. `[]`         system_program",
       ];
       accounts: [
         {
@@ -57,6 +57,11 @@ export type PreAuthorizedDebitV1 = {
           isMut: true;
           isSigner: false;
         },
+        {
+          name: "preAuthorizationCounter";
+          isMut: true;
+          isSigner: false;
+        },
         {
           name: "tokenProgram";
           isMut: false;
@@ -77,10 +82,51 @@ export type PreAuthorizedDebitV1 = {
         },
       ];
     },
+    {
+      name: "initPreAuthorizationCounter";
+      docs: [
+        "The `InitPreAuthorizationCounter` instruction allows a `token_account.owner` to set the number of open\n    `pre_authorization` accounts scoped to the `token_account` (e.g. the ones created before the\n    `pre_authorization_counter` existed), the count is then verified.\n\n    Initializes the `pre_authorization_counter` account if it does not exist yet, derived with the seeds:\n    ['pre-authorization-counter', token_account].\n    Once the count is verified, the `smart_delegate` approval is revoked when the last `pre_authorization`\n    is closed by the `token_account.owner` and bounded delegation can be used.\n    The `count` MUST be the number of open `pre_authorization` accounts scoped to the `token_account`\n    (a lower count revokes the `smart_delegate` approval early, a higher one never revokes it).\n    The count can only be set once: the `pre_authorization_counter` MUST NOT be verified yet\n    (nor be in bounded delegation mode).\n\n    The `payer` MUST sign the transaction.\n    The `owner` MUST sign the transaction.\n    The `owner` MUST equal the `token_account.owner`.\n\n    Accounts expected by this instruction:\n        0. `[writable]` payer\n        1. `[]`         owner\n        2. `[]`         token_account\n        3. `[writable]` pre_authorization_counter\n        4. `[]`         system_program",
+      ];
+      accounts: [
+        {
+          name: "payer";
+          isMut: true;
+          isSigner: true;
+        },
+        {
+          name: "owner";
+          isMut: false;
+          isSigner: true;
+        },
+        {
+          name: "tokenAccount";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "preAuthorizationCounter";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "systemProgram";
+          isMut: false;
+          isSigner: false;
+        },
+      ];
+      args: [
+        {
+          name: "params";
+          type: {
+            defined: "InitPreAuthorizationCounterParams";
+          };
+        },
+      ];
+    },
     {
       name: "closePreAuthorization";
       docs: [
-        "The `ClosePreAuthorization` instruction will close a `pre_authorization` account.\n\n    Closes an existing `pre_authorization` account and refunds the lamports\n    to the `token_account.owner` (`receiver`).\n\n    The `receiver` will receive all lamports from the closed account.\n    The `receiver` MUST be the `token_account.owner`.\n    The `authority` MUST sign for the instruction.\n    The `authority` MUST be either the `token_account.owner` or the `pre_authorization.debit_authority`.\n    The `owner` MUST be the `token_account.owner`.\n    The `token_account.owner` MUST be the `owner`.\n    The `pre_authorization.token_account` must be the same as `token_account`.\n\n    Accounts expected by this instruction:\n        0. `[writable]` receiver\n        1. `[]`         authority\n        2. `[]`         token_account\n        3. `[writable]` pre_authorization",
+        "The `ClosePreAuthorization` instruction will close a `pre_authorization` account.\n\n    Closes an existing `pre_authorization` account and refunds the lamports\n    to the `token_account.owner` (`receiver`).\n\n    The `receiver` will receive all lamports from the closed account.\n    The `receiver` MUST be the `token_account.owner`.\n    The `authority` MUST sign for the instruction.\n    The `authority` MUST be either the `token_account.owner` or the `pre_authorization.debit_authority`.\n    The `owner` MUST be the `token_account.owner`.\n    The `token_account.owner` MUST be the `owner`.\n    The `pre_authorization.token_account` must be the same as `token_account`.\n    The `pre_authorization_counter` of the `token_account` MUST be set, it can be uninitialized\n    (if initialized, it is decremented).\n    If this is the last open `pre_authorization` of the `token_account` (see `pre_authorization_counter`,\n    its count MUST be verified), the `authority` is the `token_account.owner` and the `token_account.delegate`\n    is the `smart_delegate`, the `smart_delegate` approval is revoked.\n    Otherwise, in bounded delegation mode, the `smart_delegate` is re-approved for the sum of the outstanding\n    amounts authorized of the other open `pre_authorization` accounts if the `authority` is the\n    `token_account.owner` and the `token_account.delegate` is the `smart_delegate`, the remaining accounts MUST then\n    be all the other open `pre_authorization` accounts of the `token_account` (each one exactly once).\n\n    Accounts expected by this instruction:\n        0. `[writable]` receiver\n        1. `[]`         authority\n        2. `[writable]` token_account\n        3. `[]`         smart_delegate\n        4. `[writable]` pre_authorization\n        5. `[writable]` pre_authorization_counter (optional)\n        6. `[]`         token_program\n        7.. `[]`        pre_authorization (remaining accounts, bounded delegation mode only)",
       ];
       accounts: [
         {
@@ -95,6 +141,138 @@ export type PreAuthorizedDebitV1 = {
         },
         {
           name: "tokenAccount";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "smartDelegate";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "preAuthorization";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "preAuthorizationCounter";
+          isMut: true;
+          isSigner: false;
+          isOptional: true;
+        },
+        {
+          name: "tokenProgram";
+          isMut: false;
+          isSigner: false;
+        },
+      ];
+      args: [];
+    },
+    {
+      name: "closeExpiredPreAuthorization";
+      docs: [
+        "The `CloseExpiredPreAuthorization` instruction will close a `pre_authorization` account\n    that can never be debited again. Anyone can call it.\n\n    Closes an expired `pre_authorization` account and refunds the lamports\n    to the `token_account.owner` (`receiver`), minus a bounty of 5000 lamports if a `bounty_receiver` is set.\n\n    A `pre_authorization` is expired if:\n    - one-time: the validator time is at or after the `expiry_unix_timestamp`\n    - recurring or scheduled: `num_cycles` is set and the current cycle is after the last cycle\n    - installments: all installments have been paid\n    A rolling window `pre_authorization` never expires.\n\n    The `caller` MUST sign for the instruction.\n    The `receiver` MUST be the `token_account.owner`.\n    The `bounty_receiver` (if set) receives the bounty, it can be any account (e.g. the `caller`).\n    The `pre_authorization.token_account` must be the same as `token_account`\n    (for an owner scoped `pre_authorization`, the `token_account.owner` and `token_account.mint`\n    MUST equal the `owner` and `mint` of the `pre_authorization.scope` instead).\n    The `smart_delegate` approval is never revoked here, nor lowered in bounded delegation mode\n    (the `token_account.owner` has to sign for it, see `reapprove_smart_delegate`).\n    The `pre_authorization_counter` of the `token_account` MUST be set unless the `pre_authorization` is owner scoped,\n    it can be uninitialized (if initialized, it is decremented, unless the `pre_authorization` is owner scoped).\n\n    Accounts expected by this instruction:\n        0. `[]`         caller\n        1. `[writable]` receiver\n        2. `[writable]` bounty_receiver (optional)\n        3. `[]`         token_account\n        4. `[writable]` pre_authorization\n        5. `[writable]` pre_authorization_counter (optional)",
+      ];
+      accounts: [
+        {
+          name: "caller";
+          isMut: false;
+          isSigner: true;
+        },
+        {
+          name: "receiver";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "bountyReceiver";
+          isMut: true;
+          isSigner: false;
+          isOptional: true;
+        },
+        {
+          name: "tokenAccount";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "preAuthorization";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "preAuthorizationCounter";
+          isMut: true;
+          isSigner: false;
+          isOptional: true;
+        },
+      ];
+      args: [];
+    },
+    {
+      name: "initOwnerPreAuthorization";
+      docs: [
+        "The `InitOwnerPreAuthorization` instruction will create a `pre_authorization` account scoped to\n    an `owner` and a `mint` (instead of a single `token_account`).\n\n    Initializes a new account (`pre_authorization`) derived with the seeds:\n    ['pre-authorization', owner, mint, debit_authority].\n    The `pre_authorization.debit_authority` can debit from any token account owned by the `owner`\n    with the `mint` (and delegated to the `smart_delegate`), the amounts authorized are tracked once\n    across all these token accounts.\n    Unlike `init_pre_authorization`, no token account is approved by this instruction.\n    The same `params` rules as in `init_pre_authorization` apply.\n    Only `debit` and `close_owner_pre_authorization` support owner scoped pre-authorizations.\n\n    The `payer` MUST sign the transaction.\n    The `payer` MUST have enough lamports to pay for the `pre_authorization` account.\n    The `owner` MUST sign the transaction.\n    The `payer` and `owner` may be the same account.\n    The `bounded_delegation` MUST NOT be set (the `owner` approves the `smart_delegate` on each token account).\n\n    Accounts expected by this instruction:\n        0. `[writable]` payer\n        1. `[]`         owner\n        2. `[]`         mint\n        3. `[writable]` pre_authorization\n        4. `[]`         system_program",
+      ];
+      accounts: [
+        {
+          name: "payer";
+          isMut: true;
+          isSigner: true;
+        },
+        {
+          name: "owner";
+          isMut: false;
+          isSigner: true;
+        },
+        {
+          name: "mint";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "preAuthorization";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "systemProgram";
+          isMut: false;
+          isSigner: false;
+        },
+      ];
+      args: [
+        {
+          name: "params";
+          type: {
+            defined: "InitPreAuthorizationParams";
+          };
+        },
+      ];
+    },
+    {
+      name: "closeOwnerPreAuthorization";
+      docs: [
+        "The `CloseOwnerPreAuthorization` instruction will close a `pre_authorization` account\n    created with `init_owner_pre_authorization`.\n\n    The `receiver` will receive all lamports from the closed account.\n    The `receiver` MUST be the `owner` unless the `authority` is the `owner`.\n    The `authority` MUST sign for the instruction.\n    The `authority` MUST be either the `owner` or the `pre_authorization.debit_authority`.\n    The `owner` and `mint` MUST be the ones the `pre_authorization` was created with.\n\n    Accounts expected by this instruction:\n        0. `[writable]` receiver\n        1. `[]`         authority\n        2. `[]`         owner\n        3. `[]`         mint\n        4. `[writable]` pre_authorization",
+      ];
+      accounts: [
+        {
+          name: "receiver";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "authority";
+          isMut: false;
+          isSigner: true;
+        },
+        {
+          name: "owner";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "mint";
           isMut: false;
           isSigner: false;
         },
@@ -109,7 +287,7 @@ export type PreAuthorizedDebitV1 = {
     {
       name: "debit";
       docs: [
-        "The `Debit` instruction allows a `pre_authorization.debit_authority` to debit from the\n    `pre_authorization.token_account` via the `smart_delegate` PDA. To successfully debit from\n    the `token_account`, the constraints for the `pre_authorization` must be met.\n\n    Definitions:\n      - PA = pre_authorization\n\n    Common Rules:\n    - The `pre_authorization` MUST not be paused.\n    - The amount being requested to debit must be less than or equal to the available amount for the current_cycle\n    - The current timestamp must be less than the `PA.expiry_unix_timestamp`\n    - If the PA has a `num_cycles` defined, the `current_cycle` must be less than or equal to `PA.num_cycles`\n\n    For a recurring pre-authorization:\n    - The debit_authority must not have already done a debit in the current cycle\n\n    For a one-time pre-authorization:\n    - the validator time must be greater than or equal to the `pre_authorization.activation_unix_timestamp`\n\n    For a more in-depth understanding around the constraints in a debit, it is recommended to read through\n    the validation done for a `debit` instruction.\n\n    The `debit_authority` MUST sign the transaction.\n    The `debit_authority` MUST equal the `pre_authorization.debit_authority`.\n    The `mint` MUST equal `token_account.mint` and `destination_token_account.mint`.\n    The `token_account.delegate` MUST equal the `smart_delegate`.\n    The `token_account.mint` MUST equal the `mint`.\n    The `destination_token_account.mint` MUST equal `mint`.\n    The `pre_authorization.token_account` MUST equal the `token_account`.\n    The `token_program` MUST equal the token program matching the `token_account`.\n\n    Accounts expected by this instruction:\n        0. `[]`         debit_authority\n        1. `[]`         mint\n        2. `[writable]` token_account\n        3. `[writable]` destination_token_account\n        4. `[]`         smart_delegate\n        5. `[writable]` pre_authorization\n        6. `[]`         token_program",
+        "The `Debit` instruction allows a `pre_authorization.debit_authority` to debit from the\n    `pre_authorization.token_account` via the `smart_delegate` PDA. To successfully debit from\n    the `token_account`, the constraints for the `pre_authorization` must be met.\n\n    Definitions:\n      - PA = pre_authorization\n\n    Common Rules:\n    - The `pre_authorization` MUST not be paused.\n    - The validator time must be greater than or equal to the `PA.activation_unix_timestamp`\n    - The amount being requested to debit must be less than or equal to the available amount for the current_cycle\n      (see the rules of each variant below, several debits can occur in the same cycle)\n    - The `token_account.delegate` MUST be the `smart_delegate` (see `reapprove_smart_delegate`)\n    - The amount being requested to debit must be less than or equal to the `token_account.delegated_amount`\n      (in bounded delegation mode, it decreases with each debit along with the outstanding amount authorized)\n    - If the PA has a `num_cycles` defined, the `current_cycle` must be less than or equal to `PA.num_cycles`\n    - If the PA has a `destination_constraint` defined, the `destination_token_account` must satisfy it\n    - If the PA has a `max_amount_per_debit` defined, the amount must be less than or equal to it\n\n    For a recurring pre-authorization:\n    - If the PA resets every cycle, the available amount is the amount authorized in the current cycle minus\n      the amount debited in the current cycle\n    - Otherwise, the available amount is the amount authorized across the cycles up to the current cycle\n      (including the amount authorized before an amendment) minus the `PA.amount_debited_total`\n    - The amount authorized in a cycle is the `PA.recurring_amount_authorized`, or the\n      `intro_amount_authorized` in the first `num_intro_cycles` cycles if the PA has an `intro_schedule` defined\n    - If the PA has a `calendar_schedule` defined, the cycles are calendar cycles instead of\n      `PA.repeat_frequency_seconds` long\n    - If the PA has a `min_amount_per_debit` defined, the amount must be greater than or equal to it\n    - If the PA has a `max_accrued_cycles` defined (and does not reset every cycle), the available amount\n      is capped to `max_accrued_cycles` times the amount authorized in the current cycle,\n      the amount accrued above it is forfeited\n    - If the PA has a `debit_window_seconds` defined, the validator time must be within the debit window of the\n      current cycle (starting `debit_window_offset_seconds`, if set, into the cycle)\n\n    For a scheduled pre-authorization:\n    - The amount authorized in the current cycle is the `amount_authorized` of the step active in the current cycle\n      (the amount is reset every cycle), the `DebitEvent` reports the step the debit was validated against\n\n    For an installments pre-authorization:\n    - The amount of the installments due (i.e. past their due date) minus the amount already debited can be debited,\n      overdue installments (see `grace_period_seconds`) can still be debited\n\n    For a rolling window pre-authorization:\n    - The amount debited in the trailing window (rounded up to whole buckets of `window_seconds / 24` seconds)\n      plus the amount MUST NOT exceed the `amount_authorized_per_window`\n\n    For a one-time pre-authorization:\n    - The validator time must be less than the `PA.expiry_unix_timestamp`\n    - The available amount is the `PA.amount_authorized` minus the `PA.amount_debited`\n    - If the PA has `close_on_exhaustion` set and the debit exhausts the amount authorized, the `pre_authorization`\n      is closed (emitting a `OneTimePreAuthorizationClosed` event) and its rent is refunded to the `receiver`\n\n    Amount modes:\n    - `Exact`: exactly the `amount` is debited (or the debit fails)\n    - `UpTo`: the `amount` is capped to the amount available, the `max_amount_per_debit` (if any), the\n      `token_account.amount` and the `token_account.delegated_amount`, the capped amount MUST be greater than 0\n      and greater than or equal to the `min_amount_per_debit` (if any), or the debit fails with `NothingToDebit`\n      (and is then validated with the rules above). The `DebitEvent` reports the amount requested\n      (`requested_amount`) and the amount debited (`debit_variant`).\n\n    The `params.reference` (if set, e.g. an invoice id or hash) is emitted in the `DebitEvent`.\n    If the `memo_program` is set, the `params.reference` is also logged (hex encoded) in a SPL memo right before\n    the transfer (e.g. for a `destination_token_account` requiring memos on incoming transfers).\n\n    If the `debit_receipt` is set, a `debit_receipt` recording the debit (amount, cycle, timestamp, destination\n    and reference) is created at the PDA derived with the `pre_authorization.num_debits` before the debit,\n    paid by the `receipt_payer`. The `pre_authorization.num_debits` is incremented with every debit.\n\n    For a more in-depth understanding around the constraints in a debit, it is recommended to read through\n    the validation done for a `debit` instruction.\n\n    The `debit_authority` MUST sign the transaction.\n    The `debit_authority` MUST equal the `pre_authorization.debit_authority`.\n    The `mint` MUST equal `token_account.mint` and `destination_token_account.mint`.\n    The `token_account.delegate` MUST equal the `smart_delegate`.\n    The `token_account.mint` MUST equal the `mint`.\n    The `destination_token_account.mint` MUST equal `mint`.\n    The `pre_authorization.token_account` MUST equal the `token_account`\n    (for an owner scoped `pre_authorization`, the `token_account.owner` and `token_account.mint`\n    MUST equal the `owner` and `mint` of the `pre_authorization.scope` instead).\n    The `token_program` MUST equal the token program matching the `token_account`.\n    The `receiver` MUST equal the `token_account.owner` (only required if the debit closes the `pre_authorization`).\n    The `pre_authorization_counter` of the `token_account` is only required if the debit closes a `pre_authorization`\n    that is not owner scoped, or if the `pre_authorization` is owner scoped (it can be uninitialized then).\n    An owner scoped `pre_authorization` MUST NOT debit a `token_account` in bounded delegation mode\n    (its debits are not part of the outstanding amount authorized the `smart_delegate` is approved for).\n    The `memo_program` (optional) MUST equal the SPL Memo program and the `params.reference` MUST be set if it is.\n    The `receipt_payer` and `system_program` are only required if the `debit_receipt` is set.\n    The `receipt_payer` MUST sign the transaction and have enough lamports to pay for the `debit_receipt` account.\n    The `debit_receipt` MUST be the PDA derived with the seeds:\n    ['debit-receipt', pre_authorization, pre_authorization.num_debits (u64 little-endian)].\n\n    Accounts expected by this instruction:\n        0. `[]`         debit_authority\n        1. `[]`         mint\n        2. `[writable]` token_account\n        3. `[writable]` destination_token_account\n        4. `[]`         smart_delegate\n        5. `[writable]` pre_authorization\n        6. `[]`         token_program\n        7. `[writable]` receiver (optional)\n        8. `[writable]` pre_authorization_counter (optional)\n        9. `[]`         memo_program (optional)\n        10. `[writable]` receipt_payer (optional)\n        11. `[writable]` debit_receipt (optional)\n        12. `[]`         system_program (optional)",
       ];
       accounts: [
         {
@@ -147,6 +325,42 @@ export type PreAuthorizedDebitV1 = {
           isMut: false;
           isSigner: false;
         },
+        {
+          name: "receiver";
+          isMut: true;
+          isSigner: false;
+          isOptional: true;
+        },
+        {
+          name: "preAuthorizationCounter";
+          isMut: true;
+          isSigner: false;
+          isOptional: true;
+        },
+        {
+          name: "memoProgram";
+          isMut: false;
+          isSigner: false;
+          isOptional: true;
+        },
+        {
+          name: "receiptPayer";
+          isMut: true;
+          isSigner: true;
+          isOptional: true;
+        },
+        {
+          name: "debitReceipt";
+          isMut: true;
+          isSigner: false;
+          isOptional: true;
+        },
+        {
+          name: "systemProgram";
+          isMut: false;
+          isSigner: false;
+          isOptional: true;
+        },
       ];
       args: [
         {
@@ -157,10 +371,70 @@ export type PreAuthorizedDebitV1 = {
         },
       ];
     },
+    {
+      name: "debitBatch";
+      docs: [
+        "The `DebitBatch` instruction allows a `debit_authority` to debit many `pre_authorization` accounts\n    of the same `mint` in a single instruction. Each entry of the batch is a `token_account`,\n    `pre_authorization` and `destination_token_account` (passed as remaining accounts) with the amount\n    to debit in `params.amounts`. Each entry is validated with the same rules as the `debit` instruction\n    and emits a `DebitEvent`. A `DebitBatchProcessed` event is emitted once all the entries are processed.\n\n    Modes:\n    - `Atomic`: the batch fails if any entry fails\n    - `SkipFailed`: an entry failing validation is skipped and reported (with its error code) in the\n      `DebitBatchProcessed` event, a failed token transfer (e.g. a transfer hook) still fails the batch\n\n    The `debit_authority` MUST sign the transaction.\n    The `params.amounts` MUST NOT be empty (nor have more than 65535 amounts)\n    and there MUST be 3 remaining accounts per amount.\n    The remaining accounts MUST be writable and the 3 accounts of an entry MUST be distinct.\n    A `pre_authorization` MUST NOT be in more than one entry.\n    The `debit_authority` MUST equal the `pre_authorization.debit_authority` of every entry.\n    The `token_account.mint` and `destination_token_account.mint` of every entry MUST equal the `mint`.\n    The `token_account.amount` of every entry MUST be greater than or equal to the amount debited.\n    The `pre_authorization.token_account` of every entry MUST equal the `token_account`.\n    An owner scoped `pre_authorization` MUST use the `debit` instruction (its `pre_authorization_counter` is required).\n    A debit exhausting a one-time `pre_authorization` with `close_on_exhaustion` set MUST use the `debit` instruction.\n    No `debit_receipt` is created for the entries (their `pre_authorization.num_debits` is still incremented)\n    and the entries have no reference (the `debit` instruction MUST be used for either).\n    The `token_program` MUST equal the token program matching the `token_account` of every entry.\n\n    Accounts expected by this instruction:\n        0. `[]`         debit_authority\n        1. `[]`         mint\n        2. `[]`         smart_delegate\n        3. `[]`         token_program\n        4.. `[writable]` token_account, `[writable]` pre_authorization, `[writable]` destination_token_account\n            (remaining accounts, one triple per entry)",
+      ];
+      accounts: [
+        {
+          name: "debitAuthority";
+          isMut: false;
+          isSigner: true;
+        },
+        {
+          name: "mint";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "smartDelegate";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "tokenProgram";
+          isMut: false;
+          isSigner: false;
+        },
+      ];
+      args: [
+        {
+          name: "params";
+          type: {
+            defined: "DebitBatchParams";
+          };
+        },
+      ];
+    },
+    {
+      name: "closeDebitReceipt";
+      docs: [
+        "The `CloseDebitReceipt` instruction will close a `debit_receipt` account.\n\n    Closes an existing `debit_receipt` account and refunds the lamports to the `debit_receipt.payer` (`receiver`).\n\n    The `authority` MUST sign the transaction.\n    The `authority` MUST be either the `debit_receipt.payer` or the `debit_receipt.debit_authority`.\n    The `receiver` MUST equal the `debit_receipt.payer`.\n    The retention period (`DEBIT_RECEIPT_RETENTION_SECONDS` after the debit) MUST be over.\n\n    Accounts expected by this instruction:\n        0. `[]`         authority\n        1. `[writable]` receiver\n        2. `[writable]` debit_receipt",
+      ];
+      accounts: [
+        {
+          name: "authority";
+          isMut: false;
+          isSigner: true;
+        },
+        {
+          name: "receiver";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "debitReceipt";
+          isMut: true;
+          isSigner: false;
+        },
+      ];
+      args: [];
+    },
     {
       name: "updatePausePreAuthorization";
       docs: [
-        "The `UpdatePausePreAuthorization` instruction allows a `token_account.owner` to pause a\n    `pre_authorization`.\n\n    The `owner` MUST sign the transaction.\n    The `owner` MUST equal the `token_account.owner`.\n    The `token_account.owner` MUST equal the `owner`.\n    The `pre_authorization.token_account` MUST equal the `token_account`.\n\n    Accounts expected by this instruction:\n        0. `[writable]` owner\n        2. `[]`         token_account\n        3. `[writable]` pre_authorization",
+        "The `UpdatePausePreAuthorization` instruction allows a `token_account.owner` to pause a\n    `pre_authorization`.\n\n    The `owner` MUST sign the transaction.\n    The `owner` MUST equal the `token_account.owner`.\n    The `token_account.owner` MUST equal the `owner`.\n    The `pre_authorization.token_account` MUST equal the `token_account`\n    (for an owner scoped `pre_authorization`, the `token_account.owner` and `token_account.mint`\n    MUST equal the `owner` and `mint` of the `pre_authorization.scope` instead).\n\n    Accounts expected by this instruction:\n        0. `[writable]` owner\n        2. `[]`         token_account\n        3. `[writable]` pre_authorization",
       ];
       accounts: [
         {
@@ -188,1142 +462,5363 @@ export type PreAuthorizedDebitV1 = {
         },
       ];
     },
-  ];
-  accounts: [
     {
-      name: "preAuthorization";
+      name: "reapproveSmartDelegate";
       docs: [
-        "The `pre_authorization` is a PDA account derived with the seeds:\n ['pre-authorization', token_account, debit_authority].\n The `pre_authorization` can be thought of as the rule for the `smart_delegate`.\n The `pre_authorization` can be used to validate a recurring or one-time debit from the `token_account`.\n The `smart_delegate` will validate the rules of the `pre_authorization` in the `debit` instruction.\n A `pre_authorization` is associated many:1 with a `token_account`,\n however, for a given `debit_authority` and `token_account` there can only be one `pre_authorization`.",
+        "The `ReapproveSmartDelegate` instruction allows a `token_account.owner` to restore the `smart_delegate`\n    approval on the `token_account` (e.g. after approving another delegate), no `pre_authorization` is modified.\n\n    The `smart_delegate` is approved for `u64::MAX`, or in bounded delegation mode, for the sum of the\n    outstanding amounts authorized of the open `pre_authorization` accounts of the `token_account`.\n\n    The `owner` MUST sign the transaction.\n    The `owner` MUST equal the `token_account.owner`.\n    In bounded delegation mode, the remaining accounts MUST be all the open `pre_authorization` accounts\n    of the `token_account` (each one exactly once, see `pre_authorization_counter.count`).\n\n    Accounts expected by this instruction:\n        0. `[]`         owner\n        1. `[writable]` token_account\n        2. `[]`         smart_delegate\n        3. `[]`         pre_authorization_counter\n        4. `[]`         token_program\n        5.. `[]`        pre_authorization (remaining accounts, bounded delegation mode only)",
       ];
-      type: {
-        kind: "struct";
-        fields: [
-          {
-            name: "bump";
-            docs: [
-              "The `bump` is the canonical PDA bump when derived with seeds:\n      ['pre-authorization', token_account, debit_authority].\n      This field is initialized in `init_pre_authorization`.\n      This field is never updated in any instruction.",
-            ];
-            type: "u8";
-          },
-          {
-            name: "paused";
-            docs: [
-              "If `paused === true`, then the `debit_authority` cannot debit via the `token_account`.\n      This field is initialized to `false` in `init_pre_authorization`.\n      This field can be updated by the `token_account.owner` in `update_pause_pre_authorization`.\n      Note that recurring rebits that accrue the amounts across cycles will continue to do so while paused\n        (close the pre-authorization to stop this).",
+      accounts: [
+        {
+          name: "owner";
+          isMut: false;
+          isSigner: true;
+        },
+        {
+          name: "tokenAccount";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "smartDelegate";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "preAuthorizationCounter";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "tokenProgram";
+          isMut: false;
+          isSigner: false;
+        },
+      ];
+      args: [];
+    },
+    {
+      name: "updatePreAuthorization";
+      docs: [
+        "The `UpdatePreAuthorization` instruction allows a `token_account.owner` to amend the terms of a\n    `pre_authorization` without closing it (i.e. the debited amounts are kept).\n\n    For a one-time pre-authorization, the `amount_authorized` and `expiry_unix_timestamp` can be updated.\n    For a recurring pre-authorization, the `recurring_amount_authorized`, `num_cycles` and `reset_every_cycle`\n    can be updated. The new terms apply from the current cycle onwards, the amounts authorized in\n    already elapsed cycles are never increased.\n\n    Rules:\n    - The variant (one-time or recurring) of the `pre_authorization` cannot be changed.\n    - Scheduled, installments and rolling window pre-authorizations cannot be updated (close and re-create them instead).\n    - The amount authorized (for the current cycle if recurring) cannot be less than the amount already debited.\n    - For a one-time pre-authorization, the `expiry_unix_timestamp` MUST be after the `activation_unix_timestamp`.\n    - For a recurring pre-authorization, the `num_cycles` (if set) MUST be greater than or equal to the current cycle.\n    - In bounded delegation mode, the `num_cycles` MUST be set for a recurring pre-authorization and the\n      `smart_delegate` is re-approved for the new outstanding amount authorized if it is still the\n      `token_account.delegate` (owner scoped pre-authorizations are not part of the bounded allowance).\n    - The `pre_authorization_counter` of the `token_account` MUST be set unless the `pre_authorization` is owner\n      scoped, it can be uninitialized (the delegation mode is read from it).\n\n    The `owner` MUST sign the transaction.\n    The `owner` MUST equal the `token_account.owner`.\n    The `token_account.owner` MUST equal the `owner`.\n    The `pre_authorization.token_account` MUST equal the `token_account`\n    (for an owner scoped `pre_authorization`, the `token_account.owner` and `token_account.mint`\n    MUST equal the `owner` and `mint` of the `pre_authorization.scope` instead).\n\n    Accounts expected by this instruction:\n        0. `[]`         owner\n        1. `[writable]` token_account\n        2. `[writable]` pre_authorization\n        3. `[]`         smart_delegate\n        4. `[]`         pre_authorization_counter (optional)\n        5. `[]`         token_program",
+      ];
+      accounts: [
+        {
+          name: "owner";
+          isMut: false;
+          isSigner: true;
+        },
+        {
+          name: "tokenAccount";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "preAuthorization";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "smartDelegate";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "preAuthorizationCounter";
+          isMut: false;
+          isSigner: false;
+          isOptional: true;
+        },
+        {
+          name: "tokenProgram";
+          isMut: false;
+          isSigner: false;
+        },
+      ];
+      args: [
+        {
+          name: "params";
+          type: {
+            defined: "UpdatePreAuthorizationParams";
+          };
+        },
+      ];
+    },
+    {
+      name: "proposeAmendment";
+      docs: [
+        "The `ProposeAmendment` instruction allows a `pre_authorization.debit_authority` to propose new terms\n    for a `pre_authorization` that the `token_account.owner` can accept or reject.\n\n    Initializes a new account (`pending_amendment`).\n    The `pending_amendment` PDA is derived with the seeds: ['pending-amendment', pre_authorization].\n    Only one `pending_amendment` can exist for a `pre_authorization` at a time.\n\n    The `payer` MUST sign the transaction.\n    The `payer` MUST have enough lamports to pay for the `pending_amendment` account.\n    The `debit_authority` MUST sign the transaction.\n    The `debit_authority` MUST equal the `pre_authorization.debit_authority`.\n    The `new_values` variant MUST match the `pre_authorization` variant.\n    The `acceptance_deadline_unix_timestamp` MUST be in the future.\n\n    Accounts expected by this instruction:\n        0. `[writable]` payer\n        1. `[]`         debit_authority\n        2. `[]`         pre_authorization\n        3. `[writable]` pending_amendment\n        4. `[]`         system_program",
+      ];
+      accounts: [
+        {
+          name: "payer";
+          isMut: true;
+          isSigner: true;
+        },
+        {
+          name: "debitAuthority";
+          isMut: false;
+          isSigner: true;
+        },
+        {
+          name: "preAuthorization";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "pendingAmendment";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "systemProgram";
+          isMut: false;
+          isSigner: false;
+        },
+      ];
+      args: [
+        {
+          name: "params";
+          type: {
+            defined: "ProposeAmendmentParams";
+          };
+        },
+      ];
+    },
+    {
+      name: "acceptAmendment";
+      docs: [
+        "The `AcceptAmendment` instruction allows a `token_account.owner` to apply the terms of a\n    `pending_amendment` to the `pre_authorization` and closes the `pending_amendment`.\n\n    The new terms are applied with the same rules as the `update_pre_authorization` instruction.\n    The lamports of the closed `pending_amendment` are refunded to the `pending_amendment.payer` (`receiver`).\n\n    The `owner` MUST sign the transaction.\n    The `owner` MUST equal the `token_account.owner`.\n    The `receiver` MUST equal the `pending_amendment.payer`.\n    The `pre_authorization.token_account` MUST equal the `token_account`\n    (for an owner scoped `pre_authorization`, the `token_account.owner` and `token_account.mint`\n    MUST equal the `owner` and `mint` of the `pre_authorization.scope` instead).\n    The current timestamp MUST be less than or equal to the `pending_amendment.acceptance_deadline_unix_timestamp`.\n    The `expected_new_values` MUST equal the `pending_amendment.new_values`.\n    The `pre_authorization_counter` of the `token_account` MUST be set unless the `pre_authorization` is owner scoped,\n    it can be uninitialized (the delegation mode is read from it).\n\n    Accounts expected by this instruction:\n        0. `[]`         owner\n        1. `[writable]` receiver\n        2. `[writable]` token_account\n        3. `[writable]` pre_authorization\n        4. `[writable]` pending_amendment\n        5. `[]`         smart_delegate\n        6. `[]`         pre_authorization_counter (optional)\n        7. `[]`         token_program",
+      ];
+      accounts: [
+        {
+          name: "owner";
+          isMut: false;
+          isSigner: true;
+        },
+        {
+          name: "receiver";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "tokenAccount";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "preAuthorization";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "pendingAmendment";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "smartDelegate";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "preAuthorizationCounter";
+          isMut: false;
+          isSigner: false;
+          isOptional: true;
+        },
+        {
+          name: "tokenProgram";
+          isMut: false;
+          isSigner: false;
+        },
+      ];
+      args: [
+        {
+          name: "params";
+          type: {
+            defined: "AcceptAmendmentParams";
+          };
+        },
+      ];
+    },
+    {
+      name: "rejectAmendment";
+      docs: [
+        "The `RejectAmendment` instruction closes a `pending_amendment` without applying its terms.\n\n    The lamports of the closed `pending_amendment` are refunded to the `pending_amendment.payer` (`receiver`).\n\n    The `authority` MUST sign the transaction.\n    The `authority` MUST be either the `token_account.owner` or the `pending_amendment.debit_authority`.\n    The `receiver` MUST equal the `pending_amendment.payer`.\n    The `pre_authorization.token_account` MUST equal the `token_account`\n    (for an owner scoped `pre_authorization`, the `token_account.owner` and `token_account.mint`\n    MUST equal the `owner` and `mint` of the `pre_authorization.scope` instead).\n\n    Accounts expected by this instruction:\n        0. `[]`         authority\n        1. `[writable]` receiver\n        2. `[]`         token_account\n        3. `[]`         pre_authorization\n        4. `[writable]` pending_amendment",
+      ];
+      accounts: [
+        {
+          name: "authority";
+          isMut: false;
+          isSigner: true;
+        },
+        {
+          name: "receiver";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "tokenAccount";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "preAuthorization";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "pendingAmendment";
+          isMut: true;
+          isSigner: false;
+        },
+      ];
+      args: [];
+    },
+    {
+      name: "rotateDebitAuthority";
+      docs: [
+        "The `RotateDebitAuthority` instruction allows a `pre_authorization.debit_authority` to hand over\n    a `pre_authorization` to a `new_debit_authority` without the `token_account.owner` re-authorizing.\n\n    Initializes a new account (`new_pre_authorization`) derived with the seeds:\n    ['pre-authorization', token_account, new_debit_authority]\n    (['pre-authorization', owner, mint, new_debit_authority] for an owner scoped `pre_authorization`).\n    The state of the `pre_authorization` (including the debited amounts, `last_debited_cycle` and `paused`)\n    is carried over to the `new_pre_authorization` and the `pre_authorization` is closed.\n    The lamports of the closed `pre_authorization` are refunded to the `token_account.owner` (`receiver`).\n\n    The `payer` MUST sign the transaction.\n    The `payer` MUST have enough lamports to pay for the `new_pre_authorization` account.\n    The `debit_authority` MUST sign the transaction.\n    The `debit_authority` MUST equal the `pre_authorization.debit_authority`.\n    The `new_debit_authority` MUST NOT equal the `debit_authority`.\n    If `pre_authorization.rotation_requires_owner_signature` is set, the `owner` MUST be provided and sign the transaction.\n    The `owner` (if provided) MUST equal the `token_account.owner`.\n    The `receiver` MUST equal the `token_account.owner`.\n    The `pre_authorization.token_account` MUST equal the `token_account`\n    (for an owner scoped `pre_authorization`, the `token_account.owner` and `token_account.mint`\n    MUST equal the `owner` and `mint` of the `pre_authorization.scope` instead).\n    The `pre_authorization` MUST NOT have a `pending_amendment`.\n\n    Accounts expected by this instruction:\n        0. `[writable]` payer\n        1. `[]`         debit_authority\n        2. `[]`         new_debit_authority\n        3. `[]`         owner (optional)\n        4. `[writable]` receiver\n        5. `[]`         token_account\n        6. `[writable]` pre_authorization\n        7. `[]`         pending_amendment\n        8. `[writable]` new_pre_authorization\n        9. `[]`         system_program",
+      ];
+      accounts: [
+        {
+          name: "payer";
+          isMut: true;
+          isSigner: true;
+        },
+        {
+          name: "debitAuthority";
+          isMut: false;
+          isSigner: true;
+        },
+        {
+          name: "newDebitAuthority";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "owner";
+          isMut: false;
+          isSigner: true;
+          isOptional: true;
+        },
+        {
+          name: "receiver";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "tokenAccount";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "preAuthorization";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "pendingAmendment";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "newPreAuthorization";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "systemProgram";
+          isMut: false;
+          isSigner: false;
+        },
+      ];
+      args: [];
+    },
+    {
+      name: "migratePreAuthorization";
+      docs: [
+        "The `MigratePreAuthorization` instruction converts a `pre_authorization` account created by\n    the v1.0.0 program to the current layout, it can be called by anyone.\n\n    Reads the v1.0.0 layout of the `pre_authorization`, reallocates the account to the current size\n    and writes it back in the current layout. The state of the `pre_authorization` (including the debited\n    amounts, `last_debited_cycle` and `paused`) is kept and the fields added since v1.0.0 are set\n    such that the `pre_authorization` behaves as it did in v1.0.0 (e.g. no `max_amount_per_debit`).\n    The `pre_authorization` can then be debited, updated, paused and closed again.\n\n    The `payer` MUST sign the transaction.\n    The `payer` MUST have enough lamports to pay for the additional rent of the `pre_authorization`.\n    The `pre_authorization` MUST be a v1.0.0 `pre_authorization` (i.e. not migrated yet).\n\n    Accounts expected by this instruction:\n        0. `[writable]` payer\n        1. `[writable]` pre_authorization\n        2. `[]`         system_program",
+      ];
+      accounts: [
+        {
+          name: "payer";
+          isMut: true;
+          isSigner: true;
+        },
+        {
+          name: "preAuthorization";
+          isMut: true;
+          isSigner: false;
+          docs: [
+            "the discriminator and the layout are validated in the handler",
+          ];
+        },
+        {
+          name: "systemProgram";
+          isMut: false;
+          isSigner: false;
+        },
+      ];
+      args: [];
+    },
+  ];
+  accounts: [
+    {
+      name: "debitReceipt";
+      docs: [
+        "The `debit_receipt` is a PDA account derived with the seeds:\n ['debit-receipt', pre_authorization, debit_index (u64 little-endian)].\n The `debit_receipt` is an on-chain record of a single `debit` (opt-in, see the `debit` instruction),\n the `debit_index` is the `pre_authorization.num_debits` before the debit.\n The `debit_receipt` can be closed by its `payer` or `debit_authority` once the retention period is over.",
+      ];
+      type: {
+        kind: "struct";
+        fields: [
+          {
+            name: "bump";
+            docs: [
+              "The `bump` is the canonical PDA bump when derived with seeds:\n      ['debit-receipt', pre_authorization, debit_index (u64 little-endian)].\n      This field is initialized in `debit`.\n      This field is never updated in any instruction.",
+            ];
+            type: "u8";
+          },
+          {
+            name: "preAuthorization";
+            docs: [
+              "The `pre_authorization` the debit was done with (it may be closed since).\n      This field is initialized in `debit`.\n      This field is never updated in any instruction.",
+            ];
+            type: "publicKey";
+          },
+          {
+            name: "debitIndex";
+            docs: [
+              "The index of the debit in the `pre_authorization` (its `num_debits` before the debit).\n      This field is initialized in `debit`.\n      This field is never updated in any instruction.",
+            ];
+            type: "u64";
+          },
+          {
+            name: "debitAuthority";
+            docs: [
+              "The `debit_authority` that did the debit.\n      This field is initialized in `debit`.\n      This field is never updated in any instruction.",
+            ];
+            type: "publicKey";
+          },
+          {
+            name: "payer";
+            docs: [
+              "The `payer` that paid for the `debit_receipt` account and receives the lamports when it is closed.\n      This field is initialized in `debit`.\n      This field is never updated in any instruction.",
+            ];
+            type: "publicKey";
+          },
+          {
+            name: "sourceTokenAccount";
+            docs: [
+              "The token account the amount was debited from.\n      This field is initialized in `debit`.\n      This field is never updated in any instruction.",
+            ];
+            type: "publicKey";
+          },
+          {
+            name: "destinationTokenAccount";
+            docs: [
+              "The token account the amount was transferred to.\n      This field is initialized in `debit`.\n      This field is never updated in any instruction.",
+            ];
+            type: "publicKey";
+          },
+          {
+            name: "amount";
+            docs: [
+              "The amount debited.\n      This field is initialized in `debit`.\n      This field is never updated in any instruction.",
+            ];
+            type: "u64";
+          },
+          {
+            name: "cycle";
+            docs: [
+              "The cycle (or rolling window bucket) the debit was done in, `None` for a one-time pre-authorization.\n      This field is initialized in `debit`.\n      This field is never updated in any instruction.",
+            ];
+            type: {
+              option: "u64";
+            };
+          },
+          {
+            name: "unixTimestamp";
+            docs: [
+              "The unix timestamp of the debit.\n      This field is initialized in `debit`.\n      This field is never updated in any instruction.",
+            ];
+            type: "i64";
+          },
+          {
+            name: "reference";
+            docs: [
+              "The `reference` of the debit (see `DebitParams`).\n      This field is initialized in `debit`.\n      This field is never updated in any instruction.",
+            ];
+            type: {
+              option: {
+                array: [
+                  "u8",
+                  32,
+                ];
+              };
+            };
+          },
+        ];
+      };
+    },
+    {
+      name: "pendingAmendment";
+      docs: [
+        "The `pending_amendment` is a PDA account derived with the seeds:\n ['pending-amendment', pre_authorization].\n The `pending_amendment` holds new terms for a `pre_authorization` proposed by its `debit_authority`.\n The new terms are only applied to the `pre_authorization` once accepted by the `token_account.owner`.\n There can only be one `pending_amendment` for a given `pre_authorization` at a time.",
+      ];
+      type: {
+        kind: "struct";
+        fields: [
+          {
+            name: "bump";
+            docs: [
+              "The `bump` is the canonical PDA bump when derived with seeds:\n      ['pending-amendment', pre_authorization].\n      This field is initialized in `propose_amendment`.\n      This field is never updated in any instruction.",
+            ];
+            type: "u8";
+          },
+          {
+            name: "preAuthorization";
+            docs: [
+              "The `pre_authorization` the new terms are proposed for.\n      This field is initialized in `propose_amendment`.\n      This field is never updated in any instruction.",
+            ];
+            type: "publicKey";
+          },
+          {
+            name: "debitAuthority";
+            docs: [
+              "The `debit_authority` of the `pre_authorization` that proposed the new terms.\n      This field is initialized in `propose_amendment`.\n      This field is never updated in any instruction.",
+            ];
+            type: "publicKey";
+          },
+          {
+            name: "payer";
+            docs: [
+              "The `payer` that paid for the `pending_amendment` account and receives the lamports when it is closed.\n      This field is initialized in `propose_amendment`.\n      This field is never updated in any instruction.",
+            ];
+            type: "publicKey";
+          },
+          {
+            name: "newValues";
+            docs: [
+              "The proposed terms, applied to the `pre_authorization` the same way as in `update_pre_authorization`.\n      This field is initialized in `propose_amendment`.\n      This field is never updated in any instruction.",
+            ];
+            type: {
+              defined: "UpdatePreAuthorizationVariant";
+            };
+          },
+          {
+            name: "acceptanceDeadlineUnixTimestamp";
+            docs: [
+              "The `acceptance_deadline_unix_timestamp` is the last unix timestamp at which the amendment can be accepted.\n      This field is initialized in `propose_amendment`.\n      This field is never updated in any instruction.",
+            ];
+            type: "i64";
+          },
+        ];
+      };
+    },
+    {
+      name: "preAuthorizationCounter";
+      docs: [
+        "The `pre_authorization_counter` is a PDA account derived with the seeds:\n ['pre-authorization-counter', token_account].\n The `pre_authorization_counter` tracks the number of open `pre_authorization` accounts for a `token_account`\n (owner scoped `pre_authorization` accounts are not counted),\n such that the `smart_delegate` approval can be revoked once the last one is closed.\n A `pre_authorization_counter` created by `init_pre_authorization` cannot know about the `pre_authorization`\n accounts created before it existed, its `count` is only relied on once `count_verified` is set\n (see `init_pre_authorization_counter`).",
+      ];
+      type: {
+        kind: "struct";
+        fields: [
+          {
+            name: "bump";
+            docs: [
+              "The `bump` is the canonical PDA bump when derived with seeds:\n      ['pre-authorization-counter', token_account].\n      This field is initialized in `init_pre_authorization` or `init_pre_authorization_counter`\n        (if the account does not exist yet).\n      This field is never updated in any instruction.",
+            ];
+            type: "u8";
+          },
+          {
+            name: "tokenAccount";
+            docs: [
+              "The `token_account` the `pre_authorization` accounts are counted for.\n      This field is initialized in `init_pre_authorization` or `init_pre_authorization_counter`\n        (if the account does not exist yet).\n      This field is never updated in any instruction.",
+            ];
+            type: "publicKey";
+          },
+          {
+            name: "count";
+            docs: [
+              "The number of open `pre_authorization` accounts for the `token_account`.\n      This field is incremented in `init_pre_authorization`.\n      This field is decremented whenever a `pre_authorization` for the `token_account` is closed\n      (`close_pre_authorization`, `close_expired_pre_authorization` or a `debit` with `close_on_exhaustion`,\n      all of them require the `pre_authorization_counter`).\n      This field can be set once by the `token_account.owner` in `init_pre_authorization_counter`\n        (while `count_verified` is false).",
+            ];
+            type: "u64";
+          },
+          {
+            name: "boundedDelegation";
+            docs: [
+              "true: the `smart_delegate` is approved for the sum of the outstanding amounts authorized\n      of the open `pre_authorization` accounts (see `PreAuthorization::outstanding_amount_authorized`),\n        it can be above the sum until the owner re-approves it (see `reapprove_smart_delegate`)\n      false: the `smart_delegate` is approved for `u64::MAX`\n      This field is set in `init_pre_authorization` when no `pre_authorization` is open for the `token_account`.\n      This field is never updated while a `pre_authorization` is open for the `token_account`.\n      This field can only be set to true if `count_verified` is true.",
+            ];
+            type: "bool";
+          },
+          {
+            name: "countVerified";
+            docs: [
+              "true: the `count` was set by the `token_account.owner` (in `init_pre_authorization_counter`),\n        the `smart_delegate` approval is revoked when it reaches 0\n      false: the `count` may not include the `pre_authorization` accounts created before this account,\n        the `smart_delegate` approval is never revoked and bounded delegation is not supported\n      This field is initialized to false in `init_pre_authorization` (if the account does not exist yet).\n      This field is set to true in `init_pre_authorization_counter`.",
+            ];
+            type: "bool";
+          },
+        ];
+      };
+    },
+    {
+      name: "preAuthorization";
+      docs: [
+        "The `pre_authorization` is a PDA account derived with the seeds (see `scope_seeds`):\n ['pre-authorization', token_account, debit_authority] for a `TokenAccount` scope, or\n ['pre-authorization', owner, mint, debit_authority] for an `OwnerAndMint` scope.\n The `pre_authorization` can be thought of as the rule for the `smart_delegate`.\n The `pre_authorization` can be used to validate a one-time, recurring, scheduled, installments\n or rolling window debit from the token account(s) covered by its `scope`.\n The `smart_delegate` will validate the rules of the `pre_authorization` in the `debit` instruction.\n A `pre_authorization` is associated many:1 with a `token_account` (or with an `owner` and `mint`),\n however, for a given `debit_authority` and `token_account` (or `owner` and `mint`)\n there can only be one `pre_authorization`.",
+      ];
+      type: {
+        kind: "struct";
+        fields: [
+          {
+            name: "bump";
+            docs: [
+              "The `bump` is the canonical PDA bump when derived with the seeds of the `scope`:\n      ['pre-authorization', token_account, debit_authority] or\n      ['pre-authorization', owner, mint, debit_authority].\n      This field is initialized in `init_pre_authorization` (or `init_owner_pre_authorization`,\n        and in `rotate_debit_authority` for the `new_pre_authorization`).\n      This field is never updated in any instruction.",
+            ];
+            type: "u8";
+          },
+          {
+            name: "paused";
+            docs: [
+              "If `paused === true`, then the `debit_authority` cannot debit via the `token_account`.\n      This field is initialized to `false` in `init_pre_authorization`.\n      This field can be updated by the `token_account.owner` in `update_pause_pre_authorization`.\n      Note that recurring rebits that accrue the amounts across cycles will continue to do so while paused\n        (close the pre-authorization to stop this).",
+            ];
+            type: "bool";
+          },
+          {
+            name: "tokenAccount";
+            docs: [
+              "The `token_account` is the account the `debit_authority` will be able to debit from.\n      This field is initialized in `init_pre_authorization`\n        (to `Pubkey::default()` in `init_owner_pre_authorization`, see `scope`).\n      This field is never updated in any instruction.",
+            ];
+            type: "publicKey";
+          },
+          {
+            name: "variant";
+            docs: [
+              "The `variant` contains the data specific to a one-time,\n      recurring, scheduled, installments or rolling window debit.\n      This field is initialized in `init_pre_authorization`.\n      The debited amounts are updated in `debit`.\n      The authorized amounts, expiry and cycle configuration can be updated by the\n      `token_account.owner` in `update_pre_authorization`.",
+            ];
+            type: {
+              defined: "PreAuthorizationVariant";
+            };
+          },
+          {
+            name: "debitAuthority";
+            docs: [
+              "The `debit_authority` is the signer that can debit from the `token_account`.\n      This field is initialized in `init_pre_authorization`.\n      This field is never updated in any instruction\n        (`rotate_debit_authority` moves the state to a new `pre_authorization` PDA instead).",
+            ];
+            type: "publicKey";
+          },
+          {
+            name: "activationUnixTimestamp";
+            docs: [
+              "The `activation_unix_timestamp` represents when the pre-authorization becomes active (i.e. debits can occur).\n      The field is initialized in `init_pre_authorization`.",
+            ];
+            type: "i64";
+          },
+          {
+            name: "rotationRequiresOwnerSignature";
+            docs: [
+              "If `rotation_requires_owner_signature === true`, then the `token_account.owner` MUST co-sign\n      the `rotate_debit_authority` instruction.\n      This field is initialized in `init_pre_authorization`.\n      This field is never updated in any instruction.",
+            ];
+            type: "bool";
+          },
+          {
+            name: "scope";
+            docs: [
+              "The `scope` defines the token accounts the `debit_authority` can debit from.\n      This field is initialized to `TokenAccount` in `init_pre_authorization`\n        and to `OwnerAndMint` in `init_owner_pre_authorization`.\n      This field is never updated in any instruction.",
+            ];
+            type: {
+              defined: "PreAuthorizationScope";
+            };
+          },
+          {
+            name: "destinationConstraint";
+            docs: [
+              "The `destination_constraint` (if set) restricts the `destination_token_account` of a `debit`.\n      This field is initialized in `init_pre_authorization` (or `init_owner_pre_authorization`).\n      This field is never updated in any instruction.",
+            ];
+            type: {
+              option: {
+                defined: "DestinationConstraint";
+              };
+            };
+          },
+          {
+            name: "numDebits";
+            docs: [
+              "The number of debits done with the `pre_authorization` (the index of the next `debit_receipt`).\n      This field is initialized to 0 in `init_pre_authorization` (or `init_owner_pre_authorization`).\n      This field is incremented in `debit` and `debit_batch`.",
+            ];
+            type: "u64";
+          },
+        ];
+      };
+    },
+    {
+      name: "smartDelegate";
+      docs: [
+        "The `smart_delegate` is a PDA account derived with the seeds:\n  ['smart-delegate'].\n  The `smart_delegate` should be set as the delegate of any\n  `token_account` specified in a `pre_authorization`.\n  The `smart_delegate` is a global account and is only initialized once.",
+      ];
+      type: {
+        kind: "struct";
+        fields: [
+          {
+            name: "bump";
+            docs: [
+              "The `bump` is the canonical PDA bump when derived with seeds:\n      ['smart-delegate'].\n      This field is initialized in `init_smart_delegate`.\n      This field is never updated in any instruction.",
+            ];
+            type: "u8";
+          },
+        ];
+      };
+    },
+  ];
+  types: [
+    {
+      name: "CalendarSchedule";
+      type: {
+        kind: "struct";
+        fields: [
+          {
+            name: "interval";
+            type: {
+              defined: "CalendarInterval";
+            };
+          },
+          {
+            name: "dayOfMonth";
+            type: "u8";
+          },
+        ];
+      };
+    },
+    {
+      name: "AcceptAmendmentParams";
+      type: {
+        kind: "struct";
+        fields: [
+          {
+            name: "expectedNewValues";
+            type: {
+              defined: "UpdatePreAuthorizationVariant";
+            };
+          },
+        ];
+      };
+    },
+    {
+      name: "OwnerPreAuthorizationClosedEventData";
+      type: {
+        kind: "struct";
+        fields: [
+          {
+            name: "debitAuthority";
+            type: "publicKey";
+          },
+          {
+            name: "closingAuthority";
+            type: "publicKey";
+          },
+          {
+            name: "owner";
+            type: "publicKey";
+          },
+          {
+            name: "mint";
+            type: "publicKey";
+          },
+          {
+            name: "receiver";
+            type: "publicKey";
+          },
+          {
+            name: "preAuthorization";
+            type: "publicKey";
+          },
+        ];
+      };
+    },
+    {
+      name: "PreAuthorizationClosedEventData";
+      type: {
+        kind: "struct";
+        fields: [
+          {
+            name: "debitAuthority";
+            type: "publicKey";
+          },
+          {
+            name: "closingAuthority";
+            type: "publicKey";
+          },
+          {
+            name: "tokenAccountOwner";
+            type: "publicKey";
+          },
+          {
+            name: "receiver";
+            type: "publicKey";
+          },
+          {
+            name: "tokenAccount";
+            type: "publicKey";
+          },
+          {
+            name: "preAuthorization";
+            type: "publicKey";
+          },
+        ];
+      };
+    },
+    {
+      name: "DebitBatchParams";
+      type: {
+        kind: "struct";
+        fields: [
+          {
+            name: "amounts";
+            type: {
+              vec: "u64";
+            };
+          },
+          {
+            name: "mode";
+            type: {
+              defined: "DebitBatchMode";
+            };
+          },
+        ];
+      };
+    },
+    {
+      name: "DebitBatchFailedEntry";
+      type: {
+        kind: "struct";
+        fields: [
+          {
+            name: "index";
+            type: "u16";
+          },
+          {
+            name: "preAuthorization";
+            type: "publicKey";
+          },
+          {
+            name: "errorCode";
+            type: "u64";
+          },
+        ];
+      };
+    },
+    {
+      name: "DebitParams";
+      type: {
+        kind: "struct";
+        fields: [
+          {
+            name: "amount";
+            type: "u64";
+          },
+          {
+            name: "amountMode";
+            type: {
+              defined: "DebitAmountMode";
+            };
+          },
+          {
+            name: "reference";
+            type: {
+              option: {
+                array: [
+                  "u8",
+                  32,
+                ];
+              };
+            };
+          },
+        ];
+      };
+    },
+    {
+      name: "OwnerPreAuthorizationCreatedEventData";
+      type: {
+        kind: "struct";
+        fields: [
+          {
+            name: "debitAuthority";
+            type: "publicKey";
+          },
+          {
+            name: "owner";
+            type: "publicKey";
+          },
+          {
+            name: "mint";
+            type: "publicKey";
+          },
+          {
+            name: "payer";
+            type: "publicKey";
+          },
+          {
+            name: "preAuthorization";
+            type: "publicKey";
+          },
+          {
+            name: "initParams";
+            type: {
+              defined: "InitPreAuthorizationParams";
+            };
+          },
+        ];
+      };
+    },
+    {
+      name: "InitPreAuthorizationCounterParams";
+      type: {
+        kind: "struct";
+        fields: [
+          {
+            name: "count";
+            type: "u64";
+          },
+        ];
+      };
+    },
+    {
+      name: "InitPreAuthorizationParams";
+      type: {
+        kind: "struct";
+        fields: [
+          {
+            name: "variant";
+            type: {
+              defined: "InitPreAuthorizationVariant";
+            };
+          },
+          {
+            name: "debitAuthority";
+            type: "publicKey";
+          },
+          {
+            name: "activationUnixTimestamp";
+            type: "i64";
+          },
+          {
+            name: "rotationRequiresOwnerSignature";
+            type: "bool";
+          },
+          {
+            name: "destinationConstraint";
+            type: {
+              option: {
+                defined: "DestinationConstraint";
+              };
+            };
+          },
+          {
+            name: "boundedDelegation";
+            type: "bool";
+          },
+        ];
+      };
+    },
+    {
+      name: "PreAuthorizationCreatedEventData";
+      type: {
+        kind: "struct";
+        fields: [
+          {
+            name: "debitAuthority";
+            type: "publicKey";
+          },
+          {
+            name: "owner";
+            type: "publicKey";
+          },
+          {
+            name: "payer";
+            type: "publicKey";
+          },
+          {
+            name: "tokenAccount";
+            type: "publicKey";
+          },
+          {
+            name: "preAuthorization";
+            type: "publicKey";
+          },
+          {
+            name: "initParams";
+            type: {
+              defined: "InitPreAuthorizationParams";
+            };
+          },
+        ];
+      };
+    },
+    {
+      name: "ProposeAmendmentParams";
+      type: {
+        kind: "struct";
+        fields: [
+          {
+            name: "newValues";
+            type: {
+              defined: "UpdatePreAuthorizationVariant";
+            };
+          },
+          {
+            name: "acceptanceDeadlineUnixTimestamp";
+            type: "i64";
+          },
+        ];
+      };
+    },
+    {
+      name: "DebitAuthorityRotatedEventData";
+      type: {
+        kind: "struct";
+        fields: [
+          {
+            name: "owner";
+            type: "publicKey";
+          },
+          {
+            name: "tokenAccount";
+            type: "publicKey";
+          },
+          {
+            name: "oldDebitAuthority";
+            type: "publicKey";
+          },
+          {
+            name: "newDebitAuthority";
+            type: "publicKey";
+          },
+          {
+            name: "oldPreAuthorization";
+            type: "publicKey";
+          },
+          {
+            name: "newPreAuthorization";
+            type: "publicKey";
+          },
+        ];
+      };
+    },
+    {
+      name: "UpdatePausePreAuthorizationParams";
+      type: {
+        kind: "struct";
+        fields: [
+          {
+            name: "pause";
+            type: "bool";
+          },
+        ];
+      };
+    },
+    {
+      name: "PausePreAuthorizationEventData";
+      type: {
+        kind: "struct";
+        fields: [
+          {
+            name: "owner";
+            type: "publicKey";
+          },
+          {
+            name: "tokenAccount";
+            type: "publicKey";
+          },
+          {
+            name: "preAuthorization";
+            type: "publicKey";
+          },
+          {
+            name: "newPausedValue";
+            type: "bool";
+          },
+        ];
+      };
+    },
+    {
+      name: "UpdatePreAuthorizationParams";
+      type: {
+        kind: "struct";
+        fields: [
+          {
+            name: "variant";
+            type: {
+              defined: "UpdatePreAuthorizationVariant";
+            };
+          },
+        ];
+      };
+    },
+    {
+      name: "PreAuthorizationUpdatedEventData";
+      type: {
+        kind: "struct";
+        fields: [
+          {
+            name: "owner";
+            type: "publicKey";
+          },
+          {
+            name: "tokenAccount";
+            type: "publicKey";
+          },
+          {
+            name: "preAuthorization";
+            type: "publicKey";
+          },
+          {
+            name: "debitAuthority";
+            type: "publicKey";
+          },
+          {
+            name: "oldValues";
+            type: {
+              defined: "UpdatePreAuthorizationVariant";
+            };
+          },
+          {
+            name: "newValues";
+            type: {
+              defined: "UpdatePreAuthorizationVariant";
+            };
+          },
+        ];
+      };
+    },
+    {
+      name: "PreAuthorizationV1";
+      docs: [
+        "The layout of a `pre_authorization` account created by the v1.0.0 program\n (after the 8 byte `PreAuthorization` discriminator, which is unchanged).\n It is only read in `migrate_pre_authorization`, see `PreAuthorization` for the fields.",
+      ];
+      type: {
+        kind: "struct";
+        fields: [
+          {
+            name: "bump";
+            type: "u8";
+          },
+          {
+            name: "paused";
+            type: "bool";
+          },
+          {
+            name: "tokenAccount";
+            type: "publicKey";
+          },
+          {
+            name: "variant";
+            type: {
+              defined: "PreAuthorizationVariantV1";
+            };
+          },
+          {
+            name: "debitAuthority";
+            type: "publicKey";
+          },
+          {
+            name: "activationUnixTimestamp";
+            type: "i64";
+          },
+        ];
+      };
+    },
+    {
+      name: "IntroSchedule";
+      type: {
+        kind: "struct";
+        fields: [
+          {
+            name: "numIntroCycles";
+            type: "u64";
+          },
+          {
+            name: "introAmountAuthorized";
+            type: "u64";
+          },
+        ];
+      };
+    },
+    {
+      name: "ScheduleStep";
+      type: {
+        kind: "struct";
+        fields: [
+          {
+            name: "cycleStart";
+            type: "u64";
+          },
+          {
+            name: "amountAuthorized";
+            type: "u64";
+          },
+        ];
+      };
+    },
+    {
+      name: "CalendarInterval";
+      type: {
+        kind: "enum";
+        variants: [
+          {
+            name: "Monthly";
+          },
+          {
+            name: "Quarterly";
+          },
+          {
+            name: "Yearly";
+          },
+        ];
+      };
+    },
+    {
+      name: "DebitBatchMode";
+      type: {
+        kind: "enum";
+        variants: [
+          {
+            name: "Atomic";
+          },
+          {
+            name: "SkipFailed";
+          },
+        ];
+      };
+    },
+    {
+      name: "DebitAmountMode";
+      type: {
+        kind: "enum";
+        variants: [
+          {
+            name: "Exact";
+          },
+          {
+            name: "UpTo";
+          },
+        ];
+      };
+    },
+    {
+      name: "DebitEventVariant";
+      type: {
+        kind: "enum";
+        variants: [
+          {
+            name: "OneTime";
+            fields: [
+              {
+                name: "debit_amount";
+                type: "u64";
+              },
+            ];
+          },
+          {
+            name: "Recurring";
+            fields: [
+              {
+                name: "debit_amount";
+                type: "u64";
+              },
+              {
+                name: "cycle";
+                type: "u64";
+              },
+            ];
+          },
+          {
+            name: "Scheduled";
+            fields: [
+              {
+                name: "debit_amount";
+                type: "u64";
+              },
+              {
+                name: "cycle";
+                type: "u64";
+              },
+              {
+                name: "step_index";
+                type: "u8";
+              },
+              {
+                name: "step_amount_authorized";
+                type: "u64";
+              },
+            ];
+          },
+          {
+            name: "Installments";
+            fields: [
+              {
+                name: "debit_amount";
+                type: "u64";
+              },
+              {
+                name: "installments_paid";
+                type: "u64";
+              },
+              {
+                name: "installment_count";
+                type: "u64";
+              },
+            ];
+          },
+          {
+            name: "RollingWindow";
+            fields: [
+              {
+                name: "debit_amount";
+                type: "u64";
+              },
+              {
+                name: "bucket";
+                type: "u64";
+              },
+              {
+                name: "amount_debited_in_window";
+                type: "u64";
+              },
+            ];
+          },
+        ];
+      };
+    },
+    {
+      name: "InitPreAuthorizationVariant";
+      type: {
+        kind: "enum";
+        variants: [
+          {
+            name: "OneTime";
+            fields: [
+              {
+                name: "amount_authorized";
+                type: "u64";
+              },
+              {
+                name: "expiry_unix_timestamp";
+                type: "i64";
+              },
+              {
+                name: "max_amount_per_debit";
+                type: {
+                  option: "u64";
+                };
+              },
+              {
+                name: "close_on_exhaustion";
+                type: "bool";
+              },
+            ];
+          },
+          {
+            name: "Recurring";
+            fields: [
+              {
+                name: "repeat_frequency_seconds";
+                type: "u64";
+              },
+              {
+                name: "recurring_amount_authorized";
+                type: "u64";
+              },
+              {
+                name: "num_cycles";
+                type: {
+                  option: "u64";
+                };
+              },
+              {
+                name: "reset_every_cycle";
+                type: "bool";
+              },
+              {
+                name: "max_amount_per_debit";
+                type: {
+                  option: "u64";
+                };
+              },
+              {
+                name: "min_amount_per_debit";
+                type: {
+                  option: "u64";
+                };
+              },
+              {
+                name: "max_accrued_cycles";
+                type: {
+                  option: "u64";
+                };
+              },
+              {
+                name: "calendar_schedule";
+                type: {
+                  option: {
+                    defined: "CalendarSchedule";
+                  };
+                };
+              },
+              {
+                name: "debit_window_seconds";
+                type: {
+                  option: "u64";
+                };
+              },
+              {
+                name: "debit_window_offset_seconds";
+                type: {
+                  option: "u64";
+                };
+              },
+              {
+                name: "intro_schedule";
+                type: {
+                  option: {
+                    defined: "IntroSchedule";
+                  };
+                };
+              },
+            ];
+          },
+          {
+            name: "Scheduled";
+            fields: [
+              {
+                name: "repeat_frequency_seconds";
+                type: "u64";
+              },
+              {
+                name: "steps";
+                type: {
+                  vec: {
+                    defined: "ScheduleStep";
+                  };
+                };
+              },
+              {
+                name: "num_cycles";
+                type: {
+                  option: "u64";
+                };
+              },
+            ];
+          },
+          {
+            name: "Installments";
+            fields: [
+              {
+                name: "total_amount";
+                type: "u64";
+              },
+              {
+                name: "installment_count";
+                type: "u64";
+              },
+              {
+                name: "interval_seconds";
+                type: "u64";
+              },
+              {
+                name: "grace_period_seconds";
+                type: "u64";
+              },
+            ];
+          },
+          {
+            name: "RollingWindow";
+            fields: [
+              {
+                name: "window_seconds";
+                type: "u64";
+              },
+              {
+                name: "amount_authorized_per_window";
+                type: "u64";
+              },
+            ];
+          },
+        ];
+      };
+    },
+    {
+      name: "UpdatePreAuthorizationVariant";
+      type: {
+        kind: "enum";
+        variants: [
+          {
+            name: "OneTime";
+            fields: [
+              {
+                name: "amount_authorized";
+                type: "u64";
+              },
+              {
+                name: "expiry_unix_timestamp";
+                type: "i64";
+              },
+            ];
+          },
+          {
+            name: "Recurring";
+            fields: [
+              {
+                name: "recurring_amount_authorized";
+                type: "u64";
+              },
+              {
+                name: "num_cycles";
+                type: {
+                  option: "u64";
+                };
+              },
+              {
+                name: "reset_every_cycle";
+                type: "bool";
+              },
+            ];
+          },
+        ];
+      };
+    },
+    {
+      name: "MathError";
+      type: {
+        kind: "enum";
+        variants: [
+          {
+            name: "ArithmeticOverflow";
+          },
+          {
+            name: "ArithmeticUnderflow";
+          },
+          {
+            name: "CurrentTimestampBeforeActivation";
+          },
+          {
+            name: "InvalidAmountDebited";
+          },
+          {
+            name: "InvalidCycle";
+          },
+          {
+            name: "InvalidDayOfMonth";
+          },
+          {
+            name: "InvalidRepeatFrequency";
+          },
+          {
+            name: "LastDebitedCycleBeforeCurrentCycle";
+          },
+        ];
+      };
+    },
+    {
+      name: "PreAuthorizationVariantV1";
+      type: {
+        kind: "enum";
+        variants: [
+          {
+            name: "OneTime";
+            fields: [
+              {
+                name: "amount_authorized";
+                type: "u64";
+              },
+              {
+                name: "expiry_unix_timestamp";
+                type: "i64";
+              },
+              {
+                name: "amount_debited";
+                type: "u64";
+              },
+            ];
+          },
+          {
+            name: "Recurring";
+            fields: [
+              {
+                name: "repeat_frequency_seconds";
+                type: "u64";
+              },
+              {
+                name: "recurring_amount_authorized";
+                type: "u64";
+              },
+              {
+                name: "amount_debited_last_cycle";
+                type: "u64";
+              },
+              {
+                name: "amount_debited_total";
+                type: "u64";
+              },
+              {
+                name: "last_debited_cycle";
+                type: "u64";
+              },
+              {
+                name: "num_cycles";
+                type: {
+                  option: "u64";
+                };
+              },
+              {
+                name: "reset_every_cycle";
+                type: "bool";
+              },
+            ];
+          },
+        ];
+      };
+    },
+    {
+      name: "PreAuthorizationVariant";
+      type: {
+        kind: "enum";
+        variants: [
+          {
+            name: "OneTime";
+            fields: [
+              {
+                name: "amount_authorized";
+                type: "u64";
+              },
+              {
+                name: "expiry_unix_timestamp";
+                type: "i64";
+              },
+              {
+                name: "amount_debited";
+                type: "u64";
+              },
+              {
+                name: "max_amount_per_debit";
+                type: {
+                  option: "u64";
+                };
+              },
+              {
+                name: "close_on_exhaustion";
+                type: "bool";
+              },
+            ];
+          },
+          {
+            name: "Recurring";
+            fields: [
+              {
+                name: "repeat_frequency_seconds";
+                type: "u64";
+              },
+              {
+                name: "recurring_amount_authorized";
+                type: "u64";
+              },
+              {
+                name: "amount_debited_last_cycle";
+                type: "u64";
+              },
+              {
+                name: "amount_debited_total";
+                type: "u64";
+              },
+              {
+                name: "last_debited_cycle";
+                type: "u64";
+              },
+              {
+                name: "num_cycles";
+                type: {
+                  option: "u64";
+                };
+              },
+              {
+                name: "reset_every_cycle";
+                type: "bool";
+              },
+              {
+                name: "amended_at_cycle";
+                type: "u64";
+              },
+              {
+                name: "amount_authorized_before_amendment";
+                type: "u64";
+              },
+              {
+                name: "max_amount_per_debit";
+                type: {
+                  option: "u64";
+                };
+              },
+              {
+                name: "min_amount_per_debit";
+                type: {
+                  option: "u64";
+                };
+              },
+              {
+                name: "max_accrued_cycles";
+                type: {
+                  option: "u64";
+                };
+              },
+              {
+                name: "calendar_schedule";
+                type: {
+                  option: {
+                    defined: "CalendarSchedule";
+                  };
+                };
+              },
+              {
+                name: "debit_window_seconds";
+                type: {
+                  option: "u64";
+                };
+              },
+              {
+                name: "debit_window_offset_seconds";
+                type: {
+                  option: "u64";
+                };
+              },
+              {
+                name: "intro_schedule";
+                type: {
+                  option: {
+                    defined: "IntroSchedule";
+                  };
+                };
+              },
+            ];
+          },
+          {
+            name: "Scheduled";
+            fields: [
+              {
+                name: "repeat_frequency_seconds";
+                type: "u64";
+              },
+              {
+                name: "steps";
+                type: {
+                  vec: {
+                    defined: "ScheduleStep";
+                  };
+                };
+              },
+              {
+                name: "amount_debited_last_cycle";
+                type: "u64";
+              },
+              {
+                name: "amount_debited_total";
+                type: "u64";
+              },
+              {
+                name: "last_debited_cycle";
+                type: "u64";
+              },
+              {
+                name: "num_cycles";
+                type: {
+                  option: "u64";
+                };
+              },
+            ];
+          },
+          {
+            name: "Installments";
+            fields: [
+              {
+                name: "total_amount";
+                type: "u64";
+              },
+              {
+                name: "installment_count";
+                type: "u64";
+              },
+              {
+                name: "interval_seconds";
+                type: "u64";
+              },
+              {
+                name: "grace_period_seconds";
+                type: "u64";
+              },
+              {
+                name: "amount_debited";
+                type: "u64";
+              },
+            ];
+          },
+          {
+            name: "RollingWindow";
+            fields: [
+              {
+                name: "window_seconds";
+                type: "u64";
+              },
+              {
+                name: "amount_authorized_per_window";
+                type: "u64";
+              },
+              {
+                name: "debited_buckets";
+                type: {
+                  array: [
+                    "u64",
+                    25,
+                  ];
+                };
+              },
+              {
+                name: "last_debited_bucket";
+                type: "u64";
+              },
+              {
+                name: "amount_debited_total";
+                type: "u64";
+              },
             ];
-            type: "bool";
+          },
+        ];
+      };
+    },
+    {
+      name: "PreAuthorizationScope";
+      type: {
+        kind: "enum";
+        variants: [
+          {
+            name: "TokenAccount";
+          },
+          {
+            name: "OwnerAndMint";
+            fields: [
+              {
+                name: "owner";
+                type: "publicKey";
+              },
+              {
+                name: "mint";
+                type: "publicKey";
+              },
+            ];
+          },
+        ];
+      };
+    },
+    {
+      name: "DestinationConstraint";
+      type: {
+        kind: "enum";
+        variants: [
+          {
+            name: "TokenAccount";
+            fields: [
+              {
+                name: "token_account";
+                type: "publicKey";
+              },
+            ];
+          },
+          {
+            name: "Owner";
+            fields: [
+              {
+                name: "owner";
+                type: "publicKey";
+              },
+            ];
+          },
+        ];
+      };
+    },
+  ];
+  events: [
+    {
+      name: "AmendmentAccepted";
+      fields: [
+        {
+          name: "owner";
+          type: "publicKey";
+          index: false;
+        },
+        {
+          name: "debitAuthority";
+          type: "publicKey";
+          index: false;
+        },
+        {
+          name: "tokenAccount";
+          type: "publicKey";
+          index: false;
+        },
+        {
+          name: "preAuthorization";
+          type: "publicKey";
+          index: false;
+        },
+        {
+          name: "pendingAmendment";
+          type: "publicKey";
+          index: false;
+        },
+        {
+          name: "oldValues";
+          type: {
+            defined: "UpdatePreAuthorizationVariant";
+          };
+          index: false;
+        },
+        {
+          name: "newValues";
+          type: {
+            defined: "UpdatePreAuthorizationVariant";
+          };
+          index: false;
+        },
+      ];
+    },
+    {
+      name: "DebitReceiptClosed";
+      fields: [
+        {
+          name: "closingAuthority";
+          type: "publicKey";
+          index: false;
+        },
+        {
+          name: "receiver";
+          type: "publicKey";
+          index: false;
+        },
+        {
+          name: "preAuthorization";
+          type: "publicKey";
+          index: false;
+        },
+        {
+          name: "debitReceipt";
+          type: "publicKey";
+          index: false;
+        },
+        {
+          name: "debitIndex";
+          type: "u64";
+          index: false;
+        },
+      ];
+    },
+    {
+      name: "ExpiredPreAuthorizationClosed";
+      fields: [
+        {
+          name: "data";
+          type: {
+            defined: "PreAuthorizationClosedEventData";
+          };
+          index: false;
+        },
+        {
+          name: "bountyReceiver";
+          type: {
+            option: "publicKey";
+          };
+          index: false;
+        },
+        {
+          name: "bountyLamports";
+          type: "u64";
+          index: false;
+        },
+      ];
+    },
+    {
+      name: "OwnerPreAuthorizationClosed";
+      fields: [
+        {
+          name: "data";
+          type: {
+            defined: "OwnerPreAuthorizationClosedEventData";
+          };
+          index: false;
+        },
+      ];
+    },
+    {
+      name: "OneTimePreAuthorizationClosed";
+      fields: [
+        {
+          name: "data";
+          type: {
+            defined: "PreAuthorizationClosedEventData";
+          };
+          index: false;
+        },
+      ];
+    },
+    {
+      name: "RecurringPreAuthorizationClosed";
+      fields: [
+        {
+          name: "data";
+          type: {
+            defined: "PreAuthorizationClosedEventData";
+          };
+          index: false;
+        },
+      ];
+    },
+    {
+      name: "ScheduledPreAuthorizationClosed";
+      fields: [
+        {
+          name: "data";
+          type: {
+            defined: "PreAuthorizationClosedEventData";
+          };
+          index: false;
+        },
+      ];
+    },
+    {
+      name: "InstallmentsPreAuthorizationClosed";
+      fields: [
+        {
+          name: "data";
+          type: {
+            defined: "PreAuthorizationClosedEventData";
+          };
+          index: false;
+        },
+      ];
+    },
+    {
+      name: "RollingWindowPreAuthorizationClosed";
+      fields: [
+        {
+          name: "data";
+          type: {
+            defined: "PreAuthorizationClosedEventData";
+          };
+          index: false;
+        },
+      ];
+    },
+    {
+      name: "DebitBatchProcessed";
+      fields: [
+        {
+          name: "debitAuthority";
+          type: "publicKey";
+          index: false;
+        },
+        {
+          name: "mint";
+          type: "publicKey";
+          index: false;
+        },
+        {
+          name: "numEntries";
+          type: "u16";
+          index: false;
+        },
+        {
+          name: "failedEntries";
+          type: {
+            vec: {
+              defined: "DebitBatchFailedEntry";
+            };
+          };
+          index: false;
+        },
+      ];
+    },
+    {
+      name: "DebitEvent";
+      fields: [
+        {
+          name: "preAuthorization";
+          type: "publicKey";
+          index: false;
+        },
+        {
+          name: "debitAuthority";
+          type: "publicKey";
+          index: false;
+        },
+        {
+          name: "smartDelegate";
+          type: "publicKey";
+          index: false;
+        },
+        {
+          name: "mint";
+          type: "publicKey";
+          index: false;
+        },
+        {
+          name: "tokenProgram";
+          type: "publicKey";
+          index: false;
+        },
+        {
+          name: "sourceTokenAccountOwner";
+          type: "publicKey";
+          index: false;
+        },
+        {
+          name: "destinationTokenAccountOwner";
+          type: "publicKey";
+          index: false;
+        },
+        {
+          name: "sourceTokenAccount";
+          type: "publicKey";
+          index: false;
+        },
+        {
+          name: "destinationTokenAccount";
+          type: "publicKey";
+          index: false;
+        },
+        {
+          name: "requestedAmount";
+          type: "u64";
+          index: false;
+        },
+        {
+          name: "reference";
+          type: {
+            option: {
+              array: [
+                "u8",
+                32,
+              ];
+            };
+          };
+          index: false;
+        },
+        {
+          name: "debitReceipt";
+          type: {
+            option: "publicKey";
+          };
+          index: false;
+        },
+        {
+          name: "debitVariant";
+          type: {
+            defined: "DebitEventVariant";
+          };
+          index: false;
+        },
+      ];
+    },
+    {
+      name: "OwnerPreAuthorizationCreated";
+      fields: [
+        {
+          name: "data";
+          type: {
+            defined: "OwnerPreAuthorizationCreatedEventData";
+          };
+          index: false;
+        },
+      ];
+    },
+    {
+      name: "PreAuthorizationCounterInitialized";
+      fields: [
+        {
+          name: "owner";
+          type: "publicKey";
+          index: false;
+        },
+        {
+          name: "tokenAccount";
+          type: "publicKey";
+          index: false;
+        },
+        {
+          name: "preAuthorizationCounter";
+          type: "publicKey";
+          index: false;
+        },
+        {
+          name: "oldCount";
+          type: "u64";
+          index: false;
+        },
+        {
+          name: "count";
+          type: "u64";
+          index: false;
+        },
+      ];
+    },
+    {
+      name: "OneTimePreAuthorizationCreated";
+      fields: [
+        {
+          name: "data";
+          type: {
+            defined: "PreAuthorizationCreatedEventData";
+          };
+          index: false;
+        },
+      ];
+    },
+    {
+      name: "RecurringPreAuthorizationCreated";
+      fields: [
+        {
+          name: "data";
+          type: {
+            defined: "PreAuthorizationCreatedEventData";
+          };
+          index: false;
+        },
+      ];
+    },
+    {
+      name: "ScheduledPreAuthorizationCreated";
+      fields: [
+        {
+          name: "data";
+          type: {
+            defined: "PreAuthorizationCreatedEventData";
+          };
+          index: false;
+        },
+      ];
+    },
+    {
+      name: "InstallmentsPreAuthorizationCreated";
+      fields: [
+        {
+          name: "data";
+          type: {
+            defined: "PreAuthorizationCreatedEventData";
+          };
+          index: false;
+        },
+      ];
+    },
+    {
+      name: "RollingWindowPreAuthorizationCreated";
+      fields: [
+        {
+          name: "data";
+          type: {
+            defined: "PreAuthorizationCreatedEventData";
+          };
+          index: false;
+        },
+      ];
+    },
+    {
+      name: "SmartDelegateInitialized";
+      fields: [
+        {
+          name: "payer";
+          type: "publicKey";
+          index: false;
+        },
+        {
+          name: "smartDelegate";
+          type: "publicKey";
+          index: false;
+        },
+      ];
+    },
+    {
+      name: "PreAuthorizationMigrated";
+      fields: [
+        {
+          name: "payer";
+          type: "publicKey";
+          index: false;
+        },
+        {
+          name: "tokenAccount";
+          type: "publicKey";
+          index: false;
+        },
+        {
+          name: "debitAuthority";
+          type: "publicKey";
+          index: false;
+        },
+        {
+          name: "preAuthorization";
+          type: "publicKey";
+          index: false;
+        },
+      ];
+    },
+    {
+      name: "AmendmentProposed";
+      fields: [
+        {
+          name: "payer";
+          type: "publicKey";
+          index: false;
+        },
+        {
+          name: "debitAuthority";
+          type: "publicKey";
+          index: false;
+        },
+        {
+          name: "tokenAccount";
+          type: "publicKey";
+          index: false;
+        },
+        {
+          name: "preAuthorization";
+          type: "publicKey";
+          index: false;
+        },
+        {
+          name: "pendingAmendment";
+          type: "publicKey";
+          index: false;
+        },
+        {
+          name: "newValues";
+          type: {
+            defined: "UpdatePreAuthorizationVariant";
+          };
+          index: false;
+        },
+        {
+          name: "acceptanceDeadlineUnixTimestamp";
+          type: "i64";
+          index: false;
+        },
+      ];
+    },
+    {
+      name: "SmartDelegateReapproved";
+      fields: [
+        {
+          name: "owner";
+          type: "publicKey";
+          index: false;
+        },
+        {
+          name: "tokenAccount";
+          type: "publicKey";
+          index: false;
+        },
+        {
+          name: "smartDelegate";
+          type: "publicKey";
+          index: false;
+        },
+        {
+          name: "delegatedAmount";
+          type: "u64";
+          index: false;
+        },
+      ];
+    },
+    {
+      name: "AmendmentRejected";
+      fields: [
+        {
+          name: "rejectingAuthority";
+          type: "publicKey";
+          index: false;
+        },
+        {
+          name: "debitAuthority";
+          type: "publicKey";
+          index: false;
+        },
+        {
+          name: "tokenAccount";
+          type: "publicKey";
+          index: false;
+        },
+        {
+          name: "preAuthorization";
+          type: "publicKey";
+          index: false;
+        },
+        {
+          name: "pendingAmendment";
+          type: "publicKey";
+          index: false;
+        },
+        {
+          name: "receiver";
+          type: "publicKey";
+          index: false;
+        },
+      ];
+    },
+    {
+      name: "DebitAuthorityRotated";
+      fields: [
+        {
+          name: "data";
+          type: {
+            defined: "DebitAuthorityRotatedEventData";
+          };
+          index: false;
+        },
+      ];
+    },
+    {
+      name: "PreAuthorizationPaused";
+      fields: [
+        {
+          name: "data";
+          type: {
+            defined: "PausePreAuthorizationEventData";
+          };
+          index: false;
+        },
+      ];
+    },
+    {
+      name: "PreAuthorizationUnpaused";
+      fields: [
+        {
+          name: "data";
+          type: {
+            defined: "PausePreAuthorizationEventData";
+          };
+          index: false;
+        },
+      ];
+    },
+    {
+      name: "PreAuthorizationUpdated";
+      fields: [
+        {
+          name: "data";
+          type: {
+            defined: "PreAuthorizationUpdatedEventData";
+          };
+          index: false;
+        },
+      ];
+    },
+  ];
+  errors: [
+    {
+      code: 6000;
+      name: "PreAuthorizationNotActive";
+      msg: "Pre-Authorization not active";
+    },
+    {
+      code: 6001;
+      name: "CannotDebitMoreThanAvailable";
+      msg: "Cannot debit more than authorized";
+    },
+    {
+      code: 6002;
+      name: "LastDebitedCycleBeforeCurrentCycle";
+      msg: "Last debited cycle is after current debited cycle (invalid state)";
+    },
+    {
+      code: 6003;
+      name: "InvalidTimestamp";
+      msg: "Invalid timestamp value provided";
+    },
+    {
+      code: 6004;
+      name: "PreAuthorizationPaused";
+      msg: "Pre-Authorization paused";
+    },
+    {
+      code: 6005;
+      name: "OnlyTokenAccountOwnerCanReceiveClosePreAuthFunds";
+      msg: "Only token account owner can receive funds from closing pre-authorization account";
+    },
+    {
+      code: 6006;
+      name: "PreAuthorizationTokenAccountMismatch";
+      msg: "Pre-authorization and token account mismatch";
+    },
+    {
+      code: 6007;
+      name: "PreAuthorizationCloseUnauthorized";
+      msg: "Pre-authorization can only be closed by debit_authority or token_account.owner";
+    },
+    {
+      code: 6008;
+      name: "SmartDelegateCloseUnauthorized";
+      msg: "Smart delegate can only be closed by token account owner";
+    },
+    {
+      code: 6009;
+      name: "PausePreAuthorizationUnauthorized";
+      msg: "Only token account owner can pause a pre-authorization";
+    },
+    {
+      code: 6010;
+      name: "DebitUnauthorized";
+      msg: "Only pre_authorization.debit_authority is authorized to debit funds using pre-authorizations";
+    },
+    {
+      code: 6011;
+      name: "InitPreAuthorizationUnauthorized";
+      msg: "Only token account owner can initialize a pre-authorization";
+    },
+    {
+      code: 6012;
+      name: "InitSmartDelegateUnauthorized";
+      msg: "Only token account owner can initialize a smart delegate";
+    },
+    {
+      code: 6013;
+      name: "ArithmeticOverflow";
+      msg: "Arithmetic overflow";
+    },
+    {
+      code: 6014;
+      name: "ArithmeticUnderflow";
+      msg: "Arithmetic underflow";
+    },
+    {
+      code: 6015;
+      name: "CurrentTimestampBeforeActivation";
+      msg: "Current timestamp is before the pre-authorization activation timestamp";
+    },
+    {
+      code: 6016;
+      name: "InvalidRepeatFrequency";
+      msg: "Repeat frequency seconds must be greater than 0";
+    },
+    {
+      code: 6017;
+      name: "InvalidCycle";
+      msg: "Recurring cycles start at 1 (invalid state)";
+    },
+    {
+      code: 6018;
+      name: "InvalidAmountDebited";
+      msg: "Amount debited in the last cycle is greater than the total amount debited (invalid state)";
+    },
+    {
+      code: 6019;
+      name: "ExpiryBeforeActivation";
+      msg: "Pre-authorization expiry timestamp must be after its activation timestamp";
+    },
+    {
+      code: 6020;
+      name: "InvalidNumCycles";
+      msg: "Number of cycles must be greater than 0 and the last cycle must end before i64::MAX";
+    },
+    {
+      code: 6021;
+      name: "TotalAmountAuthorizedOverflow";
+      msg: "Recurring amount authorized across all cycles overflows u64";
+    },
+    {
+      code: 6022;
+      name: "UpdatePreAuthorizationUnauthorized";
+      msg: "Only token account owner can update a pre-authorization";
+    },
+    {
+      code: 6023;
+      name: "PreAuthorizationVariantMismatch";
+      msg: "Pre-authorization variant cannot be changed";
+    },
+    {
+      code: 6024;
+      name: "AmountAuthorizedBelowAmountDebited";
+      msg: "Amount authorized cannot be less than the amount already debited";
+    },
+    {
+      code: 6025;
+      name: "ProposeAmendmentUnauthorized";
+      msg: "Only pre_authorization.debit_authority can propose an amendment";
+    },
+    {
+      code: 6026;
+      name: "AcceptAmendmentUnauthorized";
+      msg: "Only token account owner can accept an amendment";
+    },
+    {
+      code: 6027;
+      name: "RejectAmendmentUnauthorized";
+      msg: "Amendment can only be rejected by token_account.owner or debit_authority";
+    },
+    {
+      code: 6028;
+      name: "OnlyAmendmentPayerCanReceiveFunds";
+      msg: "Only the amendment payer can receive funds from closing the pending amendment account";
+    },
+    {
+      code: 6029;
+      name: "InvalidAcceptanceDeadline";
+      msg: "Amendment acceptance deadline must be in the future";
+    },
+    {
+      code: 6030;
+      name: "AmendmentExpired";
+      msg: "Amendment acceptance deadline has passed";
+    },
+    {
+      code: 6031;
+      name: "AmendmentMismatch";
+      msg: "Amendment terms do not match the expected terms";
+    },
+    {
+      code: 6032;
+      name: "RotateDebitAuthorityUnauthorized";
+      msg: "Only pre_authorization.debit_authority can rotate the debit authority";
+    },
+    {
+      code: 6033;
+      name: "OwnerSignatureRequiredForRotation";
+      msg: "Token account owner signature is required to rotate the debit authority";
+    },
+    {
+      code: 6034;
+      name: "NewDebitAuthorityMatchesCurrent";
+      msg: "New debit authority must be different from the current debit authority";
+    },
+    {
+      code: 6035;
+      name: "PendingAmendmentExists";
+      msg: "Pre-authorization has a pending amendment";
+    },
+    {
+      code: 6036;
+      name: "DestinationNotAllowed";
+      msg: "Destination token account is not allowed by the pre-authorization";
+    },
+    {
+      code: 6037;
+      name: "DebitAmountAboveMaximum";
+      msg: "Debit amount is above the maximum amount per debit";
+    },
+    {
+      code: 6038;
+      name: "DebitAmountBelowMinimum";
+      msg: "Debit amount is below the minimum amount per debit";
+    },
+    {
+      code: 6039;
+      name: "InvalidAmountPerDebitBounds";
+      msg: "Invalid minimum or maximum amount per debit";
+    },
+    {
+      code: 6040;
+      name: "InvalidMaxAccruedCycles";
+      msg: "Max accrued cycles must be greater than 0";
+    },
+    {
+      code: 6041;
+      name: "InvalidDayOfMonth";
+      msg: "Day of month must be between 1 and 31";
+    },
+    {
+      code: 6042;
+      name: "OutsideDebitWindow";
+      msg: "Debit is outside of the debit window of the current cycle";
+    },
+    {
+      code: 6043;
+      name: "InvalidDebitWindow";
+      msg: "Debit window must be non-empty and start within the shortest cycle";
+    },
+    {
+      code: 6044;
+      name: "InvalidIntroSchedule";
+      msg: "Intro schedule must have at least one intro cycle";
+    },
+    {
+      code: 6045;
+      name: "InvalidScheduleSteps";
+      msg: "Schedule steps must start at cycle 1 and be strictly increasing (at most 12 steps)";
+    },
+    {
+      code: 6046;
+      name: "InvalidRollingWindow";
+      msg: "Rolling window must be a non-zero multiple of the number of buckets";
+    },
+    {
+      code: 6047;
+      name: "ReceiverRequiredForCloseOnExhaustion";
+      msg: "Receiver is required to close an exhausted one-time pre-authorization";
+    },
+    {
+      code: 6048;
+      name: "PreAuthorizationNotExpired";
+      msg: "Pre-authorization can still be debited";
+    },
+    {
+      code: 6049;
+      name: "PreAuthorizationCounterRequired";
+      msg: "Pre-authorization counter of the token account is required";
+    },
+    {
+      code: 6050;
+      name: "DelegatedAmountExhausted";
+      msg: "Delegated amount of the smart delegate is below the debit amount (exhausted or changed outside of the program)";
+    },
+    {
+      code: 6051;
+      name: "DelegationModeMismatch";
+      msg: "Bounded delegation mode must match the other pre-authorizations of the token account";
+    },
+    {
+      code: 6052;
+      name: "UnboundedPreAuthorization";
+      msg: "Bounded delegation requires a pre-authorization with a bounded amount authorized";
+    },
+    {
+      code: 6053;
+      name: "BoundedDelegationNotSupported";
+      msg: "Bounded delegation is only supported for pre-authorizations scoped to a token account";
+    },
+    {
+      code: 6054;
+      name: "SmartDelegateNotApproved";
+      msg: "Smart delegate is not the delegate of the token account (approve it with reapprove_smart_delegate)";
+    },
+    {
+      code: 6055;
+      name: "ReapproveSmartDelegateUnauthorized";
+      msg: "Only token account owner can reapprove the smart delegate";
+    },
+    {
+      code: 6056;
+      name: "MissingPreAuthorizations";
+      msg: "All the open pre-authorizations of the token account must be provided once";
+    },
+    {
+      code: 6057;
+      name: "InvalidDebitBatch";
+      msg: "Debit batch must have 1 to 65535 entries of 3 distinct writable remaining accounts, one per pre-authorization";
+    },
+    {
+      code: 6058;
+      name: "MintMismatch";
+      msg: "Token account mint does not match the mint";
+    },
+    {
+      code: 6059;
+      name: "InsufficientTokenAccountBalance";
+      msg: "Token account balance is below the debit amount";
+    },
+    {
+      code: 6060;
+      name: "NothingToDebit";
+      msg: "Nothing can be debited from the token account";
+    },
+    {
+      code: 6061;
+      name: "InvalidMemoProgram";
+      msg: "Memo program must be the SPL Memo program";
+    },
+    {
+      code: 6062;
+      name: "ReferenceRequiredForMemo";
+      msg: "Reference is required to log a memo";
+    },
+    {
+      code: 6063;
+      name: "ReceiptPayerRequired";
+      msg: "Receipt payer is required to create a debit receipt";
+    },
+    {
+      code: 6064;
+      name: "CloseDebitReceiptUnauthorized";
+      msg: "Debit receipt can only be closed by its payer or debit authority";
+    },
+    {
+      code: 6065;
+      name: "OnlyDebitReceiptPayerCanReceiveFunds";
+      msg: "Only the debit receipt payer can receive the lamports of a closed debit receipt";
+    },
+    {
+      code: 6066;
+      name: "DebitReceiptRetentionPeriodNotOver";
+      msg: "Debit receipt retention period is not over";
+    },
+    {
+      code: 6067;
+      name: "PreAuthorizationCounterNotVerified";
+      msg: "Pre-authorization counter count must be set by the token account owner first (init_pre_authorization_counter)";
+    },
+    {
+      code: 6068;
+      name: "InitPreAuthorizationCounterUnauthorized";
+      msg: "Only token account owner can initialize the pre-authorization counter";
+    },
+    {
+      code: 6069;
+      name: "OwnerScopedDebitInBoundedDelegation";
+      msg: "Owner scoped pre-authorizations cannot debit a token account in bounded delegation mode";
+    },
+    {
+      code: 6070;
+      name: "PreAuthorizationCounterAlreadyVerified";
+      msg: "Pre-authorization counter count is already verified";
+    },
+    {
+      code: 6071;
+      name: "PreAuthorizationCounterInBoundedDelegation";
+      msg: "Pre-authorization counter count cannot be set in bounded delegation mode";
+    },
+    {
+      code: 6072;
+      name: "PreAuthorizationNotV1";
+      msg: "Pre-authorization is not a v1.0.0 pre-authorization";
+    },
+  ];
+};
+
+export const IDL: PreAuthorizedDebitV1 = {
+  version: "2.0.0",
+  name: "pre_authorized_debit_v1",
+  instructions: [
+    {
+      name: "initSmartDelegate",
+      docs: [
+        "The `InitSmartDelegate` instruction will create a global `smart_delegate` account.\n\n    Initializes a new account (`smart_delegate`).\n    The `smart_delegate` PDA is used by the `pre_authorized_debit` program to sign for\n    valid pre-authorized debits to transfer funds from the `pre_authorization.token_account`.\n    The `smart_delegate` account can NEVER be closed.\n\n    The `payer` MUST sign the transaction.\n    The `payer` MUST have enough lamports to pay for the `smart_delegate` account.\n\n    Accounts expected by this instruction:\n        0. `[writable]` payer\n        1. `[writable]` smart_delegate\n        2. `[]`         system_program",
+      ],
+      accounts: [
+        {
+          name: "payer",
+          isMut: true,
+          isSigner: true,
+        },
+        {
+          name: "smartDelegate",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "systemProgram",
+          isMut: false,
+          isSigner: false,
+        },
+      ],
+      args: [],
+    },
+    {
+      name: "initPreAuthorization",
+      docs: [
+        "The `InitPreAuthorization` instruction will create a `pre_authorization` account.\n\n    Initializes a new account (`pre_authorization`).\n    The `pre_authorization` defines a set of rules.\n    The `pre_authorization` rules/constraints are verified during a `debit` instruction.\n    The `pre_authorization` in conjunction with a `smart_delegate` for the same `token_account`\n    can allow the `pre_authorization.debit_authority` to do a one-time, recurring, scheduled, installments\n    or rolling window debit from the `token_account.\n    For a pair of `debit_authority` and `token_account`, only a single `pre_authorization` account can exist.\n    To create another `pre_authorization` for the same `token_account`, another `debit_authority` must be used.\n\n    The `payer` MUST sign the transaction.\n    The `payer` MUST have enough lamports to pay for the `pre_authorization` account.\n    The `owner` MUST sign the transaction.\n    The `owner` MUST be the `token_account.owner`.\n    The `payer` and `owner` may be the same account.\n    The `token_account.owner` MUST be the `owner`.\n    The `pre_authorization.token_account` must be the same as `token_account`.\n    If `rotation_requires_owner_signature` is set, the `owner` MUST co-sign any `rotate_debit_authority`.\n    The `max_amount_per_debit` (if set) MUST be greater than 0.\n    For a recurring pre-authorization, the `min_amount_per_debit` (if set) MUST NOT be greater than\n    the `max_amount_per_debit` (if set) and the `max_accrued_cycles` (if set) MUST be greater than 0.\n    If `destination_constraint` is set, the `debit` destination MUST be the given token account\n    (or a token account owned by the given owner).\n    For a one-time pre-authorization, the `expiry_unix_timestamp` MUST be after the `activation_unix_timestamp`.\n    For a one-time pre-authorization with `close_on_exhaustion` set, the `debit` that exhausts the amount authorized\n    also closes the `pre_authorization`.\n    For a recurring pre-authorization, the `repeat_frequency_seconds` MUST be greater than 0 and at most i64::MAX.\n    For a recurring pre-authorization with a `calendar_schedule` (monthly, quarterly or yearly cycles anchored to\n    a UTC day of month), the `repeat_frequency_seconds` MUST be 0 and the `day_of_month` MUST be in [1, 31].\n    For a recurring pre-authorization, the `debit_window_seconds` (if set) MUST be greater than 0 and the\n    `debit_window_offset_seconds` (if set) MUST be less than the shortest cycle,\n    the `debit_window_offset_seconds` MUST NOT be set without the `debit_window_seconds`.\n    For a recurring pre-authorization, the `intro_schedule` (if set) authorizes the `intro_amount_authorized`\n    (instead of the `recurring_amount_authorized`) in each of the first `num_intro_cycles` cycles,\n    the `num_intro_cycles` MUST be greater than 0.\n    For a recurring pre-authorization, the `num_cycles` (if set) MUST be greater than 0 and\n    the total amount authorized across the `num_cycles` MUST fit in a u64.\n    For a scheduled pre-authorization (a list of `steps`, each authorizing an amount per cycle from its\n    `cycle_start` onwards), the `repeat_frequency_seconds` MUST be greater than 0 and at most i64::MAX,\n    there MUST be 1 to 12 `steps`, the first step MUST start at cycle 1 and the `cycle_start` of the steps\n    MUST be strictly increasing.\n    For a scheduled pre-authorization, the `num_cycles` (if set) MUST be greater than 0, the last step MUST start\n    within the `num_cycles` and the total amount authorized across the `num_cycles` MUST fit in a u64.\n    For an installments pre-authorization (`total_amount` split into `installment_count` equal installments due every\n    `interval_seconds` from activation), the `interval_seconds` MUST be greater than 0 and at most i64::MAX,\n    the `grace_period_seconds` MUST be at most i64::MAX, the `installment_count` MUST be greater than 0\n    and the last installment MUST be due before i64::MAX.\n    For a rolling window pre-authorization (at most `amount_authorized_per_window` in any trailing `window_seconds`),\n    the `window_seconds` MUST be a non-zero multiple of 24 (the number of buckets the window is tracked in)\n    and at most i64::MAX.\n    The `pre_authorization_counter` of the `token_account` is created if it does not exist yet\n    and counts the open `pre_authorization` accounts of the `token_account`. A counter created here\n    cannot know about the `pre_authorization` accounts created before it, its count is unverified until the\n    `token_account.owner` sets it in `init_pre_authorization_counter`.\n    The `smart_delegate` is approved for `u64::MAX`, unless `bounded_delegation` is set: the `smart_delegate`\n    is then approved for the sum of the outstanding amounts authorized (total amount authorized minus the\n    amount debited) of the open `pre_authorization` accounts of the `token_account`.\n    The `bounded_delegation` MUST match the other open `pre_authorization` accounts of the `token_account`.\n    With `bounded_delegation`, the `pre_authorization_counter` count MUST be verified and the amount authorized\n    MUST be bounded (i.e. `num_cycles` MUST be set for a recurring or scheduled pre-authorization and it cannot\n    be a rolling window pre-authorization).\n    The approval can be above that sum when the outstanding amount authorized decreases without the\n    `token_account.owner` signature (required by the token program to lower it): a `pre_authorization` closed\n    by the `debit_authority` or in `close_expired_pre_authorization`, or an amount accrued above\n    `max_accrued_cycles` forfeited in a debit. It is approved for the exact sum again when the\n    `token_account.owner` closes a `pre_authorization` or calls `reapprove_smart_delegate`.\n\n    Accounts expected by this instruction:\n        0. `[writable]` payer\n        1. `[]`         owner\n        2. `[]`         smart_delegate\n        3. `[writable]` token_account\n        4. `[writable]` pre_authorization\n        5. `[writable]` pre_authorization_counter\n        6. `[]`         token_program\n        7. `[]`         system_program",
+      ],
+      accounts: [
+        {
+          name: "payer",
+          isMut: true,
+          isSigner: true,
+        },
+        {
+          name: "owner",
+          isMut: false,
+          isSigner: true,
+        },
+        {
+          name: "smartDelegate",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "tokenAccount",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "preAuthorization",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "preAuthorizationCounter",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "tokenProgram",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "systemProgram",
+          isMut: false,
+          isSigner: false,
+        },
+      ],
+      args: [
+        {
+          name: "params",
+          type: {
+            defined: "InitPreAuthorizationParams",
+          },
+        },
+      ],
+    },
+    {
+      name: "initPreAuthorizationCounter",
+      docs: [
+        "The `InitPreAuthorizationCounter` instruction allows a `token_account.owner` to set the number of open\n    `pre_authorization` accounts scoped to the `token_account` (e.g. the ones created before the\n    `pre_authorization_counter` existed), the count is then verified.\n\n    Initializes the `pre_authorization_counter` account if it does not exist yet, derived with the seeds:\n    ['pre-authorization-counter', token_account].\n    Once the count is verified, the `smart_delegate` approval is revoked when the last `pre_authorization`\n    is closed by the `token_account.owner` and bounded delegation can be used.\n    The `count` MUST be the number of open `pre_authorization` accounts scoped to the `token_account`\n    (a lower count revokes the `smart_delegate` approval early, a higher one never revokes it).\n    The count can only be set once: the `pre_authorization_counter` MUST NOT be verified yet\n    (nor be in bounded delegation mode).\n\n    The `payer` MUST sign the transaction.\n    The `owner` MUST sign the transaction.\n    The `owner` MUST equal the `token_account.owner`.\n\n    Accounts expected by this instruction:\n        0. `[writable]` payer\n        1. `[]`         owner\n        2. `[]`         token_account\n        3. `[writable]` pre_authorization_counter\n        4. `[]`         system_program",
+      ],
+      accounts: [
+        {
+          name: "payer",
+          isMut: true,
+          isSigner: true,
+        },
+        {
+          name: "owner",
+          isMut: false,
+          isSigner: true,
+        },
+        {
+          name: "tokenAccount",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "preAuthorizationCounter",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "systemProgram",
+          isMut: false,
+          isSigner: false,
+        },
+      ],
+      args: [
+        {
+          name: "params",
+          type: {
+            defined: "InitPreAuthorizationCounterParams",
+          },
+        },
+      ],
+    },
+    {
+      name: "closePreAuthorization",
+      docs: [
+        "The `ClosePreAuthorization` instruction will close a `pre_authorization` account.\n\n    Closes an existing `pre_authorization` account and refunds the lamports\n    to the `token_account.owner` (`receiver`).\n\n    The `receiver` will receive all lamports from the closed account.\n    The `receiver` MUST be the `token_account.owner`.\n    The `authority` MUST sign for the instruction.\n    The `authority` MUST be either the `token_account.owner` or the `pre_authorization.debit_authority`.\n    The `owner` MUST be the `token_account.owner`.\n    The `token_account.owner` MUST be the `owner`.\n    The `pre_authorization.token_account` must be the same as `token_account`.\n    The `pre_authorization_counter` of the `token_account` MUST be set, it can be uninitialized\n    (if initialized, it is decremented).\n    If this is the last open `pre_authorization` of the `token_account` (see `pre_authorization_counter`,\n    its count MUST be verified), the `authority` is the `token_account.owner` and the `token_account.delegate`\n    is the `smart_delegate`, the `smart_delegate` approval is revoked.\n    Otherwise, in bounded delegation mode, the `smart_delegate` is re-approved for the sum of the outstanding\n    amounts authorized of the other open `pre_authorization` accounts if the `authority` is the\n    `token_account.owner` and the `token_account.delegate` is the `smart_delegate`, the remaining accounts MUST then\n    be all the other open `pre_authorization` accounts of the `token_account` (each one exactly once).\n\n    Accounts expected by this instruction:\n        0. `[writable]` receiver\n        1. `[]`         authority\n        2. `[writable]` token_account\n        3. `[]`         smart_delegate\n        4. `[writable]` pre_authorization\n        5. `[writable]` pre_authorization_counter (optional)\n        6. `[]`         token_program\n        7.. `[]`        pre_authorization (remaining accounts, bounded delegation mode only)",
+      ],
+      accounts: [
+        {
+          name: "receiver",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "authority",
+          isMut: false,
+          isSigner: true,
+        },
+        {
+          name: "tokenAccount",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "smartDelegate",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "preAuthorization",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "preAuthorizationCounter",
+          isMut: true,
+          isSigner: false,
+          isOptional: true,
+        },
+        {
+          name: "tokenProgram",
+          isMut: false,
+          isSigner: false,
+        },
+      ],
+      args: [],
+    },
+    {
+      name: "closeExpiredPreAuthorization",
+      docs: [
+        "The `CloseExpiredPreAuthorization` instruction will close a `pre_authorization` account\n    that can never be debited again. Anyone can call it.\n\n    Closes an expired `pre_authorization` account and refunds the lamports\n    to the `token_account.owner` (`receiver`), minus a bounty of 5000 lamports if a `bounty_receiver` is set.\n\n    A `pre_authorization` is expired if:\n    - one-time: the validator time is at or after the `expiry_unix_timestamp`\n    - recurring or scheduled: `num_cycles` is set and the current cycle is after the last cycle\n    - installments: all installments have been paid\n    A rolling window `pre_authorization` never expires.\n\n    The `caller` MUST sign for the instruction.\n    The `receiver` MUST be the `token_account.owner`.\n    The `bounty_receiver` (if set) receives the bounty, it can be any account (e.g. the `caller`).\n    The `pre_authorization.token_account` must be the same as `token_account`\n    (for an owner scoped `pre_authorization`, the `token_account.owner` and `token_account.mint`\n    MUST equal the `owner` and `mint` of the `pre_authorization.scope` instead).\n    The `smart_delegate` approval is never revoked here, nor lowered in bounded delegation mode\n    (the `token_account.owner` has to sign for it, see `reapprove_smart_delegate`).\n    The `pre_authorization_counter` of the `token_account` MUST be set unless the `pre_authorization` is owner scoped,\n    it can be uninitialized (if initialized, it is decremented, unless the `pre_authorization` is owner scoped).\n\n    Accounts expected by this instruction:\n        0. `[]`         caller\n        1. `[writable]` receiver\n        2. `[writable]` bounty_receiver (optional)\n        3. `[]`         token_account\n        4. `[writable]` pre_authorization\n        5. `[writable]` pre_authorization_counter (optional)",
+      ],
+      accounts: [
+        {
+          name: "caller",
+          isMut: false,
+          isSigner: true,
+        },
+        {
+          name: "receiver",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "bountyReceiver",
+          isMut: true,
+          isSigner: false,
+          isOptional: true,
+        },
+        {
+          name: "tokenAccount",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "preAuthorization",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "preAuthorizationCounter",
+          isMut: true,
+          isSigner: false,
+          isOptional: true,
+        },
+      ],
+      args: [],
+    },
+    {
+      name: "initOwnerPreAuthorization",
+      docs: [
+        "The `InitOwnerPreAuthorization` instruction will create a `pre_authorization` account scoped to\n    an `owner` and a `mint` (instead of a single `token_account`).\n\n    Initializes a new account (`pre_authorization`) derived with the seeds:\n    ['pre-authorization', owner, mint, debit_authority].\n    The `pre_authorization.debit_authority` can debit from any token account owned by the `owner`\n    with the `mint` (and delegated to the `smart_delegate`), the amounts authorized are tracked once\n    across all these token accounts.\n    Unlike `init_pre_authorization`, no token account is approved by this instruction.\n    The same `params` rules as in `init_pre_authorization` apply.\n    Only `debit` and `close_owner_pre_authorization` support owner scoped pre-authorizations.\n\n    The `payer` MUST sign the transaction.\n    The `payer` MUST have enough lamports to pay for the `pre_authorization` account.\n    The `owner` MUST sign the transaction.\n    The `payer` and `owner` may be the same account.\n    The `bounded_delegation` MUST NOT be set (the `owner` approves the `smart_delegate` on each token account).\n\n    Accounts expected by this instruction:\n        0. `[writable]` payer\n        1. `[]`         owner\n        2. `[]`         mint\n        3. `[writable]` pre_authorization\n        4. `[]`         system_program",
+      ],
+      accounts: [
+        {
+          name: "payer",
+          isMut: true,
+          isSigner: true,
+        },
+        {
+          name: "owner",
+          isMut: false,
+          isSigner: true,
+        },
+        {
+          name: "mint",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "preAuthorization",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "systemProgram",
+          isMut: false,
+          isSigner: false,
+        },
+      ],
+      args: [
+        {
+          name: "params",
+          type: {
+            defined: "InitPreAuthorizationParams",
+          },
+        },
+      ],
+    },
+    {
+      name: "closeOwnerPreAuthorization",
+      docs: [
+        "The `CloseOwnerPreAuthorization` instruction will close a `pre_authorization` account\n    created with `init_owner_pre_authorization`.\n\n    The `receiver` will receive all lamports from the closed account.\n    The `receiver` MUST be the `owner` unless the `authority` is the `owner`.\n    The `authority` MUST sign for the instruction.\n    The `authority` MUST be either the `owner` or the `pre_authorization.debit_authority`.\n    The `owner` and `mint` MUST be the ones the `pre_authorization` was created with.\n\n    Accounts expected by this instruction:\n        0. `[writable]` receiver\n        1. `[]`         authority\n        2. `[]`         owner\n        3. `[]`         mint\n        4. `[writable]` pre_authorization",
+      ],
+      accounts: [
+        {
+          name: "receiver",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "authority",
+          isMut: false,
+          isSigner: true,
+        },
+        {
+          name: "owner",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "mint",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "preAuthorization",
+          isMut: true,
+          isSigner: false,
+        },
+      ],
+      args: [],
+    },
+    {
+      name: "debit",
+      docs: [
+        "The `Debit` instruction allows a `pre_authorization.debit_authority` to debit from the\n    `pre_authorization.token_account` via the `smart_delegate` PDA. To successfully debit from\n    the `token_account`, the constraints for the `pre_authorization` must be met.\n\n    Definitions:\n      - PA = pre_authorization\n\n    Common Rules:\n    - The `pre_authorization` MUST not be paused.\n    - The validator time must be greater than or equal to the `PA.activation_unix_timestamp`\n    - The amount being requested to debit must be less than or equal to the available amount for the current_cycle\n      (see the rules of each variant below, several debits can occur in the same cycle)\n    - The `token_account.delegate` MUST be the `smart_delegate` (see `reapprove_smart_delegate`)\n    - The amount being requested to debit must be less than or equal to the `token_account.delegated_amount`\n      (in bounded delegation mode, it decreases with each debit along with the outstanding amount authorized)\n    - If the PA has a `num_cycles` defined, the `current_cycle` must be less than or equal to `PA.num_cycles`\n    - If the PA has a `destination_constraint` defined, the `destination_token_account` must satisfy it\n    - If the PA has a `max_amount_per_debit` defined, the amount must be less than or equal to it\n\n    For a recurring pre-authorization:\n    - If the PA resets every cycle, the available amount is the amount authorized in the current cycle minus\n      the amount debited in the current cycle\n    - Otherwise, the available amount is the amount authorized across the cycles up to the current cycle\n      (including the amount authorized before an amendment) minus the `PA.amount_debited_total`\n    - The amount authorized in a cycle is the `PA.recurring_amount_authorized`, or the\n      `intro_amount_authorized` in the first `num_intro_cycles` cycles if the PA has an `intro_schedule` defined\n    - If the PA has a `calendar_schedule` defined, the cycles are calendar cycles instead of\n      `PA.repeat_frequency_seconds` long\n    - If the PA has a `min_amount_per_debit` defined, the amount must be greater than or equal to it\n    - If the PA has a `max_accrued_cycles` defined (and does not reset every cycle), the available amount\n      is capped to `max_accrued_cycles` times the amount authorized in the current cycle,\n      the amount accrued above it is forfeited\n    - If the PA has a `debit_window_seconds` defined, the validator time must be within the debit window of the\n      current cycle (starting `debit_window_offset_seconds`, if set, into the cycle)\n\n    For a scheduled pre-authorization:\n    - The amount authorized in the current cycle is the `amount_authorized` of the step active in the current cycle\n      (the amount is reset every cycle), the `DebitEvent` reports the step the debit was validated against\n\n    For an installments pre-authorization:\n    - The amount of the installments due (i.e. past their due date) minus the amount already debited can be debited,\n      overdue installments (see `grace_period_seconds`) can still be debited\n\n    For a rolling window pre-authorization:\n    - The amount debited in the trailing window (rounded up to whole buckets of `window_seconds / 24` seconds)\n      plus the amount MUST NOT exceed the `amount_authorized_per_window`\n\n    For a one-time pre-authorization:\n    - The validator time must be less than the `PA.expiry_unix_timestamp`\n    - The available amount is the `PA.amount_authorized` minus the `PA.amount_debited`\n    - If the PA has `close_on_exhaustion` set and the debit exhausts the amount authorized, the `pre_authorization`\n      is closed (emitting a `OneTimePreAuthorizationClosed` event) and its rent is refunded to the `receiver`\n\n    Amount modes:\n    - `Exact`: exactly the `amount` is debited (or the debit fails)\n    - `UpTo`: the `amount` is capped to the amount available, the `max_amount_per_debit` (if any), the\n      `token_account.amount` and the `token_account.delegated_amount`, the capped amount MUST be greater than 0\n      and greater than or equal to the `min_amount_per_debit` (if any), or the debit fails with `NothingToDebit`\n      (and is then validated with the rules above). The `DebitEvent` reports the amount requested\n      (`requested_amount`) and the amount debited (`debit_variant`).\n\n    The `params.reference` (if set, e.g. an invoice id or hash) is emitted in the `DebitEvent`.\n    If the `memo_program` is set, the `params.reference` is also logged (hex encoded) in a SPL memo right before\n    the transfer (e.g. for a `destination_token_account` requiring memos on incoming transfers).\n\n    If the `debit_receipt` is set, a `debit_receipt` recording the debit (amount, cycle, timestamp, destination\n    and reference) is created at the PDA derived with the `pre_authorization.num_debits` before the debit,\n    paid by the `receipt_payer`. The `pre_authorization.num_debits` is incremented with every debit.\n\n    For a more in-depth understanding around the constraints in a debit, it is recommended to read through\n    the validation done for a `debit` instruction.\n\n    The `debit_authority` MUST sign the transaction.\n    The `debit_authority` MUST equal the `pre_authorization.debit_authority`.\n    The `mint` MUST equal `token_account.mint` and `destination_token_account.mint`.\n    The `token_account.delegate` MUST equal the `smart_delegate`.\n    The `token_account.mint` MUST equal the `mint`.\n    The `destination_token_account.mint` MUST equal `mint`.\n    The `pre_authorization.token_account` MUST equal the `token_account`\n    (for an owner scoped `pre_authorization`, the `token_account.owner` and `token_account.mint`\n    MUST equal the `owner` and `mint` of the `pre_authorization.scope` instead).\n    The `token_program` MUST equal the token program matching the `token_account`.\n    The `receiver` MUST equal the `token_account.owner` (only required if the debit closes the `pre_authorization`).\n    The `pre_authorization_counter` of the `token_account` is only required if the debit closes a `pre_authorization`\n    that is not owner scoped, or if the `pre_authorization` is owner scoped (it can be uninitialized then).\n    An owner scoped `pre_authorization` MUST NOT debit a `token_account` in bounded delegation mode\n    (its debits are not part of the outstanding amount authorized the `smart_delegate` is approved for).\n    The `memo_program` (optional) MUST equal the SPL Memo program and the `params.reference` MUST be set if it is.\n    The `receipt_payer` and `system_program` are only required if the `debit_receipt` is set.\n    The `receipt_payer` MUST sign the transaction and have enough lamports to pay for the `debit_receipt` account.\n    The `debit_receipt` MUST be the PDA derived with the seeds:\n    ['debit-receipt', pre_authorization, pre_authorization.num_debits (u64 little-endian)].\n\n    Accounts expected by this instruction:\n        0. `[]`         debit_authority\n        1. `[]`         mint\n        2. `[writable]` token_account\n        3. `[writable]` destination_token_account\n        4. `[]`         smart_delegate\n        5. `[writable]` pre_authorization\n        6. `[]`         token_program\n        7. `[writable]` receiver (optional)\n        8. `[writable]` pre_authorization_counter (optional)\n        9. `[]`         memo_program (optional)\n        10. `[writable]` receipt_payer (optional)\n        11. `[writable]` debit_receipt (optional)\n        12. `[]`         system_program (optional)",
+      ],
+      accounts: [
+        {
+          name: "debitAuthority",
+          isMut: false,
+          isSigner: true,
+        },
+        {
+          name: "mint",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "tokenAccount",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "destinationTokenAccount",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "smartDelegate",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "preAuthorization",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "tokenProgram",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "receiver",
+          isMut: true,
+          isSigner: false,
+          isOptional: true,
+        },
+        {
+          name: "preAuthorizationCounter",
+          isMut: true,
+          isSigner: false,
+          isOptional: true,
+        },
+        {
+          name: "memoProgram",
+          isMut: false,
+          isSigner: false,
+          isOptional: true,
+        },
+        {
+          name: "receiptPayer",
+          isMut: true,
+          isSigner: true,
+          isOptional: true,
+        },
+        {
+          name: "debitReceipt",
+          isMut: true,
+          isSigner: false,
+          isOptional: true,
+        },
+        {
+          name: "systemProgram",
+          isMut: false,
+          isSigner: false,
+          isOptional: true,
+        },
+      ],
+      args: [
+        {
+          name: "params",
+          type: {
+            defined: "DebitParams",
+          },
+        },
+      ],
+    },
+    {
+      name: "debitBatch",
+      docs: [
+        "The `DebitBatch` instruction allows a `debit_authority` to debit many `pre_authorization` accounts\n    of the same `mint` in a single instruction. Each entry of the batch is a `token_account`,\n    `pre_authorization` and `destination_token_account` (passed as remaining accounts) with the amount\n    to debit in `params.amounts`. Each entry is validated with the same rules as the `debit` instruction\n    and emits a `DebitEvent`. A `DebitBatchProcessed` event is emitted once all the entries are processed.\n\n    Modes:\n    - `Atomic`: the batch fails if any entry fails\n    - `SkipFailed`: an entry failing validation is skipped and reported (with its error code) in the\n      `DebitBatchProcessed` event, a failed token transfer (e.g. a transfer hook) still fails the batch\n\n    The `debit_authority` MUST sign the transaction.\n    The `params.amounts` MUST NOT be empty (nor have more than 65535 amounts)\n    and there MUST be 3 remaining accounts per amount.\n    The remaining accounts MUST be writable and the 3 accounts of an entry MUST be distinct.\n    A `pre_authorization` MUST NOT be in more than one entry.\n    The `debit_authority` MUST equal the `pre_authorization.debit_authority` of every entry.\n    The `token_account.mint` and `destination_token_account.mint` of every entry MUST equal the `mint`.\n    The `token_account.amount` of every entry MUST be greater than or equal to the amount debited.\n    The `pre_authorization.token_account` of every entry MUST equal the `token_account`.\n    An owner scoped `pre_authorization` MUST use the `debit` instruction (its `pre_authorization_counter` is required).\n    A debit exhausting a one-time `pre_authorization` with `close_on_exhaustion` set MUST use the `debit` instruction.\n    No `debit_receipt` is created for the entries (their `pre_authorization.num_debits` is still incremented)\n    and the entries have no reference (the `debit` instruction MUST be used for either).\n    The `token_program` MUST equal the token program matching the `token_account` of every entry.\n\n    Accounts expected by this instruction:\n        0. `[]`         debit_authority\n        1. `[]`         mint\n        2. `[]`         smart_delegate\n        3. `[]`         token_program\n        4.. `[writable]` token_account, `[writable]` pre_authorization, `[writable]` destination_token_account\n            (remaining accounts, one triple per entry)",
+      ],
+      accounts: [
+        {
+          name: "debitAuthority",
+          isMut: false,
+          isSigner: true,
+        },
+        {
+          name: "mint",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "smartDelegate",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "tokenProgram",
+          isMut: false,
+          isSigner: false,
+        },
+      ],
+      args: [
+        {
+          name: "params",
+          type: {
+            defined: "DebitBatchParams",
+          },
+        },
+      ],
+    },
+    {
+      name: "closeDebitReceipt",
+      docs: [
+        "The `CloseDebitReceipt` instruction will close a `debit_receipt` account.\n\n    Closes an existing `debit_receipt` account and refunds the lamports to the `debit_receipt.payer` (`receiver`).\n\n    The `authority` MUST sign the transaction.\n    The `authority` MUST be either the `debit_receipt.payer` or the `debit_receipt.debit_authority`.\n    The `receiver` MUST equal the `debit_receipt.payer`.\n    The retention period (`DEBIT_RECEIPT_RETENTION_SECONDS` after the debit) MUST be over.\n\n    Accounts expected by this instruction:\n        0. `[]`         authority\n        1. `[writable]` receiver\n        2. `[writable]` debit_receipt",
+      ],
+      accounts: [
+        {
+          name: "authority",
+          isMut: false,
+          isSigner: true,
+        },
+        {
+          name: "receiver",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "debitReceipt",
+          isMut: true,
+          isSigner: false,
+        },
+      ],
+      args: [],
+    },
+    {
+      name: "updatePausePreAuthorization",
+      docs: [
+        "The `UpdatePausePreAuthorization` instruction allows a `token_account.owner` to pause a\n    `pre_authorization`.\n\n    The `owner` MUST sign the transaction.\n    The `owner` MUST equal the `token_account.owner`.\n    The `token_account.owner` MUST equal the `owner`.\n    The `pre_authorization.token_account` MUST equal the `token_account`\n    (for an owner scoped `pre_authorization`, the `token_account.owner` and `token_account.mint`\n    MUST equal the `owner` and `mint` of the `pre_authorization.scope` instead).\n\n    Accounts expected by this instruction:\n        0. `[writable]` owner\n        2. `[]`         token_account\n        3. `[writable]` pre_authorization",
+      ],
+      accounts: [
+        {
+          name: "owner",
+          isMut: false,
+          isSigner: true,
+        },
+        {
+          name: "tokenAccount",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "preAuthorization",
+          isMut: true,
+          isSigner: false,
+        },
+      ],
+      args: [
+        {
+          name: "params",
+          type: {
+            defined: "UpdatePausePreAuthorizationParams",
+          },
+        },
+      ],
+    },
+    {
+      name: "reapproveSmartDelegate",
+      docs: [
+        "The `ReapproveSmartDelegate` instruction allows a `token_account.owner` to restore the `smart_delegate`\n    approval on the `token_account` (e.g. after approving another delegate), no `pre_authorization` is modified.\n\n    The `smart_delegate` is approved for `u64::MAX`, or in bounded delegation mode, for the sum of the\n    outstanding amounts authorized of the open `pre_authorization` accounts of the `token_account`.\n\n    The `owner` MUST sign the transaction.\n    The `owner` MUST equal the `token_account.owner`.\n    In bounded delegation mode, the remaining accounts MUST be all the open `pre_authorization` accounts\n    of the `token_account` (each one exactly once, see `pre_authorization_counter.count`).\n\n    Accounts expected by this instruction:\n        0. `[]`         owner\n        1. `[writable]` token_account\n        2. `[]`         smart_delegate\n        3. `[]`         pre_authorization_counter\n        4. `[]`         token_program\n        5.. `[]`        pre_authorization (remaining accounts, bounded delegation mode only)",
+      ],
+      accounts: [
+        {
+          name: "owner",
+          isMut: false,
+          isSigner: true,
+        },
+        {
+          name: "tokenAccount",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "smartDelegate",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "preAuthorizationCounter",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "tokenProgram",
+          isMut: false,
+          isSigner: false,
+        },
+      ],
+      args: [],
+    },
+    {
+      name: "updatePreAuthorization",
+      docs: [
+        "The `UpdatePreAuthorization` instruction allows a `token_account.owner` to amend the terms of a\n    `pre_authorization` without closing it (i.e. the debited amounts are kept).\n\n    For a one-time pre-authorization, the `amount_authorized` and `expiry_unix_timestamp` can be updated.\n    For a recurring pre-authorization, the `recurring_amount_authorized`, `num_cycles` and `reset_every_cycle`\n    can be updated. The new terms apply from the current cycle onwards, the amounts authorized in\n    already elapsed cycles are never increased.\n\n    Rules:\n    - The variant (one-time or recurring) of the `pre_authorization` cannot be changed.\n    - Scheduled, installments and rolling window pre-authorizations cannot be updated (close and re-create them instead).\n    - The amount authorized (for the current cycle if recurring) cannot be less than the amount already debited.\n    - For a one-time pre-authorization, the `expiry_unix_timestamp` MUST be after the `activation_unix_timestamp`.\n    - For a recurring pre-authorization, the `num_cycles` (if set) MUST be greater than or equal to the current cycle.\n    - In bounded delegation mode, the `num_cycles` MUST be set for a recurring pre-authorization and the\n      `smart_delegate` is re-approved for the new outstanding amount authorized if it is still the\n      `token_account.delegate` (owner scoped pre-authorizations are not part of the bounded allowance).\n    - The `pre_authorization_counter` of the `token_account` MUST be set unless the `pre_authorization` is owner\n      scoped, it can be uninitialized (the delegation mode is read from it).\n\n    The `owner` MUST sign the transaction.\n    The `owner` MUST equal the `token_account.owner`.\n    The `token_account.owner` MUST equal the `owner`.\n    The `pre_authorization.token_account` MUST equal the `token_account`\n    (for an owner scoped `pre_authorization`, the `token_account.owner` and `token_account.mint`\n    MUST equal the `owner` and `mint` of the `pre_authorization.scope` instead).\n\n    Accounts expected by this instruction:\n        0. `[]`         owner\n        1. `[writable]` token_account\n        2. `[writable]` pre_authorization\n        3. `[]`         smart_delegate\n        4. `[]`         pre_authorization_counter (optional)\n        5. `[]`         token_program",
+      ],
+      accounts: [
+        {
+          name: "owner",
+          isMut: false,
+          isSigner: true,
+        },
+        {
+          name: "tokenAccount",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "preAuthorization",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "smartDelegate",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "preAuthorizationCounter",
+          isMut: false,
+          isSigner: false,
+          isOptional: true,
+        },
+        {
+          name: "tokenProgram",
+          isMut: false,
+          isSigner: false,
+        },
+      ],
+      args: [
+        {
+          name: "params",
+          type: {
+            defined: "UpdatePreAuthorizationParams",
+          },
+        },
+      ],
+    },
+    {
+      name: "proposeAmendment",
+      docs: [
+        "The `ProposeAmendment` instruction allows a `pre_authorization.debit_authority` to propose new terms\n    for a `pre_authorization` that the `token_account.owner` can accept or reject.\n\n    Initializes a new account (`pending_amendment`).\n    The `pending_amendment` PDA is derived with the seeds: ['pending-amendment', pre_authorization].\n    Only one `pending_amendment` can exist for a `pre_authorization` at a time.\n\n    The `payer` MUST sign the transaction.\n    The `payer` MUST have enough lamports to pay for the `pending_amendment` account.\n    The `debit_authority` MUST sign the transaction.\n    The `debit_authority` MUST equal the `pre_authorization.debit_authority`.\n    The `new_values` variant MUST match the `pre_authorization` variant.\n    The `acceptance_deadline_unix_timestamp` MUST be in the future.\n\n    Accounts expected by this instruction:\n        0. `[writable]` payer\n        1. `[]`         debit_authority\n        2. `[]`         pre_authorization\n        3. `[writable]` pending_amendment\n        4. `[]`         system_program",
+      ],
+      accounts: [
+        {
+          name: "payer",
+          isMut: true,
+          isSigner: true,
+        },
+        {
+          name: "debitAuthority",
+          isMut: false,
+          isSigner: true,
+        },
+        {
+          name: "preAuthorization",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "pendingAmendment",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "systemProgram",
+          isMut: false,
+          isSigner: false,
+        },
+      ],
+      args: [
+        {
+          name: "params",
+          type: {
+            defined: "ProposeAmendmentParams",
+          },
+        },
+      ],
+    },
+    {
+      name: "acceptAmendment",
+      docs: [
+        "The `AcceptAmendment` instruction allows a `token_account.owner` to apply the terms of a\n    `pending_amendment` to the `pre_authorization` and closes the `pending_amendment`.\n\n    The new terms are applied with the same rules as the `update_pre_authorization` instruction.\n    The lamports of the closed `pending_amendment` are refunded to the `pending_amendment.payer` (`receiver`).\n\n    The `owner` MUST sign the transaction.\n    The `owner` MUST equal the `token_account.owner`.\n    The `receiver` MUST equal the `pending_amendment.payer`.\n    The `pre_authorization.token_account` MUST equal the `token_account`\n    (for an owner scoped `pre_authorization`, the `token_account.owner` and `token_account.mint`\n    MUST equal the `owner` and `mint` of the `pre_authorization.scope` instead).\n    The current timestamp MUST be less than or equal to the `pending_amendment.acceptance_deadline_unix_timestamp`.\n    The `expected_new_values` MUST equal the `pending_amendment.new_values`.\n    The `pre_authorization_counter` of the `token_account` MUST be set unless the `pre_authorization` is owner scoped,\n    it can be uninitialized (the delegation mode is read from it).\n\n    Accounts expected by this instruction:\n        0. `[]`         owner\n        1. `[writable]` receiver\n        2. `[writable]` token_account\n        3. `[writable]` pre_authorization\n        4. `[writable]` pending_amendment\n        5. `[]`         smart_delegate\n        6. `[]`         pre_authorization_counter (optional)\n        7. `[]`         token_program",
+      ],
+      accounts: [
+        {
+          name: "owner",
+          isMut: false,
+          isSigner: true,
+        },
+        {
+          name: "receiver",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "tokenAccount",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "preAuthorization",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "pendingAmendment",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "smartDelegate",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "preAuthorizationCounter",
+          isMut: false,
+          isSigner: false,
+          isOptional: true,
+        },
+        {
+          name: "tokenProgram",
+          isMut: false,
+          isSigner: false,
+        },
+      ],
+      args: [
+        {
+          name: "params",
+          type: {
+            defined: "AcceptAmendmentParams",
+          },
+        },
+      ],
+    },
+    {
+      name: "rejectAmendment",
+      docs: [
+        "The `RejectAmendment` instruction closes a `pending_amendment` without applying its terms.\n\n    The lamports of the closed `pending_amendment` are refunded to the `pending_amendment.payer` (`receiver`).\n\n    The `authority` MUST sign the transaction.\n    The `authority` MUST be either the `token_account.owner` or the `pending_amendment.debit_authority`.\n    The `receiver` MUST equal the `pending_amendment.payer`.\n    The `pre_authorization.token_account` MUST equal the `token_account`\n    (for an owner scoped `pre_authorization`, the `token_account.owner` and `token_account.mint`\n    MUST equal the `owner` and `mint` of the `pre_authorization.scope` instead).\n\n    Accounts expected by this instruction:\n        0. `[]`         authority\n        1. `[writable]` receiver\n        2. `[]`         token_account\n        3. `[]`         pre_authorization\n        4. `[writable]` pending_amendment",
+      ],
+      accounts: [
+        {
+          name: "authority",
+          isMut: false,
+          isSigner: true,
+        },
+        {
+          name: "receiver",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "tokenAccount",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "preAuthorization",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "pendingAmendment",
+          isMut: true,
+          isSigner: false,
+        },
+      ],
+      args: [],
+    },
+    {
+      name: "rotateDebitAuthority",
+      docs: [
+        "The `RotateDebitAuthority` instruction allows a `pre_authorization.debit_authority` to hand over\n    a `pre_authorization` to a `new_debit_authority` without the `token_account.owner` re-authorizing.\n\n    Initializes a new account (`new_pre_authorization`) derived with the seeds:\n    ['pre-authorization', token_account, new_debit_authority]\n    (['pre-authorization', owner, mint, new_debit_authority] for an owner scoped `pre_authorization`).\n    The state of the `pre_authorization` (including the debited amounts, `last_debited_cycle` and `paused`)\n    is carried over to the `new_pre_authorization` and the `pre_authorization` is closed.\n    The lamports of the closed `pre_authorization` are refunded to the `token_account.owner` (`receiver`).\n\n    The `payer` MUST sign the transaction.\n    The `payer` MUST have enough lamports to pay for the `new_pre_authorization` account.\n    The `debit_authority` MUST sign the transaction.\n    The `debit_authority` MUST equal the `pre_authorization.debit_authority`.\n    The `new_debit_authority` MUST NOT equal the `debit_authority`.\n    If `pre_authorization.rotation_requires_owner_signature` is set, the `owner` MUST be provided and sign the transaction.\n    The `owner` (if provided) MUST equal the `token_account.owner`.\n    The `receiver` MUST equal the `token_account.owner`.\n    The `pre_authorization.token_account` MUST equal the `token_account`\n    (for an owner scoped `pre_authorization`, the `token_account.owner` and `token_account.mint`\n    MUST equal the `owner` and `mint` of the `pre_authorization.scope` instead).\n    The `pre_authorization` MUST NOT have a `pending_amendment`.\n\n    Accounts expected by this instruction:\n        0. `[writable]` payer\n        1. `[]`         debit_authority\n        2. `[]`         new_debit_authority\n        3. `[]`         owner (optional)\n        4. `[writable]` receiver\n        5. `[]`         token_account\n        6. `[writable]` pre_authorization\n        7. `[]`         pending_amendment\n        8. `[writable]` new_pre_authorization\n        9. `[]`         system_program",
+      ],
+      accounts: [
+        {
+          name: "payer",
+          isMut: true,
+          isSigner: true,
+        },
+        {
+          name: "debitAuthority",
+          isMut: false,
+          isSigner: true,
+        },
+        {
+          name: "newDebitAuthority",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "owner",
+          isMut: false,
+          isSigner: true,
+          isOptional: true,
+        },
+        {
+          name: "receiver",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "tokenAccount",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "preAuthorization",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "pendingAmendment",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "newPreAuthorization",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "systemProgram",
+          isMut: false,
+          isSigner: false,
+        },
+      ],
+      args: [],
+    },
+    {
+      name: "migratePreAuthorization",
+      docs: [
+        "The `MigratePreAuthorization` instruction converts a `pre_authorization` account created by\n    the v1.0.0 program to the current layout, it can be called by anyone.\n\n    Reads the v1.0.0 layout of the `pre_authorization`, reallocates the account to the current size\n    and writes it back in the current layout. The state of the `pre_authorization` (including the debited\n    amounts, `last_debited_cycle` and `paused`) is kept and the fields added since v1.0.0 are set\n    such that the `pre_authorization` behaves as it did in v1.0.0 (e.g. no `max_amount_per_debit`).\n    The `pre_authorization` can then be debited, updated, paused and closed again.\n\n    The `payer` MUST sign the transaction.\n    The `payer` MUST have enough lamports to pay for the additional rent of the `pre_authorization`.\n    The `pre_authorization` MUST be a v1.0.0 `pre_authorization` (i.e. not migrated yet).\n\n    Accounts expected by this instruction:\n        0. `[writable]` payer\n        1. `[writable]` pre_authorization\n        2. `[]`         system_program",
+      ],
+      accounts: [
+        {
+          name: "payer",
+          isMut: true,
+          isSigner: true,
+        },
+        {
+          name: "preAuthorization",
+          isMut: true,
+          isSigner: false,
+          docs: [
+            "the discriminator and the layout are validated in the handler",
+          ],
+        },
+        {
+          name: "systemProgram",
+          isMut: false,
+          isSigner: false,
+        },
+      ],
+      args: [],
+    },
+  ],
+  accounts: [
+    {
+      name: "debitReceipt",
+      docs: [
+        "The `debit_receipt` is a PDA account derived with the seeds:\n ['debit-receipt', pre_authorization, debit_index (u64 little-endian)].\n The `debit_receipt` is an on-chain record of a single `debit` (opt-in, see the `debit` instruction),\n the `debit_index` is the `pre_authorization.num_debits` before the debit.\n The `debit_receipt` can be closed by its `payer` or `debit_authority` once the retention period is over.",
+      ],
+      type: {
+        kind: "struct",
+        fields: [
+          {
+            name: "bump",
+            docs: [
+              "The `bump` is the canonical PDA bump when derived with seeds:\n      ['debit-receipt', pre_authorization, debit_index (u64 little-endian)].\n      This field is initialized in `debit`.\n      This field is never updated in any instruction.",
+            ],
+            type: "u8",
+          },
+          {
+            name: "preAuthorization",
+            docs: [
+              "The `pre_authorization` the debit was done with (it may be closed since).\n      This field is initialized in `debit`.\n      This field is never updated in any instruction.",
+            ],
+            type: "publicKey",
+          },
+          {
+            name: "debitIndex",
+            docs: [
+              "The index of the debit in the `pre_authorization` (its `num_debits` before the debit).\n      This field is initialized in `debit`.\n      This field is never updated in any instruction.",
+            ],
+            type: "u64",
+          },
+          {
+            name: "debitAuthority",
+            docs: [
+              "The `debit_authority` that did the debit.\n      This field is initialized in `debit`.\n      This field is never updated in any instruction.",
+            ],
+            type: "publicKey",
+          },
+          {
+            name: "payer",
+            docs: [
+              "The `payer` that paid for the `debit_receipt` account and receives the lamports when it is closed.\n      This field is initialized in `debit`.\n      This field is never updated in any instruction.",
+            ],
+            type: "publicKey",
+          },
+          {
+            name: "sourceTokenAccount",
+            docs: [
+              "The token account the amount was debited from.\n      This field is initialized in `debit`.\n      This field is never updated in any instruction.",
+            ],
+            type: "publicKey",
+          },
+          {
+            name: "destinationTokenAccount",
+            docs: [
+              "The token account the amount was transferred to.\n      This field is initialized in `debit`.\n      This field is never updated in any instruction.",
+            ],
+            type: "publicKey",
+          },
+          {
+            name: "amount",
+            docs: [
+              "The amount debited.\n      This field is initialized in `debit`.\n      This field is never updated in any instruction.",
+            ],
+            type: "u64",
+          },
+          {
+            name: "cycle",
+            docs: [
+              "The cycle (or rolling window bucket) the debit was done in, `None` for a one-time pre-authorization.\n      This field is initialized in `debit`.\n      This field is never updated in any instruction.",
+            ],
+            type: {
+              option: "u64",
+            },
+          },
+          {
+            name: "unixTimestamp",
+            docs: [
+              "The unix timestamp of the debit.\n      This field is initialized in `debit`.\n      This field is never updated in any instruction.",
+            ],
+            type: "i64",
+          },
+          {
+            name: "reference",
+            docs: [
+              "The `reference` of the debit (see `DebitParams`).\n      This field is initialized in `debit`.\n      This field is never updated in any instruction.",
+            ],
+            type: {
+              option: {
+                array: [
+                  "u8",
+                  32,
+                ],
+              },
+            },
+          },
+        ],
+      },
+    },
+    {
+      name: "pendingAmendment",
+      docs: [
+        "The `pending_amendment` is a PDA account derived with the seeds:\n ['pending-amendment', pre_authorization].\n The `pending_amendment` holds new terms for a `pre_authorization` proposed by its `debit_authority`.\n The new terms are only applied to the `pre_authorization` once accepted by the `token_account.owner`.\n There can only be one `pending_amendment` for a given `pre_authorization` at a time.",
+      ],
+      type: {
+        kind: "struct",
+        fields: [
+          {
+            name: "bump",
+            docs: [
+              "The `bump` is the canonical PDA bump when derived with seeds:\n      ['pending-amendment', pre_authorization].\n      This field is initialized in `propose_amendment`.\n      This field is never updated in any instruction.",
+            ],
+            type: "u8",
+          },
+          {
+            name: "preAuthorization",
+            docs: [
+              "The `pre_authorization` the new terms are proposed for.\n      This field is initialized in `propose_amendment`.\n      This field is never updated in any instruction.",
+            ],
+            type: "publicKey",
+          },
+          {
+            name: "debitAuthority",
+            docs: [
+              "The `debit_authority` of the `pre_authorization` that proposed the new terms.\n      This field is initialized in `propose_amendment`.\n      This field is never updated in any instruction.",
+            ],
+            type: "publicKey",
+          },
+          {
+            name: "payer",
+            docs: [
+              "The `payer` that paid for the `pending_amendment` account and receives the lamports when it is closed.\n      This field is initialized in `propose_amendment`.\n      This field is never updated in any instruction.",
+            ],
+            type: "publicKey",
+          },
+          {
+            name: "newValues",
+            docs: [
+              "The proposed terms, applied to the `pre_authorization` the same way as in `update_pre_authorization`.\n      This field is initialized in `propose_amendment`.\n      This field is never updated in any instruction.",
+            ],
+            type: {
+              defined: "UpdatePreAuthorizationVariant",
+            },
+          },
+          {
+            name: "acceptanceDeadlineUnixTimestamp",
+            docs: [
+              "The `acceptance_deadline_unix_timestamp` is the last unix timestamp at which the amendment can be accepted.\n      This field is initialized in `propose_amendment`.\n      This field is never updated in any instruction.",
+            ],
+            type: "i64",
+          },
+        ],
+      },
+    },
+    {
+      name: "preAuthorizationCounter",
+      docs: [
+        "The `pre_authorization_counter` is a PDA account derived with the seeds:\n ['pre-authorization-counter', token_account].\n The `pre_authorization_counter` tracks the number of open `pre_authorization` accounts for a `token_account`\n (owner scoped `pre_authorization` accounts are not counted),\n such that the `smart_delegate` approval can be revoked once the last one is closed.\n A `pre_authorization_counter` created by `init_pre_authorization` cannot know about the `pre_authorization`\n accounts created before it existed, its `count` is only relied on once `count_verified` is set\n (see `init_pre_authorization_counter`).",
+      ],
+      type: {
+        kind: "struct",
+        fields: [
+          {
+            name: "bump",
+            docs: [
+              "The `bump` is the canonical PDA bump when derived with seeds:\n      ['pre-authorization-counter', token_account].\n      This field is initialized in `init_pre_authorization` or `init_pre_authorization_counter`\n        (if the account does not exist yet).\n      This field is never updated in any instruction.",
+            ],
+            type: "u8",
+          },
+          {
+            name: "tokenAccount",
+            docs: [
+              "The `token_account` the `pre_authorization` accounts are counted for.\n      This field is initialized in `init_pre_authorization` or `init_pre_authorization_counter`\n        (if the account does not exist yet).\n      This field is never updated in any instruction.",
+            ],
+            type: "publicKey",
+          },
+          {
+            name: "count",
+            docs: [
+              "The number of open `pre_authorization` accounts for the `token_account`.\n      This field is incremented in `init_pre_authorization`.\n      This field is decremented whenever a `pre_authorization` for the `token_account` is closed\n      (`close_pre_authorization`, `close_expired_pre_authorization` or a `debit` with `close_on_exhaustion`,\n      all of them require the `pre_authorization_counter`).\n      This field can be set once by the `token_account.owner` in `init_pre_authorization_counter`\n        (while `count_verified` is false).",
+            ],
+            type: "u64",
+          },
+          {
+            name: "boundedDelegation",
+            docs: [
+              "true: the `smart_delegate` is approved for the sum of the outstanding amounts authorized\n      of the open `pre_authorization` accounts (see `PreAuthorization::outstanding_amount_authorized`),\n        it can be above the sum until the owner re-approves it (see `reapprove_smart_delegate`)\n      false: the `smart_delegate` is approved for `u64::MAX`\n      This field is set in `init_pre_authorization` when no `pre_authorization` is open for the `token_account`.\n      This field is never updated while a `pre_authorization` is open for the `token_account`.\n      This field can only be set to true if `count_verified` is true.",
+            ],
+            type: "bool",
+          },
+          {
+            name: "countVerified",
+            docs: [
+              "true: the `count` was set by the `token_account.owner` (in `init_pre_authorization_counter`),\n        the `smart_delegate` approval is revoked when it reaches 0\n      false: the `count` may not include the `pre_authorization` accounts created before this account,\n        the `smart_delegate` approval is never revoked and bounded delegation is not supported\n      This field is initialized to false in `init_pre_authorization` (if the account does not exist yet).\n      This field is set to true in `init_pre_authorization_counter`.",
+            ],
+            type: "bool",
+          },
+        ],
+      },
+    },
+    {
+      name: "preAuthorization",
+      docs: [
+        "The `pre_authorization` is a PDA account derived with the seeds (see `scope_seeds`):\n ['pre-authorization', token_account, debit_authority] for a `TokenAccount` scope, or\n ['pre-authorization', owner, mint, debit_authority] for an `OwnerAndMint` scope.\n The `pre_authorization` can be thought of as the rule for the `smart_delegate`.\n The `pre_authorization` can be used to validate a one-time, recurring, scheduled, installments\n or rolling window debit from the token account(s) covered by its `scope`.\n The `smart_delegate` will validate the rules of the `pre_authorization` in the `debit` instruction.\n A `pre_authorization` is associated many:1 with a `token_account` (or with an `owner` and `mint`),\n however, for a given `debit_authority` and `token_account` (or `owner` and `mint`)\n there can only be one `pre_authorization`.",
+      ],
+      type: {
+        kind: "struct",
+        fields: [
+          {
+            name: "bump",
+            docs: [
+              "The `bump` is the canonical PDA bump when derived with the seeds of the `scope`:\n      ['pre-authorization', token_account, debit_authority] or\n      ['pre-authorization', owner, mint, debit_authority].\n      This field is initialized in `init_pre_authorization` (or `init_owner_pre_authorization`,\n        and in `rotate_debit_authority` for the `new_pre_authorization`).\n      This field is never updated in any instruction.",
+            ],
+            type: "u8",
+          },
+          {
+            name: "paused",
+            docs: [
+              "If `paused === true`, then the `debit_authority` cannot debit via the `token_account`.\n      This field is initialized to `false` in `init_pre_authorization`.\n      This field can be updated by the `token_account.owner` in `update_pause_pre_authorization`.\n      Note that recurring rebits that accrue the amounts across cycles will continue to do so while paused\n        (close the pre-authorization to stop this).",
+            ],
+            type: "bool",
+          },
+          {
+            name: "tokenAccount",
+            docs: [
+              "The `token_account` is the account the `debit_authority` will be able to debit from.\n      This field is initialized in `init_pre_authorization`\n        (to `Pubkey::default()` in `init_owner_pre_authorization`, see `scope`).\n      This field is never updated in any instruction.",
+            ],
+            type: "publicKey",
+          },
+          {
+            name: "variant",
+            docs: [
+              "The `variant` contains the data specific to a one-time,\n      recurring, scheduled, installments or rolling window debit.\n      This field is initialized in `init_pre_authorization`.\n      The debited amounts are updated in `debit`.\n      The authorized amounts, expiry and cycle configuration can be updated by the\n      `token_account.owner` in `update_pre_authorization`.",
+            ],
+            type: {
+              defined: "PreAuthorizationVariant",
+            },
+          },
+          {
+            name: "debitAuthority",
+            docs: [
+              "The `debit_authority` is the signer that can debit from the `token_account`.\n      This field is initialized in `init_pre_authorization`.\n      This field is never updated in any instruction\n        (`rotate_debit_authority` moves the state to a new `pre_authorization` PDA instead).",
+            ],
+            type: "publicKey",
+          },
+          {
+            name: "activationUnixTimestamp",
+            docs: [
+              "The `activation_unix_timestamp` represents when the pre-authorization becomes active (i.e. debits can occur).\n      The field is initialized in `init_pre_authorization`.",
+            ],
+            type: "i64",
+          },
+          {
+            name: "rotationRequiresOwnerSignature",
+            docs: [
+              "If `rotation_requires_owner_signature === true`, then the `token_account.owner` MUST co-sign\n      the `rotate_debit_authority` instruction.\n      This field is initialized in `init_pre_authorization`.\n      This field is never updated in any instruction.",
+            ],
+            type: "bool",
+          },
+          {
+            name: "scope",
+            docs: [
+              "The `scope` defines the token accounts the `debit_authority` can debit from.\n      This field is initialized to `TokenAccount` in `init_pre_authorization`\n        and to `OwnerAndMint` in `init_owner_pre_authorization`.\n      This field is never updated in any instruction.",
+            ],
+            type: {
+              defined: "PreAuthorizationScope",
+            },
+          },
+          {
+            name: "destinationConstraint",
+            docs: [
+              "The `destination_constraint` (if set) restricts the `destination_token_account` of a `debit`.\n      This field is initialized in `init_pre_authorization` (or `init_owner_pre_authorization`).\n      This field is never updated in any instruction.",
+            ],
+            type: {
+              option: {
+                defined: "DestinationConstraint",
+              },
+            },
+          },
+          {
+            name: "numDebits",
+            docs: [
+              "The number of debits done with the `pre_authorization` (the index of the next `debit_receipt`).\n      This field is initialized to 0 in `init_pre_authorization` (or `init_owner_pre_authorization`).\n      This field is incremented in `debit` and `debit_batch`.",
+            ],
+            type: "u64",
+          },
+        ],
+      },
+    },
+    {
+      name: "smartDelegate",
+      docs: [
+        "The `smart_delegate` is a PDA account derived with the seeds:\n  ['smart-delegate'].\n  The `smart_delegate` should be set as the delegate of any\n  `token_account` specified in a `pre_authorization`.\n  The `smart_delegate` is a global account and is only initialized once.",
+      ],
+      type: {
+        kind: "struct",
+        fields: [
+          {
+            name: "bump",
+            docs: [
+              "The `bump` is the canonical PDA bump when derived with seeds:\n      ['smart-delegate'].\n      This field is initialized in `init_smart_delegate`.\n      This field is never updated in any instruction.",
+            ],
+            type: "u8",
+          },
+        ],
+      },
+    },
+  ],
+  types: [
+    {
+      name: "CalendarSchedule",
+      type: {
+        kind: "struct",
+        fields: [
+          {
+            name: "interval",
+            type: {
+              defined: "CalendarInterval",
+            },
+          },
+          {
+            name: "dayOfMonth",
+            type: "u8",
+          },
+        ],
+      },
+    },
+    {
+      name: "AcceptAmendmentParams",
+      type: {
+        kind: "struct",
+        fields: [
+          {
+            name: "expectedNewValues",
+            type: {
+              defined: "UpdatePreAuthorizationVariant",
+            },
+          },
+        ],
+      },
+    },
+    {
+      name: "OwnerPreAuthorizationClosedEventData",
+      type: {
+        kind: "struct",
+        fields: [
+          {
+            name: "debitAuthority",
+            type: "publicKey",
+          },
+          {
+            name: "closingAuthority",
+            type: "publicKey",
+          },
+          {
+            name: "owner",
+            type: "publicKey",
+          },
+          {
+            name: "mint",
+            type: "publicKey",
+          },
+          {
+            name: "receiver",
+            type: "publicKey",
+          },
+          {
+            name: "preAuthorization",
+            type: "publicKey",
+          },
+        ],
+      },
+    },
+    {
+      name: "PreAuthorizationClosedEventData",
+      type: {
+        kind: "struct",
+        fields: [
+          {
+            name: "debitAuthority",
+            type: "publicKey",
+          },
+          {
+            name: "closingAuthority",
+            type: "publicKey",
+          },
+          {
+            name: "tokenAccountOwner",
+            type: "publicKey",
+          },
+          {
+            name: "receiver",
+            type: "publicKey",
+          },
+          {
+            name: "tokenAccount",
+            type: "publicKey",
+          },
+          {
+            name: "preAuthorization",
+            type: "publicKey",
+          },
+        ],
+      },
+    },
+    {
+      name: "DebitBatchParams",
+      type: {
+        kind: "struct",
+        fields: [
+          {
+            name: "amounts",
+            type: {
+              vec: "u64",
+            },
+          },
+          {
+            name: "mode",
+            type: {
+              defined: "DebitBatchMode",
+            },
+          },
+        ],
+      },
+    },
+    {
+      name: "DebitBatchFailedEntry",
+      type: {
+        kind: "struct",
+        fields: [
+          {
+            name: "index",
+            type: "u16",
+          },
+          {
+            name: "preAuthorization",
+            type: "publicKey",
+          },
+          {
+            name: "errorCode",
+            type: "u64",
+          },
+        ],
+      },
+    },
+    {
+      name: "DebitParams",
+      type: {
+        kind: "struct",
+        fields: [
+          {
+            name: "amount",
+            type: "u64",
+          },
+          {
+            name: "amountMode",
+            type: {
+              defined: "DebitAmountMode",
+            },
+          },
+          {
+            name: "reference",
+            type: {
+              option: {
+                array: [
+                  "u8",
+                  32,
+                ],
+              },
+            },
+          },
+        ],
+      },
+    },
+    {
+      name: "OwnerPreAuthorizationCreatedEventData",
+      type: {
+        kind: "struct",
+        fields: [
+          {
+            name: "debitAuthority",
+            type: "publicKey",
+          },
+          {
+            name: "owner",
+            type: "publicKey",
+          },
+          {
+            name: "mint",
+            type: "publicKey",
+          },
+          {
+            name: "payer",
+            type: "publicKey",
+          },
+          {
+            name: "preAuthorization",
+            type: "publicKey",
+          },
+          {
+            name: "initParams",
+            type: {
+              defined: "InitPreAuthorizationParams",
+            },
+          },
+        ],
+      },
+    },
+    {
+      name: "InitPreAuthorizationCounterParams",
+      type: {
+        kind: "struct",
+        fields: [
+          {
+            name: "count",
+            type: "u64",
+          },
+        ],
+      },
+    },
+    {
+      name: "InitPreAuthorizationParams",
+      type: {
+        kind: "struct",
+        fields: [
+          {
+            name: "variant",
+            type: {
+              defined: "InitPreAuthorizationVariant",
+            },
+          },
+          {
+            name: "debitAuthority",
+            type: "publicKey",
+          },
+          {
+            name: "activationUnixTimestamp",
+            type: "i64",
+          },
+          {
+            name: "rotationRequiresOwnerSignature",
+            type: "bool",
+          },
+          {
+            name: "destinationConstraint",
+            type: {
+              option: {
+                defined: "DestinationConstraint",
+              },
+            },
+          },
+          {
+            name: "boundedDelegation",
+            type: "bool",
+          },
+        ],
+      },
+    },
+    {
+      name: "PreAuthorizationCreatedEventData",
+      type: {
+        kind: "struct",
+        fields: [
+          {
+            name: "debitAuthority",
+            type: "publicKey",
+          },
+          {
+            name: "owner",
+            type: "publicKey",
+          },
+          {
+            name: "payer",
+            type: "publicKey",
+          },
+          {
+            name: "tokenAccount",
+            type: "publicKey",
+          },
+          {
+            name: "preAuthorization",
+            type: "publicKey",
+          },
+          {
+            name: "initParams",
+            type: {
+              defined: "InitPreAuthorizationParams",
+            },
+          },
+        ],
+      },
+    },
+    {
+      name: "ProposeAmendmentParams",
+      type: {
+        kind: "struct",
+        fields: [
+          {
+            name: "newValues",
+            type: {
+              defined: "UpdatePreAuthorizationVariant",
+            },
+          },
+          {
+            name: "acceptanceDeadlineUnixTimestamp",
+            type: "i64",
+          },
+        ],
+      },
+    },
+    {
+      name: "DebitAuthorityRotatedEventData",
+      type: {
+        kind: "struct",
+        fields: [
+          {
+            name: "owner",
+            type: "publicKey",
+          },
+          {
+            name: "tokenAccount",
+            type: "publicKey",
+          },
+          {
+            name: "oldDebitAuthority",
+            type: "publicKey",
+          },
+          {
+            name: "newDebitAuthority",
+            type: "publicKey",
+          },
+          {
+            name: "oldPreAuthorization",
+            type: "publicKey",
+          },
+          {
+            name: "newPreAuthorization",
+            type: "publicKey",
+          },
+        ],
+      },
+    },
+    {
+      name: "UpdatePausePreAuthorizationParams",
+      type: {
+        kind: "struct",
+        fields: [
+          {
+            name: "pause",
+            type: "bool",
+          },
+        ],
+      },
+    },
+    {
+      name: "PausePreAuthorizationEventData",
+      type: {
+        kind: "struct",
+        fields: [
+          {
+            name: "owner",
+            type: "publicKey",
+          },
+          {
+            name: "tokenAccount",
+            type: "publicKey",
+          },
+          {
+            name: "preAuthorization",
+            type: "publicKey",
+          },
+          {
+            name: "newPausedValue",
+            type: "bool",
+          },
+        ],
+      },
+    },
+    {
+      name: "UpdatePreAuthorizationParams",
+      type: {
+        kind: "struct",
+        fields: [
+          {
+            name: "variant",
+            type: {
+              defined: "UpdatePreAuthorizationVariant",
+            },
+          },
+        ],
+      },
+    },
+    {
+      name: "PreAuthorizationUpdatedEventData",
+      type: {
+        kind: "struct",
+        fields: [
+          {
+            name: "owner",
+            type: "publicKey",
+          },
+          {
+            name: "tokenAccount",
+            type: "publicKey",
+          },
+          {
+            name: "preAuthorization",
+            type: "publicKey",
+          },
+          {
+            name: "debitAuthority",
+            type: "publicKey",
           },
           {
-            name: "tokenAccount";
-            docs: [
-              "The `token_account` is the account the `debit_authority` will be able to debit from.\n      This field is initialized in `init_pre_authorization`.\n      This field is never updated in any instruction.",
-            ];
-            type: "publicKey";
+            name: "oldValues",
+            type: {
+              defined: "UpdatePreAuthorizationVariant",
+            },
           },
           {
-            name: "variant";
-            docs: [
-              "The `variant` contains the data specific to a one-time\n      or recurring debit.\n      This field is initialized in `init_pre_authorization`.\n      This field is never updated in any instruction.",
-            ];
+            name: "newValues",
             type: {
-              defined: "PreAuthorizationVariant";
-            };
+              defined: "UpdatePreAuthorizationVariant",
+            },
           },
+        ],
+      },
+    },
+    {
+      name: "PreAuthorizationV1",
+      docs: [
+        "The layout of a `pre_authorization` account created by the v1.0.0 program\n (after the 8 byte `PreAuthorization` discriminator, which is unchanged).\n It is only read in `migrate_pre_authorization`, see `PreAuthorization` for the fields.",
+      ],
+      type: {
+        kind: "struct",
+        fields: [
           {
-            name: "debitAuthority";
-            docs: [
-              "The `debit_authority` is the signer that can debit from the `token_account`.\n      This field is initialized in `init_pre_authorization`.\n      This field is never updated in any instruction.",
-            ];
-            type: "publicKey";
+            name: "bump",
+            type: "u8",
           },
           {
-            name: "activationUnixTimestamp";
-            docs: [
-              "The `activation_unix_timestamp` represents when the pre-authorization becomes active (i.e. debits can occur).\n      The field is initialized in `init_pre_authorization`.",
-            ];
-            type: "i64";
+            name: "paused",
+            type: "bool",
           },
-        ];
-      };
+          {
+            name: "tokenAccount",
+            type: "publicKey",
+          },
+          {
+            name: "variant",
+            type: {
+              defined: "PreAuthorizationVariantV1",
+            },
+          },
+          {
+            name: "debitAuthority",
+            type: "publicKey",
+          },
+          {
+            name: "activationUnixTimestamp",
+            type: "i64",
+          },
+        ],
+      },
     },
     {
-      name: "smartDelegate";
-      docs: [
-        "The `smart_delegate` is a PDA account derived with the seeds:\n  ['smart-delegate'].\n  The `smart_delegate` should be set as the delegate of any\n  `token_account` specified in a `pre_authorization`.\n  The `smart_delegate` is a global account and is only initialized once.",
-      ];
+      name: "IntroSchedule",
       type: {
-        kind: "struct";
+        kind: "struct",
         fields: [
           {
-            name: "bump";
-            docs: [
-              "The `bump` is the canonical PDA bump when derived with seeds:\n      ['smart-delegate'].\n      This field is initialized in `init_smart_delegate`.\n      This field is never updated in any instruction.",
-            ];
-            type: "u8";
+            name: "numIntroCycles",
+            type: "u64",
           },
-        ];
-      };
+          {
+            name: "introAmountAuthorized",
+            type: "u64",
+          },
+        ],
+      },
     },
-  ];
-  types: [
     {
-      name: "PreAuthorizationClosedEventData";
+      name: "ScheduleStep",
       type: {
-        kind: "struct";
+        kind: "struct",
         fields: [
           {
-            name: "debitAuthority";
-            type: "publicKey";
+            name: "cycleStart",
+            type: "u64",
           },
           {
-            name: "closingAuthority";
-            type: "publicKey";
+            name: "amountAuthorized",
+            type: "u64",
           },
+        ],
+      },
+    },
+    {
+      name: "CalendarInterval",
+      type: {
+        kind: "enum",
+        variants: [
           {
-            name: "tokenAccountOwner";
-            type: "publicKey";
+            name: "Monthly",
           },
           {
-            name: "receiver";
-            type: "publicKey";
+            name: "Quarterly",
+          },
+          {
+            name: "Yearly",
+          },
+        ],
+      },
+    },
+    {
+      name: "DebitBatchMode",
+      type: {
+        kind: "enum",
+        variants: [
+          {
+            name: "Atomic",
+          },
+          {
+            name: "SkipFailed",
+          },
+        ],
+      },
+    },
+    {
+      name: "DebitAmountMode",
+      type: {
+        kind: "enum",
+        variants: [
+          {
+            name: "Exact",
+          },
+          {
+            name: "UpTo",
+          },
+        ],
+      },
+    },
+    {
+      name: "DebitEventVariant",
+      type: {
+        kind: "enum",
+        variants: [
+          {
+            name: "OneTime",
+            fields: [
+              {
+                name: "debit_amount",
+                type: "u64",
+              },
+            ],
+          },
+          {
+            name: "Recurring",
+            fields: [
+              {
+                name: "debit_amount",
+                type: "u64",
+              },
+              {
+                name: "cycle",
+                type: "u64",
+              },
+            ],
+          },
+          {
+            name: "Scheduled",
+            fields: [
+              {
+                name: "debit_amount",
+                type: "u64",
+              },
+              {
+                name: "cycle",
+                type: "u64",
+              },
+              {
+                name: "step_index",
+                type: "u8",
+              },
+              {
+                name: "step_amount_authorized",
+                type: "u64",
+              },
+            ],
+          },
+          {
+            name: "Installments",
+            fields: [
+              {
+                name: "debit_amount",
+                type: "u64",
+              },
+              {
+                name: "installments_paid",
+                type: "u64",
+              },
+              {
+                name: "installment_count",
+                type: "u64",
+              },
+            ],
+          },
+          {
+            name: "RollingWindow",
+            fields: [
+              {
+                name: "debit_amount",
+                type: "u64",
+              },
+              {
+                name: "bucket",
+                type: "u64",
+              },
+              {
+                name: "amount_debited_in_window",
+                type: "u64",
+              },
+            ],
+          },
+        ],
+      },
+    },
+    {
+      name: "InitPreAuthorizationVariant",
+      type: {
+        kind: "enum",
+        variants: [
+          {
+            name: "OneTime",
+            fields: [
+              {
+                name: "amount_authorized",
+                type: "u64",
+              },
+              {
+                name: "expiry_unix_timestamp",
+                type: "i64",
+              },
+              {
+                name: "max_amount_per_debit",
+                type: {
+                  option: "u64",
+                },
+              },
+              {
+                name: "close_on_exhaustion",
+                type: "bool",
+              },
+            ],
+          },
+          {
+            name: "Recurring",
+            fields: [
+              {
+                name: "repeat_frequency_seconds",
+                type: "u64",
+              },
+              {
+                name: "recurring_amount_authorized",
+                type: "u64",
+              },
+              {
+                name: "num_cycles",
+                type: {
+                  option: "u64",
+                },
+              },
+              {
+                name: "reset_every_cycle",
+                type: "bool",
+              },
+              {
+                name: "max_amount_per_debit",
+                type: {
+                  option: "u64",
+                },
+              },
+              {
+                name: "min_amount_per_debit",
+                type: {
+                  option: "u64",
+                },
+              },
+              {
+                name: "max_accrued_cycles",
+                type: {
+                  option: "u64",
+                },
+              },
+              {
+                name: "calendar_schedule",
+                type: {
+                  option: {
+                    defined: "CalendarSchedule",
+                  },
+                },
+              },
+              {
+                name: "debit_window_seconds",
+                type: {
+                  option: "u64",
+                },
+              },
+              {
+                name: "debit_window_offset_seconds",
+                type: {
+                  option: "u64",
+                },
+              },
+              {
+                name: "intro_schedule",
+                type: {
+                  option: {
+                    defined: "IntroSchedule",
+                  },
+                },
+              },
+            ],
           },
           {
-            name: "tokenAccount";
-            type: "publicKey";
+            name: "Scheduled",
+            fields: [
+              {
+                name: "repeat_frequency_seconds",
+                type: "u64",
+              },
+              {
+                name: "steps",
+                type: {
+                  vec: {
+                    defined: "ScheduleStep",
+                  },
+                },
+              },
+              {
+                name: "num_cycles",
+                type: {
+                  option: "u64",
+                },
+              },
+            ],
           },
           {
-            name: "preAuthorization";
-            type: "publicKey";
+            name: "Installments",
+            fields: [
+              {
+                name: "total_amount",
+                type: "u64",
+              },
+              {
+                name: "installment_count",
+                type: "u64",
+              },
+              {
+                name: "interval_seconds",
+                type: "u64",
+              },
+              {
+                name: "grace_period_seconds",
+                type: "u64",
+              },
+            ],
           },
-        ];
-      };
-    },
-    {
-      name: "DebitParams";
-      type: {
-        kind: "struct";
-        fields: [
           {
-            name: "amount";
-            type: "u64";
+            name: "RollingWindow",
+            fields: [
+              {
+                name: "window_seconds",
+                type: "u64",
+              },
+              {
+                name: "amount_authorized_per_window",
+                type: "u64",
+              },
+            ],
           },
-        ];
-      };
+        ],
+      },
     },
     {
-      name: "InitPreAuthorizationParams";
+      name: "UpdatePreAuthorizationVariant",
       type: {
-        kind: "struct";
-        fields: [
-          {
-            name: "variant";
-            type: {
-              defined: "InitPreAuthorizationVariant";
-            };
-          },
+        kind: "enum",
+        variants: [
           {
-            name: "debitAuthority";
-            type: "publicKey";
+            name: "OneTime",
+            fields: [
+              {
+                name: "amount_authorized",
+                type: "u64",
+              },
+              {
+                name: "expiry_unix_timestamp",
+                type: "i64",
+              },
+            ],
           },
           {
-            name: "activationUnixTimestamp";
-            type: "i64";
+            name: "Recurring",
+            fields: [
+              {
+                name: "recurring_amount_authorized",
+                type: "u64",
+              },
+              {
+                name: "num_cycles",
+                type: {
+                  option: "u64",
+                },
+              },
+              {
+                name: "reset_every_cycle",
+                type: "bool",
+              },
+            ],
           },
-        ];
-      };
+        ],
+      },
     },
     {
-      name: "PreAuthorizationCreatedEventData";
+      name: "MathError",
       type: {
-        kind: "struct";
-        fields: [
+        kind: "enum",
+        variants: [
           {
-            name: "debitAuthority";
-            type: "publicKey";
+            name: "ArithmeticOverflow",
           },
           {
-            name: "owner";
-            type: "publicKey";
+            name: "ArithmeticUnderflow",
           },
           {
-            name: "payer";
-            type: "publicKey";
+            name: "CurrentTimestampBeforeActivation",
           },
           {
-            name: "tokenAccount";
-            type: "publicKey";
+            name: "InvalidAmountDebited",
           },
           {
-            name: "preAuthorization";
-            type: "publicKey";
+            name: "InvalidCycle",
           },
           {
-            name: "initParams";
-            type: {
-              defined: "InitPreAuthorizationParams";
-            };
+            name: "InvalidDayOfMonth",
           },
-        ];
-      };
-    },
-    {
-      name: "UpdatePausePreAuthorizationParams";
-      type: {
-        kind: "struct";
-        fields: [
           {
-            name: "pause";
-            type: "bool";
+            name: "InvalidRepeatFrequency",
           },
-        ];
-      };
+          {
+            name: "LastDebitedCycleBeforeCurrentCycle",
+          },
+        ],
+      },
     },
     {
-      name: "PausePreAuthorizationEventData";
+      name: "PreAuthorizationVariantV1",
       type: {
-        kind: "struct";
-        fields: [
-          {
-            name: "owner";
-            type: "publicKey";
-          },
-          {
-            name: "tokenAccount";
-            type: "publicKey";
-          },
+        kind: "enum",
+        variants: [
           {
-            name: "preAuthorization";
-            type: "publicKey";
+            name: "OneTime",
+            fields: [
+              {
+                name: "amount_authorized",
+                type: "u64",
+              },
+              {
+                name: "expiry_unix_timestamp",
+                type: "i64",
+              },
+              {
+                name: "amount_debited",
+                type: "u64",
+              },
+            ],
           },
           {
-            name: "newPausedValue";
-            type: "bool";
+            name: "Recurring",
+            fields: [
+              {
+                name: "repeat_frequency_seconds",
+                type: "u64",
+              },
+              {
+                name: "recurring_amount_authorized",
+                type: "u64",
+              },
+              {
+                name: "amount_debited_last_cycle",
+                type: "u64",
+              },
+              {
+                name: "amount_debited_total",
+                type: "u64",
+              },
+              {
+                name: "last_debited_cycle",
+                type: "u64",
+              },
+              {
+                name: "num_cycles",
+                type: {
+                  option: "u64",
+                },
+              },
+              {
+                name: "reset_every_cycle",
+                type: "bool",
+              },
+            ],
           },
-        ];
-      };
+        ],
+      },
     },
     {
-      name: "DebitEventVariant";
+      name: "PreAuthorizationVariant",
       type: {
-        kind: "enum";
+        kind: "enum",
         variants: [
           {
-            name: "OneTime";
+            name: "OneTime",
             fields: [
               {
-                name: "debit_amount";
-                type: "u64";
+                name: "amount_authorized",
+                type: "u64",
+              },
+              {
+                name: "expiry_unix_timestamp",
+                type: "i64",
+              },
+              {
+                name: "amount_debited",
+                type: "u64",
+              },
+              {
+                name: "max_amount_per_debit",
+                type: {
+                  option: "u64",
+                },
+              },
+              {
+                name: "close_on_exhaustion",
+                type: "bool",
               },
-            ];
+            ],
           },
           {
-            name: "Recurring";
+            name: "Recurring",
             fields: [
               {
-                name: "debit_amount";
-                type: "u64";
+                name: "repeat_frequency_seconds",
+                type: "u64",
               },
               {
-                name: "cycle";
-                type: "u64";
+                name: "recurring_amount_authorized",
+                type: "u64",
               },
-            ];
-          },
-        ];
-      };
-    },
-    {
-      name: "InitPreAuthorizationVariant";
-      type: {
-        kind: "enum";
-        variants: [
-          {
-            name: "OneTime";
-            fields: [
               {
-                name: "amount_authorized";
-                type: "u64";
+                name: "amount_debited_last_cycle",
+                type: "u64",
               },
               {
-                name: "expiry_unix_timestamp";
-                type: "i64";
+                name: "amount_debited_total",
+                type: "u64",
               },
-            ];
-          },
-          {
-            name: "Recurring";
-            fields: [
               {
-                name: "repeat_frequency_seconds";
-                type: "u64";
+                name: "last_debited_cycle",
+                type: "u64",
               },
               {
-                name: "recurring_amount_authorized";
-                type: "u64";
+                name: "num_cycles",
+                type: {
+                  option: "u64",
+                },
               },
               {
-                name: "num_cycles";
+                name: "reset_every_cycle",
+                type: "bool",
+              },
+              {
+                name: "amended_at_cycle",
+                type: "u64",
+              },
+              {
+                name: "amount_authorized_before_amendment",
+                type: "u64",
+              },
+              {
+                name: "max_amount_per_debit",
                 type: {
-                  option: "u64";
-                };
+                  option: "u64",
+                },
               },
               {
-                name: "reset_every_cycle";
-                type: "bool";
+                name: "min_amount_per_debit",
+                type: {
+                  option: "u64",
+                },
               },
-            ];
+              {
+                name: "max_accrued_cycles",
+                type: {
+                  option: "u64",
+                },
+              },
+              {
+                name: "calendar_schedule",
+                type: {
+                  option: {
+                    defined: "CalendarSchedule",
+                  },
+                },
+              },
+              {
+                name: "debit_window_seconds",
+                type: {
+                  option: "u64",
+                },
+              },
+              {
+                name: "debit_window_offset_seconds",
+                type: {
+                  option: "u64",
+                },
+              },
+              {
+                name: "intro_schedule",
+                type: {
+                  option: {
+                    defined: "IntroSchedule",
+                  },
+                },
+              },
+            ],
           },
-        ];
-      };
-    },
-    {
-      name: "PreAuthorizationVariant";
-      type: {
-        kind: "enum";
-        variants: [
           {
-            name: "OneTime";
+            name: "Scheduled",
             fields: [
               {
-                name: "amount_authorized";
-                type: "u64";
+                name: "repeat_frequency_seconds",
+                type: "u64",
               },
               {
-                name: "expiry_unix_timestamp";
-                type: "i64";
+                name: "steps",
+                type: {
+                  vec: {
+                    defined: "ScheduleStep",
+                  },
+                },
               },
               {
-                name: "amount_debited";
-                type: "u64";
+                name: "amount_debited_last_cycle",
+                type: "u64",
               },
-            ];
+              {
+                name: "amount_debited_total",
+                type: "u64",
+              },
+              {
+                name: "last_debited_cycle",
+                type: "u64",
+              },
+              {
+                name: "num_cycles",
+                type: {
+                  option: "u64",
+                },
+              },
+            ],
           },
           {
-            name: "Recurring";
+            name: "Installments",
             fields: [
               {
-                name: "repeat_frequency_seconds";
-                type: "u64";
+                name: "total_amount",
+                type: "u64",
               },
               {
-                name: "recurring_amount_authorized";
-                type: "u64";
+                name: "installment_count",
+                type: "u64",
               },
               {
-                name: "amount_debited_last_cycle";
-                type: "u64";
+                name: "interval_seconds",
+                type: "u64",
               },
               {
-                name: "amount_debited_total";
-                type: "u64";
+                name: "grace_period_seconds",
+                type: "u64",
               },
               {
-                name: "last_debited_cycle";
-                type: "u64";
+                name: "amount_debited",
+                type: "u64",
+              },
+            ],
+          },
+          {
+            name: "RollingWindow",
+            fields: [
+              {
+                name: "window_seconds",
+                type: "u64",
               },
               {
-                name: "num_cycles";
+                name: "amount_authorized_per_window",
+                type: "u64",
+              },
+              {
+                name: "debited_buckets",
                 type: {
-                  option: "u64";
-                };
+                  array: [
+                    "u64",
+                    25,
+                  ],
+                },
               },
               {
-                name: "reset_every_cycle";
-                type: "bool";
+                name: "last_debited_bucket",
+                type: "u64",
               },
-            ];
+              {
+                name: "amount_debited_total",
+                type: "u64",
+              },
+            ],
           },
-        ];
-      };
+        ],
+      },
     },
-  ];
-  events: [
     {
-      name: "OneTimePreAuthorizationClosed";
-      fields: [
-        {
-          name: "data";
-          type: {
-            defined: "PreAuthorizationClosedEventData";
-          };
-          index: false;
-        },
-      ];
+      name: "PreAuthorizationScope",
+      type: {
+        kind: "enum",
+        variants: [
+          {
+            name: "TokenAccount",
+          },
+          {
+            name: "OwnerAndMint",
+            fields: [
+              {
+                name: "owner",
+                type: "publicKey",
+              },
+              {
+                name: "mint",
+                type: "publicKey",
+              },
+            ],
+          },
+        ],
+      },
     },
     {
-      name: "RecurringPreAuthorizationClosed";
-      fields: [
-        {
-          name: "data";
-          type: {
-            defined: "PreAuthorizationClosedEventData";
-          };
-          index: false;
-        },
-      ];
+      name: "DestinationConstraint",
+      type: {
+        kind: "enum",
+        variants: [
+          {
+            name: "TokenAccount",
+            fields: [
+              {
+                name: "token_account",
+                type: "publicKey",
+              },
+            ],
+          },
+          {
+            name: "Owner",
+            fields: [
+              {
+                name: "owner",
+                type: "publicKey",
+              },
+            ],
+          },
+        ],
+      },
     },
+  ],
+  events: [
     {
-      name: "DebitEvent";
+      name: "AmendmentAccepted",
       fields: [
         {
-          name: "preAuthorization";
-          type: "publicKey";
-          index: false;
-        },
-        {
-          name: "debitAuthority";
-          type: "publicKey";
-          index: false;
-        },
-        {
-          name: "smartDelegate";
-          type: "publicKey";
-          index: false;
-        },
-        {
-          name: "mint";
-          type: "publicKey";
-          index: false;
+          name: "owner",
+          type: "publicKey",
+          index: false,
         },
         {
-          name: "tokenProgram";
-          type: "publicKey";
-          index: false;
+          name: "debitAuthority",
+          type: "publicKey",
+          index: false,
         },
         {
-          name: "sourceTokenAccountOwner";
-          type: "publicKey";
-          index: false;
+          name: "tokenAccount",
+          type: "publicKey",
+          index: false,
         },
         {
-          name: "destinationTokenAccountOwner";
-          type: "publicKey";
-          index: false;
+          name: "preAuthorization",
+          type: "publicKey",
+          index: false,
         },
         {
-          name: "sourceTokenAccount";
-          type: "publicKey";
-          index: false;
+          name: "pendingAmendment",
+          type: "publicKey",
+          index: false,
         },
         {
-          name: "destinationTokenAccount";
-          type: "publicKey";
-          index: false;
+          name: "oldValues",
+          type: {
+            defined: "UpdatePreAuthorizationVariant",
+          },
+          index: false,
         },
         {
-          name: "debitVariant";
+          name: "newValues",
           type: {
-            defined: "DebitEventVariant";
-          };
-          index: false;
+            defined: "UpdatePreAuthorizationVariant",
+          },
+          index: false,
         },
-      ];
+      ],
     },
     {
-      name: "OneTimePreAuthorizationCreated";
+      name: "DebitReceiptClosed",
       fields: [
         {
-          name: "data";
-          type: {
-            defined: "PreAuthorizationCreatedEventData";
-          };
-          index: false;
+          name: "closingAuthority",
+          type: "publicKey",
+          index: false,
         },
-      ];
+        {
+          name: "receiver",
+          type: "publicKey",
+          index: false,
+        },
+        {
+          name: "preAuthorization",
+          type: "publicKey",
+          index: false,
+        },
+        {
+          name: "debitReceipt",
+          type: "publicKey",
+          index: false,
+        },
+        {
+          name: "debitIndex",
+          type: "u64",
+          index: false,
+        },
+      ],
     },
     {
-      name: "RecurringPreAuthorizationCreated";
+      name: "ExpiredPreAuthorizationClosed",
       fields: [
         {
-          name: "data";
+          name: "data",
           type: {
-            defined: "PreAuthorizationCreatedEventData";
-          };
-          index: false;
+            defined: "PreAuthorizationClosedEventData",
+          },
+          index: false,
         },
-      ];
-    },
-    {
-      name: "SmartDelegateInitialized";
-      fields: [
         {
-          name: "payer";
-          type: "publicKey";
-          index: false;
+          name: "bountyReceiver",
+          type: {
+            option: "publicKey",
+          },
+          index: false,
         },
         {
-          name: "smartDelegate";
-          type: "publicKey";
-          index: false;
+          name: "bountyLamports",
+          type: "u64",
+          index: false,
         },
-      ];
+      ],
     },
     {
-      name: "PreAuthorizationPaused";
+      name: "OwnerPreAuthorizationClosed",
       fields: [
         {
-          name: "data";
+          name: "data",
           type: {
-            defined: "PausePreAuthorizationEventData";
-          };
-          index: false;
+            defined: "OwnerPreAuthorizationClosedEventData",
+          },
+          index: false,
         },
-      ];
+      ],
     },
     {
-      name: "PreAuthorizationUnpaused";
+      name: "OneTimePreAuthorizationClosed",
       fields: [
         {
-          name: "data";
+          name: "data",
           type: {
-            defined: "PausePreAuthorizationEventData";
-          };
-          index: false;
+            defined: "PreAuthorizationClosedEventData",
+          },
+          index: false,
         },
-      ];
-    },
-  ];
-  errors: [
-    {
-      code: 6000;
-      name: "PreAuthorizationNotActive";
-      msg: "Pre-Authorization not active";
-    },
-    {
-      code: 6001;
-      name: "CannotDebitMoreThanAvailable";
-      msg: "Cannot debit more than authorized";
-    },
-    {
-      code: 6002;
-      name: "LastDebitedCycleBeforeCurrentCycle";
-      msg: "Last debited cycle is after current debited cycle (invalid state)";
-    },
-    {
-      code: 6003;
-      name: "InvalidTimestamp";
-      msg: "Invalid timestamp value provided";
-    },
-    {
-      code: 6004;
-      name: "PreAuthorizationPaused";
-      msg: "Pre-Authorization paused";
-    },
-    {
-      code: 6005;
-      name: "OnlyTokenAccountOwnerCanReceiveClosePreAuthFunds";
-      msg: "Only token account owner can receive funds from closing pre-authorization account";
-    },
-    {
-      code: 6006;
-      name: "PreAuthorizationTokenAccountMismatch";
-      msg: "Pre-authorization and token account mismatch";
-    },
-    {
-      code: 6007;
-      name: "PreAuthorizationCloseUnauthorized";
-      msg: "Pre-authorization can only be closed by debit_authority or token_account.owner";
-    },
-    {
-      code: 6008;
-      name: "SmartDelegateCloseUnauthorized";
-      msg: "Smart delegate can only be closed by token account owner";
-    },
-    {
-      code: 6009;
-      name: "PausePreAuthorizationUnauthorized";
-      msg: "Only token account owner can pause a pre-authorization";
-    },
-    {
-      code: 6010;
-      name: "DebitUnauthorized";
-      msg: "Only pre_authorization.debit_authority is authorized to debit funds using pre-authorizations";
-    },
-    {
-      code: 6011;
-      name: "InitPreAuthorizationUnauthorized";
-      msg: "Only token account owner can initialize a pre-authorization";
-    },
-    {
-      code: 6012;
-      name: "InitSmartDelegateUnauthorized";
-      msg: "Only token account owner can initialize a smart delegate";
+      ],
     },
-  ];
-};
-
-export const IDL: PreAuthorizedDebitV1 = {
-  version: "1.0.0",
-  name: "pre_authorized_debit_v1",
-  instructions: [
     {
-      name: "initSmartDelegate",
-      docs: [
-        "The `InitSmartDelegate` instruction will create a global `smart_delegate` account.\n\n    Initializes a new account (`smart_delegate`).\n    The `smart_delegate` PDA is used by the `pre_authorized_debit` program to sign for\n    valid pre-authorized debits to transfer funds from the `pre_authorization.token_account`.\n    The `smart_delegate` account can NEVER be closed.\n\n    The `payer` MUST sign the transaction.\n    The `payer` MUST have enough lamports to pay for the `smart_delegate` account.\n\n    Accounts expected by this instruction:\n        0. `[writable]` payer\n        1. `[writable]` smart_delegate\n        2. `[]`         system_program",
-      ],
-      accounts: [
-        {
-          name: "payer",
-          isMut: true,
-          isSigner: true,
-        },
-        {
-          name: "smartDelegate",
-          isMut: true,
-          isSigner: false,
-        },
+      name: "RecurringPreAuthorizationClosed",
+      fields: [
         {
-          name: "systemProgram",
-          isMut: false,
-          isSigner: false,
+          name: "data",
+          type: {
+            defined: "PreAuthorizationClosedEventData",
+          },
+          index: false,
         },
       ],
-      args: [],
     },
     {
-      name: "initPreAuthorization",
-      docs: [
-        "The `InitPreAuthorization` instruction will create a `pre_authorization` account.\n\n    Initializes a new account (`pre_authorization`).\n    The `pre_authorization` defines a set of rules.\n    The `pre_authorization` rules/constraints are verified during a `debit` instruction.\n    The `pre_authorization` in conjunction with a `smart_delegate` for the same `token_account`\n    can allow the `pre_authorization.debit_authority` to do a one-time or recurring debit from the\n    `token_account.\n    For a pair of `debit_authority` and `token_account`, only a single `pre_authorization` account can exist.\n    To create another `pre_authorization` for the same `token_account`, another `debit_authority` must be used.\n\n    The `payer` MUST sign the transaction.\n    The `payer` MUST have enough lamports to pay for the `pre_authorization` account.\n    The `owner` MUST sign the transaction.\n    The `owner` MUST be the `token_account.owner`.\n    The `payer` and `owner` may be the same account.\n    The `token_account.owner` MUST be the `owner`.\n    The `pre_authorization.token_account` must be the same as `token_account`.\n\n    Accounts expected by this instruction:\n        0. `[writable]` payer\n        1. `[]`         owner\n        2. `[writable]` token_account\n        3. `[writable]` pre_authorization\n        4. `[]`         system_program",
-      ],
-      accounts: [
-        {
-          name: "payer",
-          isMut: true,
-          isSigner: true,
-        },
-        {
-          name: "owner",
-          isMut: false,
-          isSigner: true,
-        },
-        {
-          name: "smartDelegate",
-          isMut: false,
-          isSigner: false,
-        },
-        {
-          name: "tokenAccount",
-          isMut: true,
-          isSigner: false,
-        },
-        {
-          name: "preAuthorization",
-          isMut: true,
-          isSigner: false,
-        },
+      name: "ScheduledPreAuthorizationClosed",
+      fields: [
         {
-          name: "tokenProgram",
-          isMut: false,
-          isSigner: false,
+          name: "data",
+          type: {
+            defined: "PreAuthorizationClosedEventData",
+          },
+          index: false,
         },
-        {
-          name: "systemProgram",
-          isMut: false,
-          isSigner: false,
+      ],
+    },
+    {
+      name: "InstallmentsPreAuthorizationClosed",
+      fields: [
+        {
+          name: "data",
+          type: {
+            defined: "PreAuthorizationClosedEventData",
+          },
+          index: false,
         },
       ],
-      args: [
+    },
+    {
+      name: "RollingWindowPreAuthorizationClosed",
+      fields: [
         {
-          name: "params",
+          name: "data",
           type: {
-            defined: "InitPreAuthorizationParams",
+            defined: "PreAuthorizationClosedEventData",
           },
+          index: false,
         },
       ],
     },
     {
-      name: "closePreAuthorization",
-      docs: [
-        "The `ClosePreAuthorization` instruction will close a `pre_authorization` account.\n\n    Closes an existing `pre_authorization` account and refunds the lamports\n    to the `token_account.owner` (`receiver`).\n\n    The `receiver` will receive all lamports from the closed account.\n    The `receiver` MUST be the `token_account.owner`.\n    The `authority` MUST sign for the instruction.\n    The `authority` MUST be either the `token_account.owner` or the `pre_authorization.debit_authority`.\n    The `owner` MUST be the `token_account.owner`.\n    The `token_account.owner` MUST be the `owner`.\n    The `pre_authorization.token_account` must be the same as `token_account`.\n\n    Accounts expected by this instruction:\n        0. `[writable]` receiver\n        1. `[]`         authority\n        2. `[]`         token_account\n        3. `[writable]` pre_authorization",
-      ],
-      accounts: [
+      name: "DebitBatchProcessed",
+      fields: [
         {
-          name: "receiver",
-          isMut: true,
-          isSigner: false,
+          name: "debitAuthority",
+          type: "publicKey",
+          index: false,
         },
         {
-          name: "authority",
-          isMut: false,
-          isSigner: true,
+          name: "mint",
+          type: "publicKey",
+          index: false,
         },
         {
-          name: "tokenAccount",
-          isMut: false,
-          isSigner: false,
+          name: "numEntries",
+          type: "u16",
+          index: false,
         },
         {
-          name: "preAuthorization",
-          isMut: true,
-          isSigner: false,
+          name: "failedEntries",
+          type: {
+            vec: {
+              defined: "DebitBatchFailedEntry",
+            },
+          },
+          index: false,
         },
       ],
-      args: [],
     },
     {
-      name: "debit",
-      docs: [
-        "The `Debit` instruction allows a `pre_authorization.debit_authority` to debit from the\n    `pre_authorization.token_account` via the `smart_delegate` PDA. To successfully debit from\n    the `token_account`, the constraints for the `pre_authorization` must be met.\n\n    Definitions:\n      - PA = pre_authorization\n\n    Common Rules:\n    - The `pre_authorization` MUST not be paused.\n    - The amount being requested to debit must be less than or equal to the available amount for the current_cycle\n    - The current timestamp must be less than the `PA.expiry_unix_timestamp`\n    - If the PA has a `num_cycles` defined, the `current_cycle` must be less than or equal to `PA.num_cycles`\n\n    For a recurring pre-authorization:\n    - The debit_authority must not have already done a debit in the current cycle\n\n    For a one-time pre-authorization:\n    - the validator time must be greater than or equal to the `pre_authorization.activation_unix_timestamp`\n\n    For a more in-depth understanding around the constraints in a debit, it is recommended to read through\n    the validation done for a `debit` instruction.\n\n    The `debit_authority` MUST sign the transaction.\n    The `debit_authority` MUST equal the `pre_authorization.debit_authority`.\n    The `mint` MUST equal `token_account.mint` and `destination_token_account.mint`.\n    The `token_account.delegate` MUST equal the `smart_delegate`.\n    The `token_account.mint` MUST equal the `mint`.\n    The `destination_token_account.mint` MUST equal `mint`.\n    The `pre_authorization.token_account` MUST equal the `token_account`.\n    The `token_program` MUST equal the token program matching the `token_account`.\n\n    Accounts expected by this instruction:\n        0. `[]`         debit_authority\n        1. `[]`         mint\n        2. `[writable]` token_account\n        3. `[writable]` destination_token_account\n        4. `[]`         smart_delegate\n        5. `[writable]` pre_authorization\n        6. `[]`         token_program",
-      ],
-      accounts: [
-        {
-          name: "debitAuthority",
-          isMut: false,
-          isSigner: true,
-        },
-        {
-          name: "mint",
-          isMut: false,
-          isSigner: false,
-        },
+      name: "DebitEvent",
+      fields: [
         {
-          name: "tokenAccount",
-          isMut: true,
-          isSigner: false,
+          name: "preAuthorization",
+          type: "publicKey",
+          index: false,
         },
         {
-          name: "destinationTokenAccount",
-          isMut: true,
-          isSigner: false,
+          name: "debitAuthority",
+          type: "publicKey",
+          index: false,
         },
         {
           name: "smartDelegate",
-          isMut: false,
-          isSigner: false,
+          type: "publicKey",
+          index: false,
         },
         {
-          name: "preAuthorization",
-          isMut: true,
-          isSigner: false,
+          name: "mint",
+          type: "publicKey",
+          index: false,
         },
         {
           name: "tokenProgram",
-          isMut: false,
-          isSigner: false,
+          type: "publicKey",
+          index: false,
         },
-      ],
-      args: [
         {
-          name: "params",
-          type: {
-            defined: "DebitParams",
-          },
+          name: "sourceTokenAccountOwner",
+          type: "publicKey",
+          index: false,
         },
-      ],
-    },
-    {
-      name: "updatePausePreAuthorization",
-      docs: [
-        "The `UpdatePausePreAuthorization` instruction allows a `token_account.owner` to pause a\n    `pre_authorization`.\n\n    The `owner` MUST sign the transaction.\n    The `owner` MUST equal the `token_account.owner`.\n    The `token_account.owner` MUST equal the `owner`.\n    The `pre_authorization.token_account` MUST equal the `token_account`.\n\n    Accounts expected by this instruction:\n        0. `[writable]` owner\n        2. `[]`         token_account\n        3. `[writable]` pre_authorization",
-      ],
-      accounts: [
         {
-          name: "owner",
-          isMut: false,
-          isSigner: true,
+          name: "destinationTokenAccountOwner",
+          type: "publicKey",
+          index: false,
         },
         {
-          name: "tokenAccount",
-          isMut: false,
-          isSigner: false,
+          name: "sourceTokenAccount",
+          type: "publicKey",
+          index: false,
         },
         {
-          name: "preAuthorization",
-          isMut: true,
-          isSigner: false,
+          name: "destinationTokenAccount",
+          type: "publicKey",
+          index: false,
         },
-      ],
-      args: [
         {
-          name: "params",
-          type: {
-            defined: "UpdatePausePreAuthorizationParams",
-          },
+          name: "requestedAmount",
+          type: "u64",
+          index: false,
         },
-      ],
-    },
-  ],
-  accounts: [
-    {
-      name: "preAuthorization",
-      docs: [
-        "The `pre_authorization` is a PDA account derived with the seeds:\n ['pre-authorization', token_account, debit_authority].\n The `pre_authorization` can be thought of as the rule for the `smart_delegate`.\n The `pre_authorization` can be used to validate a recurring or one-time debit from the `token_account`.\n The `smart_delegate` will validate the rules of the `pre_authorization` in the `debit` instruction.\n A `pre_authorization` is associated many:1 with a `token_account`,\n however, for a given `debit_authority` and `token_account` there can only be one `pre_authorization`.",
-      ],
-      type: {
-        kind: "struct",
-        fields: [
-          {
-            name: "bump",
-            docs: [
-              "The `bump` is the canonical PDA bump when derived with seeds:\n      ['pre-authorization', token_account, debit_authority].\n      This field is initialized in `init_pre_authorization`.\n      This field is never updated in any instruction.",
-            ],
-            type: "u8",
-          },
-          {
-            name: "paused",
-            docs: [
-              "If `paused === true`, then the `debit_authority` cannot debit via the `token_account`.\n      This field is initialized to `false` in `init_pre_authorization`.\n      This field can be updated by the `token_account.owner` in `update_pause_pre_authorization`.\n      Note that recurring rebits that accrue the amounts across cycles will continue to do so while paused\n        (close the pre-authorization to stop this).",
-            ],
-            type: "bool",
-          },
-          {
-            name: "tokenAccount",
-            docs: [
-              "The `token_account` is the account the `debit_authority` will be able to debit from.\n      This field is initialized in `init_pre_authorization`.\n      This field is never updated in any instruction.",
-            ],
-            type: "publicKey",
-          },
-          {
-            name: "variant",
-            docs: [
-              "The `variant` contains the data specific to a one-time\n      or recurring debit.\n      This field is initialized in `init_pre_authorization`.\n      This field is never updated in any instruction.",
-            ],
-            type: {
-              defined: "PreAuthorizationVariant",
-            },
-          },
-          {
-            name: "debitAuthority",
-            docs: [
-              "The `debit_authority` is the signer that can debit from the `token_account`.\n      This field is initialized in `init_pre_authorization`.\n      This field is never updated in any instruction.",
-            ],
-            type: "publicKey",
-          },
-          {
-            name: "activationUnixTimestamp",
-            docs: [
-              "The `activation_unix_timestamp` represents when the pre-authorization becomes active (i.e. debits can occur).\n      The field is initialized in `init_pre_authorization`.",
-            ],
-            type: "i64",
-          },
-        ],
-      },
-    },
-    {
-      name: "smartDelegate",
-      docs: [
-        "The `smart_delegate` is a PDA account derived with the seeds:\n  ['smart-delegate'].\n  The `smart_delegate` should be set as the delegate of any\n  `token_account` specified in a `pre_authorization`.\n  The `smart_delegate` is a global account and is only initialized once.",
-      ],
-      type: {
-        kind: "struct",
-        fields: [
-          {
-            name: "bump",
-            docs: [
-              "The `bump` is the canonical PDA bump when derived with seeds:\n      ['smart-delegate'].\n      This field is initialized in `init_smart_delegate`.\n      This field is never updated in any instruction.",
-            ],
-            type: "u8",
-          },
-        ],
-      },
-    },
-  ],
-  types: [
-    {
-      name: "PreAuthorizationClosedEventData",
-      type: {
-        kind: "struct",
-        fields: [
-          {
-            name: "debitAuthority",
-            type: "publicKey",
-          },
-          {
-            name: "closingAuthority",
-            type: "publicKey",
-          },
-          {
-            name: "tokenAccountOwner",
-            type: "publicKey",
-          },
-          {
-            name: "receiver",
-            type: "publicKey",
-          },
-          {
-            name: "tokenAccount",
-            type: "publicKey",
-          },
-          {
-            name: "preAuthorization",
-            type: "publicKey",
-          },
-        ],
-      },
-    },
-    {
-      name: "DebitParams",
-      type: {
-        kind: "struct",
-        fields: [
-          {
-            name: "amount",
-            type: "u64",
-          },
-        ],
-      },
-    },
-    {
-      name: "InitPreAuthorizationParams",
-      type: {
-        kind: "struct",
-        fields: [
-          {
-            name: "variant",
-            type: {
-              defined: "InitPreAuthorizationVariant",
+        {
+          name: "reference",
+          type: {
+            option: {
+              array: [
+                "u8",
+                32,
+              ],
             },
           },
-          {
-            name: "debitAuthority",
-            type: "publicKey",
-          },
-          {
-            name: "activationUnixTimestamp",
-            type: "i64",
-          },
-        ],
-      },
-    },
-    {
-      name: "PreAuthorizationCreatedEventData",
-      type: {
-        kind: "struct",
-        fields: [
-          {
-            name: "debitAuthority",
-            type: "publicKey",
-          },
-          {
-            name: "owner",
-            type: "publicKey",
-          },
-          {
-            name: "payer",
-            type: "publicKey",
-          },
-          {
-            name: "tokenAccount",
-            type: "publicKey",
-          },
-          {
-            name: "preAuthorization",
-            type: "publicKey",
-          },
-          {
-            name: "initParams",
-            type: {
-              defined: "InitPreAuthorizationParams",
-            },
+          index: false,
+        },
+        {
+          name: "debitReceipt",
+          type: {
+            option: "publicKey",
           },
-        ],
-      },
-    },
-    {
-      name: "UpdatePausePreAuthorizationParams",
-      type: {
-        kind: "struct",
-        fields: [
-          {
-            name: "pause",
-            type: "bool",
+          index: false,
+        },
+        {
+          name: "debitVariant",
+          type: {
+            defined: "DebitEventVariant",
           },
-        ],
-      },
+          index: false,
+        },
+      ],
     },
     {
-      name: "PausePreAuthorizationEventData",
-      type: {
-        kind: "struct",
-        fields: [
-          {
-            name: "owner",
-            type: "publicKey",
-          },
-          {
-            name: "tokenAccount",
-            type: "publicKey",
-          },
-          {
-            name: "preAuthorization",
-            type: "publicKey",
-          },
-          {
-            name: "newPausedValue",
-            type: "bool",
+      name: "OwnerPreAuthorizationCreated",
+      fields: [
+        {
+          name: "data",
+          type: {
+            defined: "OwnerPreAuthorizationCreatedEventData",
           },
-        ],
-      },
+          index: false,
+        },
+      ],
     },
     {
-      name: "DebitEventVariant",
-      type: {
-        kind: "enum",
-        variants: [
-          {
-            name: "OneTime",
-            fields: [
-              {
-                name: "debit_amount",
-                type: "u64",
-              },
-            ],
-          },
-          {
-            name: "Recurring",
-            fields: [
-              {
-                name: "debit_amount",
-                type: "u64",
-              },
-              {
-                name: "cycle",
-                type: "u64",
-              },
-            ],
-          },
-        ],
-      },
+      name: "PreAuthorizationCounterInitialized",
+      fields: [
+        {
+          name: "owner",
+          type: "publicKey",
+          index: false,
+        },
+        {
+          name: "tokenAccount",
+          type: "publicKey",
+          index: false,
+        },
+        {
+          name: "preAuthorizationCounter",
+          type: "publicKey",
+          index: false,
+        },
+        {
+          name: "oldCount",
+          type: "u64",
+          index: false,
+        },
+        {
+          name: "count",
+          type: "u64",
+          index: false,
+        },
+      ],
     },
     {
-      name: "InitPreAuthorizationVariant",
-      type: {
-        kind: "enum",
-        variants: [
-          {
-            name: "OneTime",
-            fields: [
-              {
-                name: "amount_authorized",
-                type: "u64",
-              },
-              {
-                name: "expiry_unix_timestamp",
-                type: "i64",
-              },
-            ],
-          },
-          {
-            name: "Recurring",
-            fields: [
-              {
-                name: "repeat_frequency_seconds",
-                type: "u64",
-              },
-              {
-                name: "recurring_amount_authorized",
-                type: "u64",
-              },
-              {
-                name: "num_cycles",
-                type: {
-                  option: "u64",
-                },
-              },
-              {
-                name: "reset_every_cycle",
-                type: "bool",
-              },
-            ],
+      name: "OneTimePreAuthorizationCreated",
+      fields: [
+        {
+          name: "data",
+          type: {
+            defined: "PreAuthorizationCreatedEventData",
           },
-        ],
-      },
+          index: false,
+        },
+      ],
     },
     {
-      name: "PreAuthorizationVariant",
-      type: {
-        kind: "enum",
-        variants: [
-          {
-            name: "OneTime",
-            fields: [
-              {
-                name: "amount_authorized",
-                type: "u64",
-              },
-              {
-                name: "expiry_unix_timestamp",
-                type: "i64",
-              },
-              {
-                name: "amount_debited",
-                type: "u64",
-              },
-            ],
-          },
-          {
-            name: "Recurring",
-            fields: [
-              {
-                name: "repeat_frequency_seconds",
-                type: "u64",
-              },
-              {
-                name: "recurring_amount_authorized",
-                type: "u64",
-              },
-              {
-                name: "amount_debited_last_cycle",
-                type: "u64",
-              },
-              {
-                name: "amount_debited_total",
-                type: "u64",
-              },
-              {
-                name: "last_debited_cycle",
-                type: "u64",
-              },
-              {
-                name: "num_cycles",
-                type: {
-                  option: "u64",
-                },
-              },
-              {
-                name: "reset_every_cycle",
-                type: "bool",
-              },
-            ],
+      name: "RecurringPreAuthorizationCreated",
+      fields: [
+        {
+          name: "data",
+          type: {
+            defined: "PreAuthorizationCreatedEventData",
           },
-        ],
-      },
+          index: false,
+        },
+      ],
     },
-  ],
-  events: [
     {
-      name: "OneTimePreAuthorizationClosed",
+      name: "ScheduledPreAuthorizationCreated",
       fields: [
         {
           name: "data",
           type: {
-            defined: "PreAuthorizationClosedEventData",
+            defined: "PreAuthorizationCreatedEventData",
           },
           index: false,
         },
       ],
     },
     {
-      name: "RecurringPreAuthorizationClosed",
+      name: "InstallmentsPreAuthorizationCreated",
       fields: [
         {
           name: "data",
           type: {
-            defined: "PreAuthorizationClosedEventData",
+            defined: "PreAuthorizationCreatedEventData",
           },
           index: false,
         },
       ],
     },
     {
-      name: "DebitEvent",
+      name: "RollingWindowPreAuthorizationCreated",
       fields: [
         {
-          name: "preAuthorization",
-          type: "publicKey",
+          name: "data",
+          type: {
+            defined: "PreAuthorizationCreatedEventData",
+          },
           index: false,
         },
+      ],
+    },
+    {
+      name: "SmartDelegateInitialized",
+      fields: [
         {
-          name: "debitAuthority",
+          name: "payer",
           type: "publicKey",
           index: false,
         },
@@ -1332,84 +5827,147 @@ export const IDL: PreAuthorizedDebitV1 = {
           type: "publicKey",
           index: false,
         },
+      ],
+    },
+    {
+      name: "PreAuthorizationMigrated",
+      fields: [
         {
-          name: "mint",
+          name: "payer",
           type: "publicKey",
           index: false,
         },
         {
-          name: "tokenProgram",
+          name: "tokenAccount",
           type: "publicKey",
           index: false,
         },
         {
-          name: "sourceTokenAccountOwner",
+          name: "debitAuthority",
           type: "publicKey",
           index: false,
         },
         {
-          name: "destinationTokenAccountOwner",
+          name: "preAuthorization",
           type: "publicKey",
           index: false,
         },
+      ],
+    },
+    {
+      name: "AmendmentProposed",
+      fields: [
         {
-          name: "sourceTokenAccount",
+          name: "payer",
           type: "publicKey",
           index: false,
         },
         {
-          name: "destinationTokenAccount",
+          name: "debitAuthority",
           type: "publicKey",
           index: false,
         },
         {
-          name: "debitVariant",
-          type: {
-            defined: "DebitEventVariant",
-          },
+          name: "tokenAccount",
+          type: "publicKey",
           index: false,
         },
-      ],
-    },
-    {
-      name: "OneTimePreAuthorizationCreated",
-      fields: [
         {
-          name: "data",
+          name: "preAuthorization",
+          type: "publicKey",
+          index: false,
+        },
+        {
+          name: "pendingAmendment",
+          type: "publicKey",
+          index: false,
+        },
+        {
+          name: "newValues",
           type: {
-            defined: "PreAuthorizationCreatedEventData",
+            defined: "UpdatePreAuthorizationVariant",
           },
           index: false,
         },
+        {
+          name: "acceptanceDeadlineUnixTimestamp",
+          type: "i64",
+          index: false,
+        },
       ],
     },
     {
-      name: "RecurringPreAuthorizationCreated",
+      name: "SmartDelegateReapproved",
       fields: [
         {
-          name: "data",
-          type: {
-            defined: "PreAuthorizationCreatedEventData",
-          },
+          name: "owner",
+          type: "publicKey",
+          index: false,
+        },
+        {
+          name: "tokenAccount",
+          type: "publicKey",
+          index: false,
+        },
+        {
+          name: "smartDelegate",
+          type: "publicKey",
+          index: false,
+        },
+        {
+          name: "delegatedAmount",
+          type: "u64",
           index: false,
         },
       ],
     },
     {
-      name: "SmartDelegateInitialized",
+      name: "AmendmentRejected",
       fields: [
         {
-          name: "payer",
+          name: "rejectingAuthority",
           type: "publicKey",
           index: false,
         },
         {
-          name: "smartDelegate",
+          name: "debitAuthority",
+          type: "publicKey",
+          index: false,
+        },
+        {
+          name: "tokenAccount",
+          type: "publicKey",
+          index: false,
+        },
+        {
+          name: "preAuthorization",
+          type: "publicKey",
+          index: false,
+        },
+        {
+          name: "pendingAmendment",
+          type: "publicKey",
+          index: false,
+        },
+        {
+          name: "receiver",
           type: "publicKey",
           index: false,
         },
       ],
     },
+    {
+      name: "DebitAuthorityRotated",
+      fields: [
+        {
+          name: "data",
+          type: {
+            defined: "DebitAuthorityRotatedEventData",
+          },
+          index: false,
+        },
+      ],
+    },
     {
       name: "PreAuthorizationPaused",
       fields: [
@@ -1434,6 +5992,18 @@ export const IDL: PreAuthorizedDebitV1 = {
         },
       ],
     },
+    {
+      name: "PreAuthorizationUpdated",
+      fields: [
+        {
+          name: "data",
+          type: {
+            defined: "PreAuthorizationUpdatedEventData",
+          },
+          index: false,
+        },
+      ],
+    },
   ],
   errors: [
     {
@@ -1501,5 +6071,305 @@ export const IDL: PreAuthorizedDebitV1 = {
       name: "InitSmartDelegateUnauthorized",
       msg: "Only token account owner can initialize a smart delegate",
     },
+    {
+      code: 6013,
+      name: "ArithmeticOverflow",
+      msg: "Arithmetic overflow",
+    },
+    {
+      code: 6014,
+      name: "ArithmeticUnderflow",
+      msg: "Arithmetic underflow",
+    },
+    {
+      code: 6015,
+      name: "CurrentTimestampBeforeActivation",
+      msg: "Current timestamp is before the pre-authorization activation timestamp",
+    },
+    {
+      code: 6016,
+      name: "InvalidRepeatFrequency",
+      msg: "Repeat frequency seconds must be greater than 0",
+    },
+    {
+      code: 6017,
+      name: "InvalidCycle",
+      msg: "Recurring cycles start at 1 (invalid state)",
+    },
+    {
+      code: 6018,
+      name: "InvalidAmountDebited",
+      msg: "Amount debited in the last cycle is greater than the total amount debited (invalid state)",
+    },
+    {
+      code: 6019,
+      name: "ExpiryBeforeActivation",
+      msg: "Pre-authorization expiry timestamp must be after its activation timestamp",
+    },
+    {
+      code: 6020,
+      name: "InvalidNumCycles",
+      msg: "Number of cycles must be greater than 0 and the last cycle must end before i64::MAX",
+    },
+    {
+      code: 6021,
+      name: "TotalAmountAuthorizedOverflow",
+      msg: "Recurring amount authorized across all cycles overflows u64",
+    },
+    {
+      code: 6022,
+      name: "UpdatePreAuthorizationUnauthorized",
+      msg: "Only token account owner can update a pre-authorization",
+    },
+    {
+      code: 6023,
+      name: "PreAuthorizationVariantMismatch",
+      msg: "Pre-authorization variant cannot be changed",
+    },
+    {
+      code: 6024,
+      name: "AmountAuthorizedBelowAmountDebited",
+      msg: "Amount authorized cannot be less than the amount already debited",
+    },
+    {
+      code: 6025,
+      name: "ProposeAmendmentUnauthorized",
+      msg: "Only pre_authorization.debit_authority can propose an amendment",
+    },
+    {
+      code: 6026,
+      name: "AcceptAmendmentUnauthorized",
+      msg: "Only token account owner can accept an amendment",
+    },
+    {
+      code: 6027,
+      name: "RejectAmendmentUnauthorized",
+      msg: "Amendment can only be rejected by token_account.owner or debit_authority",
+    },
+    {
+      code: 6028,
+      name: "OnlyAmendmentPayerCanReceiveFunds",
+      msg: "Only the amendment payer can receive funds from closing the pending amendment account",
+    },
+    {
+      code: 6029,
+      name: "InvalidAcceptanceDeadline",
+      msg: "Amendment acceptance deadline must be in the future",
+    },
+    {
+      code: 6030,
+      name: "AmendmentExpired",
+      msg: "Amendment acceptance deadline has passed",
+    },
+    {
+      code: 6031,
+      name: "AmendmentMismatch",
+      msg: "Amendment terms do not match the expected terms",
+    },
+    {
+      code: 6032,
+      name: "RotateDebitAuthorityUnauthorized",
+      msg: "Only pre_authorization.debit_authority can rotate the debit authority",
+    },
+    {
+      code: 6033,
+      name: "OwnerSignatureRequiredForRotation",
+      msg: "Token account owner signature is required to rotate the debit authority",
+    },
+    {
+      code: 6034,
+      name: "NewDebitAuthorityMatchesCurrent",
+      msg: "New debit authority must be different from the current debit authority",
+    },
+    {
+      code: 6035,
+      name: "PendingAmendmentExists",
+      msg: "Pre-authorization has a pending amendment",
+    },
+    {
+      code: 6036,
+      name: "DestinationNotAllowed",
+      msg: "Destination token account is not allowed by the pre-authorization",
+    },
+    {
+      code: 6037,
+      name: "DebitAmountAboveMaximum",
+      msg: "Debit amount is above the maximum amount per debit",
+    },
+    {
+      code: 6038,
+      name: "DebitAmountBelowMinimum",
+      msg: "Debit amount is below the minimum amount per debit",
+    },
+    {
+      code: 6039,
+      name: "InvalidAmountPerDebitBounds",
+      msg: "Invalid minimum or maximum amount per debit",
+    },
+    {
+      code: 6040,
+      name: "InvalidMaxAccruedCycles",
+      msg: "Max accrued cycles must be greater than 0",
+    },
+    {
+      code: 6041,
+      name: "InvalidDayOfMonth",
+      msg: "Day of month must be between 1 and 31",
+    },
+    {
+      code: 6042,
+      name: "OutsideDebitWindow",
+      msg: "Debit is outside of the debit window of the current cycle",
+    },
+    {
+      code: 6043,
+      name: "InvalidDebitWindow",
+      msg: "Debit window must be non-empty and start within the shortest cycle",
+    },
+    {
+      code: 6044,
+      name: "InvalidIntroSchedule",
+      msg: "Intro schedule must have at least one intro cycle",
+    },
+    {
+      code: 6045,
+      name: "InvalidScheduleSteps",
+      msg: "Schedule steps must start at cycle 1 and be strictly increasing (at most 12 steps)",
+    },
+    {
+      code: 6046,
+      name: "InvalidRollingWindow",
+      msg: "Rolling window must be a non-zero multiple of the number of buckets",
+    },
+    {
+      code: 6047,
+      name: "ReceiverRequiredForCloseOnExhaustion",
+      msg: "Receiver is required to close an exhausted one-time pre-authorization",
+    },
+    {
+      code: 6048,
+      name: "PreAuthorizationNotExpired",
+      msg: "Pre-authorization can still be debited",
+    },
+    {
+      code: 6049,
+      name: "PreAuthorizationCounterRequired",
+      msg: "Pre-authorization counter of the token account is required",
+    },
+    {
+      code: 6050,
+      name: "DelegatedAmountExhausted",
+      msg: "Delegated amount of the smart delegate is below the debit amount (exhausted or changed outside of the program)",
+    },
+    {
+      code: 6051,
+      name: "DelegationModeMismatch",
+      msg: "Bounded delegation mode must match the other pre-authorizations of the token account",
+    },
+    {
+      code: 6052,
+      name: "UnboundedPreAuthorization",
+      msg: "Bounded delegation requires a pre-authorization with a bounded amount authorized",
+    },
+    {
+      code: 6053,
+      name: "BoundedDelegationNotSupported",
+      msg: "Bounded delegation is only supported for pre-authorizations scoped to a token account",
+    },
+    {
+      code: 6054,
+      name: "SmartDelegateNotApproved",
+      msg: "Smart delegate is not the delegate of the token account (approve it with reapprove_smart_delegate)",
+    },
+    {
+      code: 6055,
+      name: "ReapproveSmartDelegateUnauthorized",
+      msg: "Only token account owner can reapprove the smart delegate",
+    },
+    {
+      code: 6056,
+      name: "MissingPreAuthorizations",
+      msg: "All the open pre-authorizations of the token account must be provided once",
+    },
+    {
+      code: 6057,
+      name: "InvalidDebitBatch",
+      msg: "Debit batch must have 1 to 65535 entries of 3 distinct writable remaining accounts, one per pre-authorization",
+    },
+    {
+      code: 6058,
+      name: "MintMismatch",
+      msg: "Token account mint does not match the mint",
+    },
+    {
+      code: 6059,
+      name: "InsufficientTokenAccountBalance",
+      msg: "Token account balance is below the debit amount",
+    },
+    {
+      code: 6060,
+      name: "NothingToDebit",
+      msg: "Nothing can be debited from the token account",
+    },
+    {
+      code: 6061,
+      name: "InvalidMemoProgram",
+      msg: "Memo program must be the SPL Memo program",
+    },
+    {
+      code: 6062,
+      name: "ReferenceRequiredForMemo",
+      msg: "Reference is required to log a memo",
+    },
+    {
+      code: 6063,
+      name: "ReceiptPayerRequired",
+      msg: "Receipt payer is required to create a debit receipt",
+    },
+    {
+      code: 6064,
+      name: "CloseDebitReceiptUnauthorized",
+      msg: "Debit receipt can only be closed by its payer or debit authority",
+    },
+    {
+      code: 6065,
+      name: "OnlyDebitReceiptPayerCanReceiveFunds",
+      msg: "Only the debit receipt payer can receive the lamports of a closed debit receipt",
+    },
+    {
+      code: 6066,
+      name: "DebitReceiptRetentionPeriodNotOver",
+      msg: "Debit receipt retention period is not over",
+    },
+    {
+      code: 6067,
+      name: "PreAuthorizationCounterNotVerified",
+      msg: "Pre-authorization counter count must be set by the token account owner first (init_pre_authorization_counter)",
+    },
+    {
+      code: 6068,
+      name: "InitPreAuthorizationCounterUnauthorized",
+      msg: "Only token account owner can initialize the pre-authorization counter",
+    },
+    {
+      code: 6069,
+      name: "OwnerScopedDebitInBoundedDelegation",
+      msg: "Owner scoped pre-authorizations cannot debit a token account in bounded delegation mode",
+    },
+    {
+      code: 6070,
+      name: "PreAuthorizationCounterAlreadyVerified",
+      msg: "Pre-authorization counter count is already verified",
+    },
+    {
+      code: 6071,
+      name: "PreAuthorizationCounterInBoundedDelegation",
+      msg: "Pre-authorization counter count cannot be set in bounded delegation mode",
+    },
+    {
+      code: 6072,
+      name: "PreAuthorizationNotV1",
+      msg: "Pre-authorization is not a v1.0.0 pre-authorization",
+    },
   ],
 };
@@ -9,6 +9,7 @@ export type PreAuthorizationVariantOneTime = {
   amountAuthorized: bigint;
   amountDebited: bigint;
   expiryUnixTimestamp: bigint;
+  closeOnExhaustion: boolean;
 };
 
 export type PreAuthorizationVariantRecurring = {
@@ -22,6 +23,14 @@ export type PreAuthorizationVariantRecurring = {
   lastDebitedCycle: bigint;
 };
 
+export type PreAuthorizationCounterAccount = {
+  bump: number;
+  tokenAccount: PublicKey;
+  count: bigint;
+  boundedDelegation: boolean;
+  countVerified: boolean;
+};
+
 type PreAuthorizationBase<T> = {
   bump: number;
   tokenAccount: PublicKey;
@@ -116,3 +125,12 @@ export function isOneTimePreAuthorizationAccount(
 ): preAuthorization is OneTimePreAuthorizationAccount {
   return preAuthorization.variant.type === "oneTime";
 }
+
+export function isCloseOnExhaustion(
+  preAuthorization: PreAuthorizationAccount,
+): boolean {
+  return (
+    preAuthorization.variant.type === "oneTime" &&
+    preAuthorization.variant.closeOnExhaustion
+  );
+}
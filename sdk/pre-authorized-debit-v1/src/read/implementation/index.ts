@@ -25,9 +25,15 @@ import {
   IdlNotFoundOnChainError,
   NoPreAuthorizationFound,
   TokenAccountDoesNotExist,
+  UnsupportedPreAuthorization,
 } from "../../errors";
 import NodeWallet from "@coral-xyz/anchor/dist/cjs/nodewallet";
-import { PreAuthorizationAccount, SmartDelegateAccount } from "../accounts";
+import {
+  PreAuthorizationAccount,
+  PreAuthorizationCounterAccount,
+  SmartDelegateAccount,
+  isCloseOnExhaustion,
+} from "../accounts";
 import {
   Account,
   TOKEN_2022_PROGRAM_ID,
@@ -120,6 +126,18 @@ export class PreAuthorizedDebitReadClientImpl
     };
   }
 
+  public derivePreAuthorizationCounterPDA(tokenAccount: PublicKey): PDA {
+    const [pdaPubkey, pdaBump] = PublicKey.findProgramAddressSync(
+      [Buffer.from("pre-authorization-counter"), tokenAccount.toBuffer()],
+      this.programId,
+    );
+
+    return {
+      publicKey: pdaPubkey,
+      bump: pdaBump,
+    };
+  }
+
   private smartDelegateToNativeType(
     smartDelegateAnchorType: Awaited<
       ReturnType<typeof this.program.account.smartDelegate.fetch>
@@ -130,11 +148,30 @@ export class PreAuthorizedDebitReadClientImpl
     };
   }
 
+  private preAuthorizationCounterToNativeType(
+    preAuthorizationCounterAnchorType: Awaited<
+      ReturnType<typeof this.program.account.preAuthorizationCounter.fetch>
+    >,
+  ): PreAuthorizationCounterAccount {
+    return {
+      bump: preAuthorizationCounterAnchorType.bump,
+      tokenAccount: preAuthorizationCounterAnchorType.tokenAccount,
+      count: BigInt(preAuthorizationCounterAnchorType.count.toString()),
+      boundedDelegation: preAuthorizationCounterAnchorType.boundedDelegation,
+      countVerified: preAuthorizationCounterAnchorType.countVerified,
+    };
+  }
+
   private preAuthorizationToNativeType(
     preAuthorizationAnchorType: Awaited<
       ReturnType<typeof this.program.account.preAuthorization.fetch>
     >,
-  ): PreAuthorizationAccount {
+  ): PreAuthorizationAccount | null {
+    // NOTE: Only the one-time and recurring pre-authorizations scoped to a token account are supported
+    if (!preAuthorizationAnchorType.scope.tokenAccount) {
+      return null;
+    }
+
     let variant: PreAuthorizationAccount["variant"];
     if (preAuthorizationAnchorType.variant.oneTime) {
       const oneTimeVariant = preAuthorizationAnchorType.variant.oneTime;
@@ -146,8 +183,9 @@ export class PreAuthorizedDebitReadClientImpl
         expiryUnixTimestamp: BigInt(
           oneTimeVariant.expiryUnixTimestamp.toString(),
         ),
+        closeOnExhaustion: oneTimeVariant.closeOnExhaustion,
       };
-    } else {
+    } else if (preAuthorizationAnchorType.variant.recurring) {
       const recurringVariant = preAuthorizationAnchorType.variant.recurring;
       const numCycles = recurringVariant.numCycles;
 
@@ -169,6 +207,8 @@ export class PreAuthorizedDebitReadClientImpl
         lastDebitedCycle: BigInt(recurringVariant.lastDebitedCycle.toString()),
         numCycles: numCycles && BigInt(numCycles.toString()),
       };
+    } else {
+      return null;
     }
 
     return {
@@ -199,6 +239,27 @@ export class PreAuthorizedDebitReadClientImpl
     );
   }
 
+  public async fetchPreAuthorizationCounter(
+    tokenAccount: PublicKey,
+  ): Promise<ProgramAccount<PreAuthorizationCounterAccount> | null> {
+    const { publicKey: preAuthorizationCounterPubkey } =
+      this.derivePreAuthorizationCounterPDA(tokenAccount);
+
+    const preAuthorizationCounterAccount =
+      await this.program.account.preAuthorizationCounter.fetchNullable(
+        preAuthorizationCounterPubkey,
+      );
+
+    return (
+      preAuthorizationCounterAccount && {
+        publicKey: preAuthorizationCounterPubkey,
+        account: this.preAuthorizationCounterToNativeType(
+          preAuthorizationCounterAccount,
+        ),
+      }
+    );
+  }
+
   public async fetchPreAuthorization(
     params:
       | { publicKey: PublicKey }
@@ -225,12 +286,19 @@ export class PreAuthorizedDebitReadClientImpl
         preAuthorizationPubkey,
       );
 
-    return (
-      preAuthorizationAccount && {
-        publicKey: preAuthorizationPubkey,
-        account: this.preAuthorizationToNativeType(preAuthorizationAccount),
-      }
-    );
+    if (!preAuthorizationAccount) {
+      return null;
+    }
+
+    const account = this.preAuthorizationToNativeType(preAuthorizationAccount);
+    if (!account) {
+      throw new UnsupportedPreAuthorization(
+        this.connection.rpcEndpoint,
+        preAuthorizationPubkey,
+      );
+    }
+
+    return { publicKey: preAuthorizationPubkey, account };
   }
 
   private async fetchPreAuthorizations(
@@ -252,16 +320,6 @@ export class PreAuthorizedDebitReadClientImpl
       });
     }
 
-    if (filterBy.debitAuthority) {
-      filters.push({
-        // debitAuthority
-        memcmp: {
-          offset: type === "recurring" ? 93 : 67,
-          bytes: filterBy.debitAuthority.toBase58(),
-        },
-      });
-    }
-
     if (type === "oneTime") {
       filters.push({
         // PreAuthorizationVariant::OneTime (discriminator)
@@ -282,10 +340,18 @@ export class PreAuthorizedDebitReadClientImpl
     const programAccounts =
       await this.program.account.preAuthorization.all(filters);
 
-    return programAccounts.map((programAccount) => ({
-      publicKey: programAccount.publicKey,
-      account: this.preAuthorizationToNativeType(programAccount.account),
-    }));
+    // NOTE: The debitAuthority offset depends on the variant (and its optional fields), so it is filtered here
+    return programAccounts.flatMap((programAccount) => {
+      const account = this.preAuthorizationToNativeType(programAccount.account);
+      if (
+        !account ||
+        (filterBy.debitAuthority &&
+          !account.debitAuthority.equals(filterBy.debitAuthority))
+      ) {
+        return [];
+      }
+      return [{ publicKey: programAccount.publicKey, account }];
+    });
   }
 
   public async fetchPreAuthorizationsForTokenAccount(
@@ -299,12 +365,6 @@ export class PreAuthorizedDebitReadClientImpl
     debitAuthority: PublicKey,
     type: PreAuthorizationType = "all",
   ): Promise<ProgramAccount<PreAuthorizationAccount>[]> {
-    if (type === "all") {
-      return [
-        ...(await this.fetchPreAuthorizations("oneTime", { debitAuthority })),
-        ...(await this.fetchPreAuthorizations("recurring", { debitAuthority })),
-      ];
-    }
     return this.fetchPreAuthorizations(type, { debitAuthority });
   }
 
@@ -314,7 +374,8 @@ export class PreAuthorizedDebitReadClientImpl
     const { txFeePayer } = params;
     let tokenAccountPubkey: PublicKey,
       debitAuthorityPubkey: PublicKey,
-      preAuthorizationPubkey: PublicKey;
+      preAuthorizationPubkey: PublicKey,
+      closeOnExhaustion: boolean;
 
     if ("preAuthorization" in params) {
       const preAuthorizationAccount = await this.fetchPreAuthorization({
@@ -329,6 +390,7 @@ export class PreAuthorizedDebitReadClientImpl
       tokenAccountPubkey = preAuthorizationAccount.account.tokenAccount;
       debitAuthorityPubkey = preAuthorizationAccount.account.debitAuthority;
       preAuthorizationPubkey = params.preAuthorization;
+      closeOnExhaustion = isCloseOnExhaustion(preAuthorizationAccount.account);
     } else {
       const preAuthorizationAccount = await this.fetchPreAuthorization({
         tokenAccount: params.tokenAccount,
@@ -343,6 +405,7 @@ export class PreAuthorizedDebitReadClientImpl
       tokenAccountPubkey = params.tokenAccount;
       debitAuthorityPubkey = params.debitAuthority;
       preAuthorizationPubkey = preAuthorizationAccount.publicKey;
+      closeOnExhaustion = isCloseOnExhaustion(preAuthorizationAccount.account);
     }
 
     const tokenAccountInfo =
@@ -384,6 +447,8 @@ export class PreAuthorizedDebitReadClientImpl
     const tx = await this.program.methods
       .debit({
         amount: new BN(params.requestedDebitAmount.toString()),
+        amountMode: { exact: {} },
+        reference: null,
       })
       .accounts({
         debitAuthority: debitAuthorityPubkey,
@@ -393,6 +458,12 @@ export class PreAuthorizedDebitReadClientImpl
         smartDelegate,
         preAuthorization: preAuthorizationPubkey,
         tokenProgram: tokenProgramId,
+        // NOTE: Only required if the debit exhausts (and closes) the pre-authorization
+        ...(closeOnExhaustion && {
+          receiver: tokenAccount.owner,
+          preAuthorizationCounter:
+            this.derivePreAuthorizationCounterPDA(tokenAccountPubkey).publicKey,
+        }),
       })
       .transaction();
 
@@ -1,6 +1,10 @@
 import { ProgramAccount } from "@coral-xyz/anchor";
 import { PublicKey } from "@solana/web3.js";
-import { PreAuthorizationAccount, SmartDelegateAccount } from "../accounts";
+import {
+  PreAuthorizationAccount,
+  PreAuthorizationCounterAccount,
+  SmartDelegateAccount,
+} from "../accounts";
 import { PreAuthorizedDebitV1 } from "../../pre_authorized_debit_v1";
 
 export type FetchPreAuthorizationParams =
@@ -141,6 +145,20 @@ export interface PreAuthorizedDebitReadClient {
     debitAuthority: PublicKey,
   ): PDA;
 
+  /**
+   * Derive the PDA for the `PreAuthorizationCounter` account of a token account
+   *
+   * @param {PublicKey} tokenAccount - the token account whose pre-authorizations are counted
+   * @returns {PDA} the PDA object with `publicKey` and `bump`
+   * @example
+   * ```typescript
+   * const tokenAccountPubkey: PublicKey = // token account pubkey
+   * const preAuthorizationCounterPDA = readClient.derivePreAuthorizationCounterPDA(tokenAccountPubkey);
+   * const { publicKey, bump } = preAuthorizationCounterPDA;
+   * ```
+   */
+  derivePreAuthorizationCounterPDA(tokenAccount: PublicKey): PDA;
+
   /**
    * Fetch the singleton SmartDelegate account
    *
@@ -161,6 +179,35 @@ export interface PreAuthorizedDebitReadClient {
    */
   fetchSmartDelegate(): Promise<ProgramAccount<SmartDelegateAccount> | null>;
 
+  /**
+   * Fetch the PreAuthorizationCounter account of a token account
+   *
+   * @param {PublicKey} tokenAccount - the token account whose pre-authorizations are counted
+   * @returns {Promise<ProgramAccount<PreAuthorizationCounterAccount> | null>} the pre-authorization counter account or null if not found
+   *
+   * @example
+   * ```typescript
+   * const preAuthorizationCounterProgramAccount = await readClient.fetchPreAuthorizationCounter(
+   *   tokenAccount, // pubkey
+   * );
+   * const {
+   *   publicKey, // PublicKey
+   *   account, // PreAuthorizationCounterAccount
+   * } = preAuthorizationCounterProgramAccount;
+   *
+   * const {
+   *   bump, // number (on-chain type: u8)
+   *   tokenAccount, // PublicKey
+   *   count, // bigint (on-chain u64)
+   *   boundedDelegation, // boolean
+   *   countVerified, // boolean
+   * } = account;
+   * ```
+   */
+  fetchPreAuthorizationCounter(
+    tokenAccount: PublicKey,
+  ): Promise<ProgramAccount<PreAuthorizationCounterAccount> | null>;
+
   /**
    * Fetch a PreAuthorization account given pubkey or token account and debit authority.
    *
@@ -192,6 +239,7 @@ export interface PreAuthorizedDebitReadClient {
    *     amountAuthorized, // bigint (on-chain u64)
    *     amountDebited, // bigint (on-chain u64)
    *     expiryUnixTimestamp, // bigint (on-chain i64)
+   *     closeOnExhaustion, // boolean
    *   } = variant;
    * } else if (variant.type === "recurring") {
    *   const {
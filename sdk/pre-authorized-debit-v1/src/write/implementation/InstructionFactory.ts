@@ -32,6 +32,7 @@ import {
 import {
   PreAuthorizedDebitReadClient,
   PreAuthorizedDebitReadClientImpl,
+  isCloseOnExhaustion,
 } from "../../read";
 import {
   NoPreAuthorizationFound,
@@ -142,10 +143,15 @@ export class InstructionFactoryImpl implements InstructionFactory {
           oneTime: {
             amountAuthorized: new BN(amountAuthorized.toString()),
             expiryUnixTimestamp: new BN(expiryUnixTimestamp.toString()),
+            maxAmountPerDebit: null,
+            closeOnExhaustion: false,
           },
         },
         debitAuthority,
         activationUnixTimestamp: new BN(activationUnixTimestamp.toString()),
+        rotationRequiresOwnerSignature: false,
+        destinationConstraint: null,
+        boundedDelegation: false,
       })
       .accounts({
         payer,
@@ -153,6 +159,9 @@ export class InstructionFactoryImpl implements InstructionFactory {
         smartDelegate: this.readClient.getSmartDelegatePDA().publicKey,
         tokenAccount,
         preAuthorization,
+        preAuthorizationCounter:
+          this.readClient.derivePreAuthorizationCounterPDA(tokenAccount)
+            .publicKey,
         tokenProgram: tokenProgramId,
         systemProgram: SystemProgram.programId,
       })
@@ -215,10 +224,20 @@ export class InstructionFactoryImpl implements InstructionFactory {
               recurringAmountAuthorized.toString(),
             ),
             numCycles: numCycles != null ? new BN(numCycles.toString()) : null,
+            maxAmountPerDebit: null,
+            minAmountPerDebit: null,
+            maxAccruedCycles: null,
+            calendarSchedule: null,
+            debitWindowSeconds: null,
+            debitWindowOffsetSeconds: null,
+            introSchedule: null,
           },
         },
         debitAuthority,
         activationUnixTimestamp: new BN(activationUnixTimestamp.toString()),
+        rotationRequiresOwnerSignature: false,
+        destinationConstraint: null,
+        boundedDelegation: false,
       })
       .accounts({
         payer,
@@ -226,6 +245,9 @@ export class InstructionFactoryImpl implements InstructionFactory {
         smartDelegate: this.readClient.getSmartDelegatePDA().publicKey,
         tokenAccount,
         preAuthorization,
+        preAuthorizationCounter:
+          this.readClient.derivePreAuthorizationCounterPDA(tokenAccount)
+            .publicKey,
         tokenProgram: tokenProgramId,
         systemProgram: SystemProgram.programId,
       })
@@ -337,14 +359,50 @@ export class InstructionFactoryImpl implements InstructionFactory {
         preAuthorizationPubkey,
       );
 
+    const tokenProgram =
+      await this.readClient.fetchTokenProgramIdForTokenAccount(
+        preAuthorization.account.tokenAccount,
+      );
+
+    // NOTE: In bounded delegation mode, the smart delegate is re-approved for the sum of the other
+    // open pre-authorizations of the token account, which need to be passed as remaining accounts
+    const preAuthorizationCounter =
+      await this.readClient.fetchPreAuthorizationCounter(
+        preAuthorization.account.tokenAccount,
+      );
+    const otherPreAuthorizations = preAuthorizationCounter?.account
+      .boundedDelegation
+      ? (
+          await this.readClient.fetchPreAuthorizationsForTokenAccount(
+            preAuthorization.account.tokenAccount,
+          )
+        ).filter(
+          (otherPreAuthorization) =>
+            !otherPreAuthorization.publicKey.equals(preAuthorization.publicKey),
+        )
+      : [];
+
     const closePreAuthIx = await this.program.methods
       .closePreAuthorization()
       .accounts({
         receiver: rentReceiver ?? tokenAccountOwner,
         authority: tokenAccountOwner,
         tokenAccount: preAuthorization.account.tokenAccount,
+        smartDelegate: this.readClient.getSmartDelegatePDA().publicKey,
         preAuthorization: preAuthorization.publicKey,
+        // NOTE: Uninitialized for pre-authorizations created before the pre-authorization counter existed
+        preAuthorizationCounter: this.readClient.derivePreAuthorizationCounterPDA(
+          preAuthorization.account.tokenAccount,
+        ).publicKey,
+        tokenProgram,
       })
+      .remainingAccounts(
+        otherPreAuthorizations.map((otherPreAuthorization) => ({
+          pubkey: otherPreAuthorization.publicKey,
+          isSigner: false,
+          isWritable: false,
+        })),
+      )
       .instruction();
 
     return {
@@ -376,13 +434,24 @@ export class InstructionFactoryImpl implements InstructionFactory {
         preAuthorizationPubkey,
       );
 
+    const tokenProgram =
+      await this.readClient.fetchTokenProgramIdForTokenAccount(
+        preAuthorization.account.tokenAccount,
+      );
+
     const closePreAuthIx = await this.program.methods
       .closePreAuthorization()
       .accounts({
         receiver: tokenAccountOwner,
         authority: preAuthorization.account.debitAuthority,
         tokenAccount: preAuthorization.account.tokenAccount,
+        smartDelegate: this.readClient.getSmartDelegatePDA().publicKey,
         preAuthorization: preAuthorization.publicKey,
+        // NOTE: Uninitialized for pre-authorizations created before the pre-authorization counter existed
+        preAuthorizationCounter: this.readClient.derivePreAuthorizationCounterPDA(
+          preAuthorization.account.tokenAccount,
+        ).publicKey,
+        tokenProgram,
       })
       .instruction();
 
@@ -452,7 +521,11 @@ export class InstructionFactoryImpl implements InstructionFactory {
     }
 
     const debitIx = await this.program.methods
-      .debit({ amount: new BN(amount.toString()) })
+      .debit({
+        amount: new BN(amount.toString()),
+        amountMode: { exact: {} },
+        reference: null,
+      })
       .accounts({
         debitAuthority,
         mint,
@@ -461,6 +534,13 @@ export class InstructionFactoryImpl implements InstructionFactory {
         smartDelegate,
         preAuthorization: preAuthorizationPubkey,
         tokenProgram: tokenProgramId,
+        // NOTE: Only required if the debit exhausts (and closes) the pre-authorization
+        ...(isCloseOnExhaustion(preAuthorizationAccount) && {
+          receiver: tokenAccountData.owner,
+          preAuthorizationCounter:
+            this.readClient.derivePreAuthorizationCounterPDA(tokenAccount)
+              .publicKey,
+        }),
       })
       .instruction();
 
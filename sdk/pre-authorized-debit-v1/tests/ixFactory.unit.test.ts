@@ -118,6 +118,10 @@ describe("InstructionFactory Unit Tests", () => {
         params.tokenAccount,
         params.debitAuthority,
       ).publicKey;
+      const preAuthorizationCounter =
+        readClient.derivePreAuthorizationCounterPDA(
+          params.tokenAccount,
+        ).publicKey;
 
       const stubFetchCurrentOwnerOfTokenAccount = sandbox
         .stub(readClient, "fetchCurrentOwnerOfTokenAccount")
@@ -132,10 +136,13 @@ describe("InstructionFactory Unit Tests", () => {
       const ixData =
         // eslint-disable-next-line  @typescript-eslint/no-explicit-any
         (coder.instruction.decode(ix.instruction.data)?.data as any).params;
-      expect(Object.keys(ixData).length).to.equal(3);
+      expect(Object.keys(ixData).length).to.equal(6);
       expect(ixData.debitAuthority.toString()).to.equal(
         params.debitAuthority.toString(),
       );
+      expect(ixData.rotationRequiresOwnerSignature).to.equal(false);
+      expect(ixData.destinationConstraint).to.equal(null);
+      expect(ixData.boundedDelegation).to.equal(false);
       expect(ixData.activationUnixTimestamp.toString()).to.equal(
         Math.floor(params.activation.getTime() / 1e3).toString(),
       );
@@ -177,17 +184,23 @@ describe("InstructionFactory Unit Tests", () => {
       expect(ix.instruction.keys[4].isWritable).to.equal(true);
 
       expect(ix.instruction.keys[5].pubkey.toString()).to.equal(
-        mockTokenProgramId.toString(),
+        preAuthorizationCounter.toString(),
       );
       expect(ix.instruction.keys[5].isSigner).to.equal(false);
-      expect(ix.instruction.keys[5].isWritable).to.equal(false);
+      expect(ix.instruction.keys[5].isWritable).to.equal(true);
 
       expect(ix.instruction.keys[6].pubkey.toString()).to.equal(
-        SystemProgram.programId.toString(),
+        mockTokenProgramId.toString(),
       );
       expect(ix.instruction.keys[6].isSigner).to.equal(false);
       expect(ix.instruction.keys[6].isWritable).to.equal(false);
 
+      expect(ix.instruction.keys[7].pubkey.toString()).to.equal(
+        SystemProgram.programId.toString(),
+      );
+      expect(ix.instruction.keys[7].isSigner).to.equal(false);
+      expect(ix.instruction.keys[7].isWritable).to.equal(false);
+
       expect(ix.expectedSigners.length).to.equal(2);
       expect(ix.expectedSigners[0].publicKey.toString()).to.equal(
         params.payer.toString(),
@@ -237,6 +250,10 @@ describe("InstructionFactory Unit Tests", () => {
         params.tokenAccount,
         params.debitAuthority,
       ).publicKey;
+      const preAuthorizationCounter =
+        readClient.derivePreAuthorizationCounterPDA(
+          params.tokenAccount,
+        ).publicKey;
 
       const stubFetchCurrentOwnerOfTokenAccount = sandbox
         .stub(readClient, "fetchCurrentOwnerOfTokenAccount")
@@ -251,10 +268,13 @@ describe("InstructionFactory Unit Tests", () => {
       const ixData =
         // eslint-disable-next-line  @typescript-eslint/no-explicit-any
         (coder.instruction.decode(ix.instruction.data)?.data as any).params;
-      expect(Object.keys(ixData).length).to.equal(3);
+      expect(Object.keys(ixData).length).to.equal(6);
       expect(ixData.debitAuthority.toString()).to.equal(
         params.debitAuthority.toString(),
       );
+      expect(ixData.rotationRequiresOwnerSignature).to.equal(false);
+      expect(ixData.destinationConstraint).to.equal(null);
+      expect(ixData.boundedDelegation).to.equal(false);
       expect(ixData.activationUnixTimestamp.toString()).to.equal(
         Math.floor(params.activation.getTime() / 1e3).toString(),
       );
@@ -298,17 +318,23 @@ describe("InstructionFactory Unit Tests", () => {
       expect(ix.instruction.keys[4].isWritable).to.equal(true);
 
       expect(ix.instruction.keys[5].pubkey.toString()).to.equal(
-        mockTokenProgramId.toString(),
+        preAuthorizationCounter.toString(),
       );
       expect(ix.instruction.keys[5].isSigner).to.equal(false);
-      expect(ix.instruction.keys[5].isWritable).to.equal(false);
+      expect(ix.instruction.keys[5].isWritable).to.equal(true);
 
       expect(ix.instruction.keys[6].pubkey.toString()).to.equal(
-        SystemProgram.programId.toString(),
+        mockTokenProgramId.toString(),
       );
       expect(ix.instruction.keys[6].isSigner).to.equal(false);
       expect(ix.instruction.keys[6].isWritable).to.equal(false);
 
+      expect(ix.instruction.keys[7].pubkey.toString()).to.equal(
+        SystemProgram.programId.toString(),
+      );
+      expect(ix.instruction.keys[7].isSigner).to.equal(false);
+      expect(ix.instruction.keys[7].isWritable).to.equal(false);
+
       expect(ix.expectedSigners.length).to.equal(2);
       expect(ix.expectedSigners[0].publicKey.toString()).to.equal(
         params.payer.toString(),
@@ -499,6 +525,7 @@ describe("InstructionFactory Unit Tests", () => {
       const mockTokenAccount = Keypair.generate().publicKey;
       const mockRentReceiver = Keypair.generate().publicKey;
       const mockTokenAccountOwner = Keypair.generate().publicKey;
+      const mockTokenProgramId = Keypair.generate().publicKey;
       const mockDebitAuthority = Keypair.generate().publicKey;
       const preAuthorization = readClient.derivePreAuthorizationPDA(
         mockTokenAccount,
@@ -519,6 +546,12 @@ describe("InstructionFactory Unit Tests", () => {
       const stubFetchCurrentOwnerOfPreAuthTokenAccount = sandbox
         .stub(readClient, "fetchCurrentOwnerOfPreAuthTokenAccount")
         .resolves(mockTokenAccountOwner);
+      const stubFetchTokenProgramIdForTokenAccount = sandbox
+        .stub(readClient, "fetchTokenProgramIdForTokenAccount")
+        .resolves(mockTokenProgramId);
+      const stubFetchPreAuthorizationCounter = sandbox
+        .stub(readClient, "fetchPreAuthorizationCounter")
+        .resolves(null);
 
       const ix = await instructionFactory.buildClosePreAuthorizationAsOwnerIx({
         preAuthorization,
@@ -544,13 +577,34 @@ describe("InstructionFactory Unit Tests", () => {
         mockTokenAccount.toString(),
       );
       expect(ix.instruction.keys[2].isSigner).to.equal(false);
-      expect(ix.instruction.keys[2].isWritable).to.equal(false);
+      expect(ix.instruction.keys[2].isWritable).to.equal(true);
 
       expect(ix.instruction.keys[3].pubkey.toString()).to.equal(
-        preAuthorization.toString(),
+        readClient.getSmartDelegatePDA().publicKey.toString(),
       );
       expect(ix.instruction.keys[3].isSigner).to.equal(false);
-      expect(ix.instruction.keys[3].isWritable).to.equal(true);
+      expect(ix.instruction.keys[3].isWritable).to.equal(false);
+
+      expect(ix.instruction.keys[4].pubkey.toString()).to.equal(
+        preAuthorization.toString(),
+      );
+      expect(ix.instruction.keys[4].isSigner).to.equal(false);
+      expect(ix.instruction.keys[4].isWritable).to.equal(true);
+
+      expect(ix.instruction.keys[5].pubkey.toString()).to.equal(
+        readClient
+          .derivePreAuthorizationCounterPDA(mockTokenAccount)
+          .publicKey.toString(),
+      );
+      expect(ix.instruction.keys[5].isSigner).to.equal(false);
+      expect(ix.instruction.keys[5].isWritable).to.equal(true);
+
+      expect(ix.instruction.keys[6].pubkey.toString()).to.equal(
+        mockTokenProgramId.toString(),
+      );
+      expect(ix.instruction.keys[6].isSigner).to.equal(false);
+      expect(ix.instruction.keys[6].isWritable).to.equal(false);
+      expect(ix.instruction.keys.length).to.equal(7);
 
       expect(ix.expectedSigners.length).to.equal(1);
       expect(ix.expectedSigners[0].publicKey.toString()).to.equal(
@@ -572,6 +626,73 @@ describe("InstructionFactory Unit Tests", () => {
           preAuthorization,
         ),
       ).to.equal(true);
+      expect(
+        stubFetchTokenProgramIdForTokenAccount.calledOnceWith(mockTokenAccount),
+      ).to.equal(true);
+      expect(
+        stubFetchPreAuthorizationCounter.calledOnceWith(mockTokenAccount),
+      ).to.equal(true);
+    });
+
+    it("should pass the other pre-authorizations in bounded delegation mode", async () => {
+      const mockTokenAccount = Keypair.generate().publicKey;
+      const mockTokenAccountOwner = Keypair.generate().publicKey;
+      const mockTokenProgramId = Keypair.generate().publicKey;
+      const mockDebitAuthority = Keypair.generate().publicKey;
+      const mockOtherPreAuthorization = Keypair.generate().publicKey;
+      const preAuthorization = readClient.derivePreAuthorizationPDA(
+        mockTokenAccount,
+        mockDebitAuthority,
+      ).publicKey;
+
+      const mockPreAuthorization = {
+        publicKey: preAuthorization,
+        account: {
+          tokenAccount: mockTokenAccount,
+          debitAuthority: mockDebitAuthority,
+        },
+      };
+      sandbox
+        .stub(readClient, "fetchPreAuthorization")
+        // eslint-disable-next-line  @typescript-eslint/no-explicit-any
+        .resolves(mockPreAuthorization as any);
+      sandbox
+        .stub(readClient, "fetchCurrentOwnerOfPreAuthTokenAccount")
+        .resolves(mockTokenAccountOwner);
+      sandbox
+        .stub(readClient, "fetchTokenProgramIdForTokenAccount")
+        .resolves(mockTokenProgramId);
+      sandbox.stub(readClient, "fetchPreAuthorizationCounter").resolves({
+        publicKey:
+          readClient.derivePreAuthorizationCounterPDA(mockTokenAccount)
+            .publicKey,
+        account: { boundedDelegation: true },
+        // eslint-disable-next-line  @typescript-eslint/no-explicit-any
+      } as any);
+      const stubFetchPreAuthorizationsForTokenAccount = sandbox
+        .stub(readClient, "fetchPreAuthorizationsForTokenAccount")
+        .resolves([
+          mockPreAuthorization,
+          { publicKey: mockOtherPreAuthorization, account: {} },
+          // eslint-disable-next-line  @typescript-eslint/no-explicit-any
+        ] as any);
+
+      const ix = await instructionFactory.buildClosePreAuthorizationAsOwnerIx({
+        preAuthorization,
+      });
+
+      expect(ix.instruction.keys.length).to.equal(8);
+      expect(ix.instruction.keys[7].pubkey.toString()).to.equal(
+        mockOtherPreAuthorization.toString(),
+      );
+      expect(ix.instruction.keys[7].isSigner).to.equal(false);
+      expect(ix.instruction.keys[7].isWritable).to.equal(false);
+
+      expect(
+        stubFetchPreAuthorizationsForTokenAccount.calledOnceWith(
+          mockTokenAccount,
+        ),
+      ).to.equal(true);
     });
   });
 
@@ -579,6 +700,7 @@ describe("InstructionFactory Unit Tests", () => {
     it("should build buildClosePreAuthorizationAsDebitAuthorityIx", async () => {
       const mockTokenAccount = Keypair.generate().publicKey;
       const mockTokenAccountOwner = Keypair.generate().publicKey;
+      const mockTokenProgramId = Keypair.generate().publicKey;
       const mockDebitAuthority = Keypair.generate().publicKey;
       const preAuthorization = readClient.derivePreAuthorizationPDA(
         mockTokenAccount,
@@ -599,6 +721,9 @@ describe("InstructionFactory Unit Tests", () => {
       const stubFetchCurrentOwnerOfPreAuthTokenAccount = sandbox
         .stub(readClient, "fetchCurrentOwnerOfPreAuthTokenAccount")
         .resolves(mockTokenAccountOwner);
+      const stubFetchTokenProgramIdForTokenAccount = sandbox
+        .stub(readClient, "fetchTokenProgramIdForTokenAccount")
+        .resolves(mockTokenProgramId);
 
       const ix =
         await instructionFactory.buildClosePreAuthorizationAsDebitAuthorityIx({
@@ -624,13 +749,33 @@ describe("InstructionFactory Unit Tests", () => {
         mockTokenAccount.toString(),
       );
       expect(ix.instruction.keys[2].isSigner).to.equal(false);
-      expect(ix.instruction.keys[2].isWritable).to.equal(false);
+      expect(ix.instruction.keys[2].isWritable).to.equal(true);
 
       expect(ix.instruction.keys[3].pubkey.toString()).to.equal(
-        preAuthorization.toString(),
+        readClient.getSmartDelegatePDA().publicKey.toString(),
       );
       expect(ix.instruction.keys[3].isSigner).to.equal(false);
-      expect(ix.instruction.keys[3].isWritable).to.equal(true);
+      expect(ix.instruction.keys[3].isWritable).to.equal(false);
+
+      expect(ix.instruction.keys[4].pubkey.toString()).to.equal(
+        preAuthorization.toString(),
+      );
+      expect(ix.instruction.keys[4].isSigner).to.equal(false);
+      expect(ix.instruction.keys[4].isWritable).to.equal(true);
+
+      expect(ix.instruction.keys[5].pubkey.toString()).to.equal(
+        readClient
+          .derivePreAuthorizationCounterPDA(mockTokenAccount)
+          .publicKey.toString(),
+      );
+      expect(ix.instruction.keys[5].isSigner).to.equal(false);
+      expect(ix.instruction.keys[5].isWritable).to.equal(true);
+
+      expect(ix.instruction.keys[6].pubkey.toString()).to.equal(
+        mockTokenProgramId.toString(),
+      );
+      expect(ix.instruction.keys[6].isSigner).to.equal(false);
+      expect(ix.instruction.keys[6].isWritable).to.equal(false);
 
       expect(ix.expectedSigners.length).to.equal(1);
       expect(ix.expectedSigners[0].publicKey.toString()).to.equal(
@@ -652,6 +797,9 @@ describe("InstructionFactory Unit Tests", () => {
           preAuthorization,
         ),
       ).to.equal(true);
+      expect(
+        stubFetchTokenProgramIdForTokenAccount.calledOnceWith(mockTokenAccount),
+      ).to.equal(true);
     });
   });
 
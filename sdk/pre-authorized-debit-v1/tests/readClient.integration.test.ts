@@ -16,6 +16,7 @@ import {
   TokenAccountDoesNotExist,
   PreAuthorizationAccount,
   I64_MAX,
+  UnsupportedPreAuthorization,
 } from "../src";
 import { AnchorProvider, BN, Program } from "@coral-xyz/anchor";
 import { getProviderNodeWallet } from "./util";
@@ -87,6 +88,8 @@ describe("PreAuthorizedDebitReadClientImpl integration", () => {
               oneTime: {
                 amountAuthorized: new BN(100e6),
                 expiryUnixTimestamp: new BN(expirationUnixTimestamp),
+                maxAmountPerDebit: null,
+                closeOnExhaustion: false,
               },
             }
           : {
@@ -95,6 +98,13 @@ describe("PreAuthorizedDebitReadClientImpl integration", () => {
                 recurringAmountAuthorized: new anchor.BN(100),
                 numCycles: new BN(5),
                 resetEveryCycle: true,
+                maxAmountPerDebit: null,
+                minAmountPerDebit: null,
+                maxAccruedCycles: null,
+                calendarSchedule: null,
+                debitWindowSeconds: null,
+                debitWindowOffsetSeconds: null,
+                introSchedule: null,
               },
             };
       const pad = readClient.derivePreAuthorizationPDA(
@@ -107,6 +117,9 @@ describe("PreAuthorizedDebitReadClientImpl integration", () => {
           variant,
           debitAuthority: debitAuthorities[i].publicKey,
           activationUnixTimestamp: new BN(activationUnixTimestamp),
+          rotationRequiresOwnerSignature: false,
+          destinationConstraint: null,
+          boundedDelegation: false,
         })
         .accounts({
           payer: provider.publicKey,
@@ -114,6 +127,9 @@ describe("PreAuthorizedDebitReadClientImpl integration", () => {
           smartDelegate,
           tokenAccount,
           preAuthorization: pad,
+          preAuthorizationCounter: readClient.derivePreAuthorizationCounterPDA(
+            tokenAccount,
+          ).publicKey,
           tokenProgram: TOKEN_PROGRAM_ID,
           systemProgram: SystemProgram.programId,
         })
@@ -147,6 +163,34 @@ describe("PreAuthorizedDebitReadClientImpl integration", () => {
     });
   });
 
+  context("fetchPreAuthorizationCounter", () => {
+    it("should fetch preAuthorizationCounter", async () => {
+      const preAuthorizationCounter =
+        await readClient.fetchPreAuthorizationCounter(tokenAccount);
+      assert.isNotEmpty(preAuthorizationCounter);
+      expect(preAuthorizationCounter!.publicKey.toString()).to.equal(
+        readClient
+          .derivePreAuthorizationCounterPDA(tokenAccount)
+          .publicKey.toString(),
+      );
+      expect(preAuthorizationCounter!.account.tokenAccount.toString()).to.equal(
+        tokenAccount.toString(),
+      );
+      expect(preAuthorizationCounter!.account.count).to.equal(BigInt(3));
+      expect(preAuthorizationCounter!.account.boundedDelegation).to.equal(
+        false,
+      );
+      expect(preAuthorizationCounter!.account.countVerified).to.equal(false);
+    });
+    it("should return null preAuthorizationCounter", async () => {
+      const preAuthorizationCounter =
+        await readClient.fetchPreAuthorizationCounter(
+          Keypair.generate().publicKey,
+        );
+      assert.isNull(preAuthorizationCounter);
+    });
+  });
+
   context("fetchPreAuthorization", () => {
     it("should fetch preAuthorization", async () => {
       const preAuthorization = await readClient.fetchPreAuthorization({
@@ -162,6 +206,54 @@ describe("PreAuthorizedDebitReadClientImpl integration", () => {
       });
       assert.isNull(preAuthorization);
     });
+    it("should throw UnsupportedPreAuthorization for an owner scoped preAuthorization", async () => {
+      const debitAuthority = Keypair.generate().publicKey;
+      const [ownerPreAuthorization] = PublicKey.findProgramAddressSync(
+        [
+          Buffer.from("pre-authorization"),
+          provider.publicKey.toBuffer(),
+          mint.toBuffer(),
+          debitAuthority.toBuffer(),
+        ],
+        MAINNET_PAD_PROGRAM_ID,
+      );
+      await program.methods
+        .initOwnerPreAuthorization({
+          variant: {
+            oneTime: {
+              amountAuthorized: new BN(100e6),
+              expiryUnixTimestamp: new BN(I64_MAX.toString()),
+              maxAmountPerDebit: null,
+              closeOnExhaustion: false,
+            },
+          },
+          debitAuthority,
+          activationUnixTimestamp: new BN(0),
+          rotationRequiresOwnerSignature: false,
+          destinationConstraint: null,
+          boundedDelegation: false,
+        })
+        .accounts({
+          payer: provider.publicKey,
+          owner: provider.publicKey,
+          mint,
+          preAuthorization: ownerPreAuthorization,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+
+      await expect(
+        readClient.fetchPreAuthorization({
+          publicKey: ownerPreAuthorization,
+        }),
+      ).to.eventually.be.rejectedWith(UnsupportedPreAuthorization);
+
+      // owner scoped pre-authorizations are skipped when listing pre-authorizations
+      const pads = await readClient.fetchPreAuthorizationsForDebitAuthority(
+        debitAuthority,
+      );
+      expect(pads.length).to.equal(0);
+    });
   });
 
   context("fetchPreAuthorizationsForTokenAccount", () => {
@@ -594,10 +686,15 @@ describe("PreAuthorizedDebitReadClientImpl integration", () => {
             oneTime: {
               amountAuthorized: new BN(100e6),
               expiryUnixTimestamp: new BN(I64_MAX.toString()),
+              maxAmountPerDebit: null,
+              closeOnExhaustion: false,
             },
           },
           debitAuthority: oneTimeDebitAuthority.publicKey,
           activationUnixTimestamp: new BN(oneDayAgo.getTime() / 1e3),
+          rotationRequiresOwnerSignature: false,
+          destinationConstraint: null,
+          boundedDelegation: false,
         })
         .accounts({
           payer: provider.publicKey,
@@ -605,6 +702,9 @@ describe("PreAuthorizedDebitReadClientImpl integration", () => {
           smartDelegate: readClient.getSmartDelegatePDA().publicKey,
           tokenAccount: userTokenAcount,
           preAuthorization: oneTimePad,
+          preAuthorizationCounter: readClient.derivePreAuthorizationCounterPDA(
+            userTokenAcount,
+          ).publicKey,
           tokenProgram: TOKEN_PROGRAM_ID,
           systemProgram: SystemProgram.programId,
         })
@@ -623,10 +723,20 @@ describe("PreAuthorizedDebitReadClientImpl integration", () => {
               recurringAmountAuthorized: new anchor.BN(100),
               numCycles: null,
               resetEveryCycle: true,
+              maxAmountPerDebit: null,
+              minAmountPerDebit: null,
+              maxAccruedCycles: null,
+              calendarSchedule: null,
+              debitWindowSeconds: null,
+              debitWindowOffsetSeconds: null,
+              introSchedule: null,
             },
           },
           debitAuthority: recurringDebitAuthority.publicKey,
           activationUnixTimestamp: new BN(oneDayAgo.getTime() / 1e3),
+          rotationRequiresOwnerSignature: false,
+          destinationConstraint: null,
+          boundedDelegation: false,
         })
         .accounts({
           payer: provider.publicKey,
@@ -634,6 +744,9 @@ describe("PreAuthorizedDebitReadClientImpl integration", () => {
           smartDelegate: readClient.getSmartDelegatePDA().publicKey,
           tokenAccount: userTokenAcount,
           preAuthorization: recurringPad,
+          preAuthorizationCounter: readClient.derivePreAuthorizationCounterPDA(
+            userTokenAcount,
+          ).publicKey,
           tokenProgram: TOKEN_PROGRAM_ID,
           systemProgram: SystemProgram.programId,
         })
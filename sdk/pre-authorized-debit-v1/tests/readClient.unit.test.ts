@@ -34,6 +34,19 @@ describe("PreAuthorizedDebitReadClientImpl unit", () => {
     });
   });
 
+  context("derivePreAuthorizationCounterPDA", () => {
+    it("should derive a pre authorization counter pda", () => {
+      const preAuthorizationCounterPda =
+        readClient.derivePreAuthorizationCounterPDA(
+          new PublicKey("HFRSGsKeknoTHT6VUjMznUKFgfcm6zgCFp8ZcvoikSDY"),
+        );
+      expect(preAuthorizationCounterPda.publicKey.toString()).to.equal(
+        "B8SsTiFYvccyuGewACv8DUK2sV4wtx16v4YHSDY5TfeQ",
+      );
+      expect(preAuthorizationCounterPda.bump).to.equal(255);
+    });
+  });
+
   context("custom", () => {
     it("should use the custom program with the custom constructor", () => {
       const newProgramId = new Keypair().publicKey;
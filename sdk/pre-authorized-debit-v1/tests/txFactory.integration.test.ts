@@ -99,6 +99,8 @@ describe("Transaction Factory Integration Tests", () => {
               oneTime: {
                 amountAuthorized: new BN(100e6),
                 expiryUnixTimestamp: new BN(expirationUnixTimestamp),
+                maxAmountPerDebit: null,
+                closeOnExhaustion: false,
               },
             }
           : {
@@ -107,6 +109,13 @@ describe("Transaction Factory Integration Tests", () => {
                 recurringAmountAuthorized: new BN(100),
                 numCycles: null,
                 resetEveryCycle: true,
+                maxAmountPerDebit: null,
+                minAmountPerDebit: null,
+                maxAccruedCycles: null,
+                calendarSchedule: null,
+                debitWindowSeconds: null,
+                debitWindowOffsetSeconds: null,
+                introSchedule: null,
               },
             };
       const pad = readClient.derivePreAuthorizationPDA(
@@ -119,6 +128,9 @@ describe("Transaction Factory Integration Tests", () => {
           variant,
           debitAuthority: debitAuthorities[i].publicKey,
           activationUnixTimestamp: new BN(activationUnixTimestamp),
+          rotationRequiresOwnerSignature: false,
+          destinationConstraint: null,
+          boundedDelegation: false,
         })
         .accounts({
           payer: provider.publicKey,
@@ -126,6 +138,9 @@ describe("Transaction Factory Integration Tests", () => {
           smartDelegate,
           tokenAccount,
           preAuthorization: pad,
+          preAuthorizationCounter: readClient.derivePreAuthorizationCounterPDA(
+            tokenAccount,
+          ).publicKey,
           tokenProgram: TOKEN_PROGRAM_ID,
           systemProgram: SystemProgram.programId,
         })
@@ -305,10 +320,15 @@ describe("Transaction Factory Integration Tests", () => {
             oneTime: {
               amountAuthorized: new BN(100e6),
               expiryUnixTimestamp: new BN(expirationUnixTimestamp),
+              maxAmountPerDebit: null,
+              closeOnExhaustion: false,
             },
           },
           debitAuthority: newDebitAuthority.publicKey,
           activationUnixTimestamp: new BN(activationUnixTimestamp),
+          rotationRequiresOwnerSignature: false,
+          destinationConstraint: null,
+          boundedDelegation: false,
         })
         .accounts({
           payer: provider.publicKey,
@@ -316,6 +336,9 @@ describe("Transaction Factory Integration Tests", () => {
           smartDelegate,
           tokenAccount,
           preAuthorization: pad,
+          preAuthorizationCounter: readClient.derivePreAuthorizationCounterPDA(
+            tokenAccount,
+          ).publicKey,
           tokenProgram: TOKEN_PROGRAM_ID,
           systemProgram: SystemProgram.programId,
         })
@@ -373,10 +396,15 @@ describe("Transaction Factory Integration Tests", () => {
               oneTime: {
                 amountAuthorized: new BN(100e6),
                 expiryUnixTimestamp: new BN(expirationUnixTimestamp),
+                maxAmountPerDebit: null,
+                closeOnExhaustion: false,
               },
             },
             debitAuthority: newDebitAuthority.publicKey,
             activationUnixTimestamp: new BN(activationUnixTimestamp),
+            rotationRequiresOwnerSignature: false,
+            destinationConstraint: null,
+            boundedDelegation: false,
           })
           .accounts({
             payer: provider.publicKey,
@@ -384,6 +412,9 @@ describe("Transaction Factory Integration Tests", () => {
             smartDelegate,
             tokenAccount: nativeMintTokenAccount,
             preAuthorization: pad,
+            preAuthorizationCounter: readClient.derivePreAuthorizationCounterPDA(
+              nativeMintTokenAccount,
+            ).publicKey,
             tokenProgram: TOKEN_PROGRAM_ID,
             systemProgram: SystemProgram.programId,
           })